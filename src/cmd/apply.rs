@@ -395,10 +395,7 @@ use regex::Regex;
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 use smallvec::SmallVec;
-use strsim::{
-    damerau_levenshtein, hamming, jaro_winkler, normalized_damerau_levenshtein, osa_distance,
-    sorensen_dice,
-};
+use strsim::{damerau_levenshtein, hamming, osa_distance};
 use strum_macros::EnumString;
 use thousands::{Separable, SeparatorPolicy, policies};
 use titlecase::titlecase;
@@ -410,6 +407,7 @@ use crate::{
     config::{Config, DEFAULT_RDR_BUFFER_CAPACITY, Delimiter},
     llmutil, regex_oncelock,
     select::{SelectColumns, Selection},
+    similarity::{Metric, sounds_alike},
     util,
     util::replace_column_value,
 };
@@ -1698,17 +1696,17 @@ fn apply_operations(
             },
             Operations::Simdln => {
                 zmij::Buffer::new()
-                    .format_finite(normalized_damerau_levenshtein(cell, comparand))
+                    .format_finite(Metric::DamerauLevenshtein.score(cell, comparand))
                     .clone_into(cell);
             },
             Operations::Simjw => {
                 zmij::Buffer::new()
-                    .format_finite(jaro_winkler(cell, comparand))
+                    .format_finite(Metric::JaroWinkler.score(cell, comparand))
                     .clone_into(cell);
             },
             Operations::Simsd => {
                 zmij::Buffer::new()
-                    .format_finite(sorensen_dice(cell, comparand))
+                    .format_finite(Metric::SorensenDice.score(cell, comparand))
                     .clone_into(cell);
             },
             Operations::Simhm => {
@@ -1727,7 +1725,7 @@ fn apply_operations(
                 // safety: we set EUDEX_COMPARAND_HASH in validate_operations()
                 let eudex_comparand_hash = EUDEX_COMPARAND_HASH.get().unwrap();
                 let cell_hash = Hash::new(cell);
-                *cell = format!("{}", (cell_hash - *eudex_comparand_hash).similar());
+                *cell = format!("{}", sounds_alike(cell_hash, *eudex_comparand_hash));
             },
             Operations::Sentiment => {
                 // safety: we set SENTIMENT_ANALYZER in validate_operations()
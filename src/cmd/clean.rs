@@ -7,7 +7,7 @@ source file:
   * <file>.idx                                              (index)
   * <stem>.stats.csv / .stats.weighted.csv / .stats.csv.json
     / .stats.csv.data.jsonl                                 (stats cache)
-  * <stem>.freq.csv.data.jsonl                              (frequency cache)
+  * <stem>.freq.csv.data.jsonl / .freq.csv.bins.jsonl       (frequency cache)
 
 Only files that are VERIFIABLY generated by qsv are removed, so a user file that
 merely happens to be named e.g. report.stats.csv is never touched (qsv stats
@@ -240,7 +240,9 @@ fn consider_anchor(path: &Path, sel: &Selected, stale: bool, victims: &mut Vec<(
         && (full.ends_with(".stats.csv.json") || full.ends_with(".stats.weighted.csv.json"))
     {
         handle_stats(path, &full, stale, victims);
-    } else if sel.frequency && full.ends_with(".freq.csv.data.jsonl") {
+    } else if sel.frequency
+        && (full.ends_with(".freq.csv.data.jsonl") || full.ends_with(".freq.csv.bins.jsonl"))
+    {
         handle_frequency(path, stale, victims);
     } else if sel.schema && full.ends_with(".pschema.json") {
         handle_pschema(path, &full, stale, victims);
@@ -490,6 +492,7 @@ fn candidate_anchors(src: &Path) -> Vec<PathBuf> {
         v.push(parent.join(format!("{stem}.stats.csv.json")));
         v.push(parent.join(format!("{stem}.stats.weighted.csv.json")));
         v.push(parent.join(format!("{stem}.freq.csv.data.jsonl")));
+        v.push(parent.join(format!("{stem}.freq.csv.bins.jsonl")));
         v.push(parent.join(format!("{stem}.stats.bivariate.csv")));
         v.push(parent.join(format!("{stem}.stats.bivariate.joined.csv")));
     }
@@ -32,6 +32,10 @@ Examples:
   # Write duplicates to a separate file:
   qsv dedup -s col1,col2 --dupes-output dupes.csv unsorted.csv -o deduped.csv
 
+  # Cluster near-duplicate contacts with the same zip code, whose names are at least
+  # 90% similar (Jaro-Winkler) and whose streets are 80% similar (Sørensen-Dice):
+  qsv dedup --fuzzy "name:simjw:0.9,street:simsd:0.8" --block zip contacts.csv
+
 For examples, see https://github.com/dathere/qsv/blob/master/tests/test_dedup.rs.
 See also https://github.com/dathere/qsv/wiki/Aggregation-and-Statistics#dedup
 
@@ -46,6 +50,46 @@ dedup options:
                                See 'qsv select --help' for the format details.
     -N, --numeric              Compare according to string numerical value
     -i, --ignore-case          Compare strings disregarding case.
+    --collation <locale>       Compare strings with the Unicode collation of a
+                               BCP 47 locale (e.g. en, de, sv, und for the root
+                               collation), so duplicates are found, and rows are
+                               sorted, as in that language. With --sorted, the
+                               input must be sorted with the same collation, e.g.
+                               with `qsv sort --collation`.
+    --normalize <form>         Normalize strings to nfc or nfkc before comparing
+                               them, so canonically (nfc) or compatibility (nfkc)
+                               equivalent strings are duplicates.
+
+                               FUZZY DEDUP OPTIONS:
+    --fuzzy <rules>            Find near-duplicates instead of exact duplicates.
+                               Rows are near-duplicates when the values of every
+                               rule's column are similar enough. Rules are
+                               comma-separated <column>:<metric>[:<threshold>],
+                               with the metrics of `qsv apply operations`:
+                                 simjw   Jaro-Winkler similarity
+                                 simdln  normalized Damerau-Levenshtein similarity
+                                 simsd   Sørensen-Dice similarity
+                                 eudex   sounds alike (takes no threshold)
+                               where the threshold is the minimum similarity,
+                               between 0.0 and 1.0. e.g.
+                                 --fuzzy "first:eudex,last:simjw:0.9"
+                               Values are trimmed, and with --ignore-case,
+                               lowercased. Empty values are never similar.
+                               Near-duplicates are clustered transitively, and no
+                               rows are dropped: every row is written in input
+                               order with its cluster ID in a new column, numbered
+                               from 1 in order of first appearance. The count of
+                               rows that aren't the first of their cluster is sent
+                               to <stderr>.
+                               Cannot be combined with --select, --numeric,
+                               --collation, --normalize, --sorted or --dupes-output.
+                               Requires the apply feature.
+    --block <cols>             Only compare rows with the same (trimmed) values in
+                               these columns, e.g. a zip code. Without it, every
+                               row is compared with every other row, which is
+                               slow for large files.
+    --cluster-column <name>    The name of the cluster ID column.
+                               [default: cluster_id]
     --sorted                   The input is already sorted. Do not load the CSV into
                                memory to sort it first. Meant to be used in tandem and
                                after an extsort.
@@ -80,19 +124,34 @@ use csv::ByteRecord;
 use rayon::slice::ParallelSliceMut;
 use serde::Deserialize;
 
+#[cfg(feature = "apply")]
+use self::fuzzy::FuzzyDedup;
 use crate::{
     CliResult,
     cmd::sort::{iter_cmp, iter_cmp_ignore_case, iter_cmp_num},
+    collation::Collation,
     config::{Config, Delimiter},
     select::SelectColumns,
+    statscatalog::StatsCatalog,
     util,
 };
+
+#[cfg(feature = "apply")]
+pub mod fuzzy;
+
 #[derive(Deserialize)]
 struct Args {
     arg_input:           Option<String>,
     flag_select:         SelectColumns,
     flag_numeric:        bool,
     flag_ignore_case:    bool,
+    flag_collation:      Option<String>,
+    flag_normalize:      Option<String>,
+    flag_fuzzy:          Option<String>,
+    #[cfg(feature = "apply")]
+    flag_block:          Option<String>,
+    #[cfg(feature = "apply")]
+    flag_cluster_column: String,
     flag_sorted:         bool,
     flag_dupes_output:   Option<String>,
     flag_output:         Option<String>,
@@ -105,17 +164,30 @@ struct Args {
 }
 
 #[derive(Debug)]
-enum ComparisonMode {
+enum ComparisonMode<'a> {
     Numeric,
     IgnoreCase,
+    Collated(&'a Collation),
     Normal,
 }
 
 pub fn run(argv: &[&str]) -> CliResult<()> {
     let args: Args = util::get_args(USAGE, argv)?;
 
+    let collation = Collation::new(
+        args.flag_collation.as_deref(),
+        args.flag_normalize.as_deref(),
+        args.flag_ignore_case,
+    )?;
+    if collation.is_some() && args.flag_numeric {
+        return fail_incorrectusage_clierror!(
+            "--collation and --normalize cannot be combined with --numeric."
+        );
+    }
     let compare_mode = if args.flag_numeric {
         ComparisonMode::Numeric
+    } else if let Some(collation) = &collation {
+        ComparisonMode::Collated(collation)
     } else if args.flag_ignore_case {
         ComparisonMode::IgnoreCase
     } else {
@@ -125,7 +197,28 @@ pub fn run(argv: &[&str]) -> CliResult<()> {
     let rconfig = Config::new(args.arg_input.as_ref())
         .delimiter(args.flag_delimiter)
         .no_headers_flag(args.flag_no_headers)
-        .select(args.flag_select);
+        .select(args.flag_select.clone());
+
+    if args.flag_fuzzy.is_some() {
+        if args.flag_numeric
+            || collation.is_some()
+            || args.flag_sorted
+            || args.flag_dupes_output.is_some()
+        {
+            return fail_incorrectusage_clierror!(
+                "--fuzzy cannot be combined with --numeric, --collation, --normalize, --sorted or \
+                 --dupes-output."
+            );
+        }
+        #[cfg(feature = "apply")]
+        {
+            let dupe_count = fuzzy_dedup(&args, &rconfig)?;
+            print_dupe_count(&args, dupe_count);
+            return Ok(());
+        }
+        #[cfg(not(feature = "apply"))]
+        return fail_incorrectusage_clierror!("--fuzzy requires the apply feature.");
+    }
 
     let mut rdr = rconfig.reader()?;
     let mut wtr = Config::new(args.flag_output.as_ref()).writer()?;
@@ -146,7 +239,37 @@ pub fn run(argv: &[&str]) -> CliResult<()> {
     rconfig.write_headers(&mut rdr, &mut wtr)?;
     let mut dupe_count = 0_usize;
 
-    if args.flag_sorted {
+    // Without --sorted, consult the stats catalog: a single selected column it proves
+    // ascending under the same comparator needs no sort, and one it also proves unique
+    // needs no deduping either. Only the Normal and Numeric comparators match the cache's
+    // semantics, and byte-equal cardinality only proves uniqueness for Normal.
+    // Disable with QSV_STATSCACHE_MODE=none.
+    let numeric = matches!(compare_mode, ComparisonMode::Numeric);
+    let catalog = if args.flag_sorted
+        || matches!(
+            compare_mode,
+            ComparisonMode::IgnoreCase | ComparisonMode::Collated(_)
+        ) {
+        None
+    } else {
+        StatsCatalog::load(
+            args.arg_input.as_deref(),
+            args.flag_no_headers,
+            args.flag_delimiter,
+        )
+    };
+    let proven_sorted = catalog
+        .as_ref()
+        .is_some_and(|c| c.proves_ascending(&sel, numeric, false));
+    let proven_unique = !numeric && catalog.as_ref().is_some_and(|c| c.proves_unique(&sel));
+
+    if proven_sorted && proven_unique {
+        // sorted and duplicate-free: the output is the input
+        let mut record = ByteRecord::new();
+        while rdr.read_byte_record(&mut record)? {
+            wtr.write_byte_record(&record)?;
+        }
+    } else if args.flag_sorted {
         let mut record = ByteRecord::new();
         let mut next_record = ByteRecord::new();
 
@@ -166,6 +289,7 @@ pub fn run(argv: &[&str]) -> CliResult<()> {
                     ComparisonMode::Normal => iter_cmp(a, b),
                     ComparisonMode::Numeric => iter_cmp_num(a, b),
                     ComparisonMode::IgnoreCase => iter_cmp_ignore_case(a, b),
+                    ComparisonMode::Collated(collation) => collation.iter_cmp(a, b),
                 };
                 match comparison {
                     Ordering::Equal => {
@@ -207,6 +331,8 @@ pub fn run(argv: &[&str]) -> CliResult<()> {
 
         let mut all = rdr.byte_records().collect::<Result<Vec<_>, _>>()?;
         match compare_mode {
+            // already in order - a stable sort would not move anything
+            _ if proven_sorted => {},
             ComparisonMode::Normal => {
                 all.par_sort_by(|r1, r2| {
                     let a = sel.select(r1);
@@ -228,6 +354,9 @@ pub fn run(argv: &[&str]) -> CliResult<()> {
                     iter_cmp_ignore_case(a, b)
                 });
             },
+            ComparisonMode::Collated(collation) => {
+                all.par_sort_by(|r1, r2| collation.iter_cmp(sel.select(r1), sel.select(r2)));
+            },
         }
 
         // Hoist comparison dispatch out of the row loop: pick the cmp once,
@@ -255,6 +384,9 @@ pub fn run(argv: &[&str]) -> CliResult<()> {
             ComparisonMode::Normal => scan_dedup!(iter_cmp),
             ComparisonMode::Numeric => scan_dedup!(iter_cmp_num),
             ComparisonMode::IgnoreCase => scan_dedup!(iter_cmp_ignore_case),
+            ComparisonMode::Collated(collation) => {
+                scan_dedup!(|a, b| collation.iter_cmp(a, b));
+            },
         }
     }
 
@@ -263,8 +395,13 @@ pub fn run(argv: &[&str]) -> CliResult<()> {
     }
     wtr.flush()?;
 
+    print_dupe_count(&args, dupe_count);
+    Ok(())
+}
+
+fn print_dupe_count(args: &Args, dupe_count: usize) {
     if args.flag_quiet {
-        return Ok(());
+        return;
     }
 
     if args.flag_human_readable {
@@ -274,6 +411,50 @@ pub fn run(argv: &[&str]) -> CliResult<()> {
     } else {
         eprintln!("{dupe_count}");
     }
+}
 
-    Ok(())
+/// Cluster near-duplicates with --fuzzy, and write every row with its cluster ID.
+/// Returns the number of rows that aren't the first of their cluster.
+#[cfg(feature = "apply")]
+fn fuzzy_dedup(args: &Args, rconfig: &Config) -> CliResult<usize> {
+    let mut rdr = rconfig.reader()?;
+    let headers = rdr.byte_headers()?.clone();
+    // --select is set unless it selects every column
+    if rconfig.selection(&headers)?.len() != headers.len() {
+        return fail_incorrectusage_clierror!(
+            "--fuzzy cannot be combined with --select. Name the compared columns in the --fuzzy \
+             rules instead."
+        );
+    }
+    let fuzzy = FuzzyDedup::new(
+        args.flag_fuzzy.as_deref().unwrap_or_default(),
+        args.flag_block.as_deref(),
+        &headers,
+        args.flag_no_headers,
+        args.flag_ignore_case,
+    )?;
+
+    if let Some(path) = rconfig.resolved_path()? {
+        util::mem_file_check(&path, false, args.flag_memcheck)?;
+    }
+    util::njobs(args.flag_jobs);
+
+    let records = rdr.byte_records().collect::<Result<Vec<_>, _>>()?;
+    let clusters = fuzzy.clusters(&records);
+    let cluster_count = clusters.iter().max().copied().unwrap_or_default();
+
+    let mut wtr = Config::new(args.flag_output.as_ref()).writer()?;
+    if !args.flag_no_headers {
+        let mut headers = headers;
+        headers.push_field(args.flag_cluster_column.as_bytes());
+        wtr.write_byte_record(&headers)?;
+    }
+    let mut buffer = itoa::Buffer::new();
+    let record_count = records.len();
+    for (mut record, cluster) in records.into_iter().zip(clusters) {
+        record.push_field(buffer.format(cluster).as_bytes());
+        wtr.write_byte_record(&record)?;
+    }
+    wtr.flush()?;
+    Ok(record_count - cluster_count)
 }
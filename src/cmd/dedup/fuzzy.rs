@@ -0,0 +1,243 @@
+//! Near-duplicate detection for `dedup --fuzzy`.
+//!
+//! Two rows are near-duplicates when the values of every --fuzzy rule's column are similar
+//! enough, using the similarity metrics of `apply operations`. Comparing every pair of rows
+//! is quadratic, so rows are usually grouped into blocks by their --block column values, and
+//! only rows in the same block are compared.
+//!
+//! Near-duplicates are clustered transitively: if A matches B and B matches C, all three
+//! are one cluster, even if A and C aren't similar enough themselves. Since blocks never
+//! share rows, each block is clustered on its own, in parallel.
+
+use eudex::Hash;
+use foldhash::{HashMap, HashMapExt};
+use rayon::prelude::*;
+
+use crate::{
+    CliResult,
+    select::SelectColumns,
+    similarity::{Metric, sounds_alike},
+    util,
+};
+
+struct Rule {
+    column:    usize,
+    metric:    Metric,
+    threshold: f64,
+}
+
+/// A rule's value of a row: the trimmed field, and its eudex hash for eudex rules.
+/// None for empty fields, which are never similar to anything.
+type RuleValue = Option<(String, Option<Hash>)>;
+
+pub struct FuzzyDedup {
+    rules:       Vec<Rule>,
+    block:       Vec<usize>,
+    ignore_case: bool,
+}
+
+/// Resolve a column spec against `headers`, as in `qsv select`.
+fn select_columns(
+    spec: &str,
+    headers: &csv::ByteRecord,
+    no_headers: bool,
+) -> Result<Vec<usize>, String> {
+    let selection = SelectColumns::parse(spec)?.selection(headers, !no_headers)?;
+    Ok(selection.to_vec())
+}
+
+impl FuzzyDedup {
+    /// Parse the --fuzzy rules and resolve their columns, and the --block columns, against
+    /// `headers`.
+    ///
+    /// Rules are comma-separated `<column>:<metric>[:<threshold>]`, where the metric is
+    /// simjw, simdln, simsd (which need a threshold between 0.0 and 1.0) or eudex.
+    pub fn new(
+        spec: &str,
+        block: Option<&str>,
+        headers: &csv::ByteRecord,
+        no_headers: bool,
+        ignore_case: bool,
+    ) -> CliResult<Self> {
+        let mut rules = Vec::new();
+        for rule_spec in spec.split(',').map(str::trim) {
+            let mut parts: Vec<&str> = rule_spec.rsplitn(3, ':').collect();
+            parts.reverse();
+            // the threshold is optional, so a column name may end up in the metric's place
+            let (column, metric, threshold) = match parts.as_slice() {
+                [column, metric, threshold] if threshold.trim().parse::<f64>().is_ok() => (
+                    column.to_string(),
+                    *metric,
+                    threshold.trim().parse::<f64>().ok(),
+                ),
+                [column @ .., metric] if !column.is_empty() => (column.join(":"), *metric, None),
+                _ => {
+                    return fail_incorrectusage_clierror!(
+                        "Invalid --fuzzy rule \"{rule_spec}\". Expected \
+                         <column>:<metric>[:<threshold>], e.g. name:simjw:0.9."
+                    );
+                },
+            };
+            let metric = match metric.parse::<Metric>() {
+                Ok(metric) => metric,
+                Err(valid) => {
+                    return fail_incorrectusage_clierror!(
+                        "Invalid --fuzzy metric \"{}\" in rule \"{rule_spec}\". {valid}",
+                        metric.trim()
+                    );
+                },
+            };
+            let threshold = match (metric, threshold) {
+                (Metric::Eudex, None) => 0.0,
+                (Metric::Eudex, Some(_)) => {
+                    return fail_incorrectusage_clierror!(
+                        "The eudex metric doesn't take a threshold in --fuzzy rule \
+                         \"{rule_spec}\"."
+                    );
+                },
+                (_, Some(t)) if (0.0..=1.0).contains(&t) => t,
+                _ => {
+                    return fail_incorrectusage_clierror!(
+                        "--fuzzy rule \"{rule_spec}\" needs a threshold between 0.0 and 1.0."
+                    );
+                },
+            };
+            let column = match select_columns(&column, headers, no_headers) {
+                Ok(columns) if columns.len() == 1 => columns[0],
+                Ok(_) => {
+                    return fail_incorrectusage_clierror!(
+                        "--fuzzy rule \"{rule_spec}\" must select exactly one column."
+                    );
+                },
+                Err(e) => {
+                    return fail_incorrectusage_clierror!(
+                        "Invalid column in --fuzzy rule \"{rule_spec}\": {e}"
+                    );
+                },
+            };
+            rules.push(Rule {
+                column,
+                metric,
+                threshold,
+            });
+        }
+
+        let block = match block {
+            Some(block) => match select_columns(block, headers, no_headers) {
+                Ok(columns) => columns,
+                Err(e) => return fail_incorrectusage_clierror!("Invalid --block columns: {e}"),
+            },
+            None => Vec::new(),
+        };
+
+        Ok(Self {
+            rules,
+            block,
+            ignore_case,
+        })
+    }
+
+    fn normalize(&self, field: &[u8]) -> String {
+        let field = util::bytes_to_cow_str(field);
+        if self.ignore_case {
+            field.trim().to_lowercase()
+        } else {
+            field.trim().to_string()
+        }
+    }
+
+    fn rule_values(&self, record: &csv::ByteRecord) -> Vec<RuleValue> {
+        self.rules
+            .iter()
+            .map(|rule| {
+                let text = self.normalize(record.get(rule.column).unwrap_or_default());
+                if text.is_empty() {
+                    return None;
+                }
+                let hash = (rule.metric == Metric::Eudex).then(|| Hash::new(&text));
+                Some((text, hash))
+            })
+            .collect()
+    }
+
+    /// Are two rows with these rule values near-duplicates?
+    fn is_match(&self, a: &[RuleValue], b: &[RuleValue]) -> bool {
+        self.rules.iter().zip(a).zip(b).all(|((rule, a), b)| {
+            let (Some((a, a_hash)), Some((b, b_hash))) = (a, b) else {
+                return false;
+            };
+            match (rule.metric, a_hash, b_hash) {
+                // eudex rules compare the hashes computed once per row
+                (Metric::Eudex, Some(a_hash), Some(b_hash)) => sounds_alike(*a_hash, *b_hash),
+                (metric, ..) => metric.score(a, b) >= rule.threshold,
+            }
+        })
+    }
+
+    /// The cluster ID of every record. Clusters are numbered from 1 in the order of their
+    /// first rows, so rows without near-duplicates get a cluster of their own.
+    pub fn clusters(&self, records: &[csv::ByteRecord]) -> Vec<usize> {
+        let values: Vec<Vec<RuleValue>> = records
+            .par_iter()
+            .map(|record| self.rule_values(record))
+            .collect();
+
+        // the rows of each block, in input order
+        let mut blocks: HashMap<Vec<String>, Vec<usize>> = HashMap::new();
+        if self.block.is_empty() {
+            log::warn!("fuzzy dedup without --block compares every row with every other row");
+        }
+        for (i, record) in records.iter().enumerate() {
+            let key = self
+                .block
+                .iter()
+                .map(|&column| self.normalize(record.get(column).unwrap_or_default()))
+                .collect();
+            blocks.entry(key).or_default().push(i);
+        }
+
+        // the first row of the cluster of each row
+        let mut roots: Vec<usize> = (0..records.len()).collect();
+        let block_roots: Vec<Vec<(usize, usize)>> = blocks
+            .into_par_iter()
+            .map(|(_, rows)| {
+                // union-find over the block's rows, whose roots are the smallest positions
+                let mut parent: Vec<usize> = (0..rows.len()).collect();
+                let find = |parent: &mut Vec<usize>, mut i: usize| {
+                    while parent[i] != i {
+                        parent[i] = parent[parent[i]];
+                        i = parent[i];
+                    }
+                    i
+                };
+                for j in 1..rows.len() {
+                    for i in 0..j {
+                        let (root_i, root_j) = (find(&mut parent, i), find(&mut parent, j));
+                        if root_i != root_j && self.is_match(&values[rows[i]], &values[rows[j]]) {
+                            parent[root_i.max(root_j)] = root_i.min(root_j);
+                        }
+                    }
+                }
+                (0..rows.len())
+                    .map(|i| (rows[i], rows[find(&mut parent, i)]))
+                    .collect()
+            })
+            .collect();
+        for (row, root) in block_roots.into_iter().flatten() {
+            roots[row] = root;
+        }
+
+        let mut cluster_ids = vec![0; records.len()];
+        let mut clusters = 0;
+        for (row, &root) in roots.iter().enumerate() {
+            if root == row {
+                clusters += 1;
+                cluster_ids[row] = clusters;
+            } else {
+                // a root comes before the rest of its cluster
+                cluster_ids[row] = cluster_ids[root];
+            }
+        }
+        cluster_ids
+    }
+}
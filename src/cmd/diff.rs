@@ -52,6 +52,27 @@ qsv diff --no-headers-output left.csv right.csv
 # Find the difference when both CSVs have no headers (generic headers _col_1, _col_2, etc. are used)
 qsv diff --no-headers-left --no-headers-right left.csv right.csv
 
+# Use the same column selection syntax as `qsv select` for the key
+qsv diff --key 'id,/^region_/' left.csv right.csv
+
+# Find the difference when the right CSV has the same columns in a different order
+qsv diff --align-columns --key id left.csv right.csv
+
+# Compare numeric and date columns by value, so 1.0 and 1.00 are not reported as changes
+qsv diff --typed --key id left.csv right.csv
+
+# Same as above, but treat prices within a cent and timestamps within a minute as equal
+qsv diff --tolerance 'price=0.01,updated_at=60' --key id left.csv right.csv
+
+# Emit one JSON change record per line with before/after values
+qsv diff --format jsonl --key id left.csv right.csv
+
+# Emit a JSON Patch (RFC 6902) document that can be replayed against the left CSV
+qsv diff --format jsonpatch --key id left.csv right.csv
+
+# Diff two CSVs that are too large to fit in memory, sorting them on disk by key first
+qsv diff --external --memory-limit 2000 --tmp-dir /scratch --key id left.csv right.csv
+
 For more examples, see https://github.com/dathere/qsv/blob/master/tests/test_diff.rs
 See also https://github.com/dathere/qsv/wiki/Indexing-Compression-Diff#diff
 
@@ -77,17 +98,18 @@ diff options:
                                 Must be a single character. (default: ,)
     --delimiter-output <arg>    The field delimiter for writing the CSV diff result.
                                 Must be a single character. (default: ,)
-    -k, --key <arg...>          The column indices that uniquely identify a record
-                                as a comma separated list of 0-based indices, e.g. 0,1,2
-                                or column names, e.g. name,age.
-                                Note that when selecting columns by name, only the
-                                left CSV's headers are used to match the column names
-                                and it is assumed that the right CSV has the same
-                                selected column names in the same order as the left CSV.
+    -k, --key <arg...>          The columns that uniquely identify a record.
+                                A plain comma separated list of numbers is taken as
+                                0-based indices, e.g. 0,1,2. Anything else uses the
+                                same selection syntax as `qsv select` (column names,
+                                1-based ranges, name[n] for duplicate names and
+                                /regex/), e.g. name,age or 'id,/^region_/'.
+                                See 'qsv select --help' for the full syntax.
+                                The selection must resolve to the same column
+                                positions on both CSVs, unless --align-columns is set.
                                 (default: 0)
-    --sort-columns <arg...>     The column indices by which the diff result should be
-                                sorted as a comma separated list of indices, e.g. 0,1,2
-                                or column names, e.g. name,age.
+    --sort-columns <arg...>     The columns by which the diff result should be
+                                sorted. Uses the same syntax as --key.
                                 Records in the diff result that are marked as "modified"
                                 ("delete" and "add" records that have the same key,
                                 but have different content) will always be kept together
@@ -105,6 +127,60 @@ diff options:
                                 column, if it differs in any modified row, or if it has
                                 a non-empty value in any added or deleted row. Otherwise
                                 it is dropped. Can be combined with --drop-equal-fields.
+    --align-columns             Match the right CSV's columns to the left CSV's by
+                                header name instead of by position, so two CSVs with
+                                the same columns in a different order can be diffed.
+                                Both CSVs must have headers with the same set of
+                                column names. The diff result uses the left CSV's
+                                column order.
+    --typed                     Compare numeric and date fields by value instead of
+                                as raw bytes, so 1.0 and 1.00, or 2024-01-05 and
+                                Jan 5, 2024 are not reported as changes.
+                                Column types are taken from the left CSV's stats cache
+                                when a current one exists (see `qsv stats --help`).
+                                Otherwise, a pair of fields is compared as numbers
+                                when both parse as numbers, and as dates when both
+                                parse as dates. Numbers with leading zeroes
+                                (e.g. zip codes) are always compared as text.
+    --tolerance <arg>           Like --typed, but also treat typed fields as equal when
+                                they differ by at most this amount. Either a single
+                                tolerance for all typed columns (e.g. 0.001) or a comma
+                                separated list of <columns>=<tolerance> entries,
+                                with columns given in the same syntax as --key
+                                (e.g. price=0.01,updated_at=60 or
+                                price,cost=0.01). Columns without an entry are
+                                compared with a tolerance of zero.
+                                For date columns, the tolerance is in seconds.
+    --format <arg>              The output format of the diff result.
+                                  csv - the `diffresult` CSV described above.
+                                  jsonl - one JSON change record per line, with "op"
+                                      (add, delete or modify), "key", "before" and
+                                      "after" objects. Modified records also list
+                                      their "changed" columns.
+                                  jsonpatch - a JSON Patch (RFC 6902) document keyed by
+                                      "/<key>", with the values of a composite key
+                                      joined as a CSV record ("/<key1>,<key2>").
+                                      Deleted records and modified fields are
+                                      preceded by a "test" operation with their old
+                                      values, so replaying the patch against a
+                                      changed base fails loudly.
+                                [default: csv]
+    --external                  Diff CSVs that are larger than memory. Both CSVs are
+                                sorted by key with an external merge sort on disk and
+                                then compared in a single streaming pass, so memory use
+                                is bounded by --memory-limit instead of the CSV size.
+                                The diff result is in key order (sorted bytewise on
+                                the key fields) rather than in line order.
+                                Cannot be combined with --sort-columns or
+                                --drop-equal-columns.
+    --memory-limit <arg>        With --external, the memory to buffer the external
+                                merge sort of each CSV.
+                                If less than 50, this is a percentage of total memory.
+                                If more than 50, this is the memory in MB to allocate,
+                                capped at 90 percent of total memory.
+                                Defaults to 100 MB.
+    --tmp-dir <arg>             With --external, the directory to use for externally
+                                sorting file segments. [default: ./]
     -j, --jobs <arg>            The number of jobs to run in parallel.
                                 When not set, the number of jobs is set to the number
                                 of CPUs detected.
@@ -117,21 +193,27 @@ Common options:
                                 and --delimiter-output.
 "#;
 
-use std::io::{self, Write};
+use std::{
+    cmp::Ordering,
+    io::{self, Write},
+    path,
+    str::FromStr,
+};
 
 use csv::ByteRecord;
-use csv_diff::{
-    csv_diff::CsvByteDiffBuilder, csv_headers::Headers, diff_result::DiffByteRecords,
-    diff_row::DiffByteRecord,
-};
-use serde::Deserialize;
+use csv_diff::{csv_diff::CsvByteDiffBuilder, csv_headers::Headers, diff_row::DiffByteRecord};
+use ext_sort::{ExternalSorter, ExternalSorterBuilder, LimitedBufferBuilder};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value, json};
 
 use super::rename::rename_headers_all_generic;
 use crate::{
     CliResult,
     clitypes::CliError,
+    cmd::{extdedup::calculate_memory_limit, stats::StatsData},
     config::{Config, Delimiter},
-    util,
+    select::SelectColumns,
+    util::{self, ByteString},
 };
 
 #[derive(Deserialize)]
@@ -150,9 +232,40 @@ struct Args {
     flag_sort_columns:       Option<String>,
     flag_drop_equal_fields:  bool,
     flag_drop_equal_columns: bool,
+    flag_align_columns:      bool,
+    flag_typed:              bool,
+    flag_tolerance:          Option<String>,
+    flag_format:             String,
+    flag_external:           bool,
+    flag_memory_limit:       Option<u64>,
+    flag_tmp_dir:            Option<String>,
     flag_delimiter:          Option<Delimiter>,
 }
 
+const RW_BUFFER_CAPACITY: usize = 1_000_000; // 1 MB
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum DiffFormat {
+    Csv,
+    Jsonl,
+    JsonPatch,
+}
+
+impl FromStr for DiffFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "csv" => Ok(DiffFormat::Csv),
+            "jsonl" => Ok(DiffFormat::Jsonl),
+            "jsonpatch" => Ok(DiffFormat::JsonPatch),
+            _ => Err(format!(
+                "Invalid --format '{s}'. Must be one of: csv, jsonl, jsonpatch"
+            )),
+        }
+    }
+}
+
 pub fn run(argv: &[&str]) -> CliResult<()> {
     let mut args: Args = util::get_args(USAGE, argv)?;
 
@@ -164,11 +277,13 @@ pub fn run(argv: &[&str]) -> CliResult<()> {
         ] = [Some(delim); 3];
     }
 
+    let diff_format: DiffFormat = args.flag_format.parse().map_err(CliError::IncorrectUsage)?;
+
     let rconfig_left = Config::new(args.arg_input_left.as_ref())
         .delimiter(args.flag_delimiter_left)
         .no_headers(args.flag_no_headers_left);
 
-    let rconfig_right = Config::new(args.arg_input_right.as_ref())
+    let mut rconfig_right = Config::new(args.arg_input_right.as_ref())
         .delimiter(args.flag_delimiter_right)
         .no_headers(args.flag_no_headers_right);
 
@@ -179,27 +294,99 @@ pub fn run(argv: &[&str]) -> CliResult<()> {
     }
 
     let mut csv_rdr_left = rconfig_left.reader()?;
-    let mut csv_rdr_right = rconfig_right.reader()?;
+    let headers_left = csv_rdr_left.byte_headers()?.clone();
+
+    // With --align-columns, the right CSV is rewritten into a temp file whose columns
+    // follow the left CSV's order, so that csv-diff (which compares by position) can be
+    // used unchanged. The temp file must outlive the diff, hence it is bound here.
+    let _aligned_right_file = if args.flag_align_columns {
+        if args.flag_no_headers_left || args.flag_no_headers_right {
+            return fail_incorrectusage_clierror!(
+                "--align-columns matches columns by header name, so both CSVs must have headers."
+            );
+        }
+        let aligned = align_right_to_left(&rconfig_right, &headers_left)?;
+        rconfig_right = Config::new(Some(&aligned.path().to_string_lossy().into_owned()))
+            .delimiter(Some(Delimiter(b',')))
+            .no_headers(false);
+        Some(aligned)
+    } else {
+        None
+    };
 
-    let headers_left = csv_rdr_left.byte_headers()?;
-    let headers_right = csv_rdr_right.byte_headers()?;
+    let mut csv_rdr_right = rconfig_right.reader()?;
+    let headers_right = csv_rdr_right.byte_headers()?.clone();
 
     let primary_key_cols: Vec<usize> = match args.flag_key {
         None => vec![0],
-        Some(s) => parse_indices_or_colnames(&s, headers_left, headers_right, "key")?,
+        Some(ref s) => parse_indices_or_colnames(
+            s,
+            (&headers_left, args.flag_no_headers_left),
+            (&headers_right, args.flag_no_headers_right),
+            "key",
+        )?,
     };
 
     let sort_cols = args
         .flag_sort_columns
         .as_deref()
-        .map(|s| parse_indices_or_colnames(s, headers_left, headers_right, "sort"))
+        .map(|s| {
+            parse_indices_or_colnames(
+                s,
+                (&headers_left, args.flag_no_headers_left),
+                (&headers_right, args.flag_no_headers_right),
+                "sort",
+            )
+        })
         .transpose()?;
 
-    let wtr = Config::new(args.flag_output.as_ref())
-        .delimiter(args.flag_delimiter_output)
-        .writer()?;
+    let typed_comparator = if args.flag_typed || args.flag_tolerance.is_some() {
+        let (default_tolerance, col_tolerances) = match args.flag_tolerance {
+            Some(ref spec) => parse_tolerance(spec, &headers_left, args.flag_no_headers_left)?,
+            None => (0.0, Vec::new()),
+        };
+        // the stats cache is keyed on the left CSV, whose column order is also the
+        // order of the diff result (the right CSV either matches it or was aligned to it)
+        let stats = util::get_stats_records_readonly(
+            args.arg_input_left.as_deref(),
+            args.flag_no_headers_left,
+            args.flag_delimiter_left,
+        );
+        Some(TypedComparator::new(
+            headers_left.len(),
+            stats.as_deref(),
+            default_tolerance,
+            &col_tolerances,
+        ))
+    } else {
+        None
+    };
+
+    let njobs = util::njobs(args.flag_jobs);
 
-    util::njobs(args.flag_jobs);
+    if args.flag_external {
+        if sort_cols.is_some() || args.flag_drop_equal_columns {
+            return fail_incorrectusage_clierror!(
+                "--sort-columns and --drop-equal-columns are not supported with --external."
+            );
+        }
+        let header = if !args.flag_no_headers_left {
+            Some(headers_left)
+        } else if !args.flag_no_headers_right {
+            Some(headers_right)
+        } else {
+            generic_headers(headers_left.len())?
+        };
+        return diff_external(
+            &args,
+            diff_format,
+            (&rconfig_left, &rconfig_right),
+            header,
+            primary_key_cols,
+            typed_comparator.as_ref(),
+            njobs,
+        );
+    }
 
     // ===== DIFF PROCESSING =====
 
@@ -223,29 +410,41 @@ pub fn run(argv: &[&str]) -> CliResult<()> {
         },
     }
 
+    let num_cols = diff_byte_records.num_columns().unwrap_or(0);
+    let header = resolve_headers(diff_byte_records.headers(), num_cols)?;
+
+    // csv-diff compares raw bytes. With --typed/--tolerance, re-check the fields it
+    // flagged as modified and drop the modifications that are equal by value.
+    let diff_records: Vec<DiffRow> = match typed_comparator {
+        Some(ref cmp) => diff_byte_records
+            .into_iter()
+            .filter_map(|dbr| cmp.refine(dbr.into()))
+            .collect(),
+        None => diff_byte_records.into_iter().map(DiffRow::from).collect(),
+    };
+
     // When --drop-equal-columns is set, compute which columns to keep with a single
     // borrow-pass over the (already fully buffered) diff records. A column is kept if
     // it is a key column, if it differs in any modified row, or if it has a non-empty
     // value in any added or deleted row.
     let keep_cols: Option<Vec<usize>> = if args.flag_drop_equal_columns {
-        let num_cols = diff_byte_records.num_columns().unwrap_or(0);
         let mut keep = vec![false; num_cols];
         for &k in &primary_key_cols {
             if k < num_cols {
                 keep[k] = true;
             }
         }
-        for dbr in diff_byte_records.iter() {
+        for dbr in &diff_records {
             match dbr {
-                DiffByteRecord::Modify { field_indices, .. } => {
+                DiffRow::Modify { field_indices, .. } => {
                     for &fi in field_indices {
                         if fi < num_cols {
                             keep[fi] = true;
                         }
                     }
                 },
-                DiffByteRecord::Add(rec) | DiffByteRecord::Delete(rec) => {
-                    for (i, field) in rec.byte_record().iter().enumerate() {
+                DiffRow::Add(rec) | DiffRow::Delete(rec) => {
+                    for (i, field) in rec.iter().enumerate() {
                         if i < num_cols && !field.is_empty() {
                             keep[i] = true;
                         }
@@ -263,55 +462,351 @@ pub fn run(argv: &[&str]) -> CliResult<()> {
         None
     };
 
-    let mut csv_diff_writer = CsvDiffWriter::new(
-        wtr,
-        args.flag_no_headers_output,
-        args.flag_drop_equal_fields,
+    write_diff_result(
+        &args,
+        diff_format,
+        header,
+        num_cols,
         primary_key_cols,
         keep_cols,
+        diff_records.into_iter().map(Ok),
+    )
+}
+
+/// Write the diff result in the requested `--format`.
+fn write_diff_result(
+    args: &Args,
+    diff_format: DiffFormat,
+    header: Option<ByteRecord>,
+    num_cols: usize,
+    primary_key_cols: Vec<usize>,
+    keep_cols: Option<Vec<usize>>,
+    diff_records: impl IntoIterator<Item = io::Result<DiffRow>>,
+) -> CliResult<()> {
+    match diff_format {
+        DiffFormat::Csv => {
+            let wtr = Config::new(args.flag_output.as_ref())
+                .delimiter(args.flag_delimiter_output)
+                .writer()?;
+            let mut csv_diff_writer = CsvDiffWriter::new(
+                wtr,
+                args.flag_no_headers_output,
+                args.flag_drop_equal_fields,
+                primary_key_cols,
+                keep_cols,
+            );
+            Ok(csv_diff_writer.write_diff_byte_records(header.as_ref(), diff_records)?)
+        },
+        DiffFormat::Jsonl | DiffFormat::JsonPatch => {
+            let wtr = util::create_json_writer(args.flag_output.as_ref(), 0)?;
+            let column_names: Vec<String> = match header {
+                Some(ref h) => h
+                    .iter()
+                    .map(|name| String::from_utf8_lossy(name).into_owned())
+                    .collect(),
+                None => (0..num_cols).map(|i| i.to_string()).collect(),
+            };
+            let mut json_diff_writer = JsonDiffWriter {
+                wtr,
+                column_names,
+                key_fields: primary_key_cols,
+                drop_equal_fields: args.flag_drop_equal_fields,
+                keep_cols,
+            };
+            Ok(json_diff_writer.write_diff_byte_records(diff_records, diff_format)?)
+        },
+    }
+}
+
+/// `--external`: sort both CSVs by key on disk, then merge the two sorted streams.
+fn diff_external(
+    args: &Args,
+    diff_format: DiffFormat,
+    (rconfig_left, rconfig_right): (&Config, &Config),
+    header: Option<ByteRecord>,
+    primary_key_cols: Vec<usize>,
+    typed_comparator: Option<&TypedComparator>,
+    njobs: usize,
+) -> CliResult<()> {
+    let tmp_dir = match args.flag_tmp_dir {
+        Some(ref tmp_dir) => {
+            if !path::Path::new(tmp_dir).exists() {
+                return fail_clierror!("tmp-dir '{tmp_dir}' does not exist");
+            }
+            tmp_dir.clone()
+        },
+        None => "./".to_string(),
+    };
+
+    // the sort buffer is limited by element count, so convert the memory limit
+    // using the estimated in-memory size of one keyed record
+    let mem_limited_buffer_bytes = calculate_memory_limit(args.flag_memory_limit);
+    let record_bytes = estimate_keyed_record_bytes(rconfig_left, primary_key_cols.len());
+    let buffer_records = (mem_limited_buffer_bytes as usize / record_bytes).max(1);
+    log::info!(
+        "{mem_limited_buffer_bytes} bytes ({buffer_records} records) used for in memory mergesort \
+         buffer..."
     );
-    Ok(csv_diff_writer.write_diff_byte_records(diff_byte_records)?)
+
+    let sorter: KeyedRecordSorter = match ExternalSorterBuilder::new()
+        .with_tmp_dir(path::Path::new(&tmp_dir))
+        .with_buffer(LimitedBufferBuilder::new(buffer_records, false))
+        .with_rw_buf_size(RW_BUFFER_CAPACITY)
+        .with_threads_number(njobs)
+        .build()
+    {
+        Ok(sorter) => sorter,
+        Err(e) => {
+            return fail_clierror!("cannot create external sorter: {e}");
+        },
+    };
+
+    let sorted_left = sort_by_key(&sorter, rconfig_left, &primary_key_cols)?;
+    let sorted_right = sort_by_key(&sorter, rconfig_right, &primary_key_cols)?;
+    let num_cols = header.as_ref().map_or(0, ByteRecord::len);
+
+    let merged = MergeDiff {
+        left: sorted_left,
+        right: sorted_right,
+        next_left: None,
+        next_right: None,
+        started: false,
+        typed_comparator,
+    };
+    write_diff_result(
+        args,
+        diff_format,
+        header,
+        num_cols,
+        primary_key_cols,
+        None,
+        merged,
+    )
 }
 
-trait StrExt {
-    fn col_names_to_indices(
-        &self,
-        col_names_split_by: char,
-        headers: &ByteRecord,
-        msg_left_or_right: &str,
-    ) -> Result<Vec<usize>, CliError>;
+/// A record with its key fields copied out front, as sorted by `--external`.
+#[derive(Default, Serialize, Deserialize)]
+struct KeyedRecord {
+    key:    Vec<ByteString>,
+    fields: Vec<ByteString>,
 }
 
-impl StrExt for str {
-    fn col_names_to_indices(
-        &self,
-        col_names_split_by: char,
-        headers: &ByteRecord,
-        msg_left_or_right: &str,
-    ) -> Result<Vec<usize>, CliError> {
-        self.split(col_names_split_by)
-            .map(|col_name| {
-                headers
-                    .iter()
-                    .position(|h| h == col_name.as_bytes())
-                    .ok_or_else(|| {
-                        CliError::Other(format!(
-                            "Column name '{col_name}' not found on {msg_left_or_right} CSV"
-                        ))
-                    })
+type KeyedRecordSorter = ExternalSorter<KeyedRecord, io::Error, LimitedBufferBuilder>;
+
+/// Estimate the in-memory size of a `KeyedRecord` from a sample of the CSV.
+fn estimate_keyed_record_bytes(rconfig: &Config, num_key_cols: usize) -> usize {
+    const FIELD_OVERHEAD: usize = std::mem::size_of::<ByteString>();
+    util::sample_records(rconfig, 1_000).map_or(1_024, |samples| {
+        let total: usize = samples
+            .iter()
+            .map(|rec| rec.as_slice().len() + rec.len() * FIELD_OVERHEAD)
+            .sum();
+        // + the key copy, roughly sized as its share of the fields
+        let avg = total / samples.len();
+        let key_share = avg * num_key_cols / samples[0].len().max(1);
+        (avg + key_share + num_key_cols * FIELD_OVERHEAD).max(1)
+    })
+}
+
+fn sort_by_key(
+    sorter: &KeyedRecordSorter,
+    rconfig: &Config,
+    key_cols: &[usize],
+) -> CliResult<impl Iterator<Item = io::Result<KeyedRecord>> + use<>> {
+    let key_cols = key_cols.to_vec();
+    let records = rconfig.reader()?.into_byte_records().map(move |rec| {
+        let rec = rec.map_err(io::Error::other)?;
+        Ok(KeyedRecord {
+            key:    key_cols
+                .iter()
+                .map(|&k| rec.get(k).unwrap_or_default().to_vec())
+                .collect(),
+            fields: rec.iter().map(<[u8]>::to_vec).collect(),
+        })
+    });
+    match sorter.sort_by(records, |a: &KeyedRecord, b: &KeyedRecord| {
+        a.key.cmp(&b.key)
+    }) {
+        Ok(sorted) => Ok(sorted.map(|item| item.map_err(io::Error::other))),
+        Err(e) => fail_clierror!("cannot do external sort: {e}"),
+    }
+}
+
+/// Merges two key-sorted record streams into a stream of diff rows.
+struct MergeDiff<'a, L, R> {
+    left:             L,
+    right:            R,
+    next_left:        Option<KeyedRecord>,
+    next_right:       Option<KeyedRecord>,
+    started:          bool,
+    typed_comparator: Option<&'a TypedComparator>,
+}
+
+impl<L, R> MergeDiff<'_, L, R>
+where
+    L: Iterator<Item = io::Result<KeyedRecord>>,
+    R: Iterator<Item = io::Result<KeyedRecord>>,
+{
+    /// Take the current record of one side and read the one after it. Keys must be
+    /// unique, which in a sorted stream means no two consecutive records share a key.
+    fn take(
+        stream: &mut impl Iterator<Item = io::Result<KeyedRecord>>,
+        next: &mut Option<KeyedRecord>,
+        left_or_right: &str,
+    ) -> io::Result<Option<KeyedRecord>> {
+        let following = stream.next().transpose()?;
+        if let (Some(current), Some(following)) = (next.as_ref(), following.as_ref())
+            && current.key == following.key
+        {
+            return Err(io::Error::other(format!(
+                "Primary key values must be unique within each CSV. The {left_or_right} CSV has \
+                 duplicate key: {}",
+                String::from_utf8_lossy(&following.key.join(&b","[..]))
+            )));
+        }
+        Ok(std::mem::replace(next, following))
+    }
+
+    fn take_left(&mut self) -> io::Result<KeyedRecord> {
+        Self::take(&mut self.left, &mut self.next_left, "left").map(Option::unwrap_or_default)
+    }
+
+    fn take_right(&mut self) -> io::Result<KeyedRecord> {
+        Self::take(&mut self.right, &mut self.next_right, "right").map(Option::unwrap_or_default)
+    }
+
+    fn modified_fields(&self, left: &[ByteString], right: &[ByteString]) -> Vec<usize> {
+        (0..left.len().max(right.len()))
+            .filter(|&i| {
+                let l = left.get(i).map(Vec::as_slice).unwrap_or_default();
+                let r = right.get(i).map(Vec::as_slice).unwrap_or_default();
+                match self.typed_comparator {
+                    Some(cmp) => !cmp.fields_equal(i, l, r),
+                    None => l != r,
+                }
             })
-            .collect::<Result<Vec<usize>, _>>()
+            .collect()
     }
 }
 
-/// Parse a comma-separated list of either 0-based column indices or column names.
-/// When column names are given, both CSVs must resolve them to the same indices.
-/// `kind` is used in error messages (e.g., "key" or "sort").
-fn parse_indices_or_colnames(
-    s: &str,
+impl<L, R> Iterator for MergeDiff<'_, L, R>
+where
+    L: Iterator<Item = io::Result<KeyedRecord>>,
+    R: Iterator<Item = io::Result<KeyedRecord>>,
+{
+    type Item = io::Result<DiffRow>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if !self.started {
+            self.started = true;
+            // prime both sides
+            if let Err(e) = self.take_left().and_then(|_| self.take_right()) {
+                return Some(Err(e));
+            }
+        }
+        loop {
+            let ordering = match (&self.next_left, &self.next_right) {
+                (None, None) => return None,
+                (Some(_), None) => Ordering::Less,
+                (None, Some(_)) => Ordering::Greater,
+                (Some(l), Some(r)) => l.key.cmp(&r.key),
+            };
+            let row = match ordering {
+                Ordering::Less => self
+                    .take_left()
+                    .map(|l| DiffRow::Delete(ByteRecord::from(l.fields))),
+                Ordering::Greater => self
+                    .take_right()
+                    .map(|r| DiffRow::Add(ByteRecord::from(r.fields))),
+                Ordering::Equal => {
+                    let l = match self.take_left() {
+                        Ok(l) => l,
+                        Err(e) => return Some(Err(e)),
+                    };
+                    let r = match self.take_right() {
+                        Ok(r) => r,
+                        Err(e) => return Some(Err(e)),
+                    };
+                    let field_indices = self.modified_fields(&l.fields, &r.fields);
+                    if field_indices.is_empty() {
+                        continue;
+                    }
+                    Ok(DiffRow::Modify {
+                        delete: ByteRecord::from(l.fields),
+                        add: ByteRecord::from(r.fields),
+                        field_indices,
+                    })
+                },
+            };
+            return Some(row);
+        }
+    }
+}
+
+/// Rewrite the right CSV into a temporary CSV whose columns follow the left CSV's header
+/// order. Both CSVs must have the same set of column names; duplicate names are matched
+/// in order of appearance.
+fn align_right_to_left(
+    rconfig_right: &Config,
     headers_left: &ByteRecord,
-    headers_right: &ByteRecord,
-    kind: &str,
+) -> CliResult<tempfile::NamedTempFile> {
+    let mut rdr = rconfig_right.reader()?;
+    let headers_right = rdr.byte_headers()?.clone();
+
+    let mut taken = vec![false; headers_right.len()];
+    let mut positions: Vec<usize> = Vec::with_capacity(headers_left.len());
+    let mut missing_on_right: Vec<String> = Vec::new();
+    for name in headers_left {
+        match headers_right
+            .iter()
+            .enumerate()
+            .position(|(i, h)| !taken[i] && h == name)
+        {
+            Some(pos) => {
+                taken[pos] = true;
+                positions.push(pos);
+            },
+            None => missing_on_right.push(String::from_utf8_lossy(name).into_owned()),
+        }
+    }
+    let missing_on_left: Vec<String> = headers_right
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| !taken[*i])
+        .map(|(_, h)| String::from_utf8_lossy(h).into_owned())
+        .collect();
+    if !missing_on_right.is_empty() || !missing_on_left.is_empty() {
+        return fail_incorrectusage_clierror!(
+            "--align-columns requires both CSVs to have the same column names.\nOnly on the left \
+             CSV: {missing_on_right:?}\nOnly on the right CSV: {missing_on_left:?}"
+        );
+    }
+
+    let aligned = tempfile::Builder::new().suffix(".csv").tempfile()?;
+    let mut wtr = csv::Writer::from_writer(io::BufWriter::new(aligned.reopen()?));
+    let mut aligned_record = ByteRecord::with_capacity(0, positions.len());
+    aligned_record.extend(positions.iter().map(|&p| &headers_right[p]));
+    wtr.write_byte_record(&aligned_record)?;
+    let mut record = ByteRecord::new();
+    while rdr.read_byte_record(&mut record)? {
+        aligned_record.clear();
+        aligned_record.extend(positions.iter().map(|&p| record.get(p).unwrap_or_default()));
+        wtr.write_byte_record(&aligned_record)?;
+    }
+    wtr.flush()?;
+    Ok(aligned)
+}
+
+/// Resolve a `--key`/`--sort-columns` value against one CSV's headers.
+///
+/// A plain comma separated list of numbers keeps its original meaning of 0-based
+/// indices. Anything else is a `qsv select` style selection.
+/// Shared with `patch`, so a diff's `--key` means the same thing when patching.
+pub(crate) fn resolve_columns(
+    s: &str,
+    headers: &ByteRecord,
+    no_headers: bool,
+    msg_left_or_right: &str,
 ) -> Result<Vec<usize>, CliError> {
     if s.chars().all(|c| c.is_ascii_digit() || c == ',') {
         return s
@@ -319,12 +814,31 @@ fn parse_indices_or_colnames(
             .map(str::parse::<usize>)
             .collect::<Result<Vec<_>, _>>()
             .map_err(|err| {
-                CliError::IncorrectUsage(format!("Invalid {kind} column index list '{s}': {err}"))
+                CliError::IncorrectUsage(format!("Invalid column index list '{s}': {err}"))
             });
     }
 
-    let left_indices = s.col_names_to_indices(',', headers_left, "left")?;
-    let right_indices = s.col_names_to_indices(',', headers_right, "right")?;
+    SelectColumns::parse(s)
+        .and_then(|sel| sel.selection(headers, !no_headers))
+        .map(|sel| sel.to_vec())
+        .map_err(|e| CliError::IncorrectUsage(format!("{msg_left_or_right} CSV: {e}")))
+}
+
+/// Parse a `--key`/`--sort-columns` value against both CSVs.
+/// When column names are given, both CSVs must resolve them to the same indices.
+/// `kind` is used in error messages (e.g., "key" or "sort").
+fn parse_indices_or_colnames(
+    s: &str,
+    (headers_left, no_headers_left): (&ByteRecord, bool),
+    (headers_right, no_headers_right): (&ByteRecord, bool),
+    kind: &str,
+) -> Result<Vec<usize>, CliError> {
+    let left_indices = resolve_columns(s, headers_left, no_headers_left, "left")?;
+    let right_indices = resolve_columns(s, headers_right, no_headers_right, "right")?;
+
+    if left_indices.is_empty() {
+        return fail_incorrectusage_clierror!("The {kind} columns '{s}' selected no columns.");
+    }
 
     if left_indices != right_indices {
         return fail_incorrectusage_clierror!(
@@ -337,6 +851,250 @@ fn parse_indices_or_colnames(
     Ok(left_indices)
 }
 
+/// Parse the `--tolerance` spec into a default tolerance and per-column overrides.
+///
+/// A spec is either a single tolerance or comma separated `<selection>=<tolerance>`
+/// entries. A selection may itself contain commas (e.g. `price,cost=0.01`), so the
+/// comma separated parts are accumulated until one ends with `=<number>`.
+fn parse_tolerance(
+    spec: &str,
+    headers: &ByteRecord,
+    no_headers: bool,
+) -> Result<(f64, Vec<(usize, f64)>), CliError> {
+    let parse_one = |t: &str| -> Result<f64, CliError> {
+        match t.trim().parse::<f64>() {
+            Ok(tol) if tol.is_finite() && tol >= 0.0 => Ok(tol),
+            _ => fail_incorrectusage_clierror!(
+                "Invalid --tolerance '{t}'. Must be a non-negative number."
+            ),
+        }
+    };
+
+    if spec.trim().parse::<f64>().is_ok() {
+        return Ok((parse_one(spec)?, Vec::new()));
+    }
+
+    let mut col_tolerances = Vec::new();
+    let mut selection = String::new();
+    for part in spec.split(',') {
+        if !selection.is_empty() {
+            selection.push(',');
+        }
+        match part.rsplit_once('=') {
+            Some((cols, tol)) if tol.trim().parse::<f64>().is_ok() => {
+                selection.push_str(cols);
+                let tol = parse_one(tol)?;
+                for col in resolve_columns(&selection, headers, no_headers, "left")? {
+                    col_tolerances.push((col, tol));
+                }
+                selection.clear();
+            },
+            _ => selection.push_str(part),
+        }
+    }
+    if !selection.is_empty() {
+        return fail_incorrectusage_clierror!(
+            "Invalid --tolerance entry '{selection}'. Expected <columns>=<tolerance>."
+        );
+    }
+    Ok((0.0, col_tolerances))
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum ColumnKind {
+    Numeric,
+    Date,
+    Text,
+    // no stats cache - decided per pair of values
+    Infer,
+}
+
+/// Compares fields by value for `--typed`/`--tolerance`.
+struct TypedComparator {
+    kinds:      Vec<ColumnKind>,
+    tolerances: Vec<f64>,
+    prefer_dmy: bool,
+}
+
+impl TypedComparator {
+    fn new(
+        num_cols: usize,
+        stats: Option<&[StatsData]>,
+        default_tolerance: f64,
+        col_tolerances: &[(usize, f64)],
+    ) -> Self {
+        let kinds = match stats {
+            Some(stats) if stats.len() == num_cols => stats
+                .iter()
+                .map(|s| match s.r#type.as_str() {
+                    "Integer" | "Float" => ColumnKind::Numeric,
+                    "Date" | "DateTime" => ColumnKind::Date,
+                    // an all-NULL column has no type to go by
+                    "NULL" => ColumnKind::Infer,
+                    _ => ColumnKind::Text,
+                })
+                .collect(),
+            _ => vec![ColumnKind::Infer; num_cols],
+        };
+        let mut tolerances = vec![default_tolerance; num_cols];
+        for &(col, tol) in col_tolerances {
+            if let Some(t) = tolerances.get_mut(col) {
+                *t = tol;
+            }
+        }
+        Self {
+            kinds,
+            tolerances,
+            prefer_dmy: util::get_envvar_flag("QSV_PREFER_DMY"),
+        }
+    }
+
+    fn fields_equal(&self, col: usize, left: &[u8], right: &[u8]) -> bool {
+        if left == right {
+            return true;
+        }
+        let tolerance = self.tolerances.get(col).copied().unwrap_or_default();
+        match self.kinds.get(col).copied().unwrap_or(ColumnKind::Infer) {
+            ColumnKind::Text => false,
+            ColumnKind::Numeric => numbers_equal(left, right, tolerance).unwrap_or(false),
+            ColumnKind::Date => {
+                dates_equal(left, right, tolerance, self.prefer_dmy).unwrap_or(false)
+            },
+            // a field that looks like a number is never reinterpreted as a date
+            // (e.g. a zero-padded code as a timestamp)
+            ColumnKind::Infer if looks_numeric(left) || looks_numeric(right) => {
+                numbers_equal(left, right, tolerance).unwrap_or(false)
+            },
+            ColumnKind::Infer => {
+                dates_equal(left, right, tolerance, self.prefer_dmy).unwrap_or(false)
+            },
+        }
+    }
+
+    /// Re-check the fields of a modified record. Returns `None` when all of them are
+    /// equal by value, so the record is not a change after all.
+    fn refine(&self, dbr: DiffRow) -> Option<DiffRow> {
+        match dbr {
+            DiffRow::Modify {
+                delete,
+                add,
+                field_indices,
+            } => {
+                let field_indices: Vec<usize> = field_indices
+                    .into_iter()
+                    .filter(|&fi| {
+                        !self.fields_equal(
+                            fi,
+                            delete.get(fi).unwrap_or_default(),
+                            add.get(fi).unwrap_or_default(),
+                        )
+                    })
+                    .collect();
+                if field_indices.is_empty() {
+                    None
+                } else {
+                    Some(DiffRow::Modify {
+                        delete,
+                        add,
+                        field_indices,
+                    })
+                }
+            },
+            other => Some(other),
+        }
+    }
+}
+
+#[inline]
+fn looks_numeric(field: &[u8]) -> bool {
+    fast_float2::parse::<f64, &[u8]>(field.trim_ascii()).is_ok()
+}
+
+/// Compare two fields as numbers. Returns `None` when either is not a number,
+/// or is a zero-padded code that must keep its leading zeroes.
+fn numbers_equal(left: &[u8], right: &[u8], tolerance: f64) -> Option<bool> {
+    let parse = |field: &[u8]| -> Option<f64> {
+        let field = field.trim_ascii();
+        if field.len() > 1 && field[0] == b'0' && field[1].is_ascii_digit() {
+            return None;
+        }
+        fast_float2::parse::<f64, &[u8]>(field).ok()
+    };
+    let (l, r) = (parse(left)?, parse(right)?);
+    Some((l - r).abs() <= tolerance)
+}
+
+/// Compare two fields as dates, with the tolerance in seconds.
+/// Returns `None` when either is not a date.
+fn dates_equal(left: &[u8], right: &[u8], tolerance: f64, prefer_dmy: bool) -> Option<bool> {
+    let parse = |field: &[u8]| {
+        let s = simdutf8::basic::from_utf8(field).ok()?.trim();
+        qsv_dateparser::parse_with_preference(s, prefer_dmy).ok()
+    };
+    let (l, r) = (parse(left)?, parse(right)?);
+    let delta_ms = (l - r).num_milliseconds().unsigned_abs();
+    #[allow(clippy::cast_precision_loss)]
+    Some(delta_ms as f64 / 1000.0 <= tolerance)
+}
+
+/// Pick the header row of the diff result: the left or right CSV's headers, or
+/// generic `_col_N` headers when neither CSV has any.
+fn resolve_headers(headers: &Headers, num_columns: usize) -> csv::Result<Option<ByteRecord>> {
+    match (headers.headers_left(), headers.headers_right()) {
+        (Some(lbh), Some(rbh)) => {
+            // `diff` can only handle two CSVs that have the same headers ordering
+            // (--align-columns makes them so), so in this case we can either choose
+            // the left or right headers, because both are the same
+            debug_assert_eq!(
+                lbh, rbh,
+                "csv_diff invariant: left/right headers must match"
+            );
+            Ok(Some(lbh.clone()))
+        },
+        (Some(bh), None) | (None, Some(bh)) => Ok(Some(bh.clone())),
+        (None, None) => generic_headers(num_columns),
+    }
+}
+
+/// Generic `_col_N` headers for when neither CSV has headers.
+fn generic_headers(num_columns: usize) -> csv::Result<Option<ByteRecord>> {
+    if num_columns == 0 {
+        return Ok(None);
+    }
+    let headers_generic = rename_headers_all_generic(num_columns);
+    let mut new_rdr = csv::Reader::from_reader(headers_generic.as_bytes());
+    Ok(Some(new_rdr.byte_headers()?.clone()))
+}
+
+/// One change in the diff result, however it was computed.
+enum DiffRow {
+    Add(ByteRecord),
+    Delete(ByteRecord),
+    Modify {
+        delete:        ByteRecord,
+        add:           ByteRecord,
+        field_indices: Vec<usize>,
+    },
+}
+
+impl From<DiffByteRecord> for DiffRow {
+    fn from(dbr: DiffByteRecord) -> Self {
+        match dbr {
+            DiffByteRecord::Add(add) => DiffRow::Add(add.byte_record().clone()),
+            DiffByteRecord::Delete(del) => DiffRow::Delete(del.byte_record().clone()),
+            DiffByteRecord::Modify {
+                delete,
+                add,
+                field_indices,
+            } => DiffRow::Modify {
+                delete: delete.byte_record().clone(),
+                add: add.byte_record().clone(),
+                field_indices,
+            },
+        }
+    }
+}
+
 struct CsvDiffWriter<W: Write> {
     csv_writer:        csv::Writer<W>,
     no_headers:        bool,
@@ -396,65 +1154,33 @@ impl<W: Write> CsvDiffWriter<W> {
         }
     }
 
-    fn write_headers(&mut self, headers: &Headers, num_columns: Option<&usize>) -> csv::Result<()> {
-        match (headers.headers_left(), headers.headers_right()) {
-            (Some(lbh), Some(rbh)) => {
-                // currently, `diff` can only handle two CSVs that have the same
-                // headers ordering, so in this case we can either choose the left
-                // or right headers, because both are the same
-                debug_assert_eq!(
-                    lbh, rbh,
-                    "csv_diff invariant: left/right headers must match"
-                );
-                if !self.no_headers {
-                    let lbh = lbh.clone();
-                    self.write_projected_header(&lbh)?;
-                }
-            },
-            (Some(bh), None) | (None, Some(bh)) => {
-                if !self.no_headers {
-                    let bh = bh.clone();
-                    self.write_projected_header(&bh)?;
-                }
-            },
-            (None, None) => {
-                if let (Some(&num_cols), false) = (num_columns.filter(|&&c| c > 0), self.no_headers)
-                {
-                    let headers_generic = rename_headers_all_generic(num_cols);
-                    let mut new_rdr = csv::Reader::from_reader(headers_generic.as_bytes());
-                    let new_headers = new_rdr.byte_headers()?.clone();
-                    self.write_projected_header(&new_headers)?;
-                }
-            },
+    fn write_diff_byte_records(
+        &mut self,
+        header: Option<&ByteRecord>,
+        diff_records: impl IntoIterator<Item = io::Result<DiffRow>>,
+    ) -> io::Result<()> {
+        if let (Some(header), false) = (header, self.no_headers) {
+            self.write_projected_header(header)?;
         }
-
-        Ok(())
-    }
-
-    fn write_diff_byte_records(&mut self, diff_byte_records: DiffByteRecords) -> io::Result<()> {
-        self.write_headers(
-            diff_byte_records.headers(),
-            diff_byte_records.num_columns().as_ref(),
-        )?;
-        for dbr in diff_byte_records {
-            self.write_diff_byte_record(&dbr)?;
+        for dbr in diff_records {
+            self.write_diff_byte_record(&dbr?)?;
         }
         self.csv_writer.flush()?;
         Ok(())
     }
 
     #[inline]
-    fn write_diff_byte_record(&mut self, diff_byte_record: &DiffByteRecord) -> csv::Result<()> {
+    fn write_diff_byte_record(&mut self, diff_byte_record: &DiffRow) -> csv::Result<()> {
         let add_sign: &[u8] = &b"+"[..];
         let remove_sign: &[u8] = &b"-"[..];
 
         match diff_byte_record {
-            DiffByteRecord::Add(add) => {
+            DiffRow::Add(add) => {
                 let mut vec = vec![add_sign];
-                vec.extend(add.byte_record());
+                vec.extend(add);
                 self.write_projected_record(&vec)
             },
-            DiffByteRecord::Modify {
+            DiffRow::Modify {
                 delete,
                 add,
                 field_indices,
@@ -462,12 +1188,12 @@ impl<W: Write> CsvDiffWriter<W> {
                 let vec_del = if self.drop_equal_fields {
                     self.fill_modified_and_drop_equal_fields(
                         remove_sign,
-                        delete.byte_record(),
+                        delete,
                         field_indices.as_slice(),
                     )
                 } else {
                     let mut tmp = vec![remove_sign];
-                    tmp.extend(delete.byte_record());
+                    tmp.extend(delete);
                     tmp
                 };
 
@@ -476,20 +1202,20 @@ impl<W: Write> CsvDiffWriter<W> {
                 let vec_add = if self.drop_equal_fields {
                     self.fill_modified_and_drop_equal_fields(
                         add_sign,
-                        add.byte_record(),
+                        add,
                         field_indices.as_slice(),
                     )
                 } else {
                     let mut tmp = vec![add_sign];
-                    tmp.extend(add.byte_record());
+                    tmp.extend(add);
                     tmp
                 };
 
                 self.write_projected_record(&vec_add)
             },
-            DiffByteRecord::Delete(del) => {
+            DiffRow::Delete(del) => {
                 let mut vec = vec![remove_sign];
-                vec.extend(del.byte_record());
+                vec.extend(del);
                 self.write_projected_record(&vec)
             },
         }
@@ -518,6 +1244,214 @@ impl<W: Write> CsvDiffWriter<W> {
     }
 }
 
+struct JsonDiffWriter<W: Write> {
+    wtr:               W,
+    column_names:      Vec<String>,
+    key_fields:        Vec<usize>,
+    drop_equal_fields: bool,
+    // same meaning as in CsvDiffWriter
+    keep_cols:         Option<Vec<usize>>,
+}
+
+impl<W: Write> JsonDiffWriter<W> {
+    /// The output columns, in order - all columns, or `keep_cols` with --drop-equal-columns.
+    fn output_cols(&self) -> Vec<usize> {
+        match &self.keep_cols {
+            Some(keep) => keep.clone(),
+            None => (0..self.column_names.len()).collect(),
+        }
+    }
+
+    fn record_object(&self, record: &ByteRecord, cols: impl Iterator<Item = usize>) -> Value {
+        let mut obj = Map::new();
+        for c in cols {
+            let name = self
+                .column_names
+                .get(c)
+                .cloned()
+                .unwrap_or_else(|| c.to_string());
+            let value = String::from_utf8_lossy(record.get(c).unwrap_or_default()).into_owned();
+            obj.insert(name, Value::String(value));
+        }
+        Value::Object(obj)
+    }
+
+    fn key_values(record: &ByteRecord, key_fields: &[usize]) -> Vec<String> {
+        key_fields
+            .iter()
+            .map(|&k| String::from_utf8_lossy(record.get(k).unwrap_or_default()).into_owned())
+            .collect()
+    }
+
+    /// JSON Pointer (RFC 6901) to a record, optionally followed by a column name.
+    /// A record is a single reference token, so adding a record never depends on a
+    /// parent that doesn't exist: its key value, or the values of a composite key
+    /// joined as a CSV record (e.g. `/1,a`).
+    fn pointer(key_values: &[String], column: Option<&str>) -> String {
+        let escape = |token: &str| token.replace('~', "~0").replace('/', "~1");
+        let key = if let [value] = key_values {
+            value.clone()
+        } else {
+            let mut wtr = csv::WriterBuilder::new().from_writer(Vec::new());
+            // writing to a Vec can't fail
+            let _ = wtr.write_record(key_values);
+            let mut key =
+                String::from_utf8(wtr.into_inner().unwrap_or_default()).unwrap_or_default();
+            // the record terminator
+            key.pop();
+            key
+        };
+        let mut pointer = String::new();
+        for token in std::iter::once(key.as_str()).chain(column) {
+            pointer.push('/');
+            pointer.push_str(&escape(token));
+        }
+        pointer
+    }
+
+    fn write_diff_byte_records(
+        &mut self,
+        diff_records: impl IntoIterator<Item = io::Result<DiffRow>>,
+        format: DiffFormat,
+    ) -> io::Result<()> {
+        let cols = self.output_cols();
+        let mut first_op = true;
+        if format == DiffFormat::JsonPatch {
+            self.wtr.write_all(b"[")?;
+        }
+        for dbr in diff_records {
+            let dbr = dbr?;
+            let ops = match format {
+                DiffFormat::Jsonl => vec![self.change_record(&dbr, &cols)],
+                _ => self.patch_ops(&dbr, &cols),
+            };
+            for op in ops {
+                if format == DiffFormat::JsonPatch {
+                    let sep: &[u8] = if first_op { b"\n" } else { b",\n" };
+                    self.wtr.write_all(sep)?;
+                    first_op = false;
+                    serde_json::to_writer(&mut self.wtr, &op)?;
+                } else {
+                    serde_json::to_writer(&mut self.wtr, &op)?;
+                    self.wtr.write_all(b"\n")?;
+                }
+            }
+        }
+        if format == DiffFormat::JsonPatch {
+            self.wtr.write_all(b"\n]\n")?;
+        }
+        self.wtr.flush()
+    }
+
+    /// A `--format jsonl` change record.
+    fn change_record(&self, dbr: &DiffRow, cols: &[usize]) -> Value {
+        match dbr {
+            DiffRow::Add(add) => {
+                let rec = add;
+                json!({
+                    "op": "add",
+                    "key": self.record_object(rec, self.key_fields.iter().copied()),
+                    "before": Value::Null,
+                    "after": self.record_object(rec, cols.iter().copied()),
+                })
+            },
+            DiffRow::Delete(del) => {
+                let rec = del;
+                json!({
+                    "op": "delete",
+                    "key": self.record_object(rec, self.key_fields.iter().copied()),
+                    "before": self.record_object(rec, cols.iter().copied()),
+                    "after": Value::Null,
+                })
+            },
+            DiffRow::Modify {
+                delete,
+                add,
+                field_indices,
+            } => {
+                let (before, after) = (delete, add);
+                // with --drop-equal-fields, only key and modified fields are kept
+                let shown: Vec<usize> = cols
+                    .iter()
+                    .copied()
+                    .filter(|c| {
+                        !self.drop_equal_fields
+                            || self.key_fields.contains(c)
+                            || field_indices.contains(c)
+                    })
+                    .collect();
+                let changed: Vec<Value> = field_indices
+                    .iter()
+                    .map(|&fi| {
+                        Value::String(
+                            self.column_names
+                                .get(fi)
+                                .cloned()
+                                .unwrap_or_else(|| fi.to_string()),
+                        )
+                    })
+                    .collect();
+                json!({
+                    "op": "modify",
+                    "key": self.record_object(before, self.key_fields.iter().copied()),
+                    "changed": changed,
+                    "before": self.record_object(before, shown.iter().copied()),
+                    "after": self.record_object(after, shown.iter().copied()),
+                })
+            },
+        }
+    }
+
+    /// The `--format jsonpatch` operations for one diff record.
+    fn patch_ops(&self, dbr: &DiffRow, cols: &[usize]) -> Vec<Value> {
+        match dbr {
+            DiffRow::Add(add) => {
+                let rec = add;
+                let key = Self::key_values(rec, &self.key_fields);
+                vec![json!({
+                    "op": "add",
+                    "path": Self::pointer(&key, None),
+                    "value": self.record_object(rec, cols.iter().copied()),
+                })]
+            },
+            DiffRow::Delete(del) => {
+                let rec = del;
+                let path = Self::pointer(&Self::key_values(rec, &self.key_fields), None);
+                vec![
+                    json!({
+                        "op": "test",
+                        "path": path,
+                        "value": self.record_object(rec, cols.iter().copied()),
+                    }),
+                    json!({"op": "remove", "path": path}),
+                ]
+            },
+            DiffRow::Modify {
+                delete,
+                add,
+                field_indices,
+            } => {
+                let (before, after) = (delete, add);
+                let key = Self::key_values(before, &self.key_fields);
+                let mut ops = Vec::with_capacity(field_indices.len() * 2);
+                for &fi in field_indices {
+                    let name = self
+                        .column_names
+                        .get(fi)
+                        .cloned()
+                        .unwrap_or_else(|| fi.to_string());
+                    let path = Self::pointer(&key, Some(&name));
+                    let old = String::from_utf8_lossy(before.get(fi).unwrap_or_default());
+                    let new = String::from_utf8_lossy(after.get(fi).unwrap_or_default());
+                    ops.push(json!({"op": "test", "path": path, "value": old}));
+                    ops.push(json!({"op": "replace", "path": path, "value": new}));
+                }
+                ops
+            },
+        }
+    }
+}
+
 trait WriteDiffResultHeader {
     fn write_diffresult_header<W: Write>(&self, csv_writer: &mut csv::Writer<W>)
     -> csv::Result<()>;
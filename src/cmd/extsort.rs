@@ -6,6 +6,8 @@ This command has TWO modes of operation.
  * CSV MODE
    when --select is set, it sorts based on the given column/s. Requires an index.
    See `qsv select --help` for select syntax details.
+   when --order is set, it sorts by SQL-like sort keys, each with its own type,
+   direction and nulls placement.
 
    STATS-CACHE AWARE: in CSV MODE, when a single ASCII column is selected and a valid
    stats cache exists (see `qsv stats --stats-jsonl`), extsort uses the cached sort order
@@ -13,7 +15,7 @@ This command has TWO modes of operation.
    through unchanged, skipping the external sort entirely. Not applied with --reverse or
    multi-column selections. Disable with QSV_STATSCACHE_MODE=none.
  * LINE MODE
-   when --select is NOT set, it sorts any input text file (not just CSVs) on a
+   when neither --select nor --order is set, it sorts any input text file (not just CSVs) on a
    line-by-line basis. If sorting a non-CSV file, be sure to set --no-headers, 
    otherwise, the first line will not be included in the external sort.
 
@@ -29,6 +31,11 @@ External sort option:
                            If --select is NOT set, extsort will work in LINE MODE, sorting
                            the input as a text file on a line-by-line basis.
     -R, --reverse          Reverse order
+    --order <spec>         Sort by a SQL-like list of sort keys with per-column type,
+                           direction and nulls placement (CSV MODE), e.g.
+                             --order "state, population:numeric desc, name:collate=de"
+                           See `qsv sort --help` for the syntax. Requires an index.
+                           Cannot be combined with --select or --reverse.
     --memory-limit <arg>   The maximum amount of memory to buffer the external merge sort.
                            If less than 50, this is a percentage of total memory.
                            If more than 50, this is the memory in MB to allocate, capped
@@ -63,10 +70,14 @@ use serde::Deserialize;
 
 use crate::{
     CliResult,
-    cmd::extdedup::calculate_memory_limit,
+    cmd::{
+        extdedup::calculate_memory_limit,
+        sort::order::{self, SortOrder},
+    },
     config,
     config::{Config, Delimiter},
     select::SelectColumns,
+    statscatalog::StatsCatalog,
     util,
 };
 
@@ -76,6 +87,7 @@ struct Args {
     arg_output:        Option<String>,
     flag_select:       Option<SelectColumns>,
     flag_reverse:      bool,
+    flag_order:        Option<String>,
     flag_delimiter:    Option<Delimiter>,
     flag_jobs:         Option<usize>,
     flag_memory_limit: Option<u64>,
@@ -121,7 +133,14 @@ pub fn run(argv: &[&str]) -> CliResult<()> {
             },
         };
 
-    if args.flag_select.is_some() {
+    if args.flag_order.is_some() && (args.flag_select.is_some() || args.flag_reverse) {
+        return fail_incorrectusage_clierror!(
+            "--order cannot be combined with --select or --reverse. Name the sort columns and \
+             their direction in --order instead."
+        );
+    }
+
+    if args.flag_select.is_some() || args.flag_order.is_some() {
         sort_csv(&args, &tmp_dir, &sorter)
     } else {
         sort_lines(&args, &sorter)
@@ -133,10 +152,12 @@ fn sort_csv(
     tmp_dir: &str,
     sorter: &ExternalSorter<String, io::Error, LimitedBufferBuilder>,
 ) -> Result<(), crate::clitypes::CliError> {
-    let rconfig = Config::new(args.arg_input.as_ref())
+    let mut rconfig = Config::new(args.arg_input.as_ref())
         .delimiter(args.flag_delimiter)
-        .no_headers_flag(args.flag_no_headers)
-        .select(args.flag_select.clone().unwrap());
+        .no_headers_flag(args.flag_no_headers);
+    if let Some(select) = &args.flag_select {
+        rconfig = rconfig.select(select.clone());
+    }
 
     let mut idxfile = match rconfig.indexed() {
         Ok(Some(idx)) => idx,
@@ -154,7 +175,14 @@ fn sort_csv(
     let mut line_wtr = io::BufWriter::with_capacity(RW_BUFFER_CAPACITY, linewtr_tfile.as_file());
 
     let headers = input_rdr.byte_headers()?.clone();
-    let sel = rconfig.selection(&headers)?;
+    // with --order, the sort keys are its columns instead of a selection
+    let (sel, order) = match &args.flag_order {
+        Some(spec) => (
+            None,
+            Some(SortOrder::parse(spec, &headers, args.flag_no_headers)?),
+        ),
+        None => (Some(rconfig.selection(&headers)?), None),
+    };
 
     // stats-cache short-circuit (issue #2116). extsort CSV mode is byte-lexicographic
     // only, so we pass requested_numeric=false and for_extsort=true (the ASCII guard).
@@ -162,13 +190,14 @@ fn sort_csv(
     // order, stream the input straight through instead of doing the full external merge
     // sort. --reverse is intentionally NOT short-circuited (its anti-stable duplicate-key
     // tie-break can't be reproduced by a passthrough). Disable with QSV_STATSCACHE_MODE=none.
-    if !args.flag_reverse
-        && let Some(stats) = util::get_stats_records_readonly(
+    if let Some(sel) = &sel
+        && !args.flag_reverse
+        && StatsCatalog::load(
             args.arg_input.as_deref(),
             args.flag_no_headers,
             args.flag_delimiter,
         )
-        && util::stats_cache_proves_ascending(&sel, &stats, false, true)
+        .is_some_and(|catalog| catalog.proves_ascending(sel, false, true))
     {
         let mut wtr = Config::new(args.arg_output.as_ref()).writer()?;
         if !args.flag_no_headers {
@@ -193,11 +222,15 @@ fn sort_csv(
     for row in input_rdr.byte_records() {
         curr_row.clone_from(&row?);
         sort_key.clear();
-        for field in sel.select(&curr_row) {
-            if let Ok(s_utf8) = simdutf8::basic::from_utf8(field) {
-                sort_key.push_str(s_utf8);
-            } else {
-                sort_key.push_str(&util::bytes_to_cow_str(field));
+        if let Some(order) = &order {
+            order::encode(&order.key(&curr_row), &mut sort_key);
+        } else if let Some(sel) = &sel {
+            for field in sel.select(&curr_row) {
+                if let Ok(s_utf8) = simdutf8::basic::from_utf8(field) {
+                    sort_key.push_str(s_utf8);
+                } else {
+                    sort_key.push_str(&util::bytes_to_cow_str(field));
+                }
             }
         }
         let idx_position = curr_row.position().unwrap();
@@ -213,8 +246,17 @@ fn sort_csv(
     let line_rdr = io::BufReader::with_capacity(RW_BUFFER_CAPACITY, linewtr_tfile.reopen()?);
 
     let reverse_flag = args.flag_reverse;
+    let order = &order;
     let compare = |a: &String, b: &String| {
-        if reverse_flag {
+        if let Some(order) = order {
+            // the encoded keys are followed by "|" and the padded position, which breaks ties
+            // so that rows with equal keys keep their input order
+            let (a_keys, a_pos) = a.split_at(a.len() - width - 1);
+            let (b_keys, b_pos) = b.split_at(b.len() - width - 1);
+            order
+                .compare(&order::decode(a_keys), &order::decode(b_keys))
+                .then_with(|| a_pos.cmp(b_pos))
+        } else if reverse_flag {
             a.cmp(b).reverse()
         } else {
             a.cmp(b)
@@ -180,6 +180,10 @@ frequency options:
                             (original behavior).
                             Has no effect when --no-nulls is set.
     -i, --ignore-case       Ignore case when computing frequencies.
+    --collation <locale>    Order values with the same count by the Unicode collation
+                            of a BCP 47 locale (e.g. de, sv or und for the root
+                            collation) instead of byte-wise. Only the order of tied
+                            values changes; values are still counted as they are.
     --no-float <cols>       Exclude Float columns from frequency analysis.
                             Floats typically contain continuous values where
                             frequency tables are not meaningful.
@@ -209,6 +213,53 @@ frequency options:
                             an index. Note that a file handle is opened for each job.
                             When not set, defaults to the number of CPUs detected.
 
+                            CROSS-TABULATION OPTIONS:
+    --crosstab <cols>       Instead of one frequency table per column, compute an n-way
+                            contingency table of the joint values of 2 or more columns
+                            (see 'qsv select --help' for the format details). Cannot be
+                            combined with --select or --weight.
+                            Each row has the columns' values, then count, percentage
+                            (of all rows), row_percentage (of the rows with the same
+                            FIRST column value) and col_percentage (of the rows with the
+                            same LAST column value). Rows are sorted by count, descending
+                            unless --asc is set.
+                            They are followed by the marginal totals of each column -
+                            with --total-text in the other columns - and the grand total.
+                            A chi-square test of independence (statistic, degrees of
+                            freedom, p-value and, for 2 columns, Cramér's V) is written
+                            to stderr, or included in the --json/--pretty-json/--toon output.
+                            --no-trim, --ignore-case, --null-text, --no-nulls,
+                            --pct-dec-places and --vis-whitespace are honored. Rows with
+                            a NULL in any of the columns are skipped with --no-nulls.
+    --total-text <arg>      The text to use for the marginal total columns of --crosstab.
+                            [default: (TOTAL)]
+
+                            BINNING OPTIONS:
+    --bins <strategy>       Instead of listing distinct values, bin the Integer, Float,
+                            Date and DateTime columns of the selection into a histogram.
+                            Other columns are skipped. Requires a file input, as the column
+                            types and ranges come from the stats cache (created if needed).
+                            Strategies:
+                              sturges       ceil(log2(n)) + 1 equal-width bins, where n is
+                                            the number of non-null values.
+                              fd            Freedman-Diaconis: equal-width bins that are
+                                            2 * IQR / cbrt(n) wide.
+                              equal:<n>     <n> equal-width bins.
+                              quantile:<n>  <n> bins holding about as many values each.
+                            sturges and equal stream the data, using the stats cache's
+                            min/max. fd and quantile keep each binned column's values in
+                            memory to compute its quartiles/quantiles.
+                            Integer and Date bins are whole numbers/days wide, and at most
+                            1,000 bins are made.
+                            In CSV mode, the columns are field,bin,lower,upper,count,percentage.
+                            Bins are [lower, upper), except the last, which includes upper.
+                            Empty and unparsable cells are counted in a final NULL row
+                            (labelled with --null-text) unless --no-nulls is set, and
+                            are only included in the percentages with --pct-nulls.
+                            With --frequency-jsonl, the bins are also cached in
+                            FILESTEM.freq.csv.bins.jsonl, which `viz histogram` reuses
+                            when --bins is not given there.
+
                             FREQUENCY CACHE OPTIONS:
     --frequency-jsonl       Write the complete frequency distribution as a
                             JSONL cache file (FILESTEM.freq.csv.data.jsonl).
@@ -292,6 +343,7 @@ use std::{fs, io, str::FromStr, sync::OnceLock};
 use crossbeam_channel;
 use foldhash::{HashMap, HashMapExt, HashSet, HashSetExt};
 use indicatif::HumanCount;
+use qsv_dateparser::parse_with_preference;
 use rayon::prelude::*;
 use rust_decimal::prelude::*;
 use serde::{Deserialize, Serialize};
@@ -303,9 +355,11 @@ use toon_format::{EncodeOptions, encode};
 use crate::{
     CliResult,
     cmd::stats::StatsData,
+    collation::Collation,
     config::{Config, Delimiter},
     index::Indexed,
     select::{SelectColumns, Selection},
+    statscatalog::StatsCatalog,
     util::{self, ByteString, StatsMode, get_stats_records},
 };
 
@@ -356,6 +410,7 @@ pub struct Args {
     pub flag_no_nulls:            bool,
     pub flag_pct_nulls:           bool,
     pub flag_ignore_case:         bool,
+    pub flag_collation:           Option<String>,
     pub flag_no_float:            Option<String>,
     #[cfg(feature = "luau")]
     pub flag_stats_filter:        Option<String>,
@@ -377,6 +432,9 @@ pub struct Args {
     pub flag_weight:              Option<String>,
     pub flag_sketch_method:       String,
     pub flag_sketch_map_size:     usize,
+    pub flag_crosstab:            Option<SelectColumns>,
+    pub flag_total_text:          String,
+    pub flag_bins:                Option<String>,
 }
 
 const NON_UTF8_ERR: &str = "<Non-UTF8 ERROR>";
@@ -391,6 +449,8 @@ static STATS_RECORDS: OnceLock<HashMap<String, StatsData>> = OnceLock::new();
 // duplicate-named columns each report their own stats.
 static STATS_RECORDS_BY_POS: OnceLock<Vec<StatsData>> = OnceLock::new();
 static NULL_VAL: OnceLock<Vec<u8>> = OnceLock::new();
+// the --collation that orders values with the same count, None to order them byte-wise
+static COLLATION: OnceLock<Option<Collation>> = OnceLock::new();
 static UNIQUE_COLUMNS_VEC: OnceLock<Vec<usize>> = OnceLock::new();
 // Cardinalities aligned positionally to the FINAL selected columns (NOT keyed by
 // name). Name-keying collapsed duplicate-named columns onto the first match; using
@@ -459,6 +519,10 @@ struct FrequencyCacheMetadata {
     /// treat such legacy caches conservatively.
     #[serde(default)]
     canonical_input_path:     String,
+    /// The `--bins` strategy of a `.freq.csv.bins.jsonl` cache. Empty for the
+    /// distinct-value cache.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    bin_strategy:             String,
 }
 
 /// A neutral, read-only view of a frequency cache for consumers outside the
@@ -491,6 +555,94 @@ pub(crate) fn frequency_cache_path(path: &std::path::Path) -> std::path::PathBuf
     Args::cache_path_for(path)
 }
 
+/// The `--bins` JSONL cache path for `path`, next to the distinct-value frequency cache.
+pub(crate) fn bins_cache_path(path: &std::path::Path) -> std::path::PathBuf {
+    // FILESTEM.freq.csv.data.jsonl -> FILESTEM.freq.csv.bins.jsonl
+    Args::cache_path_for(path)
+        .with_extension("")
+        .with_extension("bins.jsonl")
+}
+
+/// An equal-width numeric histogram read back from a `--bins` cache, for `viz histogram`.
+#[cfg_attr(not(feature = "viz"), allow(dead_code))]
+pub(crate) struct CachedHistogram {
+    pub start:     f64,
+    pub end:       f64,
+    pub width:     f64,
+    pub midpoints: Vec<f64>,
+    pub counts:    Vec<u64>,
+}
+
+/// Read the `field` column's histogram from the `--bins` cache of `path`. Returns `None`
+/// when the cache is absent, stale, built with other parsing options or with `--no-headers`
+/// (whose positional names can't be matched safely), binned by quantile (unequal widths),
+/// or has no single numeric `field` column.
+#[cfg_attr(not(feature = "viz"), allow(dead_code))]
+pub(crate) fn read_bins_cache_histogram(
+    path: &std::path::Path,
+    field: &str,
+    no_headers: bool,
+    delimiter: Option<Delimiter>,
+) -> Option<CachedHistogram> {
+    use filetime::FileTime;
+
+    if no_headers {
+        return None;
+    }
+    let cache_path = bins_cache_path(path);
+    let csv_mtime = FileTime::from_last_modification_time(&fs::metadata(path).ok()?);
+    let cache_mtime = FileTime::from_last_modification_time(&fs::metadata(&cache_path).ok()?);
+    if cache_mtime <= csv_mtime {
+        log::info!("Frequency bins cache is stale; binning the raw values.");
+        return None;
+    }
+
+    let jsonl_content = fs::read_to_string(&cache_path).ok()?;
+    let mut lines = jsonl_content.lines();
+    let metadata: FrequencyCacheMetadata = serde_json::from_str(lines.next()?).ok()?;
+    let current_delimiter =
+        delimiter.map_or_else(|| ",".to_string(), |d| (d.as_byte() as char).to_string());
+    if metadata.flag_no_headers
+        || metadata.flag_delimiter != current_delimiter
+        || metadata.bin_strategy.is_empty()
+        || metadata.bin_strategy.starts_with("quantile")
+    {
+        return None;
+    }
+
+    let mut matches = lines
+        .filter_map(|line| serde_json::from_str::<BinnedField>(line).ok())
+        .filter(|f| f.field == field);
+    let entry = matches.next()?;
+    if matches.next().is_some() || !matches!(entry.r#type.as_str(), "Integer" | "Float") {
+        return None;
+    }
+
+    let mut edges: Vec<(f64, f64)> = Vec::with_capacity(entry.bins.len());
+    for bin in &entry.bins {
+        edges.push((bin.lower.as_f64()?, bin.upper.as_f64()?));
+    }
+    let (start, first_upper) = *edges.first()?;
+    let end = edges.last()?.1;
+    let width = first_upper - start;
+    // edges are rounded when written, so allow for that when checking they're equal-width
+    let tolerance = width.abs() * 0.02;
+    if width <= 0.0
+        || edges
+            .iter()
+            .any(|(lo, hi)| ((hi - lo) - width).abs() > tolerance)
+    {
+        return None;
+    }
+    Some(CachedHistogram {
+        start,
+        end,
+        width,
+        midpoints: edges.iter().map(|(lo, hi)| (lo + hi) / 2.0).collect(),
+        counts: entry.bins.iter().map(|b| b.count).collect(),
+    })
+}
+
 /// Read and validate a frequency JSONL cache for `path`, independent of any
 /// `frequency::Args`, for read-only reuse by other commands. Returns `None`
 /// when the cache is absent, stale (older than the source), or was generated
@@ -701,6 +853,301 @@ struct FrequencyOutput {
     rank_strategy: RankStrategy,
 }
 
+// CrosstabCell, CrosstabMargin, ChiSquare and CrosstabOutput are
+// structs for --crosstab JSON output
+#[derive(Serialize)]
+struct CrosstabCell {
+    values:         Vec<String>,
+    count:          u64,
+    percentage:     f64,
+    row_percentage: f64,
+    col_percentage: f64,
+}
+
+#[derive(Serialize)]
+struct CrosstabMargin {
+    field:  String,
+    values: Vec<FrequencyEntry>,
+}
+
+#[derive(Serialize)]
+struct ChiSquare {
+    statistic: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    dof:       Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    p_value:   Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    cramers_v: Option<f64>,
+}
+
+#[derive(Serialize)]
+struct CrosstabOutput {
+    input:       String,
+    description: String,
+    rowcount:    u64,
+    fields:      Vec<String>,
+    chi_square:  ChiSquare,
+    cells:       Vec<CrosstabCell>,
+    margins:     Vec<CrosstabMargin>,
+}
+
+// Upper bound on the number of bins --bins makes for one column, so a Freedman-Diaconis
+// width on a heavy-tailed column can't explode into millions of near-empty bins.
+const MAX_BINS: usize = 1_000;
+
+/// How `--bins` places its bin edges.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum BinStrategy {
+    Sturges,
+    FreedmanDiaconis,
+    EqualWidth(usize),
+    Quantile(usize),
+}
+
+impl FromStr for BinStrategy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.to_lowercase();
+        let (name, n) = match s.split_once(':') {
+            Some((name, n)) => (name, Some(n)),
+            None => (s.as_str(), None),
+        };
+        let bin_count = || match n.and_then(|n| n.parse::<usize>().ok()) {
+            Some(n) if (1..=MAX_BINS).contains(&n) => Ok(n),
+            _ => Err(format!(
+                "--bins {name} needs a bin count between 1 and {MAX_BINS}, e.g. {name}:10"
+            )),
+        };
+        match (name, n) {
+            ("sturges", None) => Ok(BinStrategy::Sturges),
+            ("fd", None) => Ok(BinStrategy::FreedmanDiaconis),
+            ("equal", _) => bin_count().map(BinStrategy::EqualWidth),
+            ("quantile", _) => bin_count().map(BinStrategy::Quantile),
+            _ => Err(format!(
+                "Invalid --bins strategy: '{s}'. Valid values are: sturges, fd, equal:<n>, \
+                 quantile:<n>"
+            )),
+        }
+    }
+}
+
+impl std::fmt::Display for BinStrategy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BinStrategy::Sturges => f.write_str("sturges"),
+            BinStrategy::FreedmanDiaconis => f.write_str("fd"),
+            BinStrategy::EqualWidth(n) => write!(f, "equal:{n}"),
+            BinStrategy::Quantile(n) => write!(f, "quantile:{n}"),
+        }
+    }
+}
+
+impl BinStrategy {
+    /// Does the strategy need the column's values (for its quantiles), rather than just
+    /// the stats cache's min and max?
+    const fn needs_values(self) -> bool {
+        matches!(
+            self,
+            BinStrategy::FreedmanDiaconis | BinStrategy::Quantile(_)
+        )
+    }
+}
+
+/// The axis a `--bins` column is binned on: numbers as themselves, dates as days and
+/// datetimes as milliseconds since the Unix epoch.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum BinAxis {
+    Integer,
+    Float,
+    Date,
+    DateTime,
+}
+
+impl BinAxis {
+    fn from_stats_type(r#type: &str) -> Option<Self> {
+        match r#type {
+            "Integer" => Some(BinAxis::Integer),
+            "Float" => Some(BinAxis::Float),
+            "Date" => Some(BinAxis::Date),
+            "DateTime" => Some(BinAxis::DateTime),
+            _ => None,
+        }
+    }
+
+    /// Integer and Date axes are discrete, so their bins are a whole number of units wide.
+    const fn is_discrete(self) -> bool {
+        matches!(self, BinAxis::Integer | BinAxis::Date)
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    fn parse(self, cell: &[u8], prefer_dmy: bool) -> Option<f64> {
+        let s = simdutf8::basic::from_utf8(cell).ok()?;
+        match self {
+            BinAxis::Integer | BinAxis::Float => {
+                fast_float2::parse(s).ok().filter(|v: &f64| v.is_finite())
+            },
+            BinAxis::Date => parse_with_preference(s, prefer_dmy)
+                .ok()
+                .map(|d| d.timestamp().div_euclid(86_400) as f64),
+            BinAxis::DateTime => parse_with_preference(s, prefer_dmy)
+                .ok()
+                .map(|d| d.timestamp_millis() as f64),
+        }
+    }
+
+    /// Render a bin edge: numbers rounded to `places`, dates and datetimes as RFC 3339.
+    #[allow(clippy::cast_possible_truncation)]
+    fn format(self, edge: f64, places: u32) -> JsonValue {
+        match self {
+            BinAxis::Integer | BinAxis::Float => {
+                let rounded = util::round_num(edge, places);
+                atoi_simd::parse::<i64, false, false>(rounded.as_bytes()).map_or_else(
+                    |_| {
+                        fast_float2::parse(&rounded)
+                            .ok()
+                            .and_then(serde_json::Number::from_f64)
+                            .map_or(JsonValue::Null, JsonValue::Number)
+                    },
+                    |i| JsonValue::Number(i.into()),
+                )
+            },
+            BinAxis::Date => chrono::DateTime::from_timestamp(edge as i64 * 86_400, 0)
+                .map_or(JsonValue::Null, |d| {
+                    JsonValue::String(d.format("%Y-%m-%d").to_string())
+                }),
+            BinAxis::DateTime => chrono::DateTime::from_timestamp_millis(edge as i64)
+                .map_or(JsonValue::Null, |d| {
+                    JsonValue::String(d.to_rfc3339_opts(chrono::SecondsFormat::AutoSi, true))
+                }),
+        }
+    }
+}
+
+// BinEntry, BinnedField and BinnedOutput are structs for --bins JSON output.
+// BinnedField is also a line of the --bins frequency cache (.freq.csv.bins.jsonl).
+#[derive(Serialize, Deserialize)]
+struct BinEntry {
+    lower:      JsonValue,
+    upper:      JsonValue,
+    count:      u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    percentage: Option<f64>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct BinnedField {
+    field:     String,
+    r#type:    String,
+    nullcount: u64,
+    bins:      Vec<BinEntry>,
+}
+
+#[derive(Serialize)]
+struct BinnedOutput {
+    input:        String,
+    description:  String,
+    rowcount:     u64,
+    fieldcount:   usize,
+    bin_strategy: String,
+    fields:       Vec<BinnedField>,
+}
+
+/// A column being binned by `--bins`.
+struct BinnedColumn {
+    pos:       usize,
+    field:     String,
+    r#type:    String,
+    axis:      BinAxis,
+    // bin i is [edges[i], edges[i + 1]), except the last, which includes its upper edge
+    edges:     Vec<f64>,
+    counts:    Vec<u64>,
+    // only kept for the strategies that need the column's quantiles
+    values:    Vec<f64>,
+    nullcount: u64,
+}
+
+impl BinnedColumn {
+    fn bin_of(&self, value: f64) -> usize {
+        self.edges
+            .partition_point(|&edge| edge <= value)
+            .saturating_sub(1)
+            .min(self.counts.len() - 1)
+    }
+
+    /// Place the bin edges between `min` and `max` for `n` non-null values. `sorted` holds
+    /// the column's values in ascending order when the strategy needs them.
+    #[allow(
+        clippy::cast_precision_loss,
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss
+    )]
+    fn set_edges(&mut self, strategy: BinStrategy, min: f64, max: f64, n: u64, sorted: &[f64]) {
+        let quantile = |p: f64| sorted[((sorted.len() - 1) as f64 * p).round() as usize];
+        let sturges = || (n.max(1) as f64).log2().ceil() as usize + 1;
+        let range = max - min;
+
+        if let BinStrategy::Quantile(k) = strategy {
+            // edges are data values; repeated values collapse bins together
+            self.edges = (0..=k).map(|i| quantile(i as f64 / k as f64)).collect();
+            self.edges.dedup();
+        } else {
+            let nbins = match strategy {
+                BinStrategy::Sturges => sturges(),
+                BinStrategy::EqualWidth(k) => k,
+                BinStrategy::FreedmanDiaconis => {
+                    let iqr = quantile(0.75) - quantile(0.25);
+                    if iqr > 0.0 {
+                        (range / (2.0 * iqr / (n as f64).cbrt())).ceil() as usize
+                    } else {
+                        // no spread in the middle half - fall back to Sturges' rule
+                        sturges()
+                    }
+                },
+                BinStrategy::Quantile(_) => unreachable!(),
+            }
+            .clamp(1, MAX_BINS);
+
+            let mut width = range / nbins as f64;
+            let nbins = if self.axis.is_discrete() {
+                // whole units wide; max may fall short of the closed last bin's upper edge
+                width = width.ceil().max(1.0);
+                ((range / width).ceil() as usize).max(1)
+            } else {
+                nbins
+            };
+            self.edges = (0..=nbins).map(|i| min + i as f64 * width).collect();
+            if !self.axis.is_discrete() {
+                // don't let rounding leave max outside the closed last bin
+                self.edges[nbins] = max;
+                self.edges.dedup();
+            }
+        }
+        if self.edges.len() == 1 {
+            // a single distinct value gets a single closed bin
+            self.edges.push(self.edges[0]);
+        }
+        self.counts = vec![0; self.edges.len() - 1];
+    }
+
+    /// Decimal places that tell this column's Float bin edges apart.
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn decimal_places(&self) -> u32 {
+        let narrowest = self
+            .edges
+            .windows(2)
+            .map(|w| w[1] - w[0])
+            .filter(|w| *w > 0.0)
+            .fold(f64::INFINITY, f64::min);
+        if narrowest.is_finite() {
+            ((-narrowest.log10()).ceil().max(0.0) as u32 + 2).min(15)
+        } else {
+            4
+        }
+    }
+}
+
 // Shared frequency processing result
 // used by both CSV and JSON output
 #[derive(Clone)]
@@ -916,6 +1363,7 @@ fn can_enable_frequent_items(args: &Args, user_set_sketch_method: bool) -> bool
     if args.flag_asc
         || args.flag_weight.is_some()
         || args.flag_ignore_case
+        || args.flag_collation.is_some()
         || args.flag_no_trim
         || args.flag_other_sorted
         || args.flag_null_sorted
@@ -962,6 +1410,30 @@ pub fn run(argv: &[&str]) -> CliResult<()> {
         args.flag_no_nulls = true;
     }
 
+    // --crosstab computes one joint table of its columns instead of a table per
+    // selected column, so it takes over the selection. Reject the flags it can't honor.
+    if let Some(crosstab_cols) = args.flag_crosstab.clone() {
+        if util::argv_has_flag(argv, "--select") || util::argv_has_flag(argv, "-s") {
+            return fail_incorrectusage_clierror!(
+                "--crosstab cannot be used with --select. List the columns in --crosstab."
+            );
+        }
+        if args.flag_weight.is_some() || args.flag_bins.is_some() {
+            return fail_incorrectusage_clierror!(
+                "--crosstab cannot be used with --weight or --bins."
+            );
+        }
+        if args.flag_frequency_jsonl {
+            return fail_incorrectusage_clierror!(
+                "--crosstab cannot be used with --frequency-jsonl."
+            );
+        }
+        if !args.flag_sketch_method.eq_ignore_ascii_case("exact") {
+            return fail_incorrectusage_clierror!("--crosstab requires --sketch-method exact.");
+        }
+        args.flag_select = crosstab_cols;
+    }
+
     let mut rconfig = args.rconfig();
 
     let is_stdin = rconfig.is_stdin();
@@ -985,6 +1457,28 @@ pub fn run(argv: &[&str]) -> CliResult<()> {
         rconfig = args.rconfig();
     }
 
+    if args.flag_crosstab.is_some() {
+        return args.run_crosstab(&rconfig, argv, is_stdin);
+    }
+
+    if let Some(bins) = &args.flag_bins {
+        let strategy = bins
+            .parse::<BinStrategy>()
+            .map_err(crate::CliError::IncorrectUsage)?;
+        if is_stdin {
+            return fail_incorrectusage_clierror!(
+                "--bins requires a file input, not stdin, as it needs the stats cache."
+            );
+        }
+        if args.flag_weight.is_some() {
+            return fail_incorrectusage_clierror!("--bins cannot be used with --weight.");
+        }
+        if !args.flag_sketch_method.eq_ignore_ascii_case("exact") {
+            return fail_incorrectusage_clierror!("--bins requires --sketch-method exact.");
+        }
+        return args.run_binned(&rconfig, strategy, argv);
+    }
+
     // --sketch-method: dispatch to the Frequent Items path when requested. The FI
     // path is intentionally narrow — many existing flags don't compose with a
     // streaming sketch that only tracks heavy hitters. Reject the unsupported
@@ -1179,6 +1673,14 @@ pub fn run(argv: &[&str]) -> CliResult<()> {
         .set(args.flag_all_unique_text.as_bytes().to_vec())
         .unwrap();
 
+    // values are already case-folded with --ignore-case, so the collation doesn't fold case
+    if COLLATION
+        .set(Collation::new(args.flag_collation.as_deref(), None, false)?)
+        .is_err()
+    {
+        log::warn!("COLLATION already set");
+    }
+
     if args.flag_frequency_jsonl {
         // Guard: --frequency-jsonl is incompatible with computation-changing flags
         if args.flag_ignore_case {
@@ -1456,6 +1958,23 @@ fn merge_weighted_ftables(mut a: WeightedFTables, b: WeightedFTables) -> Weighte
     a
 }
 
+/// Compare two values with the --collation, or byte-wise without one.
+fn cmp_values(a: &[u8], b: &[u8]) -> std::cmp::Ordering {
+    match COLLATION.get() {
+        Some(Some(collation)) => collation.compare(a, b),
+        _ => a.cmp(b),
+    }
+}
+
+/// Sort values with the same count, see `cmp_values`.
+fn sort_values(group: &mut [Vec<u8>]) {
+    if matches!(COLLATION.get(), Some(Some(_))) {
+        group.sort_unstable_by(|a, b| cmp_values(a, b));
+    } else {
+        group.sort_unstable();
+    }
+}
+
 #[allow(clippy::cast_precision_loss)]
 fn apply_ranking_strategy_unweighted(
     groups: Vec<(u64, Vec<Vec<u8>>)>,
@@ -1499,7 +2018,7 @@ fn apply_ranking_strategy_unweighted(
             RankStrategy::Dense => {
                 // Dense ranking (1223)
                 for (count, mut group) in groups {
-                    group.sort_unstable();
+                    sort_values(&mut group);
                     for byte_string in group {
                         emit(byte_string, count, current_rank);
                     }
@@ -1509,7 +2028,7 @@ fn apply_ranking_strategy_unweighted(
             RankStrategy::Min => {
                 // Standard competition ranking (1224)
                 for (count, mut group) in groups {
-                    group.sort_unstable();
+                    sort_values(&mut group);
                     let group_len = group.len();
                     for byte_string in group {
                         emit(byte_string, count, current_rank);
@@ -1520,7 +2039,7 @@ fn apply_ranking_strategy_unweighted(
             RankStrategy::Max => {
                 // Modified competition ranking (1334)
                 for (count, mut group) in groups {
-                    group.sort_unstable();
+                    sort_values(&mut group);
                     let group_len = group.len();
                     let max_rank = current_rank + group_len as f64 - 1.0;
                     for byte_string in group {
@@ -1533,7 +2052,7 @@ fn apply_ranking_strategy_unweighted(
                 // Ordinal ranking (1234). Sentinel-suppressed nulls do NOT
                 // consume a rank slot, matching the other strategies.
                 for (count, mut group) in groups {
-                    group.sort_unstable();
+                    sort_values(&mut group);
                     for byte_string in group {
                         let suppressed = emit(byte_string, count, current_rank);
                         if !suppressed {
@@ -1545,7 +2064,7 @@ fn apply_ranking_strategy_unweighted(
             RankStrategy::Average => {
                 // Fractional ranking (1 2.5 2.5 4)
                 for (count, mut group) in groups {
-                    group.sort_unstable();
+                    sort_values(&mut group);
                     let group_len = group.len();
                     let avg_rank = current_rank + (group_len as f64 - 1.0) / 2.0;
                     for byte_string in group {
@@ -1603,7 +2122,7 @@ fn apply_ranking_strategy_weighted(
             RankStrategy::Dense => {
                 // Dense ranking (1223)
                 for (weight, mut group) in groups {
-                    group.sort_unstable();
+                    sort_values(&mut group);
                     for byte_string in group {
                         emit(byte_string, weight, current_rank);
                     }
@@ -1613,7 +2132,7 @@ fn apply_ranking_strategy_weighted(
             RankStrategy::Min => {
                 // Standard competition ranking (1224)
                 for (weight, mut group) in groups {
-                    group.sort_unstable();
+                    sort_values(&mut group);
                     let group_len = group.len();
                     for byte_string in group {
                         emit(byte_string, weight, current_rank);
@@ -1624,7 +2143,7 @@ fn apply_ranking_strategy_weighted(
             RankStrategy::Max => {
                 // Modified competition ranking (1334)
                 for (weight, mut group) in groups {
-                    group.sort_unstable();
+                    sort_values(&mut group);
                     let group_len = group.len();
                     let max_rank = current_rank + group_len as f64 - 1.0;
                     for byte_string in group {
@@ -1637,7 +2156,7 @@ fn apply_ranking_strategy_weighted(
                 // Ordinal ranking (1234). Sentinel-suppressed nulls do NOT
                 // consume a rank slot, matching the other strategies.
                 for (weight, mut group) in groups {
-                    group.sort_unstable();
+                    sort_values(&mut group);
                     for byte_string in group {
                         let suppressed = emit(byte_string, weight, current_rank);
                         if !suppressed {
@@ -1649,7 +2168,7 @@ fn apply_ranking_strategy_weighted(
             RankStrategy::Average => {
                 // Fractional ranking (1 2.5 2.5 4)
                 for (weight, mut group) in groups {
-                    group.sort_unstable();
+                    sort_values(&mut group);
                     let group_len = group.len();
                     let avg_rank = current_rank + (group_len as f64 - 1.0) / 2.0;
                     for byte_string in group {
@@ -1792,6 +2311,33 @@ fn group_by_weight(counts: Vec<(Vec<u8>, f64)>, tolerance: f64) -> Vec<(f64, Vec
 /// Implementation of helper methods for frequency command arguments.
 /// Provides configuration helpers and post-processing utilities for results.
 impl Args {
+    /// The arguments `frequency` fetches (or creates) the stats cache with.
+    fn schema_args(&self) -> util::SchemaArgs {
+        util::SchemaArgs {
+            flag_enum_threshold:  0,
+            flag_ignore_case:     self.flag_ignore_case,
+            flag_strict_dates:    false,
+            flag_strict_formats:  false,
+            // we still get all the stats columns so we can use the stats cache
+            flag_pattern_columns: crate::select::SelectColumns::parse("").unwrap(),
+            flag_dates_whitelist: String::new(),
+            flag_prefer_dmy:      false,
+            flag_force:           false,
+            flag_stdout:          false,
+            flag_jobs:            Some(util::njobs(self.flag_jobs)),
+            flag_polars:          false,
+            flag_no_headers:      self.flag_no_headers,
+            flag_delimiter:       self.flag_delimiter,
+            arg_input:            self.arg_input.clone(),
+            flag_memcheck:        false,
+            flag_output:          None,
+            flag_format:          None,
+            flag_compare:         None,
+            flag_null_threshold:  0.0,
+            flag_null_ratios:     false,
+        }
+    }
+
     pub fn rconfig(&self) -> Config {
         Config::new(self.arg_input.as_ref())
             .delimiter(self.flag_delimiter)
@@ -1975,6 +2521,7 @@ impl Args {
                 .and_then(|p| fs::canonicalize(p).ok())
                 .map(|p| p.to_string_lossy().into_owned())
                 .unwrap_or_default(),
+            bin_strategy:             String::new(),
         };
         let num_cache_columns = entries.len();
 
@@ -2632,13 +3179,13 @@ impl Args {
             counts.sort_unstable_by(|a, b| {
                 a.1.partial_cmp(&b.1)
                     .unwrap_or(std::cmp::Ordering::Equal)
-                    .then_with(|| a.0.cmp(&b.0))
+                    .then_with(|| cmp_values(&a.0, &b.0))
             });
         } else {
             counts.sort_unstable_by(|a, b| {
                 b.1.partial_cmp(&a.1)
                     .unwrap_or(std::cmp::Ordering::Equal)
-                    .then_with(|| a.0.cmp(&b.0))
+                    .then_with(|| cmp_values(&a.0, &b.0))
             });
         }
 
@@ -3605,25 +4152,7 @@ impl Args {
         headers: &Headers,
     ) -> CliResult<(Vec<usize>, Vec<u64>, Vec<StatsData>)> {
         // get the stats records for the entire CSV
-        let schema_args = util::SchemaArgs {
-            flag_enum_threshold:  0,
-            flag_ignore_case:     self.flag_ignore_case,
-            flag_strict_dates:    false,
-            flag_strict_formats:  false,
-            // we still get all the stats columns so we can use the stats cache
-            flag_pattern_columns: crate::select::SelectColumns::parse("").unwrap(),
-            flag_dates_whitelist: String::new(),
-            flag_prefer_dmy:      false,
-            flag_force:           false,
-            flag_stdout:          false,
-            flag_jobs:            Some(util::njobs(self.flag_jobs)),
-            flag_polars:          false,
-            flag_no_headers:      self.flag_no_headers,
-            flag_delimiter:       self.flag_delimiter,
-            arg_input:            self.arg_input.clone(),
-            flag_memcheck:        false,
-            flag_output:          None,
-        };
+        let schema_args = self.schema_args();
         let is_json = self.flag_json || self.flag_pretty_json || self.flag_toon;
 
         // What positional/name-keyed structures each feature needs, so we only pay for
@@ -3807,11 +4336,15 @@ impl Args {
             if self.flag_other_sorted {
                 if self.flag_asc {
                     processed_frequencies.sort_unstable_by(|a, b| {
-                        a.count.cmp(&b.count).then_with(|| a.value.cmp(&b.value))
+                        a.count
+                            .cmp(&b.count)
+                            .then_with(|| cmp_values(&a.value, &b.value))
                     });
                 } else {
                     processed_frequencies.sort_unstable_by(|a, b| {
-                        b.count.cmp(&a.count).then_with(|| a.value.cmp(&b.value))
+                        b.count
+                            .cmp(&a.count)
+                            .then_with(|| cmp_values(&a.value, &b.value))
                     });
                 }
             }
@@ -4474,6 +5007,691 @@ impl Args {
 
         Ok(wtr.flush()?)
     }
+
+    /// `--crosstab`: count the joint values of the selected columns in one sequential pass,
+    /// then emit the contingency table with its marginal totals and a chi-square test of
+    /// independence, as CSV or as JSON/TOON.
+    #[allow(clippy::cast_precision_loss)]
+    fn run_crosstab(&self, rconfig: &Config, argv: &[&str], is_stdin: bool) -> CliResult<()> {
+        let mut rdr = rconfig.reader()?;
+        let full_headers = rdr.byte_headers()?.clone();
+        let sel = rconfig.selection(&full_headers)?;
+        let ncols = sel.len();
+        if ncols < 2 {
+            return fail_incorrectusage_clierror!(
+                "--crosstab needs at least 2 columns, got {ncols}."
+            );
+        }
+        let fields: Vec<String> = if rconfig.no_headers {
+            (1..=ncols).map(|i| i.to_string()).collect()
+        } else {
+            sel.select(&full_headers)
+                .map(|h| util::bytes_to_cow_str(h).into_owned())
+                .collect()
+        };
+
+        // joint counts, keyed by the values of the crosstab columns in selection order.
+        // NULLs are kept as empty values and only labelled with --null-text on output.
+        let flag_no_trim = self.flag_no_trim;
+        let flag_ignore_case = self.flag_ignore_case;
+        let flag_no_nulls = self.flag_no_nulls;
+        let mut joint: HashMap<Vec<Vec<u8>>, u64> = HashMap::new();
+        let mut key: Vec<Vec<u8>> = vec![Vec::new(); ncols];
+        let mut string_buf = String::with_capacity(512);
+        let mut row = csv::ByteRecord::new();
+        let mut rowcount: u64 = 0;
+        'rows: while rdr.read_byte_record(&mut row)? {
+            for (value, field) in key.iter_mut().zip(sel.select(&row)) {
+                let field = if flag_no_trim {
+                    field
+                } else {
+                    trim_bs_whitespace(field)
+                };
+                if field.is_empty() && flag_no_nulls {
+                    continue 'rows;
+                }
+                value.clear();
+                if flag_ignore_case && let Ok(s) = simdutf8::basic::from_utf8(field) {
+                    util::to_lowercase_into(
+                        if flag_no_trim { s } else { s.trim() },
+                        &mut string_buf,
+                    );
+                    value.extend_from_slice(string_buf.as_bytes());
+                } else {
+                    value.extend_from_slice(field);
+                }
+            }
+            rowcount += 1;
+            if let Some(count) = joint.get_mut(&key) {
+                *count += 1;
+            } else {
+                joint.insert(key.clone(), 1);
+            }
+        }
+
+        // one-way marginal totals, derived from the joint counts
+        let mut margins: Vec<HashMap<&[u8], u64>> = vec![HashMap::new(); ncols];
+        for (values, &count) in &joint {
+            for (margin, value) in margins.iter_mut().zip(values) {
+                *margin.entry(value.as_slice()).or_insert(0) += count;
+            }
+        }
+
+        let chi_square = chi_square_independence(&joint, &margins, rowcount);
+
+        // by count, breaking ties by value for deterministic output
+        let flag_asc = self.flag_asc;
+        let by_count = |a: u64, b: u64| if flag_asc { a.cmp(&b) } else { b.cmp(&a) };
+        let mut cells: Vec<(&Vec<Vec<u8>>, u64)> = joint.iter().map(|(k, &v)| (k, v)).collect();
+        cells.sort_unstable_by(|a, b| {
+            by_count(a.1, b.1).then_with(|| {
+                a.0.iter()
+                    .zip(b.0)
+                    .map(|(x, y)| cmp_values(x, y))
+                    .find(|ordering| ordering.is_ne())
+                    .unwrap_or_else(|| a.0.len().cmp(&b.0.len()))
+            })
+        });
+        let sorted_margins: Vec<Vec<(&[u8], u64)>> = margins
+            .iter()
+            .map(|margin| {
+                let mut values: Vec<(&[u8], u64)> = margin.iter().map(|(&k, &v)| (k, v)).collect();
+                values
+                    .sort_unstable_by(|a, b| by_count(a.1, b.1).then_with(|| cmp_values(a.0, b.0)));
+                values
+            })
+            .collect();
+
+        let abs_dec_places = self.flag_pct_dec_places.unsigned_abs() as u32;
+        let pct = |count: u64, total: u64| {
+            if total == 0 {
+                0.0
+            } else {
+                100.0 * count as f64 / total as f64
+            }
+        };
+        let display = |value: &[u8]| {
+            if value.is_empty() {
+                self.flag_null_text.clone()
+            } else if self.flag_vis_whitespace {
+                util::visualize_whitespace(&util::bytes_to_cow_str(value))
+            } else {
+                util::bytes_to_cow_str(value).into_owned()
+            }
+        };
+        let first_margin = &margins[0];
+        let last_margin = &margins[ncols - 1];
+
+        if self.flag_json || self.flag_pretty_json || self.flag_toon {
+            let to_json_pct = |p: f64| {
+                fast_float2::parse(&self.format_percentage(p, abs_dec_places)).unwrap_or(p)
+            };
+            let output = CrosstabOutput {
+                input: if is_stdin {
+                    "stdin".to_string()
+                } else {
+                    // safety: we know arg_input is not None
+                    self.arg_input.clone().unwrap()
+                },
+                description: format!("Generated with `qsv {}`", argv[1..].join(" ")),
+                rowcount,
+                fields: fields.clone(),
+                chi_square,
+                cells: cells
+                    .iter()
+                    .map(|(values, count)| CrosstabCell {
+                        values:         values.iter().map(|v| display(v)).collect(),
+                        count:          *count,
+                        percentage:     to_json_pct(pct(*count, rowcount)),
+                        row_percentage: to_json_pct(pct(
+                            *count,
+                            first_margin[values[0].as_slice()],
+                        )),
+                        col_percentage: to_json_pct(pct(
+                            *count,
+                            last_margin[values[ncols - 1].as_slice()],
+                        )),
+                    })
+                    .collect(),
+                margins: fields
+                    .iter()
+                    .zip(&sorted_margins)
+                    .map(|(field, values)| CrosstabMargin {
+                        field:  field.clone(),
+                        values: values
+                            .iter()
+                            .map(|(value, count)| FrequencyEntry {
+                                value:      display(value),
+                                count:      *count,
+                                percentage: Some(to_json_pct(pct(*count, rowcount))),
+                                rank:       None,
+                            })
+                            .collect(),
+                    })
+                    .collect(),
+            };
+
+            if self.flag_toon {
+                let json_value = serde_json::to_value(&output)?;
+                let toon_output = encode(&json_value, &EncodeOptions::new()).map_err(|e| {
+                    crate::CliError::Other(format!("Failed to encode to TOON: {e}"))
+                })?;
+                if let Some(output_path) = &self.flag_output {
+                    std::fs::write(output_path, toon_output)?;
+                } else {
+                    println!("{toon_output}");
+                }
+            } else {
+                let json_output = if self.flag_pretty_json {
+                    serde_json::to_string_pretty(&output)?
+                } else {
+                    simd_json::to_string_pretty(&output)?
+                };
+                if let Some(output_path) = &self.flag_output {
+                    std::fs::write(output_path, json_output)?;
+                } else {
+                    println!("{json_output}");
+                }
+            }
+            return Ok(());
+        }
+
+        let mut wtr = Config::new(self.flag_output.as_ref()).writer()?;
+        let mut header_row = fields.clone();
+        header_row.extend(
+            ["count", "percentage", "row_percentage", "col_percentage"].map(str::to_string),
+        );
+        wtr.write_record(&header_row)?;
+
+        let mut itoa_buf = itoa::Buffer::new();
+        let mut record: Vec<String> = Vec::with_capacity(ncols + 4);
+        let mut write_row = |values: &mut dyn Iterator<Item = String>,
+                             count: u64,
+                             row_total: u64,
+                             col_total: u64|
+         -> CliResult<()> {
+            record.clear();
+            record.extend(values);
+            record.push(itoa_buf.format(count).to_string());
+            record.push(self.format_percentage(pct(count, rowcount), abs_dec_places));
+            record.push(self.format_percentage(pct(count, row_total), abs_dec_places));
+            record.push(self.format_percentage(pct(count, col_total), abs_dec_places));
+            Ok(wtr.write_record(&record)?)
+        };
+
+        for (values, count) in &cells {
+            write_row(
+                &mut values.iter().map(|v| display(v)),
+                *count,
+                first_margin[values[0].as_slice()],
+                last_margin[values[ncols - 1].as_slice()],
+            )?;
+        }
+        // marginal totals: a column's value with --total-text everywhere else. Its row
+        // total is itself for the first column and the grand total otherwise - and
+        // likewise its column total for the last column.
+        for (i, values) in sorted_margins.iter().enumerate() {
+            for (value, count) in values {
+                write_row(
+                    &mut (0..ncols).map(|j| {
+                        if j == i {
+                            display(value)
+                        } else {
+                            self.flag_total_text.clone()
+                        }
+                    }),
+                    *count,
+                    if i == 0 { *count } else { rowcount },
+                    if i == ncols - 1 { *count } else { rowcount },
+                )?;
+            }
+        }
+        write_row(
+            &mut std::iter::repeat_n(self.flag_total_text.clone(), ncols),
+            rowcount,
+            rowcount,
+            rowcount,
+        )?;
+        wtr.flush()?;
+
+        winfo!(
+            "chi-square: {:.4}, dof: {}, p-value: {}, Cramér's V: {}",
+            chi_square.statistic,
+            chi_square
+                .dof
+                .map_or_else(|| "n/a".to_string(), |dof| dof.to_string()),
+            chi_square
+                .p_value
+                .map_or_else(|| "n/a".to_string(), |p| format!("{p:.4}")),
+            chi_square
+                .cramers_v
+                .map_or_else(|| "n/a".to_string(), |v| format!("{v:.4}")),
+        );
+        Ok(())
+    }
+
+    /// `--bins`: bin the numeric and date columns of the selection into histograms in one
+    /// sequential pass. Column types - and the ranges of the streaming strategies - come
+    /// from the stats cache.
+    #[allow(clippy::cast_precision_loss)]
+    fn run_binned(&self, rconfig: &Config, strategy: BinStrategy, argv: &[&str]) -> CliResult<()> {
+        // unlike the Frequency mode, the Schema mode infers dates, so Date and DateTime
+        // columns are typed (and binned) as such
+        let (_, csv_stats) = get_stats_records(&self.schema_args(), StatsMode::Schema)?;
+        let mut rdr = rconfig.reader()?;
+        let full_headers = rdr.byte_headers()?.clone();
+        if csv_stats.len() != full_headers.len() {
+            return fail_clierror!(
+                "--bins needs the stats cache to find the numeric and date columns, but it could \
+                 not be read or created."
+            );
+        }
+        let sel = rconfig.selection(&full_headers)?;
+
+        let mut columns: Vec<BinnedColumn> = Vec::with_capacity(sel.len());
+        let mut skipped: Vec<String> = Vec::new();
+        for (i, &pos) in sel.iter().enumerate() {
+            let field = if rconfig.no_headers {
+                (i + 1).to_string()
+            } else {
+                util::bytes_to_cow_str(&full_headers[pos]).into_owned()
+            };
+            let stats = &csv_stats[pos];
+            if let Some(axis) = BinAxis::from_stats_type(&stats.r#type) {
+                columns.push(BinnedColumn {
+                    pos,
+                    field,
+                    r#type: stats.r#type.clone(),
+                    axis,
+                    edges: Vec::new(),
+                    counts: Vec::new(),
+                    values: Vec::new(),
+                    nullcount: 0,
+                });
+            } else {
+                skipped.push(field);
+            }
+        }
+        if columns.is_empty() {
+            return fail_clierror!("--bins found no Integer, Float, Date or DateTime columns.");
+        }
+        if !skipped.is_empty() {
+            wwarn!(
+                "--bins skipped {} column(s) that are not numeric or dates: {}",
+                skipped.len(),
+                skipped.join(", ")
+            );
+        }
+
+        let prefer_dmy = util::get_envvar_flag("QSV_PREFER_DMY");
+        let collect_values = strategy.needs_values();
+        if !collect_values {
+            // the equal-width strategies bin while streaming, from the cached range. The
+            // stats cache was just read, so its metadata has the record count - only count
+            // the rows when that is missing.
+            let record_count = match StatsCatalog::load(
+                self.arg_input.as_deref(),
+                self.flag_no_headers,
+                self.flag_delimiter,
+            )
+            .and_then(|catalog| catalog.record_count())
+            {
+                Some(record_count) => record_count,
+                None => util::count_rows(rconfig)?,
+            };
+            for col in &mut columns {
+                let stats = &csv_stats[col.pos];
+                let bound =
+                    |b: Option<&String>| b.and_then(|b| col.axis.parse(b.as_bytes(), prefer_dmy));
+                let n = record_count.saturating_sub(stats.nullcount);
+                if let (Some(min), Some(max)) =
+                    (bound(stats.min.as_ref()), bound(stats.max.as_ref()))
+                    && n > 0
+                {
+                    col.set_edges(strategy, min, max, n, &[]);
+                }
+            }
+        }
+
+        let mut row = csv::ByteRecord::new();
+        let mut rowcount: u64 = 0;
+        while rdr.read_byte_record(&mut row)? {
+            rowcount += 1;
+            for col in &mut columns {
+                let cell = trim_bs_whitespace(row.get(col.pos).unwrap_or_default());
+                match col.axis.parse(cell, prefer_dmy) {
+                    Some(value) if collect_values => col.values.push(value),
+                    // no edges when the cache had no range, i.e. no values
+                    Some(value) if !col.counts.is_empty() => {
+                        let bin = col.bin_of(value);
+                        col.counts[bin] += 1;
+                    },
+                    _ => col.nullcount += 1,
+                }
+            }
+        }
+        if collect_values {
+            for col in &mut columns {
+                let mut values = std::mem::take(&mut col.values);
+                if values.is_empty() {
+                    continue;
+                }
+                values.sort_unstable_by(f64::total_cmp);
+                let (min, max) = (values[0], values[values.len() - 1]);
+                col.set_edges(strategy, min, max, values.len() as u64, &values);
+                for value in values {
+                    let bin = col.bin_of(value);
+                    col.counts[bin] += 1;
+                }
+            }
+        }
+
+        // render the bins the same way for CSV, JSON/TOON and the cache
+        let abs_dec_places = self.flag_pct_dec_places.unsigned_abs() as u32;
+        let fields: Vec<BinnedField> = columns
+            .iter()
+            .map(|col| {
+                let places = col.decimal_places();
+                let pct_denom = if self.flag_pct_nulls && !self.flag_no_nulls {
+                    rowcount
+                } else {
+                    rowcount - col.nullcount
+                };
+                let pct = |count: u64| {
+                    (pct_denom > 0).then(|| {
+                        let pct = 100.0 * count as f64 / pct_denom as f64;
+                        fast_float2::parse(&self.format_percentage(pct, abs_dec_places))
+                            .unwrap_or(pct)
+                    })
+                };
+                BinnedField {
+                    field:     col.field.clone(),
+                    r#type:    col.r#type.clone(),
+                    nullcount: col.nullcount,
+                    bins:      col
+                        .counts
+                        .iter()
+                        .enumerate()
+                        .map(|(i, &count)| BinEntry {
+                            lower: col.axis.format(col.edges[i], places),
+                            upper: col.axis.format(col.edges[i + 1], places),
+                            count,
+                            percentage: pct(count),
+                        })
+                        .collect(),
+                }
+            })
+            .collect();
+
+        if self.flag_frequency_jsonl {
+            self.write_bins_jsonl(&fields, strategy, rowcount, rconfig)?;
+        }
+
+        if self.flag_json || self.flag_pretty_json || self.flag_toon {
+            let output = BinnedOutput {
+                // safety: run() rejects --bins on stdin
+                input: self.arg_input.clone().unwrap(),
+                description: format!("Generated with `qsv {}`", argv[1..].join(" ")),
+                rowcount,
+                fieldcount: fields.len(),
+                bin_strategy: strategy.to_string(),
+                fields,
+            };
+            if self.flag_toon {
+                let json_value = serde_json::to_value(&output)?;
+                let toon_output = encode(&json_value, &EncodeOptions::new()).map_err(|e| {
+                    crate::CliError::Other(format!("Failed to encode to TOON: {e}"))
+                })?;
+                if let Some(output_path) = &self.flag_output {
+                    std::fs::write(output_path, toon_output)?;
+                } else {
+                    println!("{toon_output}");
+                }
+            } else {
+                let json_output = if self.flag_pretty_json {
+                    serde_json::to_string_pretty(&output)?
+                } else {
+                    simd_json::to_string_pretty(&output)?
+                };
+                if let Some(output_path) = &self.flag_output {
+                    std::fs::write(output_path, json_output)?;
+                } else {
+                    println!("{json_output}");
+                }
+            }
+            return Ok(());
+        }
+
+        let mut wtr = Config::new(self.flag_output.as_ref()).writer()?;
+        wtr.write_record(["field", "bin", "lower", "upper", "count", "percentage"])?;
+        let mut itoa_buf = itoa::Buffer::new();
+        let edge_text = |edge: &JsonValue| match edge {
+            JsonValue::String(s) => s.clone(),
+            JsonValue::Null => String::new(),
+            other => other.to_string(),
+        };
+        for field in &fields {
+            for (i, bin) in field.bins.iter().enumerate() {
+                wtr.write_record([
+                    field.field.as_str(),
+                    itoa_buf.format(i + 1),
+                    &edge_text(&bin.lower),
+                    &edge_text(&bin.upper),
+                    &bin.count.to_string(),
+                    &bin.percentage.map_or_else(String::new, |p| p.to_string()),
+                ])?;
+            }
+            if field.nullcount > 0 && !self.flag_no_nulls {
+                let pct = if self.flag_pct_nulls && rowcount > 0 {
+                    self.format_percentage(
+                        100.0 * field.nullcount as f64 / rowcount as f64,
+                        abs_dec_places,
+                    )
+                } else {
+                    String::new()
+                };
+                wtr.write_record([
+                    field.field.as_str(),
+                    self.flag_null_text.as_str(),
+                    "",
+                    "",
+                    &field.nullcount.to_string(),
+                    &pct,
+                ])?;
+            }
+        }
+        Ok(wtr.flush()?)
+    }
+
+    /// Write the `--bins` histograms as a JSONL cache (`.freq.csv.bins.jsonl`): the same
+    /// metadata line as the distinct-value cache, with its `bin_strategy` set, then one
+    /// `BinnedField` per line.
+    fn write_bins_jsonl(
+        &self,
+        fields: &[BinnedField],
+        strategy: BinStrategy,
+        rowcount: u64,
+        rconfig: &Config,
+    ) -> CliResult<()> {
+        let path = rconfig.path.as_ref().ok_or_else(|| {
+            crate::CliError::Other("--frequency-jsonl requires a file input, not stdin".to_string())
+        })?;
+        let metadata = FrequencyCacheMetadata {
+            arg_input:                self.arg_input.clone().unwrap_or_default(),
+            flag_high_card_threshold: self.flag_high_card_threshold,
+            flag_high_card_pct:       self.flag_high_card_pct,
+            flag_no_nulls:            self.flag_no_nulls,
+            flag_no_headers:          self.flag_no_headers,
+            flag_delimiter:           self
+                .flag_delimiter
+                .as_ref()
+                .map_or_else(|| ",".to_string(), |d| (d.as_byte() as char).to_string()),
+            record_count:             rowcount,
+            column_count:             fields.len(),
+            date_generated:           chrono::Utc::now().to_rfc3339(),
+            qsv_version:              env!("CARGO_PKG_VERSION").to_string(),
+            selection_signature:      fields
+                .iter()
+                .map(|f| f.field.as_str())
+                .collect::<Vec<_>>()
+                .join("\x1f"),
+            canonical_input_path:     fs::canonicalize(path)
+                .map(|p| p.to_string_lossy().into_owned())
+                .unwrap_or_default(),
+            bin_strategy:             strategy.to_string(),
+        };
+
+        let mut jsonl = serde_json::to_string(&metadata)?;
+        jsonl.push('\n');
+        for field in fields {
+            jsonl.push_str(&serde_json::to_string(field)?);
+            jsonl.push('\n');
+        }
+        let cache_path = bins_cache_path(path);
+        fs::write(&cache_path, &jsonl)?;
+        winfo!(
+            "Frequency bins cache written: {} ({} bytes, {} columns, {} rows).",
+            cache_path.display(),
+            jsonl.len(),
+            fields.len(),
+            rowcount
+        );
+        Ok(())
+    }
+}
+
+/// Pearson's chi-square test of independence over the joint counts of a `--crosstab`.
+/// Expected counts assume independence (`N * Π marginal_i / N`), and cells that were never
+/// observed only contribute their expected count, so the statistic is `Σ O²/E - N` summed
+/// over the observed cells alone.
+#[allow(clippy::cast_precision_loss)]
+fn chi_square_independence(
+    joint: &HashMap<Vec<Vec<u8>>, u64>,
+    margins: &[HashMap<&[u8], u64>],
+    rowcount: u64,
+) -> ChiSquare {
+    let levels: Vec<u64> = margins.iter().map(|m| m.len() as u64).collect();
+    // Π r_i - Σ (r_i - 1) - 1, ordered so it can't underflow. The cell count of a wide,
+    // high-cardinality crosstab can overflow u64, in which case the dof (and so the
+    // p-value) is reported as n/a.
+    let dof = if rowcount == 0 {
+        Some(0)
+    } else {
+        levels
+            .iter()
+            .try_fold(1_u64, |cells, &r| cells.checked_mul(r))
+            .and_then(|cells| cells.checked_add(levels.len() as u64 - 1))
+            .map(|cells| cells - levels.iter().sum::<u64>())
+    };
+    if dof == Some(0) {
+        return ChiSquare {
+            statistic: 0.0,
+            dof,
+            p_value: None,
+            cramers_v: None,
+        };
+    }
+
+    let n = rowcount as f64;
+    let statistic = joint
+        .iter()
+        .map(|(values, &count)| {
+            let expected = margins.iter().zip(values).fold(n, |e, (margin, value)| {
+                e * margin[value.as_slice()] as f64 / n
+            });
+            (count as f64).powi(2) / expected
+        })
+        .sum::<f64>()
+        - n;
+    // rounding can leave a perfectly independent table slightly below zero
+    let statistic = statistic.max(0.0);
+
+    ChiSquare {
+        statistic,
+        dof,
+        p_value: dof.map(|dof| regularized_gamma_q(dof as f64 / 2.0, statistic / 2.0)),
+        cramers_v: (levels.len() == 2).then(|| {
+            let min_levels = levels[0].min(levels[1]);
+            (statistic / (n * (min_levels - 1) as f64)).sqrt()
+        }),
+    }
+}
+
+/// The regularized upper incomplete gamma function `Q(a, x)`, i.e. the chi-square survival
+/// function for `a = dof / 2` and `x = statistic / 2`. Uses the series expansion below
+/// `x = a + 1` and Lentz's continued fraction above it (Numerical Recipes §6.2).
+#[allow(clippy::cast_precision_loss)]
+fn regularized_gamma_q(a: f64, x: f64) -> f64 {
+    const EPS: f64 = 1e-15;
+    const TINY: f64 = 1e-300;
+    const MAX_ITER: usize = 1000;
+
+    if x <= 0.0 {
+        return 1.0;
+    }
+    let prefix = (a * x.ln() - x - ln_gamma(a)).exp();
+    if x < a + 1.0 {
+        let mut ap = a;
+        let mut del = 1.0 / a;
+        let mut sum = del;
+        for _ in 0..MAX_ITER {
+            ap += 1.0;
+            del *= x / ap;
+            sum += del;
+            if del.abs() < sum.abs() * EPS {
+                break;
+            }
+        }
+        (1.0 - sum * prefix).max(0.0)
+    } else {
+        let mut b = x + 1.0 - a;
+        let mut c = 1.0 / TINY;
+        let mut d = 1.0 / b;
+        let mut h = d;
+        for i in 1..MAX_ITER {
+            let an = -(i as f64) * (i as f64 - a);
+            b += 2.0;
+            d = an * d + b;
+            if d.abs() < TINY {
+                d = TINY;
+            }
+            c = b + an / c;
+            if c.abs() < TINY {
+                c = TINY;
+            }
+            d = 1.0 / d;
+            let del = d * c;
+            h *= del;
+            if (del - 1.0).abs() < EPS {
+                break;
+            }
+        }
+        (prefix * h).min(1.0)
+    }
+}
+
+/// `ln Γ(x)` for `x >= 0.5`, by the Lanczos approximation (g = 7, n = 9).
+#[allow(clippy::cast_precision_loss)]
+fn ln_gamma(x: f64) -> f64 {
+    const LANCZOS: [f64; 9] = [
+        0.999_999_999_999_81,
+        676.520_368_121_885,
+        -1_259.139_216_722_4,
+        771.323_428_777_653,
+        -176.615_029_162_141,
+        12.507_343_278_686_9,
+        -0.138_571_095_265_72,
+        9.984_369_578_019_57e-6,
+        1.505_632_735_149_31e-7,
+    ];
+    let x = x - 1.0;
+    let t = x + 7.5;
+    let series = LANCZOS
+        .iter()
+        .enumerate()
+        .skip(1)
+        .fold(LANCZOS[0], |acc, (i, &c)| acc + c / (x + i as f64));
+    0.5 * std::f64::consts::TAU.ln() + (x + 0.5) * t.ln() - t + series.ln()
 }
 
 /// Helper function to add a field to `field_stats` if it exists
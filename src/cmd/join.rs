@@ -73,6 +73,23 @@ join options:
                            anti joins, where keys are written when NO match
                            is found.
                            Cross joins do not write keys.
+    --memory-limit <arg>   Join inputs too large to index in memory out-of-core.
+                           When the input that is indexed (<input2>, or <input1>
+                           for right joins) is larger than this limit, both inputs
+                           are hash partitioned by join key into temporary files
+                           in --tmp-dir, and the partitions are joined one pair at
+                           a time (a Grace hash join), so only one partition is
+                           indexed at a time. The output has the same rows as the
+                           in-memory join, grouped by partition instead of in
+                           input order.
+                           If less than 50, this is a percentage of total memory.
+                           If more than 50, this is the memory in MB to allocate,
+                           capped at 90 percent of total memory. 0 always spills.
+                           When not set, or when an input is stdin, joins are
+                           done in memory. Ignored for cross joins.
+    --tmp-dir <arg>        The directory for the partition files of out-of-core
+                           joins. It needs about as much free space as both inputs.
+                           Defaults to the operating system temp directory.
 
                            JOIN KEY TRANSFORMATION OPTIONS:
                            Note that transformations are applied to TEMPORARY
@@ -91,14 +108,24 @@ Common options:
                            Must be a single character. (default: ,)
 "#;
 
-use std::{collections::hash_map::Entry, fmt, io, iter::repeat_n, mem::swap, str};
+use std::{
+    collections::hash_map::Entry,
+    fmt, fs,
+    hash::BuildHasher,
+    io,
+    iter::repeat_n,
+    mem::swap,
+    path::{Path, PathBuf},
+    str,
+};
 
 use byteorder::{BigEndian, WriteBytesExt};
-use foldhash::{HashMap, HashMapExt};
+use foldhash::{HashMap, HashMapExt, fast::FixedState};
 use serde::Deserialize;
 
 use crate::{
     CliResult,
+    cmd::extdedup::calculate_memory_limit,
     config::{Config, Delimiter, SeekRead},
     index::Indexed,
     select::{SelectColumns, Selection},
@@ -106,6 +133,10 @@ use crate::{
     util::ByteString,
 };
 
+/// The most partitions an out-of-core join spills each input to. Every partition of an input
+/// is an open file while it is partitioned, so this stays well below common fd limits.
+const MAX_PARTITIONS: u64 = 256;
+
 #[derive(Deserialize)]
 struct Args {
     arg_columns1:              SelectColumns,
@@ -125,82 +156,58 @@ struct Args {
     flag_nulls:                bool,
     flag_delimiter:            Option<Delimiter>,
     flag_keys_output:          Option<String>,
+    flag_memory_limit:         Option<u64>,
+    flag_tmp_dir:              Option<String>,
     flag_ignore_case:          bool,
     flag_ignore_leading_zeros: bool,
 }
 
+#[derive(Clone, Copy, PartialEq)]
+enum JoinKind {
+    Inner,
+    Left,
+    LeftAnti,
+    LeftSemi,
+    Right,
+    RightAnti,
+    RightSemi,
+    Full,
+    Cross,
+}
+
 pub fn run(argv: &[&str]) -> CliResult<()> {
     let args: Args = util::get_args(USAGE, argv)?;
-    let mut state = args.new_io_state()?;
-    match (
-        args.flag_left,
-        args.flag_left_anti,
-        args.flag_left_semi,
-        args.flag_right,
-        args.flag_right_anti,
-        args.flag_right_semi,
-        args.flag_full,
-        args.flag_cross,
-    ) {
-        // default inner join
-        (false, false, false, false, false, false, false, false) => {
-            state.write_headers()?;
-            state.inner_join()
-        },
-        // left join
-        (true, false, false, false, false, false, false, false) => {
-            state.write_headers()?;
-            state.outer_join(false)
-        },
-        // left anti join
-        (false, true, false, false, false, false, false, false) => {
-            state.write_headers1()?;
-            state.left_join(true)
-        },
-        // left semi join
-        (false, false, true, false, false, false, false, false) => {
-            state.write_headers1()?;
-            state.left_join(false)
-        },
-        // right join
-        (false, false, false, true, false, false, false, false) => {
-            state.write_headers()?;
-            state.outer_join(true)
-        },
-        // right anti join
-        // swap left and right data sets and run left anti join
-        (false, false, false, false, true, false, false, false) => {
-            let mut swapped_join = state;
-            swap(&mut swapped_join.rdr1, &mut swapped_join.rdr2);
-            swap(&mut swapped_join.sel1, &mut swapped_join.sel2);
-            swapped_join.write_headers1()?;
-            swapped_join.left_join(true)
-        },
-        // right semi join
-        // swap left and right data sets and run left semi join
-        (false, false, false, false, false, true, false, false) => {
-            let mut swapped_join = state;
-            swap(&mut swapped_join.rdr1, &mut swapped_join.rdr2);
-            swap(&mut swapped_join.sel1, &mut swapped_join.sel2);
-            swapped_join.write_headers1()?;
-            swapped_join.left_join(false)
-        },
-        // full outer join
-        (false, false, false, false, false, false, true, false) => {
-            state.write_headers()?;
-            state.full_outer_join()
-        },
-        // cross join
-        (false, false, false, false, false, false, false, true) => {
-            state.write_headers()?;
-            state.cross_join()
+    let kind = args.join_kind()?;
+
+    let tmp_dir = match &args.flag_tmp_dir {
+        Some(tmp_dir) => {
+            if !Path::new(tmp_dir).exists() {
+                return fail_clierror!("tmp-dir '{tmp_dir}' does not exist");
+            }
+            PathBuf::from(tmp_dir)
         },
-        _ => fail_incorrectusage_clierror!("Please pick exactly one join operation."),
+        None => std::env::temp_dir(),
+    };
+
+    let mut wtr = Config::new(args.flag_output.as_ref()).writer()?;
+    let mut keys_wtr = if kind == JoinKind::Cross {
+        if args.flag_keys_output.is_some() {
+            wwarn!("--keys-output is ignored for cross joins.");
+        }
+        KeysWriter::new(None)?
+    } else {
+        KeysWriter::new(args.flag_keys_output.as_ref())?
+    };
+    let state = args.new_io_state(&mut wtr, &mut keys_wtr)?;
+
+    match args.spill_partitions(kind) {
+        Some(partitions) => state.spilled_join(kind, partitions, &tmp_dir),
+        None => state.join(kind),
     }
 }
 
-struct IoState<R, W: io::Write> {
-    wtr:        csv::Writer<W>,
+struct IoState<'a, R, W: io::Write> {
+    wtr:        &'a mut csv::Writer<W>,
     rdr1:       csv::Reader<R>,
     sel1:       Selection,
     rdr2:       csv::Reader<R>,
@@ -209,10 +216,108 @@ struct IoState<R, W: io::Write> {
     casei:      bool,
     zerosi:     bool,
     nulls:      bool,
-    keys_wtr:   KeysWriter,
+    keys_wtr:   &'a mut KeysWriter,
 }
 
-impl<R: io::Read + io::Seek, W: io::Write> IoState<R, W> {
+impl<R: io::Read + io::Seek, W: io::Write> IoState<'_, R, W> {
+    fn join(mut self, kind: JoinKind) -> CliResult<()> {
+        match kind {
+            JoinKind::Inner => {
+                self.write_headers()?;
+                self.inner_join()
+            },
+            JoinKind::Left => {
+                self.write_headers()?;
+                self.outer_join(false)
+            },
+            JoinKind::LeftAnti => {
+                self.write_headers1()?;
+                self.left_join(true)
+            },
+            JoinKind::LeftSemi => {
+                self.write_headers1()?;
+                self.left_join(false)
+            },
+            JoinKind::Right => {
+                self.write_headers()?;
+                self.outer_join(true)
+            },
+            // swap left and right data sets and run left anti join
+            JoinKind::RightAnti => {
+                swap(&mut self.rdr1, &mut self.rdr2);
+                swap(&mut self.sel1, &mut self.sel2);
+                self.write_headers1()?;
+                self.left_join(true)
+            },
+            // swap left and right data sets and run left semi join
+            JoinKind::RightSemi => {
+                swap(&mut self.rdr1, &mut self.rdr2);
+                swap(&mut self.sel1, &mut self.sel2);
+                self.write_headers1()?;
+                self.left_join(false)
+            },
+            JoinKind::Full => {
+                self.write_headers()?;
+                self.full_outer_join()
+            },
+            JoinKind::Cross => {
+                self.write_headers()?;
+                self.cross_join()
+            },
+        }
+    }
+
+    /// Out-of-core (Grace hash) join.
+    ///
+    /// Both inputs are hash partitioned by join key into `partitions` pairs of temporary
+    /// files under `tmp_dir`. Rows with equal keys always land in the same pair, so joining
+    /// each pair in memory gives the rows of the in-memory join, grouped by partition.
+    fn spilled_join(mut self, kind: JoinKind, partitions: usize, tmp_dir: &Path) -> CliResult<()> {
+        let spill_dir = tempfile::tempdir_in(tmp_dir)?;
+        let (casei, zerosi) = (self.casei, self.zerosi);
+        let parts1 = partition_rows(
+            &mut self.rdr1,
+            &self.sel1,
+            casei,
+            zerosi,
+            &spill_dir.path().join("input1"),
+            partitions,
+        )?;
+        let parts2 = partition_rows(
+            &mut self.rdr2,
+            &self.sel2,
+            casei,
+            zerosi,
+            &spill_dir.path().join("input2"),
+            partitions,
+        )?;
+        log::info!(
+            "joining {partitions} partitions spilled to {}",
+            spill_dir.path().display()
+        );
+
+        for (i, (path1, path2)) in parts1.iter().zip(&parts2).enumerate() {
+            let partition = IoState {
+                wtr: &mut *self.wtr,
+                rdr1: partition_reader(path1)?,
+                sel1: self.sel1.clone(),
+                rdr2: partition_reader(path2)?,
+                sel2: self.sel2.clone(),
+                // the partitions have the headers of their inputs, but only the first
+                // partition writes them
+                no_headers: self.no_headers || i > 0,
+                casei,
+                zerosi,
+                nulls: self.nulls,
+                keys_wtr: &mut *self.keys_wtr,
+            };
+            partition.join(kind)?;
+            fs::remove_file(path1)?;
+            fs::remove_file(path2)?;
+        }
+        Ok(())
+    }
+
     fn write_headers(&mut self) -> CliResult<()> {
         if !self.no_headers {
             let mut headers = self.rdr1.byte_headers()?.clone();
@@ -399,9 +504,74 @@ impl<R: io::Read + io::Seek, W: io::Write> IoState<R, W> {
 }
 
 impl Args {
-    fn new_io_state(
+    fn join_kind(&self) -> CliResult<JoinKind> {
+        Ok(
+            match (
+                self.flag_left,
+                self.flag_left_anti,
+                self.flag_left_semi,
+                self.flag_right,
+                self.flag_right_anti,
+                self.flag_right_semi,
+                self.flag_full,
+                self.flag_cross,
+            ) {
+                (false, false, false, false, false, false, false, false) => JoinKind::Inner,
+                (true, false, false, false, false, false, false, false) => JoinKind::Left,
+                (false, true, false, false, false, false, false, false) => JoinKind::LeftAnti,
+                (false, false, true, false, false, false, false, false) => JoinKind::LeftSemi,
+                (false, false, false, true, false, false, false, false) => JoinKind::Right,
+                (false, false, false, false, true, false, false, false) => JoinKind::RightAnti,
+                (false, false, false, false, false, true, false, false) => JoinKind::RightSemi,
+                (false, false, false, false, false, false, true, false) => JoinKind::Full,
+                (false, false, false, false, false, false, false, true) => JoinKind::Cross,
+                _ => {
+                    return fail_incorrectusage_clierror!(
+                        "Please pick exactly one join operation."
+                    );
+                },
+            },
+        )
+    }
+
+    /// The number of partitions to spill each input to with --memory-limit, or None to join
+    /// in memory.
+    ///
+    /// The memory needed to index an input is estimated from its file size. Each partition
+    /// should index well within the limit, so there are twice as many as the estimate needs.
+    fn spill_partitions(&self, kind: JoinKind) -> Option<usize> {
+        let limit = self.flag_memory_limit?;
+        if kind == JoinKind::Cross {
+            return None;
+        }
+        // right joins index the first input
+        let indexed = if matches!(
+            kind,
+            JoinKind::Right | JoinKind::RightAnti | JoinKind::RightSemi
+        ) {
+            &self.arg_input1
+        } else {
+            &self.arg_input2
+        };
+        // stdin is buffered in memory anyway
+        if indexed == "-" {
+            return None;
+        }
+        let size = fs::metadata(indexed).ok()?.len();
+        let limit_bytes = calculate_memory_limit(Some(limit));
+        log::info!("{limit_bytes} bytes memory limit to index {indexed} ({size} bytes)...");
+        if size <= limit_bytes {
+            return None;
+        }
+        let partitions = (size.div_ceil(limit_bytes.max(1)) * 2).min(MAX_PARTITIONS);
+        Some(partitions as usize)
+    }
+
+    fn new_io_state<'a>(
         &self,
-    ) -> CliResult<IoState<Box<dyn SeekRead + 'static>, Box<dyn io::Write + 'static>>> {
+        wtr: &'a mut csv::Writer<Box<dyn io::Write + 'static>>,
+        keys_wtr: &'a mut KeysWriter,
+    ) -> CliResult<IoState<'a, Box<dyn SeekRead + 'static>, Box<dyn io::Write + 'static>>> {
         let rconf1 = Config::new(Some(&self.arg_input1))
             .delimiter(self.flag_delimiter)
             .no_headers_flag(self.flag_no_headers)
@@ -421,17 +591,8 @@ impl Args {
         };
         let (sel1, sel2) = self.get_selections(&rconf1, &mut rdr1, &rconf2, &mut rdr2)?;
 
-        let keys_wtr = if self.flag_cross {
-            if self.flag_keys_output.is_some() {
-                wwarn!("--keys-output is ignored for cross joins.");
-            }
-            KeysWriter::new(None)?
-        } else {
-            KeysWriter::new(self.flag_keys_output.as_ref())?
-        };
-
         Ok(IoState {
-            wtr: Config::new(self.flag_output.as_ref()).writer()?,
+            wtr,
             rdr1,
             sel1,
             rdr2,
@@ -638,6 +799,51 @@ fn get_row_key(
         .collect()
 }
 
+/// Hash partition the rows of `rdr` by join key into `partitions` CSV files named
+/// `<prefix>-<n>.csv`, so that rows with equal keys in both inputs land in partitions with
+/// the same number. Every partition starts with the header row of `rdr`.
+fn partition_rows<R: io::Read>(
+    rdr: &mut csv::Reader<R>,
+    sel: &Selection,
+    casei: bool,
+    zerosi: bool,
+    prefix: &Path,
+    partitions: usize,
+) -> CliResult<Vec<PathBuf>> {
+    // without headers, this is the first row, which the partition readers skip
+    // as a header, while it is also partitioned as a data row below
+    let headers = rdr.byte_headers()?.clone();
+    let paths: Vec<PathBuf> = (0..partitions)
+        .map(|i| PathBuf::from(format!("{}-{i}.csv", prefix.display())))
+        .collect();
+    let mut wtrs = Vec::with_capacity(partitions);
+    for path in &paths {
+        let mut wtr = csv::WriterBuilder::new().flexible(true).from_path(path)?;
+        wtr.write_byte_record(&headers)?;
+        wtrs.push(wtr);
+    }
+
+    // a fixed seed, so both inputs are partitioned alike
+    let hasher = FixedState::default();
+    let mut row = csv::ByteRecord::new();
+    while rdr.read_byte_record(&mut row)? {
+        let key = get_row_key(sel, &row, casei, zerosi);
+        let partition = (hasher.hash_one(&key) % partitions as u64) as usize;
+        wtrs[partition].write_byte_record(&row)?;
+    }
+    for wtr in &mut wtrs {
+        wtr.flush()?;
+    }
+    Ok(paths)
+}
+
+fn partition_reader(path: &Path) -> CliResult<csv::Reader<fs::File>> {
+    Ok(csv::ReaderBuilder::new()
+        .has_headers(true)
+        .flexible(true)
+        .from_path(path)?)
+}
+
 struct KeysWriter(Option<csv::Writer<Box<dyn io::Write>>>);
 
 impl KeysWriter {
@@ -6,7 +6,7 @@ intersection of rows on the keys specified.
 
 Unlike the join command, joinp can process files larger than RAM, is multithreaded,
 has join key validation, a maintain row order option, pre-join filtering, supports
-non-equi & asof joins and its output columns can be coalesced (no duplicate columns).
+non-equi, asof & fuzzy joins and its output columns can be coalesced (no duplicate columns).
 
 Returns the shape of the join result (number of rows, number of columns) to stderr.
 
@@ -29,6 +29,15 @@ joinp arguments:
 
     Note that <input1> is the left CSV data set and <input2> is the right CSV data set.
 
+    An input can also be a glob (e.g. 'data/**/*.parquet') or a directory with Hive-style
+    name=value partition subdirectories, which is read as one data set with a column
+    per partition key (see `qsv sqlp --help`).
+
+    If an input is not an existing file but is the name of a table or view in the
+    project catalog (see `qsv sqlp --help` for its format), the catalog's table or view
+    is used. The catalog is set with --catalog, the QSV_CATALOG env var, or is a
+    qsv-catalog.toml or qsv-catalog.json file in the current directory.
+
 joinp options:
     --left                 Do a 'left outer' join. This returns all rows in
                            first CSV data set, including rows with no
@@ -75,6 +84,28 @@ joinp options:
                            (e.g. "salary_left >= min_salary_right AND \
                                   salary_left <= max_salary_right AND \
                                   experience_left >= min_exp_right")
+    --fuzzy <spec>         Do a fuzzy join, pairing rows whose <columns1> and <columns2>
+                           values are similar, e.g. names or addresses with typos.
+                           <spec> is <metric>:<threshold>, where metric is one of the
+                           `apply operations` similarity metrics:
+                             simjw - Jaro-Winkler similarity
+                             simdln - normalized Damerau-Levenshtein similarity
+                             simsd - Sørensen-Dice similarity
+                             eudex - sounds alike (1.0 if so, 0.0 otherwise)
+                           and rows are paired when their similarity (0.0 to 1.0) is at
+                           least threshold (e.g. simjw:0.9). Multiple join columns are
+                           compared as their values joined by spaces. The -i, -z and -N
+                           join key options are applied before comparing.
+                           A "fuzzy_score" column with the similarity is added, and the
+                           result has every matching pair, in left row order with the
+                           best match first.
+                           Without blocking columns, EVERY left row is compared with
+                           EVERY right row. Use --block-left for large data sets.
+    --block-left <cols>    FUZZY JOIN: only compare rows with equal values in these
+                           comma-separated left data set columns and the --block-right
+                           columns (e.g. a zip code or the first letter of a name).
+    --block-right <cols>   FUZZY JOIN: the blocking columns of the right data set.
+                           Defaults to the --block-left columns.
 
     --coalesce             Force the join to coalesce columns with the same name.
                            For inner joins, this is not necessary as the join
@@ -101,6 +132,7 @@ joinp options:
                             setting this parameter. Not specifying any order can improve
                             performance. Supported for inner, left, right and full joins.
                             [default: none]
+    --catalog <file>       The project catalog of named tables and views to use.
     --nulls                When set, joins will work on empty fields.
                            Otherwise, empty fields are completely ignored.
     --streaming            When set, the join will be done in a streaming fashion.
@@ -122,7 +154,10 @@ joinp options:
                            Ignored when --infer-len is 0.
                            ‎ -2: treat all columns as String. A Polars schema file is created & cached.
                            ‎ -1: treat all columns as String. No Polars schema file is created.
-                           ‎  0: do not cache Polars schema. Uses --infer-len to infer schema.
+                           ‎  0: do not cache Polars schema. Uses --infer-len to infer schema,
+                                unless --infer-len is not set and a current stats cache exists.
+                                Then its column types are used instead, with all integers as
+                                Int64 and all floats as Float64 so join keys always match.
                            ‎  1: cache Polars schema with the following behavior:
                                 * If schema file exists and is newer than input: use cached schema
                                 * If schema file missing/outdated and stats cache exists: 
@@ -273,11 +308,18 @@ use polars::prelude::*;
 use serde::Deserialize;
 use tempfile::tempdir;
 
+use self::fuzzy::FuzzyJoin;
 use crate::{
-    CliResult, cmd::sqlp::compress_output_if_needed, config::Delimiter, util,
+    CliResult,
+    cmd::sqlp::{catalog::Catalog, compress_output_if_needed, dataset::Dataset},
+    config::Delimiter,
+    statscatalog::{NumericWidth, StatsCatalog},
+    util,
     util::get_stats_records,
 };
 
+mod fuzzy;
+
 #[derive(Deserialize)]
 struct Args {
     arg_columns1:              String,
@@ -293,11 +335,15 @@ struct Args {
     flag_full:                 bool,
     flag_cross:                bool,
     flag_non_equi:             Option<String>,
+    flag_fuzzy:                Option<String>,
+    flag_block_left:           Option<String>,
+    flag_block_right:          Option<String>,
     flag_coalesce:             bool,
     flag_filter_left:          Option<String>,
     flag_filter_right:         Option<String>,
     flag_validate:             Option<String>,
     flag_maintain_order:       Option<String>,
+    flag_catalog:              Option<String>,
     flag_nulls:                bool,
     flag_streaming:            bool,
     flag_try_parsedates:       bool,
@@ -332,9 +378,10 @@ struct Args {
 // of the --infer-len option in the USAGE string above.
 const DEFAULT_INFER_LEN: usize = 10000;
 
-#[derive(PartialEq, Eq)]
+#[derive(PartialEq)]
 enum SpecialJoin {
     NonEqui(String),
+    Fuzzy(FuzzyJoin),
     AsOfAutoSort,
     AsOfNoSort,
     None,
@@ -407,6 +454,31 @@ pub fn run(argv: &[&str]) -> CliResult<()> {
         args.flag_asof,
         args.flag_non_equi.is_some(),
     ) {
+        // fuzzy join
+        join_ops if args.flag_fuzzy.is_some() => {
+            if join_ops
+                != (
+                    false, false, false, false, false, false, false, false, false, false,
+                )
+            {
+                return fail_incorrectusage_clierror!(
+                    "--fuzzy can't be combined with another join operation."
+                );
+            }
+            let fuzzy = FuzzyJoin::new(
+                args.flag_fuzzy.as_deref().unwrap_or_default(),
+                args.flag_block_left.as_deref(),
+                args.flag_block_right.as_deref(),
+            )?;
+            // JoinType::Inner is just a placeholder value, as fuzzy joins pair rows by score
+            join.run(
+                JoinType::Inner,
+                validation,
+                maintain_order,
+                SpecialJoin::Fuzzy(fuzzy),
+                normalization_form.as_ref(),
+            )
+        },
         // default inner join
         (false, false, false, false, false, false, false, false, false, false) => join.run(
             JoinType::Inner,
@@ -752,7 +824,15 @@ impl JoinStruct {
                     .sort(right_selcols_vec, SortMultipleOptions::default());
             }
 
-            if let SpecialJoin::NonEqui(expr) = special_join {
+            if let SpecialJoin::Fuzzy(fuzzy) = &special_join {
+                fuzzy.join(
+                    self.left_lf,
+                    self.right_lf,
+                    left_selcols,
+                    right_selcols,
+                    optflags,
+                )?
+            } else if let SpecialJoin::NonEqui(expr) = special_join {
                 // it's a non-equi join
                 let expr = polars::sql::sql_expr(expr)?;
 
@@ -909,52 +989,15 @@ impl Args {
                 arg_input:            Some(input_path.to_string_lossy().into_owned()),
                 flag_memcheck:        false,
                 flag_output:          None,
+                flag_format:          None,
+                flag_compare:         None,
+                flag_null_threshold:  0.0,
+                flag_null_ratios:     false,
             };
 
-            let (csv_fields, csv_stats) =
-                get_stats_records(&schema_args, util::StatsMode::PolarsSchema)?;
-
-            let mut schema = Schema::with_capacity(csv_stats.len());
-            for (idx, stat) in csv_stats.iter().enumerate() {
-                schema.insert(
-                    PlSmallStr::from_str(
-                        simdutf8::basic::from_utf8(csv_fields.get(idx).unwrap()).unwrap(),
-                    ),
-                    {
-                        let datatype = &stat.r#type;
-                        #[allow(clippy::match_same_arms)]
-                        match datatype.as_str() {
-                            "String" => polars::datatypes::DataType::String,
-                            "Integer" => {
-                                let min = stat.min.as_ref().unwrap();
-                                let max = stat.max.as_ref().unwrap();
-                                if min.parse::<i32>().is_ok() && max.parse::<i32>().is_ok() {
-                                    polars::datatypes::DataType::Int32
-                                } else {
-                                    polars::datatypes::DataType::Int64
-                                }
-                            },
-                            "Float" => {
-                                let min = stat.min.as_ref().unwrap();
-                                let max = stat.max.as_ref().unwrap();
-                                if min.parse::<f32>().is_ok() && max.parse::<f32>().is_ok() {
-                                    polars::datatypes::DataType::Float32
-                                } else {
-                                    polars::datatypes::DataType::Float64
-                                }
-                            },
-                            "Boolean" => polars::datatypes::DataType::Boolean,
-                            "Date" => polars::datatypes::DataType::Date,
-                            "DateTime" => polars::datatypes::DataType::Datetime(
-                                polars::datatypes::TimeUnit::Milliseconds,
-                                None,
-                            ),
-                            _ => polars::datatypes::DataType::String,
-                        }
-                    },
-                );
-            }
-            Ok(schema)
+            let (_, csv_stats) = get_stats_records(&schema_args, util::StatsMode::PolarsSchema)?;
+            // the widest numeric types, so the join columns of both inputs get the same type
+            Ok(StatsCatalog::from_stats(csv_stats).polars_schema(NumericWidth::Widest, true))
         }
 
         /// Helper function to setup a `LazyFrame` with schema handling based on `cache_schema`
@@ -1028,11 +1071,25 @@ impl Args {
 
             match cache_schema {
                 0 => {
-                    reader = reader.with_infer_schema_length(if args.flag_infer_len == 0 {
-                        None
+                    // a current stats cache already knows every column's type - skip inference
+                    // unless --infer-len explicitly asks for it
+                    if args.flag_infer_len == DEFAULT_INFER_LEN
+                        && let Some(catalog) =
+                            StatsCatalog::load(input_path.to_str(), false, args.flag_delimiter)
+                    {
+                        if debuglog_flag {
+                            log::debug!("Using stats catalog schema for: {}", input_path.display());
+                        }
+                        reader = reader.with_schema(Some(Arc::new(
+                            catalog.polars_schema(NumericWidth::Widest, args.flag_try_parsedates),
+                        )));
                     } else {
-                        Some(args.flag_infer_len)
-                    });
+                        reader = reader.with_infer_schema_length(if args.flag_infer_len == 0 {
+                            None
+                        } else {
+                            Some(args.flag_infer_len)
+                        });
+                    }
                 },
                 1 => {
                     if !valid_schema_exists {
@@ -1111,6 +1168,55 @@ impl Args {
             Ok((reader.finish()?, create_schema, canonical_path))
         }
 
+        /// Read the CSV file at `path` with the same CSV parsing options as the input files,
+        /// for catalog tables and datasets.
+        fn scan_csv(
+            path: &str,
+            separator: u8,
+            schema: Option<Arc<Schema>>,
+            comment_char: Option<&PlSmallStr>,
+            args: &Args,
+            delim: u8,
+        ) -> PolarsResult<LazyFrame> {
+            let reader =
+                create_lazy_reader(path, comment_char, args, delim).with_separator(separator);
+            if let Some(schema) = schema {
+                reader.with_schema(Some(schema))
+            } else {
+                reader.with_infer_schema_length(if args.flag_infer_len == 0 {
+                    None
+                } else {
+                    Some(args.flag_infer_len)
+                })
+            }
+            .finish()
+        }
+
+        /// The `LazyFrame` of catalog table or view `name`.
+        fn catalog_lazy_frame(
+            catalog: &Catalog,
+            name: &str,
+            comment_char: Option<&PlSmallStr>,
+            args: &Args,
+            delim: u8,
+        ) -> CliResult<LazyFrame> {
+            catalog.lazy_frame(name, delim, |path, separator, schema| {
+                scan_csv(path, separator, schema, comment_char, args, delim)
+            })
+        }
+
+        /// The `LazyFrame` of the Hive-partitioned directory or glob `input`.
+        fn dataset_lazy_frame(
+            input: &Path,
+            comment_char: Option<&PlSmallStr>,
+            args: &Args,
+            delim: u8,
+        ) -> CliResult<LazyFrame> {
+            Dataset::open(input)?.lazy_frame(delim, None, |path, separator, schema| {
+                scan_csv(path, separator, schema, comment_char, args, delim)
+            })
+        }
+
         // ============ START OF NEW_JOIN MAIN CODE ==============
         let debuglog_flag = log::log_enabled!(log::Level::Debug);
 
@@ -1126,43 +1232,64 @@ impl Args {
             None
         };
 
-        // Check if input files exist
+        // Check if input files exist, or are datasets or catalog tables/views
+        let catalog = Catalog::discover(self.flag_catalog.as_deref())?;
+        let in_catalog = |input: &str| catalog.as_ref().is_some_and(|c| c.contains(input));
         let mut input1_path = PathBuf::from(&self.arg_input1);
-        if !input1_path.exists() {
+        let left_is_dataset = Dataset::is_dataset(&input1_path);
+        let left_from_catalog =
+            !input1_path.exists() && !left_is_dataset && in_catalog(&self.arg_input1);
+        if !input1_path.exists() && !left_is_dataset && !left_from_catalog {
             return fail_clierror!("Input file {} does not exist.", self.arg_input1);
         }
         let mut input2_path = PathBuf::from(&self.arg_input2);
-        if !input2_path.exists() {
+        let right_is_dataset = Dataset::is_dataset(&input2_path);
+        let right_from_catalog =
+            !input2_path.exists() && !right_is_dataset && in_catalog(&self.arg_input2);
+        if !input2_path.exists() && !right_is_dataset && !right_from_catalog {
             return fail_clierror!("Input file {} does not exist.", self.arg_input2);
         }
 
-        // Handle snappy compression for left input
-        if input1_path.extension().and_then(std::ffi::OsStr::to_str) == Some("sz") {
-            let decompressed_path = util::decompress_snappy_file(&input1_path, tmpdir)?;
-            input1_path = PathBuf::from(&decompressed_path);
-            self.arg_input1 = decompressed_path;
-        }
-
         // Setup left LazyFrame
-        let (mut left_lf, create_left_schema, left_canonical) = setup_lazy_frame(
-            &input1_path,
-            comment_char.as_ref(),
-            self,
-            delim,
-            debuglog_flag,
-        )?;
-
-        if create_left_schema {
-            let schema = left_lf.collect_schema()?;
-            let schema_json = serde_json::to_string_pretty(&schema)?;
-            let schema_file = pschema_path(&left_canonical);
-            let mut file = BufWriter::new(File::create(&schema_file)?);
-            file.write_all(schema_json.as_bytes())?;
-            file.flush()?;
-            if debuglog_flag {
-                log::debug!("Saved left schema to file: {}", schema_file.display());
+        let mut left_lf = if let Some(catalog) = catalog.as_ref().filter(|_| left_from_catalog) {
+            catalog_lazy_frame(
+                catalog,
+                &self.arg_input1,
+                comment_char.as_ref(),
+                self,
+                delim,
+            )?
+        } else if left_is_dataset {
+            dataset_lazy_frame(&input1_path, comment_char.as_ref(), self, delim)?
+        } else {
+            // Handle snappy compression for left input
+            if input1_path.extension().and_then(std::ffi::OsStr::to_str) == Some("sz") {
+                let decompressed_path = util::decompress_snappy_file(&input1_path, tmpdir)?;
+                input1_path = PathBuf::from(&decompressed_path);
+                self.arg_input1 = decompressed_path;
             }
-        }
+
+            let (mut left_lf, create_left_schema, left_canonical) = setup_lazy_frame(
+                &input1_path,
+                comment_char.as_ref(),
+                self,
+                delim,
+                debuglog_flag,
+            )?;
+
+            if create_left_schema {
+                let schema = left_lf.collect_schema()?;
+                let schema_json = serde_json::to_string_pretty(&schema)?;
+                let schema_file = pschema_path(&left_canonical);
+                let mut file = BufWriter::new(File::create(&schema_file)?);
+                file.write_all(schema_json.as_bytes())?;
+                file.flush()?;
+                if debuglog_flag {
+                    log::debug!("Saved left schema to file: {}", schema_file.display());
+                }
+            }
+            left_lf
+        };
 
         // Apply left filter if needed
         if let Some(filter_left) = &self.flag_filter_left {
@@ -1170,33 +1297,46 @@ impl Args {
             left_lf = left_lf.filter(filter_left_expr);
         }
 
-        // Handle snappy compression for right input
-        if input2_path.extension().and_then(std::ffi::OsStr::to_str) == Some("sz") {
-            let decompressed_path = util::decompress_snappy_file(&input2_path, tmpdir)?;
-            input2_path = PathBuf::from(&decompressed_path);
-            self.arg_input2 = decompressed_path;
-        }
-
         // Setup right LazyFrame
-        let (mut right_lf, create_right_schema, right_canonical) = setup_lazy_frame(
-            &input2_path,
-            comment_char.as_ref(),
-            self,
-            delim,
-            debuglog_flag,
-        )?;
-
-        if create_right_schema {
-            let schema = right_lf.collect_schema()?;
-            let schema_json = serde_json::to_string_pretty(&schema)?;
-            let schema_file = pschema_path(&right_canonical);
-            let mut file = BufWriter::new(File::create(&schema_file)?);
-            file.write_all(schema_json.as_bytes())?;
-            file.flush()?;
-            if debuglog_flag {
-                log::debug!("Saved right schema to file: {}", schema_file.display());
+        let mut right_lf = if let Some(catalog) = catalog.as_ref().filter(|_| right_from_catalog) {
+            catalog_lazy_frame(
+                catalog,
+                &self.arg_input2,
+                comment_char.as_ref(),
+                self,
+                delim,
+            )?
+        } else if right_is_dataset {
+            dataset_lazy_frame(&input2_path, comment_char.as_ref(), self, delim)?
+        } else {
+            // Handle snappy compression for right input
+            if input2_path.extension().and_then(std::ffi::OsStr::to_str) == Some("sz") {
+                let decompressed_path = util::decompress_snappy_file(&input2_path, tmpdir)?;
+                input2_path = PathBuf::from(&decompressed_path);
+                self.arg_input2 = decompressed_path;
             }
-        }
+
+            let (mut right_lf, create_right_schema, right_canonical) = setup_lazy_frame(
+                &input2_path,
+                comment_char.as_ref(),
+                self,
+                delim,
+                debuglog_flag,
+            )?;
+
+            if create_right_schema {
+                let schema = right_lf.collect_schema()?;
+                let schema_json = serde_json::to_string_pretty(&schema)?;
+                let schema_file = pschema_path(&right_canonical);
+                let mut file = BufWriter::new(File::create(&schema_file)?);
+                file.write_all(schema_json.as_bytes())?;
+                file.flush()?;
+                if debuglog_flag {
+                    log::debug!("Saved right schema to file: {}", schema_file.display());
+                }
+            }
+            right_lf
+        };
 
         // Apply right filter if needed
         if let Some(filter_right) = &self.flag_filter_right {
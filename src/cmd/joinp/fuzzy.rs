@@ -0,0 +1,219 @@
+//! Fuzzy joins for `joinp --fuzzy`.
+//!
+//! Rows are paired when the similarity of their join key values reaches a threshold, using
+//! the same similarity metrics as `apply operations`. Comparing every left row with every
+//! right row is quadratic, so candidate pairs are usually restricted with blocking columns:
+//! only rows with equal blocking values are scored.
+
+use std::collections::HashMap;
+
+use polars::prelude::*;
+use rayon::prelude::*;
+
+use crate::{CliResult, similarity::Metric};
+
+/// The name of the similarity score column added to the join result.
+pub const SCORE_COLUMN: &str = "fuzzy_score";
+
+const LEFT_KEY: &str = "_qsv-fuzzy-left";
+const RIGHT_KEY: &str = "_qsv-fuzzy-right";
+
+#[derive(PartialEq)]
+pub struct FuzzyJoin {
+    metric:      Metric,
+    threshold:   f64,
+    block_left:  Vec<String>,
+    block_right: Vec<String>,
+}
+
+impl FuzzyJoin {
+    /// Parse a `<metric>:<threshold>` --fuzzy spec, with the comma-separated blocking columns
+    /// of each side. The right blocking columns default to the left ones.
+    pub fn new(spec: &str, block_left: Option<&str>, block_right: Option<&str>) -> CliResult<Self> {
+        let Some((metric, threshold)) = spec.split_once(':') else {
+            return fail_incorrectusage_clierror!(
+                "Invalid --fuzzy \"{spec}\". Expected <metric>:<threshold>, e.g. simjw:0.9."
+            );
+        };
+        let metric = match metric.parse::<Metric>() {
+            Ok(metric) => metric,
+            Err(valid) => {
+                return fail_incorrectusage_clierror!(
+                    "Invalid --fuzzy metric \"{}\". {valid}",
+                    metric.trim()
+                );
+            },
+        };
+        let threshold = match threshold.trim().parse::<f64>() {
+            Ok(t) if (0.0..=1.0).contains(&t) => t,
+            _ => {
+                return fail_incorrectusage_clierror!(
+                    "Invalid --fuzzy threshold \"{threshold}\". It must be between 0.0 and 1.0."
+                );
+            },
+        };
+
+        let split = |cols: &str| -> Vec<String> {
+            cols.split(',')
+                .map(|col| col.trim().to_string())
+                .filter(|col| !col.is_empty())
+                .collect()
+        };
+        let block_left = block_left.map(split).unwrap_or_default();
+        let block_right = block_right.map_or_else(|| block_left.clone(), split);
+        if block_left.len() != block_right.len() {
+            return fail_incorrectusage_clierror!(
+                "--block-left and --block-right must specify the same number of columns ({} != \
+                 {}).",
+                block_left.len(),
+                block_right.len()
+            );
+        }
+
+        Ok(Self {
+            metric,
+            threshold,
+            block_left,
+            block_right,
+        })
+    }
+
+    /// Join `left` and `right`, pairing rows whose `left_keys` and `right_keys` values are
+    /// similar enough. Multiple key columns are compared as their values joined by spaces.
+    ///
+    /// Pairs are sorted in left row order, best score first, with the score in the
+    /// `fuzzy_score` column.
+    ///
+    /// The candidate pairs are never materialized: each left row is scored in parallel
+    /// against the right rows of its block, and only its matches are kept.
+    pub fn join(
+        &self,
+        left: LazyFrame,
+        right: LazyFrame,
+        left_keys: Vec<Expr>,
+        right_keys: Vec<Expr>,
+        optflags: OptFlags,
+    ) -> CliResult<DataFrame> {
+        let helper_columns = |keys: Vec<Expr>, blocks: &[String], prefix: &str| {
+            let keys = keys.into_iter().map(|key| key.cast(DataType::String));
+            let blocks = blocks
+                .iter()
+                .map(|block| col(block.as_str()).cast(DataType::String));
+            keys.chain(blocks)
+                .enumerate()
+                .map(|(i, expr)| {
+                    let name = format!("{prefix}-{i}");
+                    (expr.alias(name.as_str()), name)
+                })
+                .unzip::<_, _, Vec<Expr>, Vec<String>>()
+        };
+        let (left_exprs, left_names) = helper_columns(left_keys, &self.block_left, LEFT_KEY);
+        let (right_exprs, right_names) = helper_columns(right_keys, &self.block_right, RIGHT_KEY);
+        let key_count = left_names.len() - self.block_left.len();
+
+        let mut left = left
+            .with_columns(left_exprs)
+            .with_optimizations(optflags)
+            .collect()?;
+        let mut right = right
+            .with_columns(right_exprs)
+            .with_optimizations(optflags)
+            .collect()?;
+
+        // the text compared for each row, None if all its key values are null,
+        // and its block, None if any of its blocking values is null
+        let row_values = |df: &DataFrame,
+                          names: &[String]|
+         -> PolarsResult<Vec<(Option<String>, Option<Vec<String>>)>> {
+            let columns = names
+                .iter()
+                .map(|name| Ok(df.column(name)?.str()?.iter().collect()))
+                .collect::<PolarsResult<Vec<Vec<Option<&str>>>>>()?;
+            let (keys, blocks) = columns.split_at(key_count);
+            Ok((0..df.height())
+                .map(|row| {
+                    let parts: Vec<&str> = keys.iter().filter_map(|c| c[row]).collect();
+                    let text = (!parts.is_empty()).then(|| parts.join(" "));
+                    let block = blocks
+                        .iter()
+                        .map(|c| c[row].map(str::to_string))
+                        .collect::<Option<Vec<String>>>();
+                    (text, block)
+                })
+                .collect())
+        };
+        let left_values = row_values(&left, &left_names)?;
+        let right_values = row_values(&right, &right_names)?;
+        left = left.drop_many(left_names.iter().map(String::as_str));
+        right = right.drop_many(right_names.iter().map(String::as_str));
+
+        if self.block_left.is_empty() {
+            log::warn!(
+                "fuzzy join without --block-left compares every left row with every right row"
+            );
+        }
+        // the right rows of each block; without blocking columns, every row is in one block
+        let mut blocks: HashMap<&[String], Vec<IdxSize>> = HashMap::new();
+        for (row, (text, block)) in right_values.iter().enumerate() {
+            // null keys never match, as in the other joins
+            if let (Some(_), Some(block)) = (text, block) {
+                blocks
+                    .entry(block.as_slice())
+                    .or_default()
+                    .push(row as IdxSize);
+            }
+        }
+
+        // the matches of each left row, best score first
+        let matches: Vec<Vec<(IdxSize, f64)>> = left_values
+            .par_iter()
+            .map(|(text, block)| {
+                let (Some(text), Some(rows)) =
+                    (text, block.as_ref().and_then(|b| blocks.get(b.as_slice())))
+                else {
+                    return Vec::new();
+                };
+                let mut row_matches: Vec<(IdxSize, f64)> = rows
+                    .iter()
+                    .filter_map(|&row| {
+                        let right_text = right_values[row as usize].0.as_deref()?;
+                        let score = self.metric.score(text, right_text);
+                        (score >= self.threshold).then_some((row, score))
+                    })
+                    .collect();
+                // a stable sort, so equal scores stay in right row order
+                row_matches.sort_by(|a, b| b.1.total_cmp(&a.1));
+                row_matches
+            })
+            .collect();
+
+        let pair_count = matches.iter().map(Vec::len).sum();
+        let mut left_rows = Vec::with_capacity(pair_count);
+        let mut right_rows = Vec::with_capacity(pair_count);
+        let mut scores = Vec::with_capacity(pair_count);
+        for (left_row, row_matches) in matches.into_iter().enumerate() {
+            for (right_row, score) in row_matches {
+                left_rows.push(left_row as IdxSize);
+                right_rows.push(right_row);
+                scores.push(score);
+            }
+        }
+
+        let left = left.take(&IdxCa::from_vec(LEFT_KEY.into(), left_rows))?;
+        // the blocking columns are coalesced, as in equi joins
+        let mut right = right
+            .take(&IdxCa::from_vec(RIGHT_KEY.into(), right_rows))?
+            .drop_many(self.block_right.iter().map(String::as_str));
+        // right columns whose names are also left columns get a "_right" suffix, as in the
+        // other joins
+        let left_columns: Vec<PlSmallStr> = left.get_column_names_owned();
+        for name in left_columns {
+            if right.get_column_index(&name).is_some() {
+                right.rename(&name, format!("{name}_right").into())?;
+            }
+        }
+        let mut matches = left.hstack(right.get_columns())?;
+        matches.with_column(Column::new(SCORE_COLUMN.into(), scores))?;
+        Ok(matches)
+    }
+}
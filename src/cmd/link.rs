@@ -0,0 +1,604 @@
+static USAGE: &str = r#"
+Links the records of two CSV files that refer to the same entity (e.g. the same person,
+business or address), even when their values differ slightly. This is known as record
+linkage or entity resolution. With one input, the records of the file are linked with
+each other, to find the records of the same entity in it.
+
+Comparing every record of one file with every record of the other is quadratic, so
+candidate pairs are usually restricted with blocking: only records with the same --block
+column values (e.g. the same zip code or birth year) are compared.
+
+Each candidate pair is compared field by field with the --compare rules, and scored with
+a Fellegi-Sunter weighted sum. A field that agrees adds log2(m/u) to the score, and a field
+that disagrees adds log2((1-m)/(1-u)), where m is the probability that the field agrees
+in two records of the same entity, and u the probability that it agrees in two records of
+different entities. Empty fields (and non-numbers or non-dates for numeric and date
+comparisons) add nothing.
+
+Pairs that score at least --match-threshold are classified as "match", pairs that score
+at least --possible-threshold (if set) as "possible" (for clerical review), and the others
+as "non-match". The output has a row for every match and possible pair, and with
+--non-matches, for every other candidate pair too:
+
+  left_row,right_row,score,class,left_<column>,right_<column>,...
+
+where left_row and right_row are the 1-based record numbers of the pair in the first and
+second input, followed by the pair's values of every compared column. Rows are in the
+order of the first input's records, highest score first.
+
+Examples:
+
+  # Link people by name (a Jaro-Winkler similarity of at least 0.9), birth date (at most
+  # 2 days apart) and zip code, only comparing people with the same birth year.
+  qsv link --compare "name:jw:0.9,dob:date:2,zip:exact" --block birth_year people1.csv people2.csv
+
+  # Find the records of the same person in one file, with the m and u probabilities
+  # of the name comparison.
+  qsv link --compare "name:jw:0.9:m=0.95:u=0.01,dob:date" --block zip people.csv
+
+  # Compare columns with different names in the two files, and classify pairs that
+  # score at least 8 as matches.
+  qsv link --compare "name=full_name:jw,income=salary:numeric:1000" \
+    --block zip --block-right postcode --match-threshold 8 people.csv employees.csv
+
+For more examples, see https://github.com/dathere/qsv/blob/master/tests/test_link.rs.
+
+Usage:
+    qsv link [options] --compare <rules> <input1> [<input2>]
+    qsv link --help
+
+link arguments:
+    <input1>                    The first CSV file.
+    <input2>                    The second CSV file. When not set, the records of
+                                <input1> are linked with each other.
+
+link options:
+    -c, --compare <rules>       The comma-separated field comparisons, each of the form
+                                  <column>[=<right column>]:<comparator>[:<param>][:m=<p>][:u=<p>]
+                                where <column> is a column name or 1-based index of
+                                the first input, also used for the second input unless
+                                =<right column> is given. The comparators are:
+                                  exact    the values are equal.
+                                  jw       the Jaro-Winkler similarity of the values is
+                                           at least <param> (default: 0.9).
+                                  numeric  the numbers are at most <param> apart
+                                           (default: 0).
+                                  date     the dates are at most <param> days apart
+                                           (default: 0). Dates are parsed as in
+                                           `qsv datefmt` (set QSV_PREFER_DMY for
+                                           day-first dates).
+                                m and u are the Fellegi-Sunter agreement probabilities,
+                                between 0 and 1 (exclusive). m defaults to 0.9. u
+                                defaults to the share of all record pairs whose values
+                                are equal for exact comparisons, and to 0.1 for the
+                                others.
+    -b, --block <cols>          Only compare records with the same values in these
+                                columns. Records whose blocking columns are all empty
+                                aren't linked. Without --block, every record of the
+                                first input is compared with every record of the
+                                second one, which is slow for large files.
+    --block-right <cols>        The blocking columns of the second input, if their
+                                names differ from --block's.
+    --match-threshold <score>   The minimum score of a match.
+                                Defaults to half the highest possible score.
+    --possible-threshold <score>  The minimum score of a possible match. Can't be
+                                higher than the match threshold. When not set, no
+                                pairs are classified as possible.
+    --non-matches               Also output the candidate pairs classified as
+                                non-matches.
+    -i, --ignore-case           Compare text, and blocking column values, regardless
+                                of case.
+    -j, --jobs <arg>            The number of jobs to run in parallel.
+                                When not set, the number of jobs is set to the
+                                number of CPUs detected.
+
+Common options:
+    -h, --help                  Display this message
+    -o, --output <file>         Write output to <file> instead of stdout.
+    -n, --no-headers            When set, the first row will not be interpreted
+                                as headers. Columns must then be referred to by
+                                their 1-based indices.
+    -d, --delimiter <arg>       The field delimiter for reading CSV data.
+                                Must be a single character. (default: ,)
+    --memcheck                  Check if there is enough memory to load both inputs
+                                into memory using CONSERVATIVE heuristics.
+"#;
+
+use foldhash::{HashMap, HashMapExt};
+use rayon::prelude::*;
+use serde::Deserialize;
+use strsim::jaro_winkler;
+
+use crate::{
+    CliResult,
+    config::{Config, Delimiter},
+    select::SelectColumns,
+    util,
+};
+
+/// The m probability of comparisons that don't set it.
+const DEFAULT_M: f64 = 0.9;
+/// The u probability of non-exact comparisons that don't set it.
+const DEFAULT_U: f64 = 0.1;
+
+#[derive(Deserialize)]
+struct Args {
+    arg_input1:              String,
+    arg_input2:              Option<String>,
+    flag_compare:            String,
+    flag_block:              Option<String>,
+    flag_block_right:        Option<String>,
+    flag_match_threshold:    Option<f64>,
+    flag_possible_threshold: Option<f64>,
+    flag_non_matches:        bool,
+    flag_ignore_case:        bool,
+    flag_jobs:               Option<usize>,
+    flag_output:             Option<String>,
+    flag_no_headers:         bool,
+    flag_delimiter:          Option<Delimiter>,
+    flag_memcheck:           bool,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Comparator {
+    Exact,
+    JaroWinkler,
+    Numeric,
+    Date,
+}
+
+struct Comparison {
+    left:       usize,
+    right:      usize,
+    comparator: Comparator,
+    /// the minimum similarity of jw, and the maximum distance of numeric and date
+    param:      f64,
+    m:          f64,
+    u:          Option<f64>,
+}
+
+/// A field's value, as compared by a comparison.
+enum FieldValue {
+    Missing,
+    Text(String),
+    /// a number, or a date in days since the Unix epoch
+    Number(f64),
+}
+
+#[derive(Clone, Copy)]
+enum Class {
+    Match,
+    Possible,
+    NonMatch,
+}
+
+impl Class {
+    const fn as_str(self) -> &'static str {
+        match self {
+            Class::Match => "match",
+            Class::Possible => "possible",
+            Class::NonMatch => "non-match",
+        }
+    }
+}
+
+/// A CSV input, loaded into memory.
+struct Input {
+    headers: csv::ByteRecord,
+    records: Vec<csv::ByteRecord>,
+}
+
+impl Input {
+    fn read(path: &str, args: &Args) -> CliResult<Self> {
+        let rconfig = Config::new(Some(&path.to_string()))
+            .delimiter(args.flag_delimiter)
+            .no_headers_flag(args.flag_no_headers);
+        if let Some(path) = rconfig.resolved_path()? {
+            util::mem_file_check(&path, false, args.flag_memcheck)?;
+        }
+        let mut rdr = rconfig.reader()?;
+        let headers = rdr.byte_headers()?.clone();
+        let records = rdr.byte_records().collect::<Result<Vec<_>, _>>()?;
+        Ok(Self { headers, records })
+    }
+
+    /// Resolve a column spec, as in `qsv select`.
+    fn columns(&self, spec: &str, no_headers: bool) -> Result<Vec<usize>, String> {
+        let selection = SelectColumns::parse(spec)?.selection(&self.headers, !no_headers)?;
+        Ok(selection.to_vec())
+    }
+
+    fn column_name(&self, column: usize, no_headers: bool) -> String {
+        if no_headers {
+            (column + 1).to_string()
+        } else {
+            String::from_utf8_lossy(&self.headers[column]).into_owned()
+        }
+    }
+}
+
+fn normalize(field: &[u8], ignore_case: bool) -> String {
+    let field = util::bytes_to_cow_str(field);
+    if ignore_case {
+        field.trim().to_lowercase()
+    } else {
+        field.trim().to_string()
+    }
+}
+
+/// Parse a `<column>[=<right column>]:<comparator>[:<param>][:m=<p>][:u=<p>]` rule.
+fn parse_comparison(
+    rule: &str,
+    left: &Input,
+    right: &Input,
+    no_headers: bool,
+) -> CliResult<Comparison> {
+    let mut parts = rule.split(':').map(str::trim);
+    let columns = parts.next().unwrap_or_default();
+    let Some(comparator) = parts.next() else {
+        return fail_incorrectusage_clierror!(
+            "Invalid --compare rule \"{rule}\". Expected <column>:<comparator>, e.g. name:jw."
+        );
+    };
+    let comparator = match comparator.to_ascii_lowercase().as_str() {
+        "exact" => Comparator::Exact,
+        "jw" | "simjw" | "jaro-winkler" => Comparator::JaroWinkler,
+        "numeric" | "number" => Comparator::Numeric,
+        "date" => Comparator::Date,
+        other => {
+            return fail_incorrectusage_clierror!(
+                "Invalid comparator \"{other}\" in --compare rule \"{rule}\". Valid comparators \
+                 are exact, jw, numeric and date."
+            );
+        },
+    };
+
+    let mut param: Option<f64> = None;
+    let mut m = DEFAULT_M;
+    let mut u: Option<f64> = None;
+    for part in parts {
+        let parsed = match part.split_once('=') {
+            Some(("m", p)) => p.parse().ok().map(|p| m = p),
+            Some(("u", p)) => p.parse().ok().map(|p| u = Some(p)),
+            Some(_) => None,
+            None => part.parse().ok().map(|p| param = Some(p)),
+        };
+        if parsed.is_none() {
+            return fail_incorrectusage_clierror!(
+                "Invalid option \"{part}\" in --compare rule \"{rule}\". Expected a number, m=<p> \
+                 or u=<p>."
+            );
+        }
+    }
+    let is_probability = |p: f64| p > 0.0 && p < 1.0;
+    if !is_probability(m) || u.is_some_and(|u| !is_probability(u)) {
+        return fail_incorrectusage_clierror!(
+            "The m and u probabilities of --compare rule \"{rule}\" must be between 0 and 1 \
+             (exclusive)."
+        );
+    }
+    let param = match (comparator, param) {
+        (Comparator::Exact, Some(_)) => {
+            return fail_incorrectusage_clierror!(
+                "The exact comparator doesn't take a parameter in --compare rule \"{rule}\"."
+            );
+        },
+        (Comparator::JaroWinkler, Some(p)) if !(0.0..=1.0).contains(&p) => {
+            return fail_incorrectusage_clierror!(
+                "The jw similarity of --compare rule \"{rule}\" must be between 0.0 and 1.0."
+            );
+        },
+        (Comparator::Numeric | Comparator::Date, Some(p)) if p < 0.0 => {
+            return fail_incorrectusage_clierror!(
+                "The distance of --compare rule \"{rule}\" can't be negative."
+            );
+        },
+        (_, Some(p)) => p,
+        (Comparator::JaroWinkler, None) => 0.9,
+        (_, None) => 0.0,
+    };
+
+    let (left_spec, right_spec) = columns.split_once('=').unwrap_or((columns, columns));
+    let column = |input: &Input, spec: &str| -> CliResult<usize> {
+        match input.columns(spec, no_headers) {
+            Ok(columns) if columns.len() == 1 => Ok(columns[0]),
+            Ok(_) => fail_incorrectusage_clierror!(
+                "--compare rule \"{rule}\" must select exactly one column."
+            ),
+            Err(e) => {
+                fail_incorrectusage_clierror!("Invalid column in --compare rule \"{rule}\": {e}")
+            },
+        }
+    };
+
+    Ok(Comparison {
+        left: column(left, left_spec)?,
+        right: column(right, right_spec)?,
+        comparator,
+        param,
+        m,
+        u,
+    })
+}
+
+impl Comparison {
+    fn value(&self, field: &[u8], ignore_case: bool, prefer_dmy: bool) -> FieldValue {
+        let text = normalize(field, ignore_case);
+        if text.is_empty() {
+            return FieldValue::Missing;
+        }
+        match self.comparator {
+            Comparator::Exact | Comparator::JaroWinkler => FieldValue::Text(text),
+            Comparator::Numeric => text
+                .parse::<f64>()
+                .ok()
+                .filter(|n| n.is_finite())
+                .map_or(FieldValue::Missing, FieldValue::Number),
+            Comparator::Date => qsv_dateparser::parse_with_preference(&text, prefer_dmy).map_or(
+                FieldValue::Missing,
+                |dt| {
+                    #[allow(clippy::cast_precision_loss)]
+                    let days = dt.timestamp() as f64 / 86_400.0;
+                    FieldValue::Number(days)
+                },
+            ),
+        }
+    }
+
+    /// Do two values agree? None if either is missing.
+    fn agrees(&self, a: &FieldValue, b: &FieldValue) -> Option<bool> {
+        match (a, b) {
+            (FieldValue::Text(a), FieldValue::Text(b)) => Some(match self.comparator {
+                Comparator::JaroWinkler => jaro_winkler(a, b) >= self.param,
+                _ => a == b,
+            }),
+            (FieldValue::Number(a), FieldValue::Number(b)) => Some((a - b).abs() <= self.param),
+            _ => None,
+        }
+    }
+}
+
+/// The values of the `i`th comparison.
+fn column_values(values: &[Vec<FieldValue>], i: usize) -> Vec<&FieldValue> {
+    values.iter().map(|v| &v[i]).collect()
+}
+
+/// Estimate u for an exact comparison: the share of record pairs whose values are equal.
+/// `right` is None when linking a file with itself, where a record isn't paired with
+/// itself.
+fn estimate_u(left: &[&FieldValue], right: Option<&[&FieldValue]>) -> Option<f64> {
+    let count = |values: &[&FieldValue]| {
+        let mut counts: HashMap<&str, u64> = HashMap::new();
+        for value in values {
+            if let FieldValue::Text(text) = value {
+                *counts.entry(text.as_str()).or_default() += 1;
+            }
+        }
+        counts
+    };
+    let left_counts = count(left);
+    let left_n: u64 = left_counts.values().sum();
+
+    #[allow(clippy::cast_precision_loss)]
+    let u = match right {
+        Some(right) => {
+            let right_counts = count(right);
+            let right_n: u64 = right_counts.values().sum();
+            let equal: u64 = left_counts
+                .iter()
+                .map(|(value, n)| n * right_counts.get(value).copied().unwrap_or_default())
+                .sum();
+            if left_n == 0 || right_n == 0 {
+                return None;
+            }
+            equal as f64 / (left_n as f64 * right_n as f64)
+        },
+        None => {
+            if left_n < 2 {
+                return None;
+            }
+            let equal: u64 = left_counts.values().map(|n| n * (n - 1)).sum();
+            equal as f64 / (left_n as f64 * (left_n - 1) as f64)
+        },
+    };
+    // a u of 0 or 1 would make the weights infinite
+    Some(u.clamp(1e-6, 1.0 - 1e-6))
+}
+
+pub fn run(argv: &[&str]) -> CliResult<()> {
+    let args: Args = util::get_args(USAGE, argv)?;
+    let no_headers = args.flag_no_headers;
+    let ignore_case = args.flag_ignore_case;
+
+    let left = Input::read(&args.arg_input1, &args)?;
+    let right = match &args.arg_input2 {
+        Some(input2) => Some(Input::read(input2, &args)?),
+        None => None,
+    };
+    // linking a file with itself
+    let self_link = right.is_none();
+    let right = right.as_ref().unwrap_or(&left);
+
+    let comparisons = args
+        .flag_compare
+        .split(',')
+        .map(|rule| parse_comparison(rule.trim(), &left, right, no_headers))
+        .collect::<CliResult<Vec<_>>>()?;
+
+    let (left_block, right_block) = match &args.flag_block {
+        Some(block) => {
+            let right_spec = args.flag_block_right.as_deref().unwrap_or(block);
+            match (
+                left.columns(block, no_headers),
+                right.columns(right_spec, no_headers),
+            ) {
+                (Ok(l), Ok(r)) if l.len() == r.len() => (l, r),
+                (Ok(l), Ok(r)) => {
+                    return fail_incorrectusage_clierror!(
+                        "--block and --block-right must select the same number of columns ({} != \
+                         {}).",
+                        l.len(),
+                        r.len()
+                    );
+                },
+                (Err(e), _) | (_, Err(e)) => {
+                    return fail_incorrectusage_clierror!("Invalid blocking columns: {e}");
+                },
+            }
+        },
+        None if args.flag_block_right.is_some() => {
+            return fail_incorrectusage_clierror!("--block-right requires --block.");
+        },
+        None => {
+            log::warn!("link without --block compares every pair of records");
+            (Vec::new(), Vec::new())
+        },
+    };
+
+    util::njobs(args.flag_jobs);
+
+    // the compared values of every record, one per comparison
+    let prefer_dmy = util::get_envvar_flag("QSV_PREFER_DMY");
+    let values = |input: &Input, left_side: bool| -> Vec<Vec<FieldValue>> {
+        input
+            .records
+            .par_iter()
+            .map(|record| {
+                comparisons
+                    .iter()
+                    .map(|c| {
+                        let column = if left_side { c.left } else { c.right };
+                        c.value(
+                            record.get(column).unwrap_or_default(),
+                            ignore_case,
+                            prefer_dmy,
+                        )
+                    })
+                    .collect()
+            })
+            .collect()
+    };
+    let left_values = values(&left, true);
+    let right_values = if self_link {
+        None
+    } else {
+        Some(values(right, false))
+    };
+    let right_values_ref = right_values.as_ref().unwrap_or(&left_values);
+
+    // the agreement and disagreement weights of every comparison
+    let weights: Vec<(f64, f64)> = comparisons
+        .iter()
+        .enumerate()
+        .map(|(i, c)| {
+            let u = c.u.unwrap_or_else(|| {
+                if c.comparator != Comparator::Exact {
+                    return DEFAULT_U;
+                }
+                let left_column = column_values(&left_values, i);
+                let right_column = right_values.as_deref().map(|v| column_values(v, i));
+                estimate_u(&left_column, right_column.as_deref()).unwrap_or(DEFAULT_U)
+            });
+            log::debug!("comparison {i}: m={} u={u}", c.m);
+            ((c.m / u).log2(), ((1.0 - c.m) / (1.0 - u)).log2())
+        })
+        .collect();
+    let max_score: f64 = weights.iter().map(|(agree, _)| agree).sum();
+    let match_threshold = args.flag_match_threshold.unwrap_or(max_score / 2.0);
+    if let Some(possible_threshold) = args.flag_possible_threshold
+        && possible_threshold > match_threshold
+    {
+        return fail_incorrectusage_clierror!(
+            "--possible-threshold ({possible_threshold}) can't be higher than the match threshold \
+             ({match_threshold})."
+        );
+    }
+    // without a possible threshold, the pairs below the match threshold are non-matches
+    let possible_threshold = args.flag_possible_threshold.unwrap_or(match_threshold);
+
+    // the blocking key of a record, None if all its blocking columns are empty
+    let block_key = |record: &csv::ByteRecord, columns: &[usize]| -> Option<Vec<String>> {
+        let key: Vec<String> = columns
+            .iter()
+            .map(|&column| normalize(record.get(column).unwrap_or_default(), ignore_case))
+            .collect();
+        (columns.is_empty() || key.iter().any(|k| !k.is_empty())).then_some(key)
+    };
+    let mut blocks: HashMap<Vec<String>, Vec<usize>> = HashMap::new();
+    for (j, record) in right.records.iter().enumerate() {
+        if let Some(key) = block_key(record, &right_block) {
+            blocks.entry(key).or_default().push(j);
+        }
+    }
+
+    // the scored pairs of every left record, highest score first
+    let pairs: Vec<Vec<(usize, f64, Class)>> = left
+        .records
+        .par_iter()
+        .enumerate()
+        .map(|(i, record)| {
+            let Some(candidates) = block_key(record, &left_block).and_then(|key| blocks.get(&key))
+            else {
+                return Vec::new();
+            };
+            let mut scored: Vec<(usize, f64, Class)> = candidates
+                .iter()
+                // a file's pairs are only compared once, and records aren't paired with themselves
+                .filter(|&&j| !self_link || j > i)
+                .filter_map(|&j| {
+                    let score: f64 = comparisons
+                        .iter()
+                        .zip(&weights)
+                        .zip(left_values[i].iter().zip(&right_values_ref[j]))
+                        .map(|((c, (agree, disagree)), (a, b))| match c.agrees(a, b) {
+                            Some(true) => *agree,
+                            Some(false) => *disagree,
+                            None => 0.0,
+                        })
+                        .sum();
+                    let class = if score >= match_threshold {
+                        Class::Match
+                    } else if score >= possible_threshold {
+                        Class::Possible
+                    } else if args.flag_non_matches {
+                        Class::NonMatch
+                    } else {
+                        return None;
+                    };
+                    Some((j, score, class))
+                })
+                .collect();
+            scored.sort_by(|a, b| b.1.total_cmp(&a.1));
+            scored
+        })
+        .collect();
+
+    let mut wtr = Config::new(args.flag_output.as_ref()).writer()?;
+    let mut header = csv::ByteRecord::new();
+    for name in ["left_row", "right_row", "score", "class"] {
+        header.push_field(name.as_bytes());
+    }
+    for c in &comparisons {
+        header.push_field(format!("left_{}", left.column_name(c.left, no_headers)).as_bytes());
+        header.push_field(format!("right_{}", right.column_name(c.right, no_headers)).as_bytes());
+    }
+    wtr.write_byte_record(&header)?;
+
+    let mut buffer = itoa::Buffer::new();
+    let mut row = csv::ByteRecord::new();
+    for (i, scored) in pairs.iter().enumerate() {
+        for &(j, score, class) in scored {
+            row.clear();
+            row.push_field(buffer.format(i + 1).as_bytes());
+            row.push_field(buffer.format(j + 1).as_bytes());
+            row.push_field(util::round_num(score, 4).as_bytes());
+            row.push_field(class.as_str().as_bytes());
+            for c in &comparisons {
+                row.push_field(left.records[i].get(c.left).unwrap_or_default());
+                row.push_field(right.records[j].get(c.right).unwrap_or_default());
+            }
+            wtr.write_byte_record(&row)?;
+        }
+    }
+    Ok(wtr.flush()?)
+}
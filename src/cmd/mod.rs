@@ -76,6 +76,8 @@ pub mod json;
 pub mod jsonl;
 #[cfg(feature = "lens")]
 pub mod lens;
+#[cfg(all(feature = "apply", feature = "feature_capable"))]
+pub mod link;
 #[cfg(feature = "mcp")]
 pub mod log;
 #[cfg(feature = "luau")]
@@ -83,6 +85,8 @@ pub mod luau;
 pub mod moarstats;
 #[cfg(any(feature = "feature_capable", feature = "lite"))]
 pub mod partition;
+#[cfg(any(feature = "feature_capable", feature = "lite"))]
+pub mod patch;
 #[cfg(all(
     feature = "polars",
     any(feature = "feature_capable", feature = "datapusher_plus")
@@ -0,0 +1,389 @@
+static USAGE: &str = r#"
+Apply a `qsv diff` result to a CSV.
+
+Takes the left-hand CSV of a diff and the diff result, and reproduces the
+right-hand CSV. With --reverse, takes the right-hand CSV and reproduces the
+left-hand one, undoing the diff.
+
+The diff result is matched to <input> by the same key used to produce it
+(see --key). Every record of <input> is written unchanged, unless the diff
+result deletes or modifies it:
+  * a deleted record ("-" row only) is dropped.
+  * a modified record ("-" and "+" rows with the same key) is replaced in place
+    by its "+" row.
+  * an added record ("+" row only) is appended at the end, in the order it
+    appears in the diff result.
+
+Before a record is deleted or modified, it is checked against the diff result's
+"-" row. If they differ, or a record to be deleted is missing, or a record to be
+added already exists, the base no longer matches what the diff was computed
+against. This is a CONFLICT, and patch stops with an error (the output is then
+incomplete and must be discarded), unless --skip-conflicts is set.
+
+The diff result must have been produced with headers (i.e. without
+--no-headers-output) and without --drop-equal-fields or --drop-equal-columns,
+as those drop values the patch needs. Its columns are matched to <input>'s by
+name, so a diff result produced with --align-columns can be applied too.
+
+The diff result is loaded into memory. <input> is streamed.
+
+Examples:
+
+# Produce a diff between two releases, then apply it to the old release
+qsv diff --key id release-1.csv release-2.csv -o changes.csv
+qsv patch --key id release-1.csv changes.csv -o release-2-rebuilt.csv
+
+# Undo the diff - reproduce the old release from the new one
+qsv patch --key id --reverse release-2.csv changes.csv -o release-1-rebuilt.csv
+
+# Apply what still applies, and write the conflicting rows to a separate file
+qsv patch --key id --skip-conflicts --conflicts conflicts.csv base.csv changes.csv
+
+For more examples, see https://github.com/dathere/qsv/blob/master/tests/test_patch.rs.
+
+Usage:
+    qsv patch [options] <input> <diff-result>
+    qsv patch --help
+
+patch arguments:
+    <input>                 The CSV to patch. Use `-` to read from stdin.
+    <diff-result>           The output of `qsv diff` (CSV format).
+
+patch options:
+    -k, --key <arg>         The columns that uniquely identify a record. Must be the
+                            same key that was passed to `qsv diff`, and uses the
+                            same syntax: a plain comma separated list of numbers is
+                            taken as 0-based indices, anything else uses the
+                            selection syntax of `qsv select`.
+                            (default: 0)
+    -r, --reverse           Apply the diff result in reverse, turning the right-hand
+                            CSV back into the left-hand one.
+    --skip-conflicts        Do not stop on conflicts. Conflicting changes are not
+                            applied (the record in <input> is kept as is), and the
+                            number of conflicts is reported on stderr.
+    --conflicts <file>      Write every conflicting diff result row to <file>, with
+                            a "conflict" column describing it and the "patch_line"
+                            it came from.
+
+Common options:
+    -h, --help              Display this message
+    -o, --output <file>     Write output to <file> instead of stdout.
+    -n, --no-headers        When set, the first row of <input> will not be
+                            interpreted as headers. Columns are then matched to the
+                            diff result by position.
+    -d, --delimiter <arg>   The field delimiter for reading CSV data.
+                            Must be a single character. (default: ,)
+"#;
+
+use std::io;
+
+use csv::ByteRecord;
+use foldhash::{HashMap, HashMapExt};
+use serde::Deserialize;
+
+use super::diff::resolve_columns;
+use crate::{
+    CliResult,
+    config::{Config, Delimiter},
+    util::{self, ByteString},
+};
+
+#[derive(Deserialize)]
+struct Args {
+    arg_input:           String,
+    arg_diff_result:     String,
+    flag_key:            Option<String>,
+    flag_reverse:        bool,
+    flag_skip_conflicts: bool,
+    flag_conflicts:      Option<String>,
+    flag_output:         Option<String>,
+    flag_no_headers:     bool,
+    flag_delimiter:      Option<Delimiter>,
+}
+
+/// The changes the diff result makes to one key.
+struct Hunk {
+    delete:      Option<ByteRecord>,
+    add:         Option<ByteRecord>,
+    // 1-based line of the hunk's first row in the diff result, for conflict reports
+    line:        u64,
+    // set once a record of <input> with this key has been seen
+    matched:     bool,
+    // set when the hunk could not be applied, so it is not also applied at the end
+    conflicting: bool,
+}
+
+struct ConflictReporter {
+    wtr:   Option<csv::Writer<Box<dyn io::Write + 'static>>>,
+    count: u64,
+    skip:  bool,
+}
+
+impl ConflictReporter {
+    fn report(&mut self, reason: &str, hunk: &Hunk, key: &[ByteString]) -> CliResult<()> {
+        self.count += 1;
+        if let Some(wtr) = self.wtr.as_mut() {
+            for (sign, rec) in [(b"-", &hunk.delete), (b"+", &hunk.add)] {
+                if let Some(rec) = rec {
+                    let line = hunk.line.to_string();
+                    let mut row: Vec<&[u8]> = vec![reason.as_bytes(), line.as_bytes(), sign];
+                    row.extend(rec);
+                    wtr.write_record(row)?;
+                }
+            }
+        }
+        if !self.skip {
+            let key = key
+                .iter()
+                .map(|k| String::from_utf8_lossy(k))
+                .collect::<Vec<_>>()
+                .join(",");
+            return fail_clierror!(
+                "Conflict applying the diff result at line {} (key: {key}): {reason}. Use \
+                 --skip-conflicts to apply the remaining changes anyway.",
+                hunk.line
+            );
+        }
+        Ok(())
+    }
+}
+
+pub fn run(argv: &[&str]) -> CliResult<()> {
+    let args: Args = util::get_args(USAGE, argv)?;
+
+    let rconfig = Config::new(Some(&args.arg_input))
+        .delimiter(args.flag_delimiter)
+        .no_headers_flag(args.flag_no_headers);
+    let pconfig = Config::new(Some(&args.arg_diff_result))
+        .delimiter(args.flag_delimiter)
+        .no_headers(false);
+
+    if rconfig.is_stdin() && pconfig.is_stdin() {
+        return fail_incorrectusage_clierror!(
+            "Only one of <input> or <diff-result> may be set to `-` to read from stdin."
+        );
+    }
+
+    let mut rdr = rconfig.reader()?;
+    let headers = rdr.byte_headers()?.clone();
+
+    let key_cols = match args.flag_key {
+        None => vec![0],
+        Some(ref s) => resolve_columns(s, &headers, rconfig.no_headers, "input")?,
+    };
+    if let Some(&bad) = key_cols.iter().find(|&&k| k >= headers.len()) {
+        return fail_incorrectusage_clierror!(
+            "Key column index {bad} is out of bounds. <input> has {} columns.",
+            headers.len()
+        );
+    }
+
+    let (mut hunks, hunk_index) = load_hunks(
+        &pconfig,
+        &headers,
+        rconfig.no_headers,
+        &key_cols,
+        args.flag_reverse,
+    )?;
+
+    let mut conflicts = ConflictReporter {
+        wtr:   match args.flag_conflicts {
+            Some(ref path) => {
+                let mut wtr = Config::new(Some(path)).writer()?;
+                let mut conflict_headers: Vec<&[u8]> =
+                    vec![&b"conflict"[..], b"patch_line", b"diffresult"];
+                conflict_headers.extend(&headers);
+                wtr.write_record(conflict_headers)?;
+                Some(wtr)
+            },
+            None => None,
+        },
+        count: 0,
+        skip:  args.flag_skip_conflicts,
+    };
+
+    let mut wtr = Config::new(args.flag_output.as_ref()).writer()?;
+    if !rconfig.no_headers {
+        wtr.write_byte_record(&headers)?;
+    }
+
+    let mut row = ByteRecord::new();
+    let mut key: Vec<ByteString> = Vec::with_capacity(key_cols.len());
+    while rdr.read_byte_record(&mut row)? {
+        key.clear();
+        key.extend(
+            key_cols
+                .iter()
+                .map(|&k| row.get(k).unwrap_or_default().to_vec()),
+        );
+
+        let Some(&h) = hunk_index.get(&key) else {
+            wtr.write_byte_record(&row)?;
+            continue;
+        };
+        let hunk = &mut hunks[h];
+        if hunk.matched {
+            // keys are unique in a diffable CSV; leave any later duplicate alone
+            wtr.write_byte_record(&row)?;
+            continue;
+        }
+        hunk.matched = true;
+
+        match hunk.delete {
+            Some(ref del) if del.iter().eq(row.iter()) => {
+                if let Some(ref add) = hunk.add {
+                    wtr.write_byte_record(add)?;
+                }
+            },
+            Some(_) => {
+                hunk.conflicting = true;
+                conflicts.report("record differs from the diff result", hunk, &key)?;
+                wtr.write_byte_record(&row)?;
+            },
+            None => {
+                hunk.conflicting = true;
+                conflicts.report("record to add already exists", hunk, &key)?;
+                wtr.write_byte_record(&row)?;
+            },
+        }
+    }
+
+    // what is left are the added records, and deletions whose record was not found
+    for hunk in &hunks {
+        if hunk.matched || hunk.conflicting {
+            continue;
+        }
+        match (&hunk.delete, &hunk.add) {
+            (None, Some(add)) => wtr.write_byte_record(add)?,
+            (Some(del), _) => {
+                let key: Vec<ByteString> = key_cols
+                    .iter()
+                    .map(|&k| del.get(k).unwrap_or_default().to_vec())
+                    .collect();
+                conflicts.report("record to delete or modify not found", hunk, &key)?;
+            },
+            (None, None) => {},
+        }
+    }
+
+    wtr.flush()?;
+    if let Some(ref mut cwtr) = conflicts.wtr {
+        cwtr.flush()?;
+    }
+    if conflicts.count > 0 {
+        wwarn!("{} conflicting change(s) were skipped.", conflicts.count);
+    }
+    Ok(())
+}
+
+/// Read the diff result into hunks keyed by `key_cols`, with every record reordered to
+/// `headers`' column order.
+fn load_hunks(
+    pconfig: &Config,
+    headers: &ByteRecord,
+    no_headers: bool,
+    key_cols: &[usize],
+    reverse: bool,
+) -> CliResult<(Vec<Hunk>, HashMap<Vec<ByteString>, usize>)> {
+    let mut prdr = pconfig.reader()?;
+    let patch_headers = prdr.byte_headers()?.clone();
+
+    if patch_headers.get(0) != Some(b"diffresult".as_slice()) {
+        return fail_incorrectusage_clierror!(
+            "<diff-result> is not a `qsv diff` result: its first column must be \"diffresult\". \
+             Note that `qsv diff --no-headers-output` results cannot be applied."
+        );
+    }
+
+    // position in the diff result of each <input> column
+    let positions: Vec<usize> = if no_headers {
+        if patch_headers.len() != headers.len() + 1 {
+            return fail_incorrectusage_clierror!(
+                "<diff-result> has {} columns, but <input> has {}.",
+                patch_headers.len() - 1,
+                headers.len()
+            );
+        }
+        (1..=headers.len()).collect()
+    } else {
+        let mut positions = Vec::with_capacity(headers.len());
+        for (i, name) in headers.iter().enumerate() {
+            // the n-th column of a duplicate name is the n-th diff result column of that name
+            let occurrence = headers.iter().take(i).filter(|h| *h == name).count();
+            let pos = patch_headers
+                .iter()
+                .skip(1)
+                .enumerate()
+                .filter(|(_, h)| *h == name)
+                .nth(occurrence)
+                .map(|(pos, _)| pos);
+            let Some(pos) = pos else {
+                return fail_incorrectusage_clierror!(
+                    "Column \"{}\" of <input> is not in <diff-result>. A diff result produced \
+                     with --drop-equal-columns cannot be applied.",
+                    String::from_utf8_lossy(name)
+                );
+            };
+            positions.push(pos + 1);
+        }
+        positions
+    };
+
+    let (delete_sign, add_sign): (&[u8], &[u8]) = if reverse {
+        (&b"+"[..], &b"-"[..])
+    } else {
+        (&b"-"[..], &b"+"[..])
+    };
+
+    let mut hunks: Vec<Hunk> = Vec::new();
+    let mut hunk_index: HashMap<Vec<ByteString>, usize> = HashMap::with_capacity(1_000);
+    let mut prow = ByteRecord::new();
+    // the header is line 1
+    let mut line = 1_u64;
+    while prdr.read_byte_record(&mut prow)? {
+        line += 1;
+        let mut rec = ByteRecord::with_capacity(prow.as_slice().len(), positions.len());
+        rec.extend(positions.iter().map(|&p| prow.get(p).unwrap_or_default()));
+        let key: Vec<ByteString> = key_cols
+            .iter()
+            .map(|&k| rec.get(k).unwrap_or_default().to_vec())
+            .collect();
+
+        let sign = prow.get(0).unwrap_or_default();
+        let is_delete = if sign == delete_sign {
+            true
+        } else if sign == add_sign {
+            false
+        } else {
+            return fail_clierror!(
+                "Invalid diffresult value \"{}\" at line {line} of <diff-result>. Must be \"-\" \
+                 or \"+\".",
+                String::from_utf8_lossy(sign)
+            );
+        };
+
+        let h = *hunk_index.entry(key).or_insert_with(|| {
+            hunks.push(Hunk {
+                delete: None,
+                add: None,
+                line,
+                matched: false,
+                conflicting: false,
+            });
+            hunks.len() - 1
+        });
+        let slot = if is_delete {
+            &mut hunks[h].delete
+        } else {
+            &mut hunks[h].add
+        };
+        if slot.is_some() {
+            return fail_clierror!(
+                "Duplicate key at line {line} of <diff-result>. Was it produced with a different \
+                 --key?"
+            );
+        }
+        *slot = Some(rec);
+    }
+    Ok((hunks, hunk_index))
+}
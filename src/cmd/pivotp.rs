@@ -159,6 +159,10 @@ fn build_schema_args(args: &Args) -> util::SchemaArgs {
         arg_input:            args.arg_input.clone(),
         flag_memcheck:        false,
         flag_output:          None,
+        flag_format:          None,
+        flag_compare:         None,
+        flag_null_threshold:  0.0,
+        flag_null_ratios:     false,
     }
 }
 
@@ -92,6 +92,10 @@ pub fn build(args: &ContextArgs, _spec: Option<&Spec>) -> CliResult<AnalysisCont
         flag_pattern_columns: SelectColumns::parse("")?,
         flag_stdout:          false,
         flag_output:          None,
+        flag_format:          None,
+        flag_compare:         None,
+        flag_null_threshold:  0.0,
+        flag_null_ratios:     false,
     };
 
     // ProfileSchema mode = Schema + quartiles + mode, so the Croissant
@@ -732,6 +732,10 @@ fn check_stats_cache(
         flag_pattern_columns: SelectColumns::parse("")?,
         flag_stdout:          false,
         flag_output:          None,
+        flag_format:          None,
+        flag_compare:         None,
+        flag_null_threshold:  0.0,
+        flag_null_ratios:     false,
     };
 
     // Get stats records
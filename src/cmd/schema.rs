@@ -47,6 +47,50 @@ skip schema inferencing which may fail when the inferencing sample is too low, i
 Polars to optimize the query and gives the user the option to tailor the schema to their specific
 query needs (e.g. using a Decimal type with explicit precision and scale instead of a Float type).
 
+Other Schema Formats:
+=====================
+The `--format` option renders the inferred schema in another schema language instead of
+JSON Schema, with the same type, range, length, pattern & enum inferencing:
+
+  frictionless  Frictionless Table Schema (<input>.tableschema.json). Can be used directly
+                with `qsv validate`.
+  csvw          W3C CSV on the Web metadata (<input>-metadata.json).
+  avro          Apache Avro record schema (<input>.avsc). Enums become Avro enums when
+                all their values are valid Avro names.
+  arrow         An empty Arrow IPC file with the schema (<input>.arrow).
+                Requires the "polars" feature.
+  postgres, sqlite, duckdb
+                A CREATE TABLE statement (<input>.<dialect>.sql). Columns without nulls
+                are NOT NULL and enums become CHECK constraints.
+
+Date & datetime types are only used with --strict-dates (otherwise, they're strings).
+When the stats cache proves a non-float column unique and without nulls (i.e. its
+cardinality equals the row count), it is a primary key candidate. The first candidate
+becomes the primary key of the Frictionless, CSVW and SQL schemas. All candidates are
+listed on stderr.
+
+Schema Drift:
+=============
+The `--compare <schema>` option checks a new CSV against a JSON Schema previously generated
+by `schema` (use the same schema options), instead of generating a schema. It writes a drift
+report CSV with the columns severity, column, change, baseline & current. A change is
+"breaking" when the new data would fail validation against the baseline schema:
+
+  breaking      removed or renamed columns, widened types (e.g. integer -> number),
+                changed date formats, nulls in a column that had none, new enum values,
+                an enum domain that grew past --enum-threshold, and values or lengths
+                outside the baseline's min/max range.
+  non-breaking  added or moved columns, narrowed types, removed enum values, columns
+                that no longer have nulls, and nullable columns whose null ratio rose
+                by more than --null-threshold.
+
+A renamed column is a removed column with a new column of the same type in its position.
+Null ratio drift is only checked if the baseline was generated with --null-ratios, which
+records the null ratio of each nullable column in an "x-qsv" annotation
+(e.g. "x-qsv": {"null_ratio": 0.25}). Otherwise, the nullable columns that couldn't be
+checked are listed on stderr. Null rates are also reported for columns that newly have nulls.
+`schema` exits with an error when there is breaking drift.
+
 For examples, see https://github.com/dathere/qsv/blob/master/tests/test_schema.rs.
 See also https://github.com/dathere/qsv/wiki/Validation-and-Schema#schema
 
@@ -88,6 +132,20 @@ Schema options:
                                Otherwise, use mdy format.
     --force                    Force recomputing cardinality and unique values
                                even if stats cache file exists and is current.
+    --format <fmt>             The schema format to generate. One of jsonschema,
+                               frictionless, csvw, avro, arrow, postgres, sqlite
+                               or duckdb. See "Other Schema Formats" above.
+                               [default: jsonschema]
+    --compare <schema>         Instead of generating a schema, report the drift of the
+                               input from this JSON Schema. See "Schema Drift" above.
+                               The report is written to stdout, or to --output.
+    --null-threshold <ratio>   With --compare, report a nullable column whose null
+                               ratio rose by more than this from the baseline's,
+                               e.g. 0.1 for 10 percentage points.
+                               [default: 0.1]
+    --null-ratios              Record the null ratio of each nullable column in an
+                               "x-qsv" annotation of the JSON Schema, so the schema
+                               can be a --compare baseline for null ratio drift.
     --stdout                   Send generated JSON schema file to stdout instead.
     -j, --jobs <arg>           The number of jobs to run in parallel.
                                When not set, the number of jobs is set to the
@@ -95,7 +153,8 @@ Schema options:
     -o, --output <file>        Write output to <file> instead of using the default
                                filename. For JSON Schema, the default is
                                <input>.schema.json. For Polars schema, the default
-                               is <input>.pschema.json.
+                               is <input>.pschema.json. For other --format's, see
+                               "Other Schema Formats" above.
 
     --polars                   Infer a Polars schema instead of a JSON Schema.
                                This option is only available if the `polars` feature is enabled.
@@ -114,6 +173,9 @@ Common options:
                                CSV into memory using CONSERVATIVE heuristics.
 "#;
 
+mod dialects;
+mod drift;
+
 #[allow(unused_imports)]
 use std::{
     fs::File,
@@ -132,6 +194,7 @@ use regex::Regex;
 use serde_json::{Map, Value, json, value::Number};
 use stats::Frequencies;
 
+use self::dialects::SchemaFormat;
 use crate::{
     CliResult,
     cmd::stats::StatsData,
@@ -177,6 +240,23 @@ pub fn run(argv: &[&str]) -> CliResult<()> {
         );
     }
 
+    let format = match args.flag_format.as_deref() {
+        Some(format) => match format.parse::<SchemaFormat>() {
+            Ok(format) => format,
+            Err(e) => return fail_incorrectusage_clierror!("{e}"),
+        },
+        None => SchemaFormat::JsonSchema,
+    };
+    if !(0.0..=1.0).contains(&args.flag_null_threshold) {
+        return fail_incorrectusage_clierror!("--null-threshold must be between 0 and 1.");
+    }
+    if args.flag_compare.is_some() && format != SchemaFormat::JsonSchema {
+        return fail_incorrectusage_clierror!(
+            "--compare compares JSON Schemas and can't be used with --format {}.",
+            args.flag_format.as_deref().unwrap_or_default()
+        );
+    }
+
     // if using stdin, we create a stdin.csv file as stdin is not seekable and we need to
     // open the file multiple times to compile stats/unique values, etc.
     // We use a fixed "stdin.csv" filename instead of a temporary file with random characters
@@ -238,33 +318,60 @@ pub fn run(argv: &[&str]) -> CliResult<()> {
         }
     }
 
-    // generate list of required fields
-    let required_fields = get_required_fields(&properties_map);
-
-    // create final JSON object for output
-    let schema = json!({
-        "$schema": "https://json-schema.org/draft/2020-12/schema",
-        "title": format!("JSON Schema for {input_filename}"),
-        "description": format!("Inferred JSON Schema with `qsv {}`", argv[1..].join(" ")),
-        "type": "object",
-        "properties": Value::Object(properties_map),
-        "required": Value::Array(required_fields)
-    });
+    if let Some(ref baseline_path) = args.flag_compare {
+        return compare_with_baseline(&args, baseline_path, &properties_map);
+    }
 
-    // Use platform-appropriate JSON serialization
-    let schema_pretty = match cfg_select! {
-        target_endian = "little" => simd_json::to_string_pretty(&schema),
-        _ => serde_json::to_string_pretty(&schema),
-    } {
-        Ok(s) => s,
-        Err(e) => return fail_clierror!("Cannot prettify schema json: {e}"),
+    let description = format!(
+        "Inferred {} with `qsv {}`",
+        if format == SchemaFormat::JsonSchema {
+            "JSON Schema"
+        } else {
+            "schema"
+        },
+        argv[1..].join(" ")
+    );
+    let schema_bytes = if format == SchemaFormat::JsonSchema {
+        // generate list of required fields
+        let required_fields = get_required_fields(&properties_map);
+
+        // create final JSON object for output
+        let schema = json!({
+            "$schema": "https://json-schema.org/draft/2020-12/schema",
+            "title": format!("JSON Schema for {input_filename}"),
+            "description": description,
+            "type": "object",
+            "properties": Value::Object(properties_map),
+            "required": Value::Array(required_fields)
+        });
+
+        // Use platform-appropriate JSON serialization
+        match cfg_select! {
+            target_endian = "little" => simd_json::to_string_pretty(&schema),
+            _ => serde_json::to_string_pretty(&schema),
+        } {
+            Ok(s) => s.into_bytes(),
+            Err(e) => return fail_clierror!("Cannot prettify schema json: {e}"),
+        }
+    } else {
+        // the first column the stats cache proves unique and non-null is the primary key
+        let candidates = dialects::primary_key_candidates(&args, &properties_map)?;
+        if !candidates.is_empty() {
+            winfo!("Primary key candidates: {}", candidates.join(", "));
+        }
+        let table = dialects::TableInfo {
+            file_name: &input_filename,
+            description,
+            primary_key: candidates.first().map(String::as_str),
+        };
+        dialects::render(format, &properties_map, &table)?
     };
 
     if args.flag_stdout {
         let stdout = std::io::stdout();
         let mut handle = stdout.lock();
 
-        handle.write_all(schema_pretty.as_bytes())?;
+        handle.write_all(&schema_bytes)?;
         handle.flush()?;
 
         info!("Schema written to stdout");
@@ -272,11 +379,11 @@ pub fn run(argv: &[&str]) -> CliResult<()> {
         let schema_output_filename = if let Some(output) = args.flag_output {
             output
         } else {
-            input_path + ".schema.json"
+            input_path + format.suffix()
         };
         let mut schema_output_file = File::create(&schema_output_filename)?;
 
-        schema_output_file.write_all(schema_pretty.as_bytes())?;
+        schema_output_file.write_all(&schema_bytes)?;
         schema_output_file.flush()?;
 
         woutinfo!("Schema written to {schema_output_filename}");
@@ -285,6 +392,66 @@ pub fn run(argv: &[&str]) -> CliResult<()> {
     Ok(())
 }
 
+/// Write the drift report of the inferred `properties_map` against the baseline JSON Schema at
+/// `baseline_path`, failing if there's any breaking change.
+fn compare_with_baseline(
+    args: &util::SchemaArgs,
+    baseline_path: &str,
+    properties_map: &Map<String, Value>,
+) -> CliResult<()> {
+    let baseline_json: Value = match std::fs::read_to_string(baseline_path) {
+        Ok(s) => match serde_json::from_str(&s) {
+            Ok(json) => json,
+            Err(e) => return fail_clierror!("Cannot parse baseline schema {baseline_path}: {e}"),
+        },
+        Err(e) => return fail_clierror!("Cannot read baseline schema {baseline_path}: {e}"),
+    };
+    let Some(baseline) = baseline_json.get("properties").and_then(Value::as_object) else {
+        return fail_incorrectusage_clierror!(
+            "Baseline schema {baseline_path} is not a JSON Schema with \"properties\"."
+        );
+    };
+
+    let changes = drift::compare(baseline, properties_map, args.flag_null_threshold);
+    let unchecked = drift::unchecked_null_ratios(baseline, properties_map);
+    if !unchecked.is_empty() {
+        wwarn!(
+            "Baseline schema {baseline_path} has no null ratios, so null ratio drift wasn't \
+             checked for: {}. Generate the baseline with --null-ratios to check them.",
+            unchecked.join(", ")
+        );
+    }
+
+    let mut wtr = Config::new(args.flag_output.as_ref()).writer()?;
+    wtr.write_record(["severity", "column", "change", "baseline", "current"])?;
+    for change in &changes {
+        wtr.write_record([
+            change.severity.to_string().as_str(),
+            &change.column,
+            change.change,
+            &change.baseline,
+            &change.current,
+        ])?;
+    }
+    wtr.flush()?;
+
+    let breaking = changes
+        .iter()
+        .filter(|c| c.severity == drift::Severity::Breaking)
+        .count();
+    let non_breaking = changes.len() - breaking;
+    if breaking > 0 {
+        return fail_clierror!(
+            "Breaking schema drift from {baseline_path}: {breaking} breaking and {non_breaking} \
+             non-breaking change(s)."
+        );
+    }
+    winfo!(
+        "No breaking schema drift from {baseline_path} ({non_breaking} non-breaking change(s))."
+    );
+    Ok(())
+}
+
 /// Builds JSON MAP object that corresponds to the "properties" object of JSON Schema (Draft
 /// 2020-12) by looking at CSV value stats Supported JSON Schema validation vocabularies:
 ///  * type
@@ -509,6 +676,15 @@ pub fn infer_schema_from_stats(
             type_list.push(Value::String("null".to_string()));
         }
 
+        // record the null ratio (the stats' sparsity), so `--compare` can report columns
+        // whose null ratio rose
+        if (args.flag_null_ratios || args.flag_compare.is_some())
+            && col_null_count > 0
+            && let Some(sparsity) = stats_record.sparsity
+        {
+            field_map.insert("x-qsv".to_string(), json!({ "null_ratio": sparsity }));
+        }
+
         if col_null_count > 0 && !enum_list.is_empty() {
             // for fields that are not mandatory and actually have enum list generated,
             // having JSON NULL indicates that missing value is allowed
@@ -699,6 +875,7 @@ fn get_unique_values(
         flag_pct_nulls: false,
         flag_no_trim: false,
         flag_ignore_case: args.flag_ignore_case,
+        flag_collation: None,
         flag_no_float: None,
         #[cfg(feature = "luau")]
         flag_stats_filter: None,
@@ -720,6 +897,9 @@ fn get_unique_values(
         flag_weight: None,
         flag_sketch_method: "exact".to_string(),
         flag_sketch_map_size: 4096,
+        flag_crosstab: None,
+        flag_total_text: "(TOTAL)".to_string(),
+        flag_bins: None,
     };
 
     // Bypass the stats cache while running our nested frequency pass.
@@ -0,0 +1,502 @@
+//! Schema dialects other than JSON Schema for `schema --format`.
+//!
+//! Every dialect is rendered from the inferred JSON Schema `properties`, so they all share the
+//! same stats/frequency-driven inference: a column's type (with `format` "date"/"date-time"
+//! for dates when `--strict-dates` is set), whether it has nulls, and its enum/const domain.
+//! Primary key candidates come from the stats cache (see [`primary_key_candidates`]).
+
+use std::str::FromStr;
+
+use foldhash::HashSet;
+use serde_json::{Map, Value, json};
+
+use crate::{CliResult, config::Config, statscatalog::StatsCatalog, util};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) enum SchemaFormat {
+    JsonSchema,
+    Frictionless,
+    Csvw,
+    Avro,
+    Arrow,
+    Postgres,
+    Sqlite,
+    Duckdb,
+}
+
+impl FromStr for SchemaFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "jsonschema" => Ok(Self::JsonSchema),
+            "frictionless" => Ok(Self::Frictionless),
+            "csvw" => Ok(Self::Csvw),
+            "avro" => Ok(Self::Avro),
+            "arrow" => Ok(Self::Arrow),
+            "postgres" | "postgresql" => Ok(Self::Postgres),
+            "sqlite" => Ok(Self::Sqlite),
+            "duckdb" => Ok(Self::Duckdb),
+            _ => Err(format!(
+                "Invalid --format: {s}. Valid formats are jsonschema, frictionless, csvw, avro, \
+                 arrow, postgres, sqlite and duckdb."
+            )),
+        }
+    }
+}
+
+impl SchemaFormat {
+    /// The suffix appended to the input path for the default output file.
+    pub(super) const fn suffix(self) -> &'static str {
+        match self {
+            Self::JsonSchema => ".schema.json",
+            Self::Frictionless => ".tableschema.json",
+            // the CSVW convention for locating metadata, e.g. data.csv-metadata.json
+            Self::Csvw => "-metadata.json",
+            Self::Avro => ".avsc",
+            Self::Arrow => ".arrow",
+            Self::Postgres => ".postgres.sql",
+            Self::Sqlite => ".sqlite.sql",
+            Self::Duckdb => ".duckdb.sql",
+        }
+    }
+}
+
+/// What the dialects need to know about the table besides its columns.
+pub(super) struct TableInfo<'a> {
+    /// the input's file name, e.g. `data.csv`
+    pub(super) file_name:   &'a str,
+    pub(super) description: String,
+    pub(super) primary_key: Option<&'a str>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    Integer,
+    Number,
+    String,
+    Date,
+    DateTime,
+    // every value is empty
+    Null,
+}
+
+struct Column<'a> {
+    name:     &'a str,
+    kind:     Kind,
+    nullable: bool,
+    def:      &'a Value,
+    /// the enum (or const) domain, without null
+    values:   Vec<&'a Value>,
+}
+
+impl Column<'_> {
+    fn get(&self, keyword: &str) -> Option<&Value> {
+        self.def.get(keyword)
+    }
+}
+
+fn columns(properties: &Map<String, Value>) -> Vec<Column<'_>> {
+    properties
+        .iter()
+        .map(|(name, def)| {
+            let types: Vec<&str> = def
+                .get("type")
+                .and_then(Value::as_array)
+                .map(|types| types.iter().filter_map(Value::as_str).collect())
+                .unwrap_or_default();
+            let format = def.get("format").and_then(Value::as_str);
+            let kind = match types.iter().find(|t| **t != "null") {
+                Some(&"integer") => Kind::Integer,
+                Some(&"number") => Kind::Number,
+                Some(_) if format == Some("date") => Kind::Date,
+                Some(_) if format == Some("date-time") => Kind::DateTime,
+                Some(_) => Kind::String,
+                None => Kind::Null,
+            };
+            let values = match (def.get("enum"), def.get("const")) {
+                (Some(Value::Array(values)), _) => values.iter().filter(|v| !v.is_null()).collect(),
+                (None, Some(value)) => vec![value],
+                _ => Vec::new(),
+            };
+            Column {
+                name,
+                kind,
+                nullable: types.contains(&"null"),
+                def,
+                values,
+            }
+        })
+        .collect()
+}
+
+/// The columns whose values the stats cache proves unique and non-null, in column order.
+/// Float columns are not considered, as they make poor keys.
+/// Empty if there is no current stats cache with an exact cardinality.
+pub(super) fn primary_key_candidates(
+    args: &util::SchemaArgs,
+    properties: &Map<String, Value>,
+) -> CliResult<Vec<String>> {
+    let Some(catalog) = StatsCatalog::load(
+        args.arg_input.as_deref(),
+        args.flag_no_headers,
+        args.flag_delimiter,
+    ) else {
+        return Ok(Vec::new());
+    };
+    let mut rdr = Config::new(args.arg_input.as_ref())
+        .delimiter(args.flag_delimiter)
+        .no_headers_flag(args.flag_no_headers)
+        .reader()?;
+    let headers = rdr.headers()?.clone();
+
+    Ok(columns(properties)
+        .iter()
+        .filter(|c| matches!(c.kind, Kind::Integer | Kind::String | Kind::Date))
+        .filter(|c| {
+            headers
+                .iter()
+                .position(|h| h == c.name)
+                .is_some_and(|i| catalog.proves_unique(&[i]))
+        })
+        .map(|c| c.name.to_string())
+        .collect())
+}
+
+/// Render the schema of `properties` in `format`.
+pub(super) fn render(
+    format: SchemaFormat,
+    properties: &Map<String, Value>,
+    table: &TableInfo,
+) -> CliResult<Vec<u8>> {
+    let columns = columns(properties);
+    let json = match format {
+        SchemaFormat::Frictionless => frictionless(&columns, table),
+        SchemaFormat::Csvw => csvw(&columns, table),
+        SchemaFormat::Avro => avro(&columns, table),
+        SchemaFormat::Arrow => return arrow_ipc(&columns),
+        SchemaFormat::Postgres | SchemaFormat::Sqlite | SchemaFormat::Duckdb => {
+            return Ok(create_table(format, &columns, table).into_bytes());
+        },
+        SchemaFormat::JsonSchema => unreachable!("JSON Schema is not a dialect"),
+    };
+    Ok(serde_json::to_vec_pretty(&json)?)
+}
+
+/// A Frictionless Table Schema (<https://specs.frictionlessdata.io/table-schema/>).
+fn frictionless(columns: &[Column], table: &TableInfo) -> Value {
+    let fields: Vec<Value> = columns
+        .iter()
+        .map(|c| {
+            let mut field = Map::new();
+            field.insert("name".to_string(), json!(c.name));
+            let ftype = match c.kind {
+                Kind::Integer => "integer",
+                Kind::Number => "number",
+                Kind::String => "string",
+                Kind::Date => "date",
+                Kind::DateTime => "datetime",
+                Kind::Null => "any",
+            };
+            field.insert("type".to_string(), json!(ftype));
+            if c.kind == Kind::String && c.get("format").and_then(Value::as_str) == Some("email") {
+                field.insert("format".to_string(), json!("email"));
+            }
+            if let Some(description) = c.get("description") {
+                field.insert("description".to_string(), description.clone());
+            }
+
+            let mut constraints = Map::new();
+            if !c.nullable {
+                constraints.insert("required".to_string(), json!(true));
+            }
+            for keyword in ["minimum", "maximum", "minLength", "maxLength", "pattern"] {
+                if let Some(value) = c.get(keyword) {
+                    constraints.insert(keyword.to_string(), value.clone());
+                }
+            }
+            if !c.values.is_empty() {
+                constraints.insert("enum".to_string(), json!(c.values));
+            }
+            if !constraints.is_empty() {
+                field.insert("constraints".to_string(), Value::Object(constraints));
+            }
+            Value::Object(field)
+        })
+        .collect();
+
+    let mut schema = Map::new();
+    schema.insert("fields".to_string(), Value::Array(fields));
+    schema.insert("missingValues".to_string(), json!([""]));
+    if let Some(primary_key) = table.primary_key {
+        schema.insert("primaryKey".to_string(), json!([primary_key]));
+    }
+    schema.insert("description".to_string(), json!(table.description));
+    Value::Object(schema)
+}
+
+/// W3C CSV on the Web metadata (<https://www.w3.org/TR/tabular-metadata/>).
+/// Columns keep their header as "titles", with an identifier-safe "name".
+fn csvw(columns: &[Column], table: &TableInfo) -> Value {
+    let mut names = Identifiers::default();
+    let mut primary_key = None;
+    let csvw_columns: Vec<Value> = columns
+        .iter()
+        .map(|c| {
+            let name = names.unique(c.name);
+            if table.primary_key == Some(c.name) {
+                primary_key = Some(name.clone());
+            }
+            let base = match c.kind {
+                Kind::Integer => "integer",
+                Kind::Number => "double",
+                Kind::Date => "date",
+                Kind::DateTime => "dateTime",
+                Kind::String | Kind::Null => "string",
+            };
+            let mut datatype = Map::new();
+            datatype.insert("base".to_string(), json!(base));
+            for (keyword, facet) in [
+                ("minimum", "minimum"),
+                ("maximum", "maximum"),
+                ("minLength", "minLength"),
+                ("maxLength", "maxLength"),
+            ] {
+                if let Some(value) = c.get(keyword) {
+                    datatype.insert(facet.to_string(), value.clone());
+                }
+            }
+            // for strings, the format is a regular expression the whole value must match
+            if c.kind == Kind::String {
+                let format = if c.values.is_empty() {
+                    c.get("pattern").and_then(Value::as_str).map(str::to_string)
+                } else {
+                    Some(
+                        c.values
+                            .iter()
+                            .map(|v| regex::escape(v.as_str().unwrap_or_default()))
+                            .collect::<Vec<_>>()
+                            .join("|"),
+                    )
+                };
+                if let Some(format) = format {
+                    datatype.insert("format".to_string(), json!(format));
+                }
+            }
+
+            let mut column = Map::new();
+            column.insert("name".to_string(), json!(name));
+            column.insert("titles".to_string(), json!(c.name));
+            column.insert(
+                "datatype".to_string(),
+                if datatype.len() == 1 {
+                    json!(base)
+                } else {
+                    Value::Object(datatype)
+                },
+            );
+            column.insert("required".to_string(), json!(!c.nullable));
+            if let Some(description) = c.get("description") {
+                column.insert("dc:description".to_string(), description.clone());
+            }
+            Value::Object(column)
+        })
+        .collect();
+
+    let mut table_schema = Map::new();
+    table_schema.insert("columns".to_string(), Value::Array(csvw_columns));
+    if let Some(primary_key) = primary_key {
+        table_schema.insert("primaryKey".to_string(), json!(primary_key));
+    }
+    json!({
+        "@context": "http://www.w3.org/ns/csvw",
+        "url": table.file_name,
+        "dc:description": table.description,
+        "tableSchema": table_schema,
+    })
+}
+
+/// An Apache Avro record schema (<https://avro.apache.org/docs/current/specification/>).
+/// Field names are made Avro-safe; the field "doc" has the original column name.
+fn avro(columns: &[Column], table: &TableInfo) -> Value {
+    let mut names = Identifiers::default();
+    let fields: Vec<Value> = columns
+        .iter()
+        .map(|c| {
+            let name = names.unique(c.name);
+            let symbols: Option<Vec<&str>> =
+                c.values.iter().map(|v| v.as_str()).collect::<Option<_>>();
+            let avro_type = match c.kind {
+                Kind::Integer => json!("long"),
+                Kind::Number => json!("double"),
+                Kind::Date => json!({"type": "int", "logicalType": "date"}),
+                Kind::DateTime => json!({"type": "long", "logicalType": "timestamp-millis"}),
+                Kind::Null => json!("null"),
+                // enum symbols must be Avro names, otherwise it's a plain string
+                Kind::String => match symbols {
+                    Some(symbols)
+                        if !symbols.is_empty() && symbols.iter().all(|s| is_identifier(s)) =>
+                    {
+                        json!({"type": "enum", "name": format!("{name}_enum"), "symbols": symbols})
+                    },
+                    _ => json!("string"),
+                },
+            };
+
+            let mut field = Map::new();
+            field.insert("name".to_string(), json!(name));
+            if c.nullable && c.kind != Kind::Null {
+                field.insert("type".to_string(), json!(["null", avro_type]));
+                field.insert("default".to_string(), Value::Null);
+            } else {
+                field.insert("type".to_string(), avro_type);
+            }
+            if let Some(description) = c.get("description") {
+                field.insert("doc".to_string(), description.clone());
+            }
+            Value::Object(field)
+        })
+        .collect();
+
+    let stem = table
+        .file_name
+        .split_once('.')
+        .map_or(table.file_name, |(stem, _)| stem);
+    json!({
+        "type": "record",
+        "name": Identifiers::default().unique(stem),
+        "doc": table.description,
+        "fields": fields,
+    })
+}
+
+/// An Arrow IPC file with the schema and no rows.
+#[cfg(feature = "polars")]
+fn arrow_ipc(columns: &[Column]) -> CliResult<Vec<u8>> {
+    use polars::prelude::{
+        DataFrame, DataType, IpcWriter, PlSmallStr, Schema, SerWriter, TimeUnit,
+    };
+
+    let mut schema = Schema::with_capacity(columns.len());
+    for c in columns {
+        let datatype = match c.kind {
+            Kind::Integer => DataType::Int64,
+            Kind::Number => DataType::Float64,
+            Kind::String => DataType::String,
+            Kind::Date => DataType::Date,
+            Kind::DateTime => DataType::Datetime(TimeUnit::Milliseconds, None),
+            Kind::Null => DataType::Null,
+        };
+        schema.insert(PlSmallStr::from_str(c.name), datatype);
+    }
+    let mut df = DataFrame::empty_with_schema(&schema);
+    let mut ipc = Vec::new();
+    IpcWriter::new(&mut ipc).finish(&mut df)?;
+    Ok(ipc)
+}
+
+#[cfg(not(feature = "polars"))]
+fn arrow_ipc(_columns: &[Column]) -> CliResult<Vec<u8>> {
+    fail_incorrectusage_clierror!("--format arrow is only available with the `polars` feature.")
+}
+
+/// A `CREATE TABLE` statement, with enum/const domains as CHECK constraints and the primary
+/// key candidate as the PRIMARY KEY.
+fn create_table(format: SchemaFormat, columns: &[Column], table: &TableInfo) -> String {
+    let sql_type = |kind: Kind| match (format, kind) {
+        (SchemaFormat::Postgres | SchemaFormat::Duckdb, Kind::Integer) => "BIGINT",
+        (SchemaFormat::Postgres, Kind::Number) => "DOUBLE PRECISION",
+        (SchemaFormat::Duckdb, Kind::Number) => "DOUBLE",
+        (SchemaFormat::Sqlite, Kind::Integer) => "INTEGER",
+        (SchemaFormat::Sqlite, Kind::Number) => "REAL",
+        // SQLite has no date types; ISO-8601 text sorts and compares correctly
+        (SchemaFormat::Postgres | SchemaFormat::Duckdb, Kind::Date) => "DATE",
+        (SchemaFormat::Postgres | SchemaFormat::Duckdb, Kind::DateTime) => "TIMESTAMP",
+        (SchemaFormat::Duckdb, _) => "VARCHAR",
+        _ => "TEXT",
+    };
+
+    let table_name = table
+        .file_name
+        .split_once('.')
+        .map_or(table.file_name, |(stem, _)| stem);
+    let mut definitions: Vec<String> = columns
+        .iter()
+        .map(|c| {
+            let column = quote_identifier(c.name);
+            let mut definition = format!("{column} {}", sql_type(c.kind));
+            if !c.nullable {
+                definition.push_str(" NOT NULL");
+            }
+            if !c.values.is_empty() {
+                let values: Vec<String> = c
+                    .values
+                    .iter()
+                    .map(|v| match v {
+                        Value::String(s) => quote_literal(s),
+                        other => other.to_string(),
+                    })
+                    .collect();
+                definition.push_str(&format!(" CHECK ({column} IN ({}))", values.join(", ")));
+            }
+            definition
+        })
+        .collect();
+    if let Some(primary_key) = table.primary_key {
+        definitions.push(format!("PRIMARY KEY ({})", quote_identifier(primary_key)));
+    }
+
+    format!(
+        "-- {}\nCREATE TABLE {} (\n    {}\n);\n",
+        table.description,
+        quote_identifier(table_name),
+        definitions.join(",\n    ")
+    )
+}
+
+fn quote_identifier(name: &str) -> String {
+    format!("\"{}\"", name.replace('"', "\"\""))
+}
+
+fn quote_literal(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "''"))
+}
+
+/// Is `name` a valid Avro name (and CSVW column name), i.e. `[A-Za-z_][A-Za-z0-9_]*`?
+fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// Makes column names into unique identifiers.
+#[derive(Default)]
+struct Identifiers {
+    used: HashSet<String>,
+}
+
+impl Identifiers {
+    /// `name` with other characters than ASCII letters, digits and `_` replaced by `_`,
+    /// starting with a letter, and with a numeric suffix if it's already used.
+    fn unique(&mut self, name: &str) -> String {
+        let sanitized: String = name
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+            .collect();
+        let sanitized = sanitized.trim_start_matches('_');
+        let base = if sanitized.starts_with(|c: char| c.is_ascii_alphabetic()) {
+            sanitized.to_string()
+        } else {
+            format!("col_{sanitized}")
+        };
+        let mut identifier = base.clone();
+        let mut n = 2;
+        while !self.used.insert(identifier.clone()) {
+            identifier = format!("{base}_{n}");
+            n += 1;
+        }
+        identifier
+    }
+}
@@ -0,0 +1,387 @@
+//! Schema drift detection for `schema --compare`.
+//!
+//! The inferred JSON Schema `properties` of the current CSV are compared with the properties of
+//! a baseline JSON Schema, usually generated by `schema` from an earlier file. A change is
+//! *breaking* when data like the current CSV would fail validation against the baseline, e.g. a
+//! removed column, an integer column that now has decimals, or an enum with new values.
+//! Changes the baseline still accepts, like a new column or a narrowed type, are non-breaking.
+//! With `--null-ratios`, `schema` records the null ratio of nullable columns in an `x-qsv`
+//! annotation, so columns whose null ratio rose by more than a threshold are reported too.
+
+use std::fmt;
+
+use serde_json::{Map, Value};
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(super) enum Severity {
+    Breaking,
+    NonBreaking,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Breaking => "breaking",
+            Self::NonBreaking => "non-breaking",
+        })
+    }
+}
+
+pub(super) struct Change {
+    pub(super) severity: Severity,
+    pub(super) column:   String,
+    pub(super) change:   &'static str,
+    pub(super) baseline: String,
+    pub(super) current:  String,
+}
+
+impl Change {
+    fn new(
+        severity: Severity,
+        column: &str,
+        change: &'static str,
+        baseline: impl Into<String>,
+        current: impl Into<String>,
+    ) -> Self {
+        Self {
+            severity,
+            column: column.to_string(),
+            change,
+            baseline: baseline.into(),
+            current: current.into(),
+        }
+    }
+}
+
+/// A column's JSON Schema definition, as far as drift detection is concerned.
+struct ColumnDef<'a> {
+    /// the non-null type, `None` if the column only has nulls
+    base_type:  Option<&'a str>,
+    nullable:   bool,
+    format:     Option<&'a str>,
+    /// the enum (or const) domain in text form, without null
+    domain:     Option<Vec<String>>,
+    /// the fraction of empty values, from the `x-qsv` annotation
+    null_ratio: Option<f64>,
+    def:        &'a Value,
+}
+
+impl<'a> ColumnDef<'a> {
+    fn new(def: &'a Value) -> Self {
+        let types: Vec<&str> = match def.get("type") {
+            Some(Value::String(t)) => vec![t.as_str()],
+            Some(Value::Array(types)) => types.iter().filter_map(Value::as_str).collect(),
+            _ => Vec::new(),
+        };
+        let domain = match (def.get("enum"), def.get("const")) {
+            (Some(Value::Array(values)), _) => Some(
+                values
+                    .iter()
+                    .filter(|v| !v.is_null())
+                    .map(value_text)
+                    .collect(),
+            ),
+            (None, Some(value)) if !value.is_null() => Some(vec![value_text(value)]),
+            _ => None,
+        };
+        Self {
+            base_type: types.iter().copied().find(|t| *t != "null"),
+            nullable: types.contains(&"null"),
+            format: def.get("format").and_then(Value::as_str),
+            domain,
+            null_ratio: def
+                .get("x-qsv")
+                .and_then(|x| x.get("null_ratio"))
+                .and_then(Value::as_f64),
+            def,
+        }
+    }
+
+    fn type_label(&self) -> String {
+        match (self.base_type, self.format) {
+            (Some(t), Some(format)) => format!("{t} ({format})"),
+            (Some(t), None) => t.to_string(),
+            (None, _) => "null".to_string(),
+        }
+    }
+
+    fn number(&self, keyword: &str) -> Option<f64> {
+        self.def.get(keyword).and_then(Value::as_f64)
+    }
+}
+
+fn value_text(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// Does a baseline column of type `baseline` accept the values of a column inferred as
+/// `current`? As `validate` reads every CSV value as text first, a string column accepts
+/// anything, and a number column also accepts integers.
+fn type_accepts(baseline: &str, current: &str) -> bool {
+    baseline == current || baseline == "string" || (baseline == "number" && current == "integer")
+}
+
+/// Compare the `current` properties with those of the `baseline` schema.
+/// A nullable column whose null ratio rose by more than `null_threshold` is reported.
+pub(super) fn compare(
+    baseline: &Map<String, Value>,
+    current: &Map<String, Value>,
+    null_threshold: f64,
+) -> Vec<Change> {
+    let mut changes = Vec::new();
+
+    let removed: Vec<(usize, &String)> = baseline
+        .keys()
+        .enumerate()
+        .filter(|(_, name)| !current.contains_key(*name))
+        .collect();
+    let mut added: Vec<(usize, &String)> = current
+        .keys()
+        .enumerate()
+        .filter(|(_, name)| !baseline.contains_key(*name))
+        .collect();
+
+    // a removed column with an added column of the same type in its place was likely renamed
+    for (position, name) in removed {
+        let renamed_to = added.iter().position(|(i, new_name)| {
+            *i == position
+                && ColumnDef::new(&baseline[name]).base_type
+                    == ColumnDef::new(&current[*new_name]).base_type
+        });
+        if let Some(renamed_to) = renamed_to {
+            let (_, new_name) = added.remove(renamed_to);
+            changes.push(Change::new(
+                Severity::Breaking,
+                name,
+                "renamed",
+                name.as_str(),
+                new_name.as_str(),
+            ));
+        } else {
+            changes.push(Change::new(
+                Severity::Breaking,
+                name,
+                "removed",
+                format!("position {}", position + 1),
+                "",
+            ));
+        }
+    }
+    for (position, name) in added {
+        changes.push(Change::new(
+            Severity::NonBreaking,
+            name,
+            "added",
+            "",
+            format!("position {}", position + 1),
+        ));
+    }
+
+    // columns in both, in current order
+    let common: Vec<(usize, &String)> = current
+        .keys()
+        .enumerate()
+        .filter(|(_, name)| baseline.contains_key(*name))
+        .collect();
+    let baseline_common: Vec<&String> = baseline
+        .keys()
+        .filter(|name| current.contains_key(*name))
+        .collect();
+    for (order, (position, name)) in common.iter().enumerate() {
+        if baseline_common[order] != *name {
+            let baseline_position = baseline.keys().position(|k| k == *name).unwrap_or_default();
+            changes.push(Change::new(
+                Severity::NonBreaking,
+                name,
+                "moved",
+                format!("position {}", baseline_position + 1),
+                format!("position {}", position + 1),
+            ));
+        }
+    }
+
+    for (_, name) in common {
+        compare_column(
+            name,
+            &ColumnDef::new(&baseline[name]),
+            &ColumnDef::new(&current[name]),
+            null_threshold,
+            &mut changes,
+        );
+    }
+    changes
+}
+
+/// The columns that are nullable in both schemas, but whose null ratio drift can't be checked
+/// because the `baseline` has no `x-qsv` null ratio for them (e.g. it was generated without
+/// `--null-ratios`).
+pub(super) fn unchecked_null_ratios<'a>(
+    baseline: &Map<String, Value>,
+    current: &'a Map<String, Value>,
+) -> Vec<&'a str> {
+    current
+        .iter()
+        .filter(|(name, def)| {
+            baseline.get(*name).is_some_and(|baseline_def| {
+                let baseline = ColumnDef::new(baseline_def);
+                baseline.nullable && baseline.null_ratio.is_none() && ColumnDef::new(def).nullable
+            })
+        })
+        .map(|(name, _)| name.as_str())
+        .collect()
+}
+
+fn compare_column(
+    name: &str,
+    baseline: &ColumnDef,
+    current: &ColumnDef,
+    null_threshold: f64,
+    changes: &mut Vec<Change>,
+) {
+    use Severity::{Breaking, NonBreaking};
+
+    match (baseline.base_type, current.base_type) {
+        (Some(b), Some(c)) if b != c => {
+            let (severity, change) = if type_accepts(b, c) {
+                (NonBreaking, "type narrowed")
+            } else {
+                (Breaking, "type widened")
+            };
+            changes.push(Change::new(
+                severity,
+                name,
+                change,
+                baseline.type_label(),
+                current.type_label(),
+            ));
+        },
+        // e.g. a date column that now has values which aren't dates
+        (Some(b), Some(_)) if baseline.format.is_some() && current.format != baseline.format => {
+            changes.push(Change::new(
+                Breaking,
+                name,
+                "format changed",
+                baseline.type_label(),
+                format!("{b} ({})", current.format.unwrap_or("any")),
+            ));
+        },
+        (None, Some(_)) => changes.push(Change::new(
+            Breaking,
+            name,
+            "type widened",
+            baseline.type_label(),
+            current.type_label(),
+        )),
+        _ => {},
+    }
+
+    if current.nullable && !baseline.nullable {
+        let current_label = match current.null_ratio {
+            Some(ratio) => format!("nullable ({:.1}% null)", ratio * 100.0),
+            None => "nullable".to_string(),
+        };
+        changes.push(Change::new(
+            Breaking,
+            name,
+            "nulls introduced",
+            "not nullable",
+            current_label,
+        ));
+    } else if baseline.nullable && !current.nullable && current.base_type.is_some() {
+        changes.push(Change::new(
+            NonBreaking,
+            name,
+            "nulls gone",
+            "nullable",
+            "not nullable",
+        ));
+    } else if current.nullable
+        && baseline.nullable
+        && let (Some(b), Some(c)) = (baseline.null_ratio, current.null_ratio)
+        && c - b > null_threshold
+    {
+        // still valid against the baseline, but likely a broken feed
+        changes.push(Change::new(
+            NonBreaking,
+            name,
+            "null ratio increased",
+            format!("{:.1}% null", b * 100.0),
+            format!("{:.1}% null", c * 100.0),
+        ));
+    }
+
+    match (&baseline.domain, &current.domain) {
+        (Some(b), Some(c)) => {
+            let new_values: Vec<&str> = c
+                .iter()
+                .filter(|v| !b.contains(v))
+                .map(String::as_str)
+                .collect();
+            let gone_values: Vec<&str> = b
+                .iter()
+                .filter(|v| !c.contains(v))
+                .map(String::as_str)
+                .collect();
+            if !new_values.is_empty() {
+                changes.push(Change::new(
+                    Breaking,
+                    name,
+                    "enum values added",
+                    format!("{} values", b.len()),
+                    new_values.join("|"),
+                ));
+            }
+            if !gone_values.is_empty() {
+                changes.push(Change::new(
+                    NonBreaking,
+                    name,
+                    "enum values removed",
+                    gone_values.join("|"),
+                    format!("{} values", c.len()),
+                ));
+            }
+        },
+        // the domain grew past the enum threshold (a changed type is already reported)
+        (Some(b), None) if current.base_type == baseline.base_type => changes.push(Change::new(
+            Breaking,
+            name,
+            "enum exceeded",
+            format!("{} values", b.len()),
+            "no enum",
+        )),
+        _ => {},
+    }
+
+    // ranges are only comparable if the type didn't change
+    if baseline.base_type != current.base_type {
+        return;
+    }
+    for (min_keyword, max_keyword, change) in [
+        ("minimum", "maximum", "range widened"),
+        ("minLength", "maxLength", "length range widened"),
+    ] {
+        let (b_min, c_min) = (baseline.number(min_keyword), current.number(min_keyword));
+        let (b_max, c_max) = (baseline.number(max_keyword), current.number(max_keyword));
+        let below = matches!((b_min, c_min), (Some(b), Some(c)) if c < b);
+        let above = matches!((b_max, c_max), (Some(b), Some(c)) if c > b);
+        if below || above {
+            let range = |min: Option<f64>, max: Option<f64>| {
+                format!(
+                    "{}..{}",
+                    min.map(|v| v.to_string()).unwrap_or_default(),
+                    max.map(|v| v.to_string()).unwrap_or_default()
+                )
+            };
+            changes.push(Change::new(
+                Breaking,
+                name,
+                change,
+                range(b_min, b_max),
+                range(c_min, c_max),
+            ));
+        }
+    }
+}
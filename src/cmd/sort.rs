@@ -27,6 +27,34 @@ sort options:
     -i, --ignore-case       Compare strings disregarding case.
                             Has no effect when numeric comparison is selected
                             (i.e. when --numeric is used without --natural).
+    --collation <locale>    Compare strings with the Unicode collation of a BCP 47
+                            locale, e.g. de, sv, es-u-co-trad, or und for the root
+                            collation, so accented letters and other scripts sort
+                            as they do in that language instead of by code point.
+                            With --ignore-case, only base letters and accents are
+                            compared. Cannot be combined with --numeric, --natural
+                            or --order (use collate=<locale> sort keys instead).
+    --normalize <form>      Normalize strings to nfc or nfkc before comparing them,
+                            so e.g. the ligature "ﬁ" sorts (and with --unique,
+                            dedups) as "fi" under nfkc.
+    --order <spec>          Sort by a SQL-like list of sort keys, each with its own
+                            comparison type, direction and nulls placement, e.g.
+                              --order "state, population:numeric desc, date:date"
+                            Keys are separated by commas and have the form
+                              <column>[:<type>] [asc|desc] [nulls first|nulls last]
+                            where <column> is a column name or 1-based index, and
+                            <type> is one of lex (the default), icase, natural,
+                            natural-icase, numeric, date, collate=<locale> or
+                            collate-icase=<locale>, e.g. name:collate=sv compares
+                            names as --collation sv does. Rows are ordered by the
+                            first key, then ties by the next key and so on.
+                            Empty fields, and fields that aren't numbers or dates in
+                            numeric and date keys, are nulls. Nulls come first in
+                            ascending keys and last in descending keys unless
+                            nulls first/last is given. Dates are parsed as in
+                            `qsv datefmt` (set QSV_PREFER_DMY for day-first dates).
+                            Cannot be combined with --select, --numeric, --natural,
+                            --reverse, --ignore-case, --collation or --random.
     -u, --unique            When set, identical consecutive lines will be dropped
                             to keep only one line per sorted value. The same
                             comparison mode used to sort the input is also used
@@ -84,19 +112,29 @@ use std::{cmp::Ordering, str::FromStr};
 use rand::{SeedableRng, rngs::StdRng, seq::SliceRandom};
 use rand_hc::Hc128Rng;
 use rand_xoshiro::Xoshiro256Plus;
-use rayon::slice::ParallelSliceMut;
+use rayon::{
+    iter::{IntoParallelIterator, ParallelIterator},
+    slice::ParallelSliceMut,
+};
 use serde::Deserialize;
 use simdutf8::basic::from_utf8;
 use strum_macros::EnumString;
 
-use self::Number::{Float, Int};
+use self::{
+    Number::{Float, Int},
+    order::SortOrder,
+};
 use crate::{
     CliResult,
+    collation::Collation,
     config::{Config, Delimiter},
     select::SelectColumns,
+    statscatalog::StatsCatalog,
     util,
 };
 
+pub mod order;
+
 #[derive(Deserialize)]
 struct Args {
     arg_input:        Option<String>,
@@ -105,6 +143,9 @@ struct Args {
     flag_natural:     bool,
     flag_reverse:     bool,
     flag_ignore_case: bool,
+    flag_collation:   Option<String>,
+    flag_normalize:   Option<String>,
+    flag_order:       Option<String>,
     flag_unique:      bool,
     flag_random:      bool,
     flag_seed:        Option<u64>,
@@ -129,13 +170,15 @@ enum RngKind {
 /// sort dispatch and the `--unique` filter so they always agree on equality.
 /// Precedence: `--natural` > `--numeric` > `--ignore-case` > lex.
 /// `--ignore-case` only applies under lex and natural; numeric ignores it.
+/// `--collation`/`--normalize` replace lex, and fold case themselves.
 #[derive(Clone, Copy)]
-enum SortMode {
+enum SortMode<'a> {
     Lex,
     LexIgnoreCase,
     Natural,
     NaturalIgnoreCase,
     Numeric,
+    Collated(&'a Collation),
 }
 
 pub fn run(argv: &[&str]) -> CliResult<()> {
@@ -160,29 +203,54 @@ pub fn run(argv: &[&str]) -> CliResult<()> {
         );
     };
 
-    // we're loading the entire file into memory, we need to check avail memory.
-    // we only check if we're doing a stable sort and its not --random,
-    // because --faster sorts in-place (non-allocating) and --random shuffles.
-    if !faster
-        && !random
-        && let Some(path) = rconfig.resolved_path()?
-    {
-        util::mem_file_check(&path, false, args.flag_memcheck)?;
-    }
-
     let mut rdr = rconfig.reader()?;
     let headers = rdr.byte_headers()?.clone();
     let sel = rconfig.selection(&headers)?;
 
-    util::njobs(args.flag_jobs);
+    let order = match &args.flag_order {
+        Some(spec) => {
+            if numeric
+                || natural
+                || reverse
+                || ignore_case
+                || random
+                || args.flag_collation.is_some()
+            {
+                return fail_incorrectusage_clierror!(
+                    "--order cannot be combined with --numeric, --natural, --reverse, \
+                     --ignore-case, --collation or --random. Set the type (e.g. collate=<locale>) \
+                     and direction of each sort key instead."
+                );
+            }
+            if sel.len() != headers.len() {
+                return fail_incorrectusage_clierror!(
+                    "--order cannot be combined with --select. Name the sort columns in --order \
+                     instead."
+                );
+            }
+            Some(SortOrder::parse(spec, &headers, args.flag_no_headers)?)
+        },
+        None => None,
+    };
 
-    let mut all = rdr.byte_records().collect::<Result<Vec<_>, _>>()?;
+    let collation = Collation::new(
+        args.flag_collation.as_deref(),
+        args.flag_normalize.as_deref(),
+        ignore_case,
+    )?;
+    if collation.is_some() && (numeric || natural || order.is_some()) {
+        return fail_incorrectusage_clierror!(
+            "--collation and --normalize cannot be combined with --numeric, --natural or --order."
+        );
+    }
 
     // Pick the comparison mode once. The same mode drives the sort and the
     // --unique filter, so unique-equality always agrees with what the sort
     // grouped (previously --unique used its own if/else chain that silently
     // disagreed with the sort under e.g. --numeric --natural).
-    let mode = if natural {
+    let mode = if let Some(collation) = &collation {
+        SortMode::Collated(collation)
+    } else if natural {
         if ignore_case {
             SortMode::NaturalIgnoreCase
         } else {
@@ -196,6 +264,54 @@ pub fn run(argv: &[&str]) -> CliResult<()> {
         SortMode::Lex
     };
 
+    // stats-cache short-circuit: if the stats catalog proves the single selected column is
+    // already ascending under the same comparator, the sorted output IS the input, so stream
+    // it through without loading it into memory. --unique is only short-circuited when the
+    // catalog also proves the column has no duplicates, which byte-equal cardinality can only
+    // do for the lexicographic comparator. --reverse is not, for the same anti-stable tie-break
+    // reason as extsort. Disable with QSV_STATSCACHE_MODE=none.
+    if !random
+        && !reverse
+        && order.is_none()
+        && matches!(mode, SortMode::Lex | SortMode::Numeric)
+        && StatsCatalog::load(
+            args.arg_input.as_deref(),
+            args.flag_no_headers,
+            args.flag_delimiter,
+        )
+        .is_some_and(|catalog| {
+            catalog.proves_ascending(&sel, matches!(mode, SortMode::Numeric), false)
+                && (!args.flag_unique
+                    || (matches!(mode, SortMode::Lex) && catalog.proves_unique(&sel)))
+        })
+    {
+        let mut wtr = Config::new(args.flag_output.as_ref()).writer()?;
+        rconfig.write_headers(&mut rdr, &mut wtr)?;
+        let mut record = csv::ByteRecord::new();
+        while rdr.read_byte_record(&mut record)? {
+            wtr.write_byte_record(&record)?;
+        }
+        return Ok(wtr.flush()?);
+    }
+
+    // we're loading the entire file into memory, we need to check avail memory.
+    // we only check if we're doing a stable sort and its not --random,
+    // because --faster sorts in-place (non-allocating) and --random shuffles.
+    if !faster
+        && !random
+        && let Some(path) = rconfig.resolved_path()?
+    {
+        util::mem_file_check(&path, false, args.flag_memcheck)?;
+    }
+
+    util::njobs(args.flag_jobs);
+
+    let mut all = rdr.byte_records().collect::<Result<Vec<_>, _>>()?;
+
+    if let Some(order) = order {
+        return sort_by_order(&order, all, &args, &rconfig, &mut rdr);
+    }
+
     if random {
         match rng_kind {
             RngKind::Standard => {
@@ -257,6 +373,7 @@ pub fn run(argv: &[&str]) -> CliResult<()> {
             SortMode::Natural => do_sort!(iter_cmp_natural),
             SortMode::NaturalIgnoreCase => do_sort!(iter_cmp_natural_ignore_case),
             SortMode::Numeric => do_sort!(iter_cmp_num),
+            SortMode::Collated(collation) => do_sort!(|a, b| collation.iter_cmp(a, b)),
         }
     }
 
@@ -286,6 +403,7 @@ pub fn run(argv: &[&str]) -> CliResult<()> {
             SortMode::Natural => unique_filter!(iter_cmp_natural),
             SortMode::NaturalIgnoreCase => unique_filter!(iter_cmp_natural_ignore_case),
             SortMode::Numeric => unique_filter!(iter_cmp_num),
+            SortMode::Collated(collation) => unique_filter!(|a, b| collation.iter_cmp(a, b)),
         }
     } else {
         for r in &all {
@@ -295,6 +413,40 @@ pub fn run(argv: &[&str]) -> CliResult<()> {
     Ok(wtr.flush()?)
 }
 
+/// Sort by the keys of --order. Each row's key values are computed once, so dates and
+/// numbers aren't parsed again on every comparison.
+fn sort_by_order<R: std::io::Read>(
+    order: &SortOrder,
+    all: Vec<csv::ByteRecord>,
+    args: &Args,
+    rconfig: &Config,
+    rdr: &mut csv::Reader<R>,
+) -> CliResult<()> {
+    let mut keyed: Vec<_> = all
+        .into_par_iter()
+        .map(|record| (order.key(&record), record))
+        .collect();
+    if args.flag_faster {
+        keyed.par_sort_unstable_by(|(k1, _), (k2, _)| order.compare(k1, k2));
+    } else {
+        keyed.par_sort_by(|(k1, _), (k2, _)| order.compare(k1, k2));
+    }
+
+    let mut wtr = Config::new(args.flag_output.as_ref()).writer()?;
+    rconfig.write_headers(rdr, &mut wtr)?;
+    let mut prev: Option<&[order::KeyValue]> = None;
+    for (key, record) in &keyed {
+        // with --unique, rows whose keys compare equal are duplicates
+        if args.flag_unique && prev.is_some_and(|prev| order.compare(prev, key) == Ordering::Equal)
+        {
+            continue;
+        }
+        wtr.write_byte_record(record)?;
+        prev = Some(key);
+    }
+    Ok(wtr.flush()?)
+}
+
 /// Order `a` and `b` lexicographically using `Ord`
 #[inline]
 pub fn iter_cmp<A, L, R>(mut a: L, mut b: R) -> Ordering
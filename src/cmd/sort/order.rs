@@ -0,0 +1,337 @@
+//! SQL-like multi-key ordering for `sort --order` and `extsort --order`.
+//!
+//! An order is a comma-separated list of sort keys, each a column with an optional type,
+//! direction and nulls placement, e.g. `state, population:numeric desc, date:date asc nulls
+//! last`. Rows are compared key by key, and the first key that differs decides. Text keys
+//! can also use a locale's Unicode collation, e.g. `name:collate=sv`.
+//!
+//! Every key's field is converted to a `KeyValue` once per row, so dates and numbers are
+//! parsed once rather than on every comparison. `extsort` stores the encoded key values of
+//! each row in its temporary file (see `encode` and `decode`).
+
+use std::cmp::Ordering;
+
+use super::compare_natural_bytes;
+use crate::{CliResult, collation::Collation, select::SelectColumns, util};
+
+#[derive(Clone, Copy, PartialEq)]
+enum KeyType {
+    Lex,
+    IgnoreCase,
+    Natural,
+    NaturalIgnoreCase,
+    Numeric,
+    Date,
+    /// compared with the key's collation
+    Collated,
+}
+
+impl KeyType {
+    fn from_name(name: &str) -> Option<Self> {
+        Some(match name.to_ascii_lowercase().as_str() {
+            "lex" | "string" => Self::Lex,
+            "icase" | "ignore-case" => Self::IgnoreCase,
+            "natural" => Self::Natural,
+            "natural-icase" => Self::NaturalIgnoreCase,
+            "numeric" | "number" => Self::Numeric,
+            "date" | "datetime" => Self::Date,
+            _ => return None,
+        })
+    }
+
+    /// Parse a key type, including `collate=<locale>` and `collate-icase=<locale>`, which
+    /// come with their collation. None if `name` isn't a key type.
+    fn parse(name: &str) -> CliResult<Option<(Self, Option<Collation>)>> {
+        let Some((kind, locale)) = name.split_once('=') else {
+            return Ok(Self::from_name(name).map(|key_type| (key_type, None)));
+        };
+        let ignore_case = match kind.to_ascii_lowercase().as_str() {
+            "collate" => false,
+            "collate-icase" => true,
+            _ => return Ok(None),
+        };
+        let collation = Collation::new(Some(locale), None, ignore_case)?;
+        Ok(Some((Self::Collated, collation)))
+    }
+}
+
+struct SortKey {
+    column:      usize,
+    key_type:    KeyType,
+    collation:   Option<Collation>,
+    descending:  bool,
+    nulls_first: bool,
+}
+
+/// The comparable value of a sort key's field.
+#[derive(Clone, Debug, PartialEq)]
+pub enum KeyValue {
+    /// an empty field, or one that isn't a number or date for such keys
+    Null,
+    Text(String),
+    Number(f64),
+    /// milliseconds since the Unix epoch
+    Timestamp(i64),
+}
+
+pub struct SortOrder {
+    keys:       Vec<SortKey>,
+    prefer_dmy: bool,
+}
+
+/// Split `spec` on the commas that aren't inside double-quoted column names.
+fn split_keys(spec: &str) -> Vec<&str> {
+    let mut keys = Vec::new();
+    let mut start = 0;
+    let mut quoted = false;
+    for (i, c) in spec.char_indices() {
+        match c {
+            '"' => quoted = !quoted,
+            ',' if !quoted => {
+                keys.push(&spec[start..i]);
+                start = i + 1;
+            },
+            _ => {},
+        }
+    }
+    keys.push(&spec[start..]);
+    keys
+}
+
+impl SortOrder {
+    /// Parse the --order `spec` and resolve its columns against `headers`.
+    ///
+    /// Each key is `<column>[:<type>] [asc|desc] [nulls first|nulls last]`, where the column
+    /// is a name or 1-based index as in `qsv select`, and the type may be `collate=<locale>`.
+    /// Nulls sort as the smallest values by default, i.e. first in ascending and last in
+    /// descending keys.
+    pub fn parse(spec: &str, headers: &csv::ByteRecord, no_headers: bool) -> CliResult<Self> {
+        let mut keys = Vec::new();
+        for key_spec in split_keys(spec) {
+            let mut words: Vec<&str> = key_spec.split_whitespace().collect();
+            if words.is_empty() {
+                return fail_incorrectusage_clierror!("Empty sort key in --order \"{spec}\".");
+            }
+
+            let mut nulls_first = None;
+            if words.len() >= 3 && words[words.len() - 2].eq_ignore_ascii_case("nulls") {
+                nulls_first = match words[words.len() - 1].to_ascii_lowercase().as_str() {
+                    "first" => Some(true),
+                    "last" => Some(false),
+                    other => {
+                        return fail_incorrectusage_clierror!(
+                            "Invalid nulls placement \"{other}\" in sort key \"{}\". Use nulls \
+                             first or nulls last.",
+                            key_spec.trim()
+                        );
+                    },
+                };
+                words.truncate(words.len() - 2);
+            }
+            let mut descending = false;
+            if words.len() >= 2 {
+                match words[words.len() - 1].to_ascii_lowercase().as_str() {
+                    "asc" => words.truncate(words.len() - 1),
+                    "desc" => {
+                        descending = true;
+                        words.truncate(words.len() - 1);
+                    },
+                    _ => {},
+                }
+            }
+
+            // column names may have spaces, and colons that aren't followed by a type
+            let column_spec = words.join(" ");
+            let parsed_type = match column_spec.rsplit_once(':') {
+                Some((column, name)) => {
+                    KeyType::parse(name)?.map(|parsed| (column.to_string(), parsed))
+                },
+                None => None,
+            };
+            let (column_spec, (key_type, collation)) =
+                parsed_type.unwrap_or((column_spec, (KeyType::Lex, None)));
+            let selection = SelectColumns::parse(&column_spec)
+                .and_then(|columns| columns.selection(headers, !no_headers));
+            let column = match selection {
+                Ok(selection) if selection.len() == 1 => selection[0],
+                Ok(_) => {
+                    return fail_incorrectusage_clierror!(
+                        "Sort key \"{}\" must select exactly one column.",
+                        key_spec.trim()
+                    );
+                },
+                Err(e) => {
+                    return fail_incorrectusage_clierror!(
+                        "Invalid column in sort key \"{}\": {e}",
+                        key_spec.trim()
+                    );
+                },
+            };
+
+            keys.push(SortKey {
+                column,
+                key_type,
+                collation,
+                descending,
+                nulls_first: nulls_first.unwrap_or(!descending),
+            });
+        }
+
+        Ok(Self {
+            keys,
+            prefer_dmy: util::get_envvar_flag("QSV_PREFER_DMY"),
+        })
+    }
+
+    /// The key values of `record`, one per sort key.
+    pub fn key(&self, record: &csv::ByteRecord) -> Vec<KeyValue> {
+        self.keys
+            .iter()
+            .map(|key| {
+                let field = record.get(key.column).unwrap_or_default();
+                let field = util::bytes_to_cow_str(field);
+                // whitespace-only fields are null, but text values are compared untrimmed
+                let text = field.trim();
+                if text.is_empty() {
+                    return KeyValue::Null;
+                }
+                match key.key_type {
+                    KeyType::Lex | KeyType::Natural | KeyType::Collated => {
+                        KeyValue::Text(field.into_owned())
+                    },
+                    KeyType::IgnoreCase | KeyType::NaturalIgnoreCase => {
+                        KeyValue::Text(field.to_lowercase())
+                    },
+                    KeyType::Numeric => {
+                        if let Ok(i) = atoi_simd::parse::<i64, false, false>(text.as_bytes()) {
+                            #[allow(clippy::cast_precision_loss)]
+                            let number = i as f64;
+                            KeyValue::Number(number)
+                        } else {
+                            text.parse::<f64>()
+                                .ok()
+                                .filter(|f| !f.is_nan())
+                                .map_or(KeyValue::Null, KeyValue::Number)
+                        }
+                    },
+                    KeyType::Date => qsv_dateparser::parse_with_preference(text, self.prefer_dmy)
+                        .map_or(KeyValue::Null, |dt| {
+                            KeyValue::Timestamp(dt.timestamp_millis())
+                        }),
+                }
+            })
+            .collect()
+    }
+
+    /// Compare the key values of two rows.
+    pub fn compare(&self, a: &[KeyValue], b: &[KeyValue]) -> Ordering {
+        for ((key, a), b) in self.keys.iter().zip(a).zip(b) {
+            let ordering = match (a, b) {
+                (KeyValue::Null, KeyValue::Null) => Ordering::Equal,
+                // nulls are placed regardless of the direction
+                (KeyValue::Null, _) => {
+                    return if key.nulls_first {
+                        Ordering::Less
+                    } else {
+                        Ordering::Greater
+                    };
+                },
+                (_, KeyValue::Null) => {
+                    return if key.nulls_first {
+                        Ordering::Greater
+                    } else {
+                        Ordering::Less
+                    };
+                },
+                (KeyValue::Text(a), KeyValue::Text(b)) => match (key.key_type, &key.collation) {
+                    (KeyType::Natural | KeyType::NaturalIgnoreCase, _) => {
+                        compare_natural_bytes(a.as_bytes(), b.as_bytes(), false)
+                    },
+                    (KeyType::Collated, Some(collation)) => {
+                        collation.compare(a.as_bytes(), b.as_bytes())
+                    },
+                    _ => a.cmp(b),
+                },
+                (KeyValue::Number(a), KeyValue::Number(b)) => a.total_cmp(b),
+                (KeyValue::Timestamp(a), KeyValue::Timestamp(b)) => a.cmp(b),
+                // a key's values all have the same type
+                _ => Ordering::Equal,
+            };
+            let ordering = if key.descending {
+                ordering.reverse()
+            } else {
+                ordering
+            };
+            if ordering != Ordering::Equal {
+                return ordering;
+            }
+        }
+        Ordering::Equal
+    }
+}
+
+/// Encode key values as a line of text for `extsort`'s temporary file. The values are
+/// separated by unit separators, and line breaks, unit separators and backslashes in text
+/// values are backslash-escaped, so the line can be split again by `decode`.
+pub fn encode(values: &[KeyValue], line: &mut String) {
+    for (i, value) in values.iter().enumerate() {
+        if i > 0 {
+            line.push('\x1f');
+        }
+        match value {
+            KeyValue::Null => line.push('N'),
+            KeyValue::Text(text) => {
+                line.push('T');
+                for c in text.chars() {
+                    match c {
+                        '\\' => line.push_str("\\\\"),
+                        '\n' => line.push_str("\\n"),
+                        '\r' => line.push_str("\\r"),
+                        '\x1f' => line.push_str("\\u"),
+                        c => line.push(c),
+                    }
+                }
+            },
+            KeyValue::Number(n) => {
+                line.push('F');
+                line.push_str(&n.to_string());
+            },
+            KeyValue::Timestamp(ts) => {
+                line.push('D');
+                line.push_str(&ts.to_string());
+            },
+        }
+    }
+}
+
+/// Decode the key values `encode` wrote.
+pub fn decode(line: &str) -> Vec<KeyValue> {
+    line.split('\x1f')
+        .map(|encoded| {
+            let (tag, value) = encoded.split_at(encoded.len().min(1));
+            match tag {
+                "T" => {
+                    let mut text = String::with_capacity(value.len());
+                    let mut chars = value.chars();
+                    while let Some(c) = chars.next() {
+                        if c == '\\' {
+                            match chars.next() {
+                                Some('n') => text.push('\n'),
+                                Some('r') => text.push('\r'),
+                                Some('u') => text.push('\x1f'),
+                                Some(c) => text.push(c),
+                                None => {},
+                            }
+                        } else {
+                            text.push(c);
+                        }
+                    }
+                    KeyValue::Text(text)
+                },
+                "F" => value.parse().map_or(KeyValue::Null, KeyValue::Number),
+                "D" => value.parse().map_or(KeyValue::Null, KeyValue::Timestamp),
+                _ => KeyValue::Null,
+            }
+        })
+        .collect()
+}
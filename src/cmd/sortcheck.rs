@@ -60,6 +60,11 @@ sort options:
     -i, --ignore-case       Compare strings disregarding case. Ignored under pure
                             numeric comparison (i.e. --numeric without --natural),
                             since numeric comparison is case-insensitive by definition.
+    --collation <locale>    Check the order of the Unicode collation of a BCP 47 locale
+                            (e.g. de, sv or und), as sorted by `qsv sort --collation`.
+                            Cannot be combined with --numeric or --natural.
+    --normalize <form>      Normalize strings to nfc or nfkc before comparing them,
+                            as with `qsv sort --normalize`.
     --all                   Check all records. Do not stop/short-circuit the check
                             on the first unsorted record.
     --json                  Return results in JSON format, scanning --all records.
@@ -97,8 +102,10 @@ use crate::{
         iter_cmp, iter_cmp_ignore_case, iter_cmp_natural, iter_cmp_natural_ignore_case,
         iter_cmp_num,
     },
+    collation::Collation,
     config::{Config, Delimiter},
     select::SelectColumns,
+    statscatalog::StatsCatalog,
     util,
 };
 
@@ -109,6 +116,8 @@ struct Args {
     flag_numeric:     bool,
     flag_natural:     bool,
     flag_ignore_case: bool,
+    flag_collation:   Option<String>,
+    flag_normalize:   Option<String>,
     flag_all:         bool,
     flag_no_headers:  bool,
     flag_delimiter:   Option<Delimiter>,
@@ -128,12 +137,13 @@ struct SortCheckStruct {
 // Mirrors `SortMode` in `cmd/sort.rs` so sortcheck verifies the same ordering
 // the user would get from `sort` / `dedup`.
 #[derive(Clone, Copy)]
-enum ComparisonMode {
+enum ComparisonMode<'a> {
     Lex,
     LexIgnoreCase,
     Numeric,
     Natural,
     NaturalIgnoreCase,
+    Collated(&'a Collation),
 }
 
 pub fn run(argv: &[&str]) -> CliResult<()> {
@@ -142,7 +152,19 @@ pub fn run(argv: &[&str]) -> CliResult<()> {
     // Resolution order matches `sort` and `dedup`: --natural beats --numeric
     // beats --ignore-case. Done once before the loop so the dispatch `match`
     // monomorphizes to a single comparator per row.
-    let compare_mode = if args.flag_natural {
+    let collation = Collation::new(
+        args.flag_collation.as_deref(),
+        args.flag_normalize.as_deref(),
+        args.flag_ignore_case,
+    )?;
+    if collation.is_some() && (args.flag_numeric || args.flag_natural) {
+        return fail_incorrectusage_clierror!(
+            "--collation and --normalize cannot be combined with --numeric or --natural."
+        );
+    }
+    let compare_mode = if let Some(collation) = &collation {
+        ComparisonMode::Collated(collation)
+    } else if args.flag_natural {
         if args.flag_ignore_case {
             ComparisonMode::NaturalIgnoreCase
         } else {
@@ -173,17 +195,14 @@ pub fn run(argv: &[&str]) -> CliResult<()> {
     // QSV_STATSCACHE_MODE=none.
     if !(args.flag_json || args.flag_pretty_json)
         && matches!(compare_mode, ComparisonMode::Lex | ComparisonMode::Numeric)
-        && let Some(stats) = util::get_stats_records_readonly(
+        && StatsCatalog::load(
             args.arg_input.as_deref(),
             args.flag_no_headers,
             args.flag_delimiter,
         )
-        && util::stats_cache_proves_ascending(
-            &sel,
-            &stats,
-            matches!(compare_mode, ComparisonMode::Numeric),
-            false,
-        )
+        .is_some_and(|catalog| {
+            catalog.proves_ascending(&sel, matches!(compare_mode, ComparisonMode::Numeric), false)
+        })
     {
         // proven sorted ascending from the cache; exit 0 without scanning
         return Ok(());
@@ -243,6 +262,7 @@ pub fn run(argv: &[&str]) -> CliResult<()> {
                 ComparisonMode::Numeric => iter_cmp_num(a, b),
                 ComparisonMode::Natural => iter_cmp_natural(a, b),
                 ComparisonMode::NaturalIgnoreCase => iter_cmp_natural_ignore_case(a, b),
+                ComparisonMode::Collated(collation) => collation.iter_cmp(a, b),
             };
 
             match comparison {
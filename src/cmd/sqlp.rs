@@ -18,6 +18,10 @@ Examples:
 
   $ qsv sqlp data.csv 'select col1, col2 as friendlyname from data' --format parquet --output data.parquet
 
+  # write Hive-partitioned parquet files, e.g. sales/year=2024/month=05/part-0.parquet
+  $ qsv sqlp sales.csv 'select *, year(date) as year, month(date) as month from sales' \
+      --format parquet --partition-by year,month --output sales
+
   # enclose column names with spaces in double quotes
   $ qsv sqlp data.csv 'select "col 1", "col 2" from data'
 
@@ -147,6 +151,11 @@ Examples:
   $ cat data.csv | qsv sqlp - 'select * from stdin'
   $ cat data.csv | qsv sqlp - data2.csv 'select * from stdin join data2 on stdin.col1 = data2.col1'
 
+  # query a Hive-partitioned parquet directory (events/date=2024-05-01/part-0.parquet, ...)
+  # as one table named events, with a date column from the paths
+  $ qsv sqlp events "select count(*) from events where date >= '2024-05-01'"
+  $ qsv sqlp 'logs/*/*.csv' 'select * from logs'
+
   # automatic snappy decompression/compression
   $ qsv sqlp data.csv.sz 'select * from data where col1 > 10' --output result.csv.sz
 
@@ -157,7 +166,8 @@ For more examples, see https://github.com/dathere/qsv/blob/master/tests/test_sql
 See also https://github.com/dathere/qsv/wiki/SQL-and-Polars#sqlp
 
 Usage:
-    qsv sqlp [options] <input>... <sql>
+    qsv sqlp [--param <kv>...] [options] <input>... <sql>
+    qsv sqlp [--param <kv>...] [options] <sql>
     qsv sqlp --help
 
 sqlp arguments:
@@ -174,6 +184,23 @@ sqlp arguments:
                            'SKIP_INPUT' to skip input preprocessing.
                            If pschema.json file/s exists for the input file/s, they will automatically be
                            used to optimize the query even if --cache-schema is not set.
+                           Otherwise, if a current stats cache exists for an input file and
+                           --infer-len is not set, its column types are used as the schema
+                           and inference is skipped (see `qsv stats --help`). Numeric columns
+                           are then always Int64 or Float64, so query results don't depend on
+                           whether the cache exists. --cache-schema narrows them instead.
+
+                           An input that is a glob (e.g. 'data/**/*.parquet' or 'events/date=*/'),
+                           or a directory with Hive-style name=value partition subdirectories
+                           (e.g. as written by --partition-by), is read as ONE table named after
+                           its directory. Its CSV or Parquet files are unified by column name,
+                           and each partition key becomes a column whose values come from the
+                           paths (integer and YYYY-MM-DD date values are typed as such). For
+                           Parquet datasets, filters on partition columns skip the files of
+                           non-matching partitions. Quote globs so the shell doesn't expand them.
+
+                           Input files can be omitted when the query only uses catalog
+                           tables and views (see --catalog).
 
     sql                    The SQL query/ies to run. Each input file will be available as a table
                            named after the file name (without the extension), or as "_t_N"
@@ -182,11 +209,43 @@ sqlp arguments:
                            with each SQL query separated by a semicolon. It will execute the queries
                            in order, and the result of the LAST query will be returned as the result.
                            SQL scripts support single-line comments starting with '--'.
+                           Queries can have named parameters (see --param).
 
 sqlp options:
     --format <arg>            The output format to use. Valid values are:
                                 csv, json, jsonl, parquet, arrow, avro
                               [default: csv]
+    --param <kv>              Bind a named parameter, written as :name or $name in the SQL,
+                              as a `name=value` pair. Can be repeated. Values are inserted as
+                              SQL literals - strings are quoted and escaped - so they can't
+                              change the query itself. The value's type is inferred as an
+                              integer, float, boolean or otherwise a string. Set it explicitly
+                              with a `name:type=value` pair, where type is one of str, int,
+                              float, bool, date (YYYY-MM-DD) or datetime, e.g.
+                                --param zip:str=02134 --param start:date=2024-01-01
+                              A parameter not set with --param is taken from the
+                              QSV_SQLP_PARAM_<NAME> env var (e.g. QSV_SQLP_PARAM_REGION for
+                              :region), and otherwise from --params-file.
+                              Using an unbound parameter is an error.
+    --params-file <file>      A JSON object of parameter names (with an optional ":type"
+                              suffix) to values. JSON numbers, booleans, null and strings keep
+                              their type. A list of values is bound as a comma-separated list
+                              of literals, for use in `IN (:name)`.
+    --catalog <file>          A project catalog (TOML, or JSON with a .json extension) of
+                              named tables and reusable SQL views, which are available in
+                              the query alongside the input files. Input files shadow
+                              catalog tables with the same name. For example:
+                                [tables.customers]
+                                path = "data/customers.csv"  # a file, glob or partitioned directory
+                                schema = "data/customers.pschema.json"  # optional
+                                delimiter = ";"  # optional
+                                [[views]]
+                                name = "customers_clean"
+                                sql = "SELECT * FROM customers WHERE email IS NOT NULL"
+                              Relative paths are resolved against the catalog's directory.
+                              If not set, the QSV_CATALOG env var is used, or otherwise
+                              a qsv-catalog.toml or qsv-catalog.json file in the current
+                              directory. Set QSV_CATALOG to "none" to not use a catalog.
 
                               POLARS CSV INPUT PARSING OPTIONS:
     --try-parsedates          Automatically try to parse dates/datetimes and time.
@@ -273,6 +332,18 @@ sqlp options:
                               Higher compression levels are slower.
                               The zstd default is 3, and the gzip default is 6.
     --statistics              Compute column statistics when writing parquet files.
+    --row-group-size <n>      The maximum number of rows in a parquet row group. Smaller row
+                              groups let readers skip more data with row group statistics,
+                              larger ones compress better. [default: 589824]
+    --partition-by <cols>     Write the result as a Hive-partitioned parquet directory instead
+                              of a single file. <cols> is a comma-separated list of result
+                              columns. Each distinct combination of their values is written to
+                              <output>/<col1>=<value1>/<col2>=<value2>/part-0.parquet, without
+                              the partition columns themselves, which Hive-aware readers
+                              restore from the path. Null and empty values are written to the
+                              __HIVE_DEFAULT_PARTITION__ directory. Requires --output, which is
+                              the directory to write to. Existing files in the directory are
+                              kept, but a partition's part-0.parquet file is overwritten.
 
 Common options:
     -h, --help             Display this message
@@ -303,14 +374,24 @@ use polars::{
 use regex::Regex;
 use serde::Deserialize;
 
+pub mod catalog;
+pub mod dataset;
+mod params;
+mod partition;
+
 use crate::{
     CliResult,
-    cmd::joinp::tsvssv_delim,
+    cmd::{
+        joinp::tsvssv_delim,
+        sqlp::{catalog::Catalog, dataset::Dataset, params::Params},
+    },
     config::{Config, DEFAULT_WTR_BUFFER_CAPACITY, Delimiter},
+    statscatalog::{NumericWidth, StatsCatalog},
     util,
     util::process_input,
 };
 
+const DEFAULT_INFER_LEN: usize = 10000;
 static DEFAULT_GZIP_COMPRESSION_LEVEL: u8 = 6;
 static DEFAULT_ZSTD_COMPRESSION_LEVEL: i32 = 3;
 
@@ -319,6 +400,9 @@ struct Args {
     arg_input:                  Vec<PathBuf>,
     arg_sql:                    String,
     flag_format:                String,
+    flag_catalog:               Option<String>,
+    flag_param:                 Vec<String>,
+    flag_params_file:           Option<String>,
     flag_try_parsedates:        bool,
     flag_infer_len:             usize,
     flag_cache_schema:          bool,
@@ -337,6 +421,8 @@ struct Args {
     flag_compression:           String,
     flag_compress_level:        Option<i32>,
     flag_statistics:            bool,
+    flag_row_group_size:        usize,
+    flag_partition_by:          Option<String>,
     flag_output:                Option<String>,
     flag_delimiter:             Option<Delimiter>,
     flag_quiet:                 bool,
@@ -376,6 +462,18 @@ impl OutputMode {
                 return Ok(());
             }
 
+            if let Some(partition_by) = args.flag_partition_by.as_deref() {
+                let dir = args.flag_output.as_deref().unwrap_or_default();
+                let partitions = partition::write_partitioned(
+                    &df,
+                    Path::new(dir),
+                    &partition::parse_columns(partition_by)?,
+                    |w| parquet_writer(w, args),
+                )?;
+                log::info!("Wrote {partitions} partition(s) to {dir}");
+                return Ok(());
+            }
+
             let float_precision = std::env::var("QSV_POLARS_FLOAT_PRECISION")
                 .ok()
                 .and_then(|s| s.parse().ok())
@@ -411,56 +509,7 @@ impl OutputMode {
                 OutputMode::Jsonl => JsonWriter::new(&mut w)
                     .with_json_format(JsonFormat::JsonLines)
                     .finish(&mut df),
-                OutputMode::Parquet => {
-                    let compression: PqtCompression = args
-                        .flag_compression
-                        .parse()
-                        .unwrap_or(PqtCompression::Uncompressed);
-
-                    let parquet_compression = match compression {
-                        PqtCompression::Uncompressed => ParquetCompression::Uncompressed,
-                        PqtCompression::Snappy => ParquetCompression::Snappy,
-                        PqtCompression::Lz4Raw => ParquetCompression::Lz4Raw,
-                        PqtCompression::Gzip => {
-                            let gzip_level = args
-                                .flag_compress_level
-                                .unwrap_or_else(|| DEFAULT_GZIP_COMPRESSION_LEVEL.into())
-                                as u8;
-                            ParquetCompression::Gzip(Some(GzipLevel::try_new(gzip_level)?))
-                        },
-                        PqtCompression::Zstd => {
-                            let zstd_level = args
-                                .flag_compress_level
-                                .unwrap_or(DEFAULT_ZSTD_COMPRESSION_LEVEL);
-                            ParquetCompression::Zstd(Some(ZstdLevel::try_new(zstd_level)?))
-                        },
-                    };
-
-                    let statistics_options = if args.flag_statistics {
-                        StatisticsOptions {
-                            min_value: true,
-                            max_value: true,
-                            distinct_count: true,
-                            null_count: true,
-                            binary_statistics_truncate_length: Some(64),
-                        }
-                    } else {
-                        StatisticsOptions {
-                            min_value: false,
-                            max_value: false,
-                            distinct_count: false,
-                            null_count: false,
-                            binary_statistics_truncate_length: None,
-                        }
-                    };
-
-                    ParquetWriter::new(&mut w)
-                        .with_row_group_size(Some(768 * 768))
-                        .with_statistics(statistics_options)
-                        .with_compression(parquet_compression)
-                        .finish(&mut df)
-                        .map(|_| ())
-                },
+                OutputMode::Parquet => parquet_writer(&mut w, args)?.finish(&mut df).map(|_| ()),
                 OutputMode::Arrow => {
                     let compression: ArrowCompression = args
                         .flag_compression
@@ -513,6 +562,56 @@ impl OutputMode {
     }
 }
 
+/// A `ParquetWriter` with the --compression, --compress-level, --statistics and
+/// --row-group-size options.
+fn parquet_writer<W: Write>(w: W, args: &Args) -> CliResult<ParquetWriter<W>> {
+    let compression: PqtCompression = args
+        .flag_compression
+        .parse()
+        .unwrap_or(PqtCompression::Uncompressed);
+
+    let parquet_compression = match compression {
+        PqtCompression::Uncompressed => ParquetCompression::Uncompressed,
+        PqtCompression::Snappy => ParquetCompression::Snappy,
+        PqtCompression::Lz4Raw => ParquetCompression::Lz4Raw,
+        PqtCompression::Gzip => {
+            let gzip_level =
+                args.flag_compress_level
+                    .unwrap_or_else(|| DEFAULT_GZIP_COMPRESSION_LEVEL.into()) as u8;
+            ParquetCompression::Gzip(Some(GzipLevel::try_new(gzip_level)?))
+        },
+        PqtCompression::Zstd => {
+            let zstd_level = args
+                .flag_compress_level
+                .unwrap_or(DEFAULT_ZSTD_COMPRESSION_LEVEL);
+            ParquetCompression::Zstd(Some(ZstdLevel::try_new(zstd_level)?))
+        },
+    };
+
+    let statistics_options = if args.flag_statistics {
+        StatisticsOptions {
+            min_value: true,
+            max_value: true,
+            distinct_count: true,
+            null_count: true,
+            binary_statistics_truncate_length: Some(64),
+        }
+    } else {
+        StatisticsOptions {
+            min_value: false,
+            max_value: false,
+            distinct_count: false,
+            null_count: false,
+            binary_statistics_truncate_length: None,
+        }
+    };
+
+    Ok(ParquetWriter::new(w)
+        .with_row_group_size(Some(args.flag_row_group_size))
+        .with_statistics(statistics_options)
+        .with_compression(parquet_compression))
+}
+
 impl FromStr for OutputMode {
     type Err = String;
 
@@ -600,12 +699,30 @@ pub fn run(argv: &[&str]) -> CliResult<()> {
 
     let tmpdir = tempfile::tempdir()?;
 
+    let catalog = Catalog::discover(args.flag_catalog.as_deref())?;
+    let params = Params::load(&args.flag_param, args.flag_params_file.as_deref())?;
+
+    // Hive-partitioned directories and globs are each registered as one table
+    let (datasets, inputs): (Vec<PathBuf>, Vec<PathBuf>) = args
+        .arg_input
+        .into_iter()
+        .partition(|input| Dataset::is_dataset(input));
+    let datasets = datasets
+        .iter()
+        .map(|input| Dataset::open(input))
+        .collect::<CliResult<Vec<_>>>()?;
+
     let mut skip_input = false;
-    args.arg_input = if args.arg_input == [PathBuf::from("SKIP_INPUT")] {
+    args.arg_input = if inputs == [PathBuf::from("SKIP_INPUT")] || inputs.is_empty() {
+        if inputs.is_empty() && datasets.is_empty() && catalog.is_none() {
+            return fail_incorrectusage_clierror!(
+                "No input files given, and no catalog was found. See --catalog."
+            );
+        }
         skip_input = true;
         Vec::new()
     } else {
-        process_input(args.arg_input, &tmpdir, "")?
+        process_input(inputs, &tmpdir, "")?
     };
 
     let rnull_values = if args.flag_rnull_values == "<empty string>" {
@@ -633,6 +750,21 @@ pub fn run(argv: &[&str]) -> CliResult<()> {
     };
     let no_output: OutputMode = OutputMode::None;
 
+    if args.flag_row_group_size == 0 {
+        return fail_incorrectusage_clierror!("--row-group-size must be greater than zero.");
+    }
+    if let Some(partition_by) = args.flag_partition_by.as_deref() {
+        if output_mode != OutputMode::Parquet {
+            return fail_incorrectusage_clierror!("--partition-by requires --format parquet.");
+        }
+        if args.flag_output.is_none() {
+            return fail_incorrectusage_clierror!(
+                "--partition-by requires --output, the directory to write the partitions to."
+            );
+        }
+        partition::parse_columns(partition_by)?;
+    }
+
     let delim = if let Some(delimiter) = args.flag_delimiter {
         delimiter.as_byte()
     } else if let Ok(delim) = env::var("QSV_DEFAULT_DELIMITER") {
@@ -670,6 +802,10 @@ pub fn run(argv: &[&str]) -> CliResult<()> {
         .extension()
         .is_some_and(|ext| ext.eq_ignore_ascii_case("sql"));
 
+    // an explicit --infer-len asks for inference, so only the default may be replaced by
+    // a schema from the stats catalog
+    let use_stats_catalog = args.flag_infer_len == DEFAULT_INFER_LEN;
+
     // if infer_len is 0, its not a SQL script, and there is only one input CSV, we can infer the
     // schema of the CSV more intelligently by counting the number of rows in the file instead of
     // scanning the entire file with a 0 infer_len which triggers a full table scan.
@@ -703,6 +839,43 @@ pub fn run(argv: &[&str]) -> CliResult<()> {
     }
 
     let mut ctx = SQLContext::new();
+
+    // reads the CSV files of catalog tables and datasets
+    let scan_csv =
+        |path: &str, separator: u8, schema: Option<Arc<Schema>>| -> PolarsResult<LazyFrame> {
+            let reader = LazyCsvReader::new(PlRefPath::new(path))
+                .with_has_header(true)
+                .with_missing_is_null(true)
+                .with_comment_prefix(comment_char.clone())
+                .with_null_values(Some(NullValues::AllColumns(rnull_values.clone())))
+                .with_separator(separator)
+                .with_try_parse_dates(args.flag_try_parsedates)
+                .with_ignore_errors(args.flag_ignore_errors)
+                .with_truncate_ragged_lines(args.flag_truncate_ragged_lines)
+                .with_decimal_comma(args.flag_decimal_comma)
+                .with_low_memory(args.flag_low_memory);
+            let reader = if let Some(schema) = schema {
+                reader.with_schema(Some(schema))
+            } else {
+                reader.with_infer_schema_length(Some(args.flag_infer_len))
+            };
+            Ok(reader.finish()?.with_optimizations(optflags))
+        };
+
+    // register the catalog's tables & views first, so datasets and input files shadow
+    // catalog tables
+    if let Some(ref catalog) = catalog {
+        catalog.register(&mut ctx, delim, &scan_csv)?;
+    }
+
+    for dataset in &datasets {
+        if debuglog_flag {
+            log::debug!("Registering dataset: {}", dataset.name());
+        }
+        let lf = dataset.lazy_frame(delim, None, &scan_csv)?;
+        ctx.register(dataset.name(), lf.with_optimizations(optflags));
+    }
+
     let mut table_aliases = HashMap::with_capacity(args.arg_input.len());
     let mut lossy_table_name = Cow::default();
     let mut table_name;
@@ -808,6 +981,31 @@ pub fn run(argv: &[&str]) -> CliResult<()> {
                     create_schema = true;
                 }
                 work_lf.finish()?
+            } else if let Some(catalog) = use_stats_catalog
+                .then(|| {
+                    StatsCatalog::load(Some(&*table.to_string_lossy()), false, args.flag_delimiter)
+                })
+                .flatten()
+            {
+                // a current stats cache already knows every column's type - skip inference
+                if debuglog_flag {
+                    log::debug!("Using stats catalog schema for: {}", table.display());
+                }
+                LazyCsvReader::new(table_plpath)
+                    .with_has_header(true)
+                    .with_missing_is_null(true)
+                    .with_comment_prefix(comment_char.clone())
+                    .with_null_values(Some(NullValues::AllColumns(rnull_values.clone())))
+                    .with_separator(tsvssv_delim(table, delim))
+                    .with_schema(Some(Arc::new(
+                        catalog.polars_schema(NumericWidth::Widest, args.flag_try_parsedates),
+                    )))
+                    .with_try_parse_dates(args.flag_try_parsedates)
+                    .with_ignore_errors(args.flag_ignore_errors)
+                    .with_truncate_ragged_lines(args.flag_truncate_ragged_lines)
+                    .with_decimal_comma(args.flag_decimal_comma)
+                    .with_low_memory(args.flag_low_memory)
+                    .finish()?
             } else {
                 // Read input file robustly
                 // First try, as --cache-schema is not enabled, try using the --infer-len length
@@ -951,8 +1149,9 @@ pub fn run(argv: &[&str]) -> CliResult<()> {
     for (idx, query) in queries.iter().enumerate() {
         let is_last_query = idx == last_query;
 
+        let query = params.bind(query)?;
         let current_query = alias_regex
-            .replace_all(query, |caps: &regex::Captures| {
+            .replace_all(&query, |caps: &regex::Captures| {
                 if let Some(name) = alias_to_name.get(&caps[0]) {
                     // we quote the table name to avoid issues with reserved keywords and
                     // other characters that are not allowed in identifiers
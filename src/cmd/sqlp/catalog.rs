@@ -0,0 +1,210 @@
+//! Project catalogs of named tables and views, shared by `sqlp` and `joinp`.
+//!
+//! A catalog maps logical table names to files (or datasets) with an optional Polars schema, and
+//! defines reusable SQL views over them, so a team can query curated tables like
+//! `customers_clean` without repeating input files, schemas and cleanup SQL:
+//!
+//! ```toml
+//! [tables.customers]
+//! path = "data/customers.csv"
+//! schema = "data/customers.pschema.json"
+//!
+//! [tables.orders]
+//! path = "data/orders/*.csv"
+//!
+//! [[views]]
+//! name = "customers_clean"
+//! sql = "SELECT * FROM customers WHERE email IS NOT NULL"
+//! ```
+//!
+//! The same structure can be written as JSON. Relative paths are resolved against the
+//! catalog's directory. Views are registered in order, so a view can use earlier views.
+
+use std::{
+    collections::BTreeMap,
+    env, fs,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use polars::{
+    prelude::{LazyFrame, PolarsResult, Schema},
+    sql::SQLContext,
+};
+use serde::Deserialize;
+
+use super::dataset::Dataset;
+use crate::{CliResult, cmd::joinp::tsvssv_delim, config::Delimiter};
+
+/// The catalog files looked for in the current directory, in order.
+const CATALOG_FILENAMES: [&str; 2] = ["qsv-catalog.toml", "qsv-catalog.json"];
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct CatalogTable {
+    /// a CSV file, or a glob or Hive-partitioned directory of CSV or Parquet files
+    path:      String,
+    /// a Polars schema file, as written by `sqlp --cache-schema` or `schema --polars`
+    #[serde(default)]
+    schema:    Option<String>,
+    /// defaults to the command's delimiter, or tab/semicolon for TSV/SSV files
+    #[serde(default)]
+    delimiter: Option<Delimiter>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct CatalogView {
+    name: String,
+    sql:  String,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Catalog {
+    #[serde(default)]
+    tables: BTreeMap<String, CatalogTable>,
+    #[serde(default)]
+    views:  Vec<CatalogView>,
+    /// the directory relative paths are resolved against
+    #[serde(skip)]
+    dir:    PathBuf,
+}
+
+impl Catalog {
+    /// Find the catalog to use: `explicit` (from `--catalog`), the `QSV_CATALOG` env var or a
+    /// `qsv-catalog.toml`/`qsv-catalog.json` file in the current directory, in that order.
+    /// `QSV_CATALOG=none` disables catalog discovery.
+    pub fn discover(explicit: Option<&str>) -> CliResult<Option<Self>> {
+        if let Some(path) = explicit {
+            return Self::load(Path::new(path)).map(Some);
+        }
+        if let Ok(path) = env::var("QSV_CATALOG") {
+            if path.eq_ignore_ascii_case("none") {
+                return Ok(None);
+            }
+            return Self::load(Path::new(&path)).map(Some);
+        }
+        for filename in CATALOG_FILENAMES {
+            let path = Path::new(filename);
+            if path.is_file() {
+                log::debug!("Using catalog: {filename}");
+                return Self::load(path).map(Some);
+            }
+        }
+        Ok(None)
+    }
+
+    /// Load a TOML catalog, or a JSON catalog if the file has a `.json` extension.
+    pub fn load(path: &Path) -> CliResult<Self> {
+        let contents = match fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(e) => return fail_clierror!("Cannot read catalog {}: {e}", path.display()),
+        };
+        let is_json = path
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("json"));
+        let parsed = if is_json {
+            serde_json::from_str::<Self>(&contents).map_err(|e| e.to_string())
+        } else {
+            toml::from_str::<Self>(&contents).map_err(|e| e.to_string())
+        };
+        let mut catalog = match parsed {
+            Ok(catalog) => catalog,
+            Err(e) => {
+                return fail_incorrectusage_clierror!("Invalid catalog {}: {e}", path.display());
+            },
+        };
+
+        for view in &catalog.views {
+            if catalog.tables.contains_key(&view.name) {
+                return fail_incorrectusage_clierror!(
+                    "Catalog {} defines \"{}\" as both a table and a view.",
+                    path.display(),
+                    view.name
+                );
+            }
+        }
+        catalog.dir = path.parent().map(Path::to_path_buf).unwrap_or_default();
+        Ok(catalog)
+    }
+
+    /// Is `name` a table or view of this catalog?
+    pub fn contains(&self, name: &str) -> bool {
+        self.tables.contains_key(name) || self.views.iter().any(|v| v.name == name)
+    }
+
+    fn resolve(&self, path: &str) -> String {
+        let path = Path::new(path);
+        if path.is_absolute() || self.dir.as_os_str().is_empty() {
+            path.to_string_lossy().into_owned()
+        } else {
+            self.dir.join(path).to_string_lossy().into_owned()
+        }
+    }
+
+    /// Register the catalog's tables, then its views, in `ctx`.
+    ///
+    /// `scan` builds a table's `LazyFrame` from its path, separator and schema (`None` to infer
+    /// it), so each command reads catalog tables with its own CSV parsing options.
+    /// `default_delim` is the command's delimiter.
+    pub fn register(
+        &self,
+        ctx: &mut SQLContext,
+        default_delim: u8,
+        scan: impl Fn(&str, u8, Option<Arc<Schema>>) -> PolarsResult<LazyFrame>,
+    ) -> CliResult<()> {
+        for (name, table) in &self.tables {
+            let path = self.resolve(&table.path);
+            let separator = table
+                .delimiter
+                .map_or_else(|| tsvssv_delim(&path, default_delim), Delimiter::as_byte);
+            let schema = match &table.schema {
+                Some(schema_path) => {
+                    let schema_path = self.resolve(schema_path);
+                    let schema_json = match fs::read_to_string(&schema_path) {
+                        Ok(json) => json,
+                        Err(e) => {
+                            return fail_clierror!(
+                                "Cannot read schema {schema_path} of catalog table \"{name}\": {e}"
+                            );
+                        },
+                    };
+                    Some(Arc::new(serde_json::from_str::<Schema>(&schema_json)?))
+                },
+                None => None,
+            };
+            log::debug!("Registering catalog table: {name} -> {path}");
+            let lf = if Dataset::is_dataset(Path::new(&path)) {
+                Dataset::open(Path::new(&path))?.lazy_frame(separator, schema, &scan)?
+            } else {
+                scan(&path, separator, schema)?
+            };
+            ctx.register(name, lf);
+        }
+
+        for view in &self.views {
+            log::debug!("Registering catalog view: {}", view.name);
+            let lf = match ctx.execute(&view.sql) {
+                Ok(lf) => lf,
+                Err(e) => {
+                    return fail_clierror!("Invalid SQL in catalog view \"{}\": {e}", view.name);
+                },
+            };
+            ctx.register(&view.name, lf);
+        }
+        Ok(())
+    }
+
+    /// The `LazyFrame` of the catalog table or view `name`.
+    pub fn lazy_frame(
+        &self,
+        name: &str,
+        default_delim: u8,
+        scan: impl Fn(&str, u8, Option<Arc<Schema>>) -> PolarsResult<LazyFrame>,
+    ) -> CliResult<LazyFrame> {
+        let mut ctx = SQLContext::new();
+        self.register(&mut ctx, default_delim, scan)?;
+        Ok(ctx.execute(&format!(r#"SELECT * FROM "{name}""#))?)
+    }
+}
@@ -0,0 +1,333 @@
+//! Partitioned datasets, shared by `sqlp` and `joinp`: a directory or glob of files read as
+//! one table.
+//!
+//! A dataset is a glob (e.g. `data/**/*.parquet` or `events/date=*/`), or a directory with
+//! Hive-style `name=value` subdirectories, like the ones `sqlp --partition-by` writes:
+//!
+//! ```text
+//! events/date=2024-05-01/part-0.parquet
+//! events/date=2024-05-02/part-0.parquet
+//! ```
+//!
+//! Its files are read as a single table named after the directory (`events`), with a column
+//! per partition key whose values come from the paths. Files are unified by column name, so a
+//! column missing from some files is null in their rows.
+//!
+//! Parquet datasets are scanned by Polars' Hive-aware Parquet reader, so filters on partition
+//! columns skip the files of non-matching partitions. CSV datasets are the union of their
+//! files, each read with the command's CSV options.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use polars::prelude::*;
+
+use super::partition::DEFAULT_PARTITION;
+use crate::{CliResult, cmd::joinp::tsvssv_delim};
+
+const GLOB_CHARS: [char; 3] = ['*', '?', '['];
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Format {
+    Csv,
+    Parquet,
+}
+
+impl Format {
+    fn of(path: &Path) -> Option<Self> {
+        let ext = path.extension()?.to_str()?.to_ascii_lowercase();
+        match ext.as_str() {
+            "csv" | "tsv" | "tab" | "ssv" => Some(Self::Csv),
+            "parquet" | "pqt" => Some(Self::Parquet),
+            _ => None,
+        }
+    }
+}
+
+pub struct Dataset {
+    name:    String,
+    /// the directory the partition directories are relative to
+    root:    PathBuf,
+    /// the glob of the dataset's files, for the Parquet reader
+    pattern: String,
+    files:   Vec<PathBuf>,
+    format:  Format,
+}
+
+fn is_glob(input: &str) -> bool {
+    input.contains(GLOB_CHARS)
+}
+
+/// The key and value of a Hive-style `name=value` path component.
+fn parse_partition(component: &str) -> Option<(&str, &str)> {
+    component.split_once('=').filter(|(key, _)| !key.is_empty())
+}
+
+/// Hidden files and directories, and Spark/Hadoop markers like `_SUCCESS`, aren't data.
+fn is_data_path(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .is_some_and(|name| {
+            !name.starts_with('.') && (!name.starts_with('_') || parse_partition(name).is_some())
+        })
+}
+
+fn collect_files(dir: &Path, files: &mut Vec<PathBuf>) -> CliResult<()> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if !is_data_path(&path) {
+            continue;
+        }
+        if path.is_dir() {
+            collect_files(&path, files)?;
+        } else {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// Decode the %XX escapes of a partition value (see `partition::escape_value`).
+fn unescape_value(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%'
+            && let Some(byte) = value
+                .get(i + 1..i + 3)
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+        {
+            decoded.push(byte);
+            i += 3;
+        } else {
+            decoded.push(bytes[i]);
+            i += 1;
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// The type of a partition column: integer or date if all its values are, otherwise string.
+fn partition_dtype(values: &[Option<String>]) -> DataType {
+    let values: Vec<&String> = values.iter().flatten().collect();
+    if values.is_empty() {
+        DataType::String
+    } else if values.iter().all(|v| v.parse::<i64>().is_ok()) {
+        DataType::Int64
+    } else if values
+        .iter()
+        .all(|v| chrono::NaiveDate::parse_from_str(v, "%Y-%m-%d").is_ok())
+    {
+        DataType::Date
+    } else {
+        DataType::String
+    }
+}
+
+impl Dataset {
+    /// Is `input` a glob, or a directory with Hive-style `name=value` subdirectories?
+    pub fn is_dataset(input: &Path) -> bool {
+        if input.exists() {
+            return input.is_dir()
+                && fs::read_dir(input).is_ok_and(|mut entries| {
+                    entries.any(|entry| {
+                        entry.is_ok_and(|e| {
+                            e.path().is_dir()
+                                && e.file_name().to_str().is_some_and(|name| {
+                                    !name.starts_with('_') && parse_partition(name).is_some()
+                                })
+                        })
+                    })
+                });
+        }
+        input.to_str().is_some_and(is_glob)
+    }
+
+    /// Find the files of the dataset `input`. They must all be CSV or all be Parquet files.
+    pub fn open(input: &Path) -> CliResult<Self> {
+        let input_str = input.to_string_lossy();
+        let mut files = Vec::new();
+        let (root, pattern) = if input.exists() {
+            collect_files(input, &mut files)?;
+            (
+                input.to_path_buf(),
+                input.join("**").to_string_lossy().into_owned(),
+            )
+        } else {
+            let paths = match glob::glob(&input_str) {
+                Ok(paths) => paths,
+                Err(e) => return fail_incorrectusage_clierror!("Invalid glob {input_str}: {e}"),
+            };
+            let mut matches_dirs = false;
+            for path in paths.flatten().filter(|p| is_data_path(p)) {
+                if path.is_dir() {
+                    matches_dirs = true;
+                    collect_files(&path, &mut files)?;
+                } else {
+                    files.push(path);
+                }
+            }
+            // the partition directories start below the glob's literal prefix
+            let root: PathBuf = input
+                .components()
+                .take_while(|c| !is_glob(&c.as_os_str().to_string_lossy()))
+                .collect();
+            let pattern = if matches_dirs {
+                format!("{}/**", input_str.trim_end_matches('/'))
+            } else {
+                input_str.to_string()
+            };
+            (root, pattern)
+        };
+
+        files.retain(|file| Format::of(file).is_some());
+        files.sort_unstable();
+        let Some(format) = files.first().and_then(|file| Format::of(file)) else {
+            return fail_incorrectusage_clierror!("No CSV or Parquet files found in {input_str}.");
+        };
+        if files.iter().any(|file| Format::of(file) != Some(format)) {
+            return fail_incorrectusage_clierror!(
+                "{input_str} has both CSV and Parquet files. The files of a dataset must all have \
+                 the same format."
+            );
+        }
+        // the Parquet reader gets a glob that only matches data files
+        let pattern = if pattern.ends_with("**") {
+            let ext = files[0].extension().unwrap_or_default().to_string_lossy();
+            format!("{pattern}/*.{ext}")
+        } else {
+            pattern
+        };
+
+        let name_dir = if root.as_os_str().is_empty() {
+            Path::new(".")
+        } else {
+            &root
+        };
+        let Some(name) = fs::canonicalize(name_dir)
+            .ok()
+            .and_then(|dir| dir.file_name().map(|n| n.to_string_lossy().into_owned()))
+        else {
+            return fail_incorrectusage_clierror!(
+                "Cannot name the table of {input_str}. Use a catalog to name it."
+            );
+        };
+        log::debug!(
+            "dataset {name}: {} {} file(s) in {}",
+            files.len(),
+            if format == Format::Parquet {
+                "parquet"
+            } else {
+                "CSV"
+            },
+            root.display()
+        );
+
+        Ok(Self {
+            name,
+            root,
+            pattern,
+            files,
+            format,
+        })
+    }
+
+    /// The table name of the dataset: the name of its directory.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The `LazyFrame` of the whole dataset.
+    ///
+    /// `scan_csv` builds the `LazyFrame` of a CSV file from its path, separator and schema
+    /// (`None` to infer it), so each command reads the files with its own CSV parsing options.
+    /// `default_delim` is the command's delimiter.
+    pub fn lazy_frame(
+        &self,
+        default_delim: u8,
+        schema: Option<Arc<Schema>>,
+        scan_csv: impl Fn(&str, u8, Option<Arc<Schema>>) -> PolarsResult<LazyFrame>,
+    ) -> CliResult<LazyFrame> {
+        if self.format == Format::Parquet {
+            let args = ScanArgsParquet {
+                hive_options: HiveOptions::new_enabled(),
+                allow_missing_columns: true,
+                ..Default::default()
+            };
+            return Ok(LazyFrame::scan_parquet(
+                PlRefPath::new(&self.pattern),
+                args,
+            )?);
+        }
+
+        // the partition values of each file, by partition key in order of appearance
+        let mut keys: Vec<String> = Vec::new();
+        let mut file_partitions = Vec::with_capacity(self.files.len());
+        for file in &self.files {
+            let relative = file.strip_prefix(&self.root).unwrap_or(file);
+            let mut partitions = Vec::new();
+            for component in relative.parent().into_iter().flat_map(Path::components) {
+                let component = component.as_os_str().to_string_lossy();
+                if let Some((key, value)) = parse_partition(&component) {
+                    let key = unescape_value(key);
+                    let value = (value != DEFAULT_PARTITION && !value.is_empty())
+                        .then(|| unescape_value(value));
+                    if !keys.contains(&key) {
+                        keys.push(key.clone());
+                    }
+                    partitions.push((key, value));
+                }
+            }
+            file_partitions.push(partitions);
+        }
+        let key_dtypes: Vec<DataType> = keys
+            .iter()
+            .map(|key| {
+                let values: Vec<Option<String>> = file_partitions
+                    .iter()
+                    .map(|partitions| {
+                        partitions
+                            .iter()
+                            .find(|(k, _)| k == key)
+                            .and_then(|(_, v)| v.clone())
+                    })
+                    .collect();
+                partition_dtype(&values)
+            })
+            .collect();
+
+        let mut frames = Vec::with_capacity(self.files.len());
+        for (file, partitions) in self.files.iter().zip(file_partitions) {
+            let separator = tsvssv_delim(file, default_delim);
+            let lf = scan_csv(&file.to_string_lossy(), separator, schema.clone())?;
+            let partition_columns: Vec<Expr> = keys
+                .iter()
+                .zip(&key_dtypes)
+                .map(|(key, dtype)| {
+                    let value = partitions
+                        .iter()
+                        .find(|(k, _)| k == key)
+                        .and_then(|(_, v)| v.as_deref());
+                    match value {
+                        Some(value) => lit(value),
+                        None => lit(NULL),
+                    }
+                    .cast(dtype.clone())
+                    .alias(key.as_str())
+                })
+                .collect();
+            frames.push(lf.with_columns(partition_columns));
+        }
+        Ok(concat_lf_diagonal(
+            frames,
+            UnionArgs {
+                to_supertypes: true,
+                ..Default::default()
+            },
+        )?)
+    }
+}
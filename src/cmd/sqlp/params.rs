@@ -0,0 +1,300 @@
+//! Named parameters for `sqlp` queries and SQL scripts.
+//!
+//! Parameters are written as `:name` or `$name` in the SQL, and bound from `--param`, a
+//! `QSV_SQLP_PARAM_<NAME>` env var or a `--params-file` JSON object, in that order of
+//! precedence. Each value is rendered as a typed SQL literal (strings quoted and escaped) and
+//! only replaces parameter tokens outside string literals, quoted identifiers and comments,
+//! so values can't inject SQL.
+//!
+//! A value's type is inferred (integer, float, boolean, otherwise string), or set explicitly
+//! with a `name:type` suffix, e.g. `--param zip:str=02134` or `--param start:date=2024-01-01`.
+
+use std::env;
+
+use chrono::{NaiveDate, NaiveDateTime};
+use foldhash::{HashMap, HashMapExt};
+use serde_json::Value;
+
+use crate::CliResult;
+
+const ENV_PREFIX: &str = "QSV_SQLP_PARAM_";
+
+pub struct Params {
+    /// from --param, name -> SQL literal
+    cli:  HashMap<String, String>,
+    /// from --params-file, name -> SQL literal
+    file: HashMap<String, String>,
+}
+
+impl Params {
+    /// Parse the `name[:type]=value` --param arguments and the --params-file JSON object.
+    pub fn load(cli_params: &[String], params_file: Option<&str>) -> CliResult<Self> {
+        let mut cli = HashMap::with_capacity(cli_params.len());
+        for param in cli_params {
+            let Some((key, value)) = param.split_once('=') else {
+                return fail_incorrectusage_clierror!(
+                    "Invalid --param \"{param}\". Expected name=value or name:type=value."
+                );
+            };
+            let (name, sql_type) = parse_key(key)?;
+            cli.insert(name.to_string(), to_literal(name, value, sql_type)?);
+        }
+
+        let mut file = HashMap::new();
+        if let Some(path) = params_file {
+            let json: Value = match std::fs::read_to_string(path) {
+                Ok(contents) => match serde_json::from_str(&contents) {
+                    Ok(json) => json,
+                    Err(e) => return fail_clierror!("Cannot parse params file {path}: {e}"),
+                },
+                Err(e) => return fail_clierror!("Cannot read params file {path}: {e}"),
+            };
+            let Value::Object(obj) = json else {
+                return fail_incorrectusage_clierror!(
+                    "Params file {path} must be a JSON object of parameter names to values."
+                );
+            };
+            file.reserve(obj.len());
+            for (key, value) in &obj {
+                let (name, sql_type) = parse_key(key)?;
+                file.insert(name.to_string(), json_to_literal(name, value, sql_type)?);
+            }
+        }
+        Ok(Self { cli, file })
+    }
+
+    /// The SQL literal bound to parameter `name`, if any.
+    fn value(&self, name: &str) -> CliResult<Option<String>> {
+        if let Some(literal) = self.cli.get(name) {
+            return Ok(Some(literal.clone()));
+        }
+        if let Ok(value) = env::var(format!("{ENV_PREFIX}{}", name.to_ascii_uppercase())) {
+            return to_literal(name, &value, None).map(Some);
+        }
+        Ok(self.file.get(name).cloned())
+    }
+
+    /// Replace the parameters in `sql` with their SQL literals.
+    /// Fails on parameters that aren't bound.
+    pub fn bind(&self, sql: &str) -> CliResult<String> {
+        let bytes = sql.as_bytes();
+        let len = bytes.len();
+        let mut bound = String::with_capacity(len);
+        let mut copied = 0;
+        let mut i = 0;
+
+        // the index just past the first occurrence of `needle` at or after `from`
+        let skip_past = |needle: &str, from: usize| {
+            sql[from.min(len)..]
+                .find(needle)
+                .map_or(len, |pos| from + pos + needle.len())
+        };
+
+        while i < len {
+            match bytes[i] {
+                // string literals and quoted identifiers, with doubled quotes as escapes
+                quote @ (b'\'' | b'"') => {
+                    i += 1;
+                    while i < len {
+                        if bytes[i] == quote {
+                            if bytes.get(i + 1) == Some(&quote) {
+                                i += 1;
+                            } else {
+                                break;
+                            }
+                        }
+                        i += 1;
+                    }
+                    i += 1;
+                },
+                b'-' if bytes.get(i + 1) == Some(&b'-') => i = skip_past("\n", i),
+                b'/' if bytes.get(i + 1) == Some(&b'*') => i = skip_past("*/", i + 2),
+                sigil @ (b':' | b'$') => {
+                    let name_len = bytes[i + 1..]
+                        .iter()
+                        .position(|b| !(b.is_ascii_alphanumeric() || *b == b'_'))
+                        .unwrap_or(len - i - 1);
+                    let name = &sql[i + 1..i + 1 + name_len];
+                    let after = i + 1 + name_len;
+
+                    if sigil == b'$' && bytes.get(after) == Some(&b'$') {
+                        // a dollar-quoted string literal: $$...$$ or $tag$...$tag$
+                        let tag = &sql[i..=after];
+                        i = skip_past(tag, after + 1);
+                        continue;
+                    }
+                    let follows_identifier = i > 0
+                        && (bytes[i - 1].is_ascii_alphanumeric()
+                            || matches!(bytes[i - 1], b'_' | b':' | b'$'));
+                    if follows_identifier
+                        || !name.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+                    {
+                        // e.g. the `::` cast operator
+                        i += 1;
+                        continue;
+                    }
+
+                    let Some(literal) = self.value(name)? else {
+                        return fail_incorrectusage_clierror!(
+                            "SQL parameter {}{name} is not bound. Set it with --param \
+                             {name}=<value>, the {ENV_PREFIX}{} env var or --params-file.",
+                            sigil as char,
+                            name.to_ascii_uppercase()
+                        );
+                    };
+                    bound.push_str(&sql[copied..i]);
+                    bound.push_str(&literal);
+                    copied = after;
+                    i = after;
+                },
+                _ => i += 1,
+            }
+        }
+        bound.push_str(&sql[copied..]);
+        Ok(bound)
+    }
+}
+
+/// Split a `name[:type]` parameter key.
+fn parse_key(key: &str) -> CliResult<(&str, Option<&str>)> {
+    let (name, sql_type) = match key.split_once(':') {
+        Some((name, sql_type)) => (name.trim(), Some(sql_type.trim())),
+        None => (key.trim(), None),
+    };
+    let valid = name.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if !valid {
+        return fail_incorrectusage_clierror!(
+            "Invalid SQL parameter name \"{name}\". Names are letters, digits and underscores, \
+             not starting with a digit."
+        );
+    }
+    Ok((name, sql_type))
+}
+
+fn quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "''"))
+}
+
+/// Render a text value as a SQL literal of `sql_type`, or of its inferred type.
+fn to_literal(name: &str, value: &str, sql_type: Option<&str>) -> CliResult<String> {
+    let invalid = |expected: &str| {
+        fail_incorrectusage_clierror!("SQL parameter {name}: \"{value}\" is not {expected}.")
+    };
+    let is_float = |v: &str| {
+        v.parse::<f64>().is_ok_and(f64::is_finite) && v.bytes().any(|b| b.is_ascii_digit())
+    };
+    let is_bool = |v: &str| v.eq_ignore_ascii_case("true") || v.eq_ignore_ascii_case("false");
+    // parenthesize negative numbers, so `a-:p` with p=-5 can't become the comment `a--5`
+    let number = |v: String| {
+        if v.starts_with('-') {
+            format!("({v})")
+        } else {
+            v
+        }
+    };
+
+    match sql_type.map(str::to_ascii_lowercase).as_deref() {
+        None => Ok(if value.parse::<i64>().is_ok() || is_float(value) {
+            number(value.to_string())
+        } else if is_bool(value) {
+            value.to_ascii_uppercase()
+        } else {
+            quote(value)
+        }),
+        Some("str" | "string" | "text") => Ok(quote(value)),
+        Some("int" | "integer") => match value.parse::<i64>() {
+            Ok(n) => Ok(number(n.to_string())),
+            Err(_) => invalid("an integer"),
+        },
+        Some("float" | "number") => {
+            if is_float(value) {
+                Ok(number(value.to_string()))
+            } else {
+                invalid("a number")
+            }
+        },
+        Some("bool" | "boolean") => {
+            if is_bool(value) {
+                Ok(value.to_ascii_uppercase())
+            } else {
+                invalid("true or false")
+            }
+        },
+        Some("date") => {
+            if NaiveDate::parse_from_str(value, "%Y-%m-%d").is_ok() {
+                Ok(format!("CAST({} AS DATE)", quote(value)))
+            } else {
+                invalid("a YYYY-MM-DD date")
+            }
+        },
+        Some("datetime" | "timestamp") => {
+            let is_datetime = ["%Y-%m-%d %H:%M:%S%.f", "%Y-%m-%dT%H:%M:%S%.f"]
+                .iter()
+                .any(|fmt| NaiveDateTime::parse_from_str(value, fmt).is_ok());
+            if is_datetime {
+                Ok(format!("CAST({} AS TIMESTAMP)", quote(value)))
+            } else {
+                invalid("a YYYY-MM-DD HH:MM:SS datetime")
+            }
+        },
+        Some(other) => fail_incorrectusage_clierror!(
+            "SQL parameter {name} has an invalid type \"{other}\". Valid types are str, int, \
+             float, bool, date and datetime."
+        ),
+    }
+}
+
+/// Render a JSON value as a SQL literal. Arrays become comma-separated lists of literals,
+/// for use in `IN (:name)`.
+fn json_to_literal(name: &str, value: &Value, sql_type: Option<&str>) -> CliResult<String> {
+    match value {
+        Value::Null => Ok("NULL".to_string()),
+        Value::Array(values) => {
+            if values.is_empty() {
+                return fail_incorrectusage_clierror!(
+                    "SQL parameter {name} is an empty list, which can't be used in SQL."
+                );
+            }
+            let literals = values
+                .iter()
+                .map(|v| match v {
+                    Value::Array(_) | Value::Object(_) => fail_incorrectusage_clierror!(
+                        "SQL parameter {name} can only be a list of scalar values."
+                    ),
+                    v => json_to_literal(name, v, sql_type),
+                })
+                .collect::<CliResult<Vec<_>>>()?;
+            Ok(literals.join(", "))
+        },
+        Value::Object(_) => fail_incorrectusage_clierror!(
+            "SQL parameter {name} must be a scalar value or a list of scalar values."
+        ),
+        // JSON strings are strings, unless a type is set
+        Value::String(s) if sql_type.is_none() => Ok(quote(s)),
+        Value::String(s) => to_literal(name, s, sql_type),
+        other => to_literal(name, &other.to_string(), sql_type),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn negative_numbers_are_parenthesized() {
+        let params = Params::load(
+            &[
+                "p=-5".to_string(),
+                "f:float=-1.5".to_string(),
+                "i:int=5".to_string(),
+            ],
+            None,
+        )
+        .unwrap();
+        assert_eq!(
+            params.bind("select a-:p, b-:f, c-:i").unwrap(),
+            "select a-(-5), b-(-1.5), c-5"
+        );
+    }
+}
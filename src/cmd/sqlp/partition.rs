@@ -0,0 +1,110 @@
+//! Hive-style partitioned Parquet output for `sqlp --partition-by`.
+//!
+//! The query result is split by the distinct values of the partition columns, and each
+//! partition is written to `<dir>/<col1>=<value1>/<col2>=<value2>/part-0.parquet`, e.g.
+//! `sales/year=2024/month=05/part-0.parquet`. As in Hive, the partition columns are encoded
+//! in the directory names rather than stored in the files, and readers that understand Hive
+//! partitioning (Polars, DuckDB, Spark, Athena, ...) restore them from the path.
+
+use std::{
+    fs::{self, File},
+    io::{BufWriter, Write},
+    path::Path,
+};
+
+use polars::prelude::*;
+
+use crate::CliResult;
+
+/// The directory value for null and empty partition values, as used by Hive and Spark.
+pub const DEFAULT_PARTITION: &str = "__HIVE_DEFAULT_PARTITION__";
+
+/// Split the comma-separated --partition-by column list.
+pub fn parse_columns(partition_by: &str) -> CliResult<Vec<String>> {
+    let columns: Vec<String> = partition_by
+        .split(',')
+        .map(|col| col.trim().to_string())
+        .filter(|col| !col.is_empty())
+        .collect();
+    if columns.is_empty() {
+        return fail_incorrectusage_clierror!("--partition-by needs at least one column.");
+    }
+    Ok(columns)
+}
+
+/// Percent-encode the characters Hive escapes in partition directory names.
+fn escape_value(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        if c.is_ascii_control()
+            || matches!(
+                c,
+                '"' | '#' | '%' | '\'' | '*' | '/' | ':' | '=' | '?' | '\\' | '{' | '[' | ']' | '^'
+            )
+        {
+            escaped.push_str(&format!("%{:02X}", c as u32));
+        } else {
+            escaped.push(c);
+        }
+    }
+    escaped
+}
+
+/// Write `df` as a Hive-partitioned Parquet directory under `dir`, partitioned by `columns`.
+///
+/// `parquet_writer` configures the writer of each partition file, so partitions are written
+/// with the same compression, statistics and row group size as a single-file output.
+/// Returns the number of partitions written.
+pub fn write_partitioned(
+    df: &DataFrame,
+    dir: &Path,
+    columns: &[String],
+    parquet_writer: impl for<'a> Fn(
+        &'a mut BufWriter<File>,
+    ) -> CliResult<ParquetWriter<&'a mut BufWriter<File>>>,
+) -> CliResult<usize> {
+    let df_columns = df.get_column_names();
+    for col in columns {
+        if !df_columns.iter().any(|name| name.as_str() == col) {
+            return fail_incorrectusage_clierror!(
+                "--partition-by column \"{col}\" is not in the query result."
+            );
+        }
+    }
+    if columns.len() >= df.width() {
+        return fail_incorrectusage_clierror!(
+            "--partition-by can't use every column of the query result, as no columns would be \
+             left to write."
+        );
+    }
+
+    let partitions = df.partition_by_stable(columns.iter().map(String::as_str), true)?;
+    let partition_count = partitions.len();
+    for mut partition in partitions {
+        let mut partition_dir = dir.to_path_buf();
+        for col in columns {
+            let value = partition.column(col)?.get(0)?;
+            let value = match value {
+                AnyValue::Null => DEFAULT_PARTITION.to_string(),
+                value => {
+                    let text = value.str_value();
+                    if text.is_empty() {
+                        DEFAULT_PARTITION.to_string()
+                    } else {
+                        escape_value(&text)
+                    }
+                },
+            };
+            partition_dir.push(format!("{}={value}", escape_value(col)));
+        }
+        fs::create_dir_all(&partition_dir)?;
+
+        partition = partition.drop_many(columns.iter().map(String::as_str));
+        let file = File::create(partition_dir.join("part-0.parquet"))?;
+        let mut w = BufWriter::with_capacity(256_000, file);
+        parquet_writer(&mut w)?.finish(&mut partition)?;
+        w.flush()?;
+        log::debug!("wrote partition {}", partition_dir.display());
+    }
+    Ok(partition_count)
+}
@@ -83,6 +83,9 @@ These cached stats are also used by other qsv commands (currently `describegpt`,
 `joinp`, `pivotp`, `schema`, `sqlp` & `tojsonl`) to work smarter & faster.
 If the cached stats are not current (i.e., the input file is newer than the cached stats),
 the cached stats will be ignored and recomputed.
+With --incremental, a cache whose input has since only grown by appended rows is not
+recomputed from scratch - just the appended rows are scanned and merged into the saved
+per-column accumulators.
 
 Examples:
 
@@ -125,6 +128,12 @@ Examples:
   # if it's larger than 5MB and delete the index and the stats cache file after the stats run:
   qsv stats -E --cache-threshold -5000005 nyc311.csv
 
+  # Profile the chunks written by `qsv split`/`qsv partition` separately, saving each one's
+  # accumulators, then combine them into the stats of the whole - without re-reading any chunk
+  qsv stats -E --save-state 0.state chunks/0.csv
+  qsv stats -E --save-state 1.state chunks/1.csv
+  qsv stats --merge 0.state 1.state
+
 For more examples, see https://github.com/dathere/qsv/tree/master/resources/test
 See also https://github.com/dathere/qsv/wiki/Aggregation-and-Statistics#stats
 
@@ -142,6 +151,7 @@ For more info, see https://github.com/dathere/qsv/blob/master/docs/STATS_DEFINIT
 
 Usage:
     qsv stats [options] [<input>]
+    qsv stats --merge [options] <state>...
     qsv stats --help
 
 stats options:
@@ -149,6 +159,14 @@ stats options:
                               See 'qsv select --help' for the format details.
                               This is provided here because piping 'qsv select'
                               into 'qsv stats' will prevent the use of indexing.
+    -g, --group-by <arg>      Compute the stats separately for each group of rows that share
+                              the same values in these columns, instead of for the whole file.
+                              Emits one row per (group, column), led by one column per group-by
+                              column holding the group's values, in the order the groups first
+                              appear. The group-by columns themselves are not profiled.
+                              Uses the index to process the groups in parallel, if available.
+                              Grouped stats are never cached, so this cannot be combined with
+                              --incremental, --save-state, --merge or --stats-jsonl.
     -E, --everything          Compute all statistics available.
     --typesonly               Infer data types only and do not compute statistics.
                               Note that if you want to infer dates and boolean types, you'll
@@ -223,6 +241,12 @@ stats options:
                                          Dunning's MergingDigest). O(K) memory per numeric column
                                          (K~200 centroids), O(1) quantile reads. Approximate
                                          (~1% rank error, more accurate at the tails).
+                                         Adds a "quantile_rank_error" column with the
+                                         bound on the normalized rank error of the
+                                         estimates, derived from the digest's compression
+                                         and value count, for each column whose quantiles
+                                         it estimated, so consumers can tell estimates
+                                         from exact values.
                                          Restrictions:
                                            * --mad is disabled with a warning under approx.
                                            * --weight is rejected; the upstream datasketches
@@ -335,6 +359,34 @@ stats options:
 
     --force                   Force recomputing stats even if valid precomputed stats
                               cache exists.
+    --incremental             Make the stats cache append-aware. Also saves the per-column
+                              accumulators in <FILESTEM>.stats.csv.acc, so that when the input
+                              later only GROWS by appended rows, the next --incremental run
+                              scans just the appended rows and merges them into the saved
+                              accumulators instead of recomputing from scratch.
+                              The already-profiled part of the file is verified unchanged with
+                              a BLAKE3 hash before it is trusted; anything else (edits, a
+                              truncated file, different args) falls back to a full recompute.
+                              Implies caching (as if --cache-threshold 1).
+                              Not available for stdin or compressed/special-format inputs.
+                              Note that the accumulators for --mode, exact --cardinality and
+                              the exact quantiles keep every unique/all values, so the .acc
+                              file can be as large as the input. With --quantile-method approx
+                              and --cardinality-method approx, their fixed-size t-digest and
+                              HyperLogLog sketches are saved instead, which bounds the .acc
+                              file (unless --mode is also set).
+    --save-state <file>       Also save this run's per-column accumulators (counts, sums,
+                              min/max, moments, the frequency/quantile buffers, and the
+                              t-digest/HyperLogLog sketches of --quantile-method/
+                              --cardinality-method approx) to <file>, so they can later be
+                              combined with other states using --merge.
+                              Always recomputes, as the cache holds results, not accumulators.
+    --merge                   Combine the states saved with --save-state into a single stats
+                              table, equal to the stats of the concatenation of their inputs,
+                              in the order the states are given. All the states must be for
+                              the same columns, saved with the same stats options by the same
+                              qsv version. The stats options of the states are used; those
+                              given with --merge are ignored, except the output options.
     -j, --jobs <arg>          The number of jobs to run in parallel.
                               This works only when the given CSV has an index.
                               Note that a file handle is opened for each job.
@@ -439,7 +491,9 @@ use itertools::Itertools;
 use phf::phf_map;
 use qsv_dateparser::parse_with_preference;
 use rayon::{
-    iter::{IndexedParallelIterator, IntoParallelRefMutIterator, ParallelIterator},
+    iter::{
+        IndexedParallelIterator, IntoParallelIterator, IntoParallelRefMutIterator, ParallelIterator,
+    },
     slice::ParallelSliceMut,
 };
 use serde::{Deserialize, Serialize};
@@ -463,7 +517,9 @@ use crate::{
 #[derive(Clone, Deserialize)]
 pub struct Args {
     pub arg_input:                 Option<String>,
+    pub arg_state:                 Vec<String>,
     pub flag_select:               SelectColumns,
+    pub flag_group_by:             Option<SelectColumns>,
     pub flag_everything:           bool,
     pub flag_typesonly:            bool,
     pub flag_infer_boolean:        bool,
@@ -485,6 +541,9 @@ pub struct Args {
     pub flag_dates_whitelist:      String,
     pub flag_prefer_dmy:           bool,
     pub flag_force:                bool,
+    pub flag_incremental:          bool,
+    pub flag_save_state:           Option<String>,
+    pub flag_merge:                bool,
     pub flag_jobs:                 Option<usize>,
     pub flag_stats_jsonl:          bool,
     pub flag_jsonl:                bool,
@@ -546,6 +605,11 @@ struct StatsArgs {
     field_count: u64,
     filesize_bytes: u64,
     hash: FileHash,
+    // BLAKE3 of the input the stats were computed over. Only written by --incremental runs,
+    // which use it to verify that a grown input still starts with the bytes already profiled.
+    // Excluded from the cache-validity comparison (zeroed before comparing).
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    input_blake3: String,
 }
 
 #[derive(Clone, Serialize, Deserialize, PartialEq, Default)]
@@ -638,6 +702,7 @@ impl StatsArgs {
             field_count: get_u64("field_count"),
             filesize_bytes: get_u64("filesize_bytes"),
             hash: get_hash(),
+            input_blake3: get_str("input_blake3"),
         })
     }
 }
@@ -877,6 +942,10 @@ pub struct StatsData {
     // entry a no-op for serialization and this a read-side fix only.
     #[serde(default)]
     pub percentiles: Option<String>,
+    // the bound on the t-digest's normalized rank error, only present for columns whose median,
+    // quartiles or percentiles were estimated under `--quantile-method approx`
+    #[serde(default)]
+    pub quantile_rank_error: Option<f64>,
     // `Some(true)` when the column is a zero-padded numeric code (zip/FIPS/ICD-9 style — see
     // the `--zero-padded-numeric` flag); `None` otherwise (the stats CSV emits an empty cell
     // for non-flagged columns, which the jsonl conversion drops entirely, and older caches
@@ -1052,6 +1121,7 @@ pub static STATSDATA_TYPES_MAP: phf::Map<&'static str, JsonTypes> = phf_map! {
     "antimode_count" => JsonTypes::Int,
     "antimode_occurrences" => JsonTypes::Int,
     "percentiles" => JsonTypes::String,
+    "quantile_rank_error" => JsonTypes::Float,
     "zero_padded_numeric" => JsonTypes::Bool,
     // moarstats fields
     "kurtosis" => JsonTypes::Float,
@@ -1067,7 +1137,6 @@ pub static STATSDATA_TYPES_MAP: phf::Map<&'static str, JsonTypes> = phf_map! {
 };
 
 static INFER_DATE_FLAGS: OnceLock<SmallVec<[bool; 50]>> = OnceLock::new();
-static RECORD_COUNT: OnceLock<u64> = OnceLock::new();
 static ANTIMODES_LEN: OnceLock<usize> = OnceLock::new();
 static STATS_SEPARATOR: OnceLock<String> = OnceLock::new();
 static STATS_STRING_MAX_LENGTH: OnceLock<Option<usize>> = OnceLock::new();
@@ -1084,13 +1153,18 @@ const MS_IN_DAY_INT: i64 = 86_400_000;
 // 5 decimal places give us sub-second precision
 const DAY_DECIMAL_PLACES: u32 = 5;
 
+// compression (delta) of the `--quantile-method approx` t-digests, i.e. roughly the number
+// of centroids each one keeps. Also determines the quantile_rank_error bound.
+const TDIGEST_COMPRESSION: u16 = 200;
+
 // maximum number of output columns, i.e. the width of `stats_headers()` under
 // --everything: 29 always-on + mad + 9 quartile + 2 cardinality + 6 mode +
 // percentiles + zero_padded_numeric. Used only as a capacity hint, so a
 // mismatch costs a reallocation rather than correctness. `stats_headers()`
 // debug_asserts that it never emits MORE than this; the other direction (a
 // constant left too large) is deliberately not asserted, because --everything
-// is not a fixed width - `--quantile-method approx` drops `mad` and yields 48.
+// is not a fixed width. `--quantile-method approx` drops `mad` but adds
+// `quantile_rank_error`, so it is 49 wide as well.
 const MAX_STAT_COLUMNS: usize = 49;
 
 // HyperLogLog precision parameter for `--cardinality-method approx`. lg_k=12
@@ -1464,6 +1538,22 @@ pub fn run(argv: &[&str]) -> CliResult<()> {
         );
     }
 
+    if args.flag_group_by.is_some()
+        && (args.flag_merge
+            || args.flag_incremental
+            || args.flag_save_state.is_some()
+            || args.flag_stats_jsonl)
+    {
+        return fail_incorrectusage_clierror!(
+            "--group-by cannot be combined with --merge, --incremental, --save-state or \
+             --stats-jsonl, as grouped stats are never cached."
+        );
+    }
+
+    if args.flag_merge {
+        return merge_states(args);
+    }
+
     // Detect whether the user explicitly passed --quantile-method /
     // --cardinality-method on the command line. docopt fills in the default
     // value ("exact") regardless, so without this scan we can't honor an
@@ -1608,6 +1698,12 @@ pub fn run(argv: &[&str]) -> CliResult<()> {
         let _ = BOOLEAN_PATTERNS.set(patterns);
     }
 
+    // a saved state needs the accumulators, which only a compute pass has - a cache hit only
+    // has the results
+    if args.flag_save_state.is_some() {
+        args.flag_force = true;
+    }
+
     // check prefer_dmy env var
     args.flag_prefer_dmy = args.flag_prefer_dmy || util::get_envvar_flag("QSV_PREFER_DMY");
 
@@ -1671,6 +1767,7 @@ pub fn run(argv: &[&str]) -> CliResult<()> {
         field_count: 0,
         filesize_bytes: 0,
         hash: FileHash::default(),
+        input_blake3: String::new(),
     };
 
     // create a temporary file to store the <FILESTEM>.stats.csv file
@@ -1831,13 +1928,28 @@ pub fn run(argv: &[&str]) -> CliResult<()> {
         .flag_dates_whitelist
         .clone_from(&resolved_whitelist);
 
+    // grouped stats have a different shape from the per-column stats the cache holds, so they
+    // are always computed, and neither read from nor written to the cache
+    if args.flag_group_by.is_some() {
+        args.write_grouped_stats(&resolved_whitelist, &rconfig, &mut wtr)?;
+        wtr.flush()?;
+        return write_stats_output(&args, &stats_csv_tempfile_fname, output_delim, snappy);
+    }
+
     let mut compute_stats = true;
     let mut create_cache = args.flag_cache_threshold == 1
         || args.flag_stats_jsonl
+        || args.flag_incremental
         || args.flag_cache_threshold.is_negative();
 
     let mut autoindex_set = false;
 
+    // set when --incremental finds that the input only grew by appended rows since the cached
+    // accumulators were saved; the compute pass then scans just the appended rows
+    let mut incremental_base: Option<IncrementalBase> = None;
+    // the serialized accumulators of this run, saved beside the cache by --incremental
+    let mut accumulators: Option<Vec<u8>> = None;
+
     let write_stats_jsonl = args.flag_stats_jsonl;
 
     if let Some(path) = rconfig.path.clone() {
@@ -1865,6 +1977,8 @@ pub fn run(argv: &[&str]) -> CliResult<()> {
 
             if !existing_stats_args_json_str.is_empty() {
                 let time_saved: u64;
+                let cached_record_count: u64;
+                let cached_input_blake3: String;
                 // deserialize the existing stats args json
                 let existing_stats_args_json: StatsArgs = {
                     #[cfg(target_endian = "big")]
@@ -1912,6 +2026,7 @@ pub fn run(argv: &[&str]) -> CliResult<()> {
                     stat_args.canonical_stats_path = String::new();
                     // raw whitelist is provenance metadata, not part of cache validity
                     stat_args.flag_dates_whitelist_raw = String::new();
+                    cached_record_count = stat_args.record_count;
                     stat_args.record_count = 0;
                     stat_args.date_generated = String::new();
                     time_saved = stat_args.compute_duration_ms;
@@ -1924,6 +2039,7 @@ pub fn run(argv: &[&str]) -> CliResult<()> {
                     // file, so checking it would mean recomputing the very stats we are
                     // trying to reuse.
                     stat_args.hash = FileHash::default();
+                    cached_input_blake3 = std::mem::take(&mut stat_args.input_blake3);
                     stat_args
                 };
 
@@ -2008,6 +2124,22 @@ pub fn run(argv: &[&str]) -> CliResult<()> {
                          saved...",
                     );
                     compute_stats = false;
+                } else if args.flag_incremental
+                    && let Some(base) = load_incremental_base(
+                        &path,
+                        &stats_file,
+                        &existing_stats_args_json,
+                        &current_stats_args,
+                        cached_record_count,
+                        &cached_input_blake3,
+                    )
+                {
+                    log::info!(
+                        "{path_file_stem} grew by appended rows since {path_file_stem}.stats.csv \
+                         was computed. Scanning only the {} appended bytes...",
+                        current_stats_args.filesize_bytes - base.offset,
+                    );
+                    incremental_base = Some(base);
                 } else {
                     log::info!(
                         "{path_file_stem}.stats.csv already exists, but is older than the input \
@@ -2038,8 +2170,14 @@ pub fn run(argv: &[&str]) -> CliResult<()> {
             }
 
             // Check if we have an index and will use parallel processing
-            // If so, skip mem_file_check since memory-aware chunking will handle it
-            let mut indexed_result = rconfig.indexed()?;
+            // If so, skip mem_file_check since memory-aware chunking will handle it.
+            // An incremental run only reads the appended rows, sequentially, so it needs
+            // neither the index nor the memory check.
+            let mut indexed_result = if incremental_base.is_some() {
+                None
+            } else {
+                rconfig.indexed()?
+            };
             let will_use_parallel = match &indexed_result {
                 Some(_) => {
                     // We have an index, check if we'll use parallel processing
@@ -2054,6 +2192,7 @@ pub fn run(argv: &[&str]) -> CliResult<()> {
             // we're loading the entire file into memory, we need to check avail mem
             // Skip this check for parallel processing since memory-aware chunking handles it
             if !will_use_parallel
+                && incremental_base.is_none()
                 && (args.flag_everything
                     || args.flag_mode
                     || args.flag_cardinality
@@ -2135,8 +2274,8 @@ pub fn run(argv: &[&str]) -> CliResult<()> {
 
             // NOTE: the count obtained here is only a CAPACITY HINT for the per-column
             // accumulators - on the unindexed path it is an ESTIMATE (or 0 when unused).
-            // The authoritative record_count - the one that seeds
-            // RECORD_COUNT and therefore every per-record denominator in to_record()
+            // The authoritative record_count - the one handed to
+            // stats_to_records() and therefore every per-record denominator in to_record()
             // (sparsity, uniqueness_ratio, the <ALL_UNIQUE> antimode sentinel) - is the
             // count RETURNED by the compute pass, i.e. the number of records actually
             // accumulated. Deriving it from a separate pre-pass was a live wrong-results
@@ -2146,8 +2285,10 @@ pub fn run(argv: &[&str]) -> CliResult<()> {
             // index existed. Taking the count from the pass itself makes the denominator
             // self-consistent by construction, for any counter disagreement, not just
             // blank lines.
-            let (headers, stats, record_count) = match indexed_result {
-                None => {
+            let verified_input_blake3 = incremental_base.as_ref().map(|b| b.input_blake3.clone());
+            let (headers, stats, record_count) = match (incremental_base.take(), indexed_result) {
+                (Some(base), _) => args.incremental_stats(&resolved_whitelist, base, &rconfig),
+                (None, None) => {
                     // Without an index, the hint used to come from a full
                     // util::count_rows() pre-pass, reading the file twice just for a
                     // preallocation size (issue #4457; the wasted scan was ~30% of a
@@ -2162,7 +2303,7 @@ pub fn run(argv: &[&str]) -> CliResult<()> {
                     };
                     args.sequential_stats(&resolved_whitelist, capacity_hint, &rconfig)
                 },
-                Some(idx) => {
+                (None, Some(idx)) => {
                     // with an index, we get the rowcount instantaneously from the index
                     let idx_count = idx.count();
                     match args.flag_jobs {
@@ -2177,11 +2318,36 @@ pub fn run(argv: &[&str]) -> CliResult<()> {
                     }
                 },
             }?;
-            // we cache the record count so we don't have to count the records again
-            let _ = RECORD_COUNT.set(record_count);
             // log::info!("scanned {record_count} records...");
 
-            let stats_sr_vec = args.stats_to_records(stats, args.flag_vis_whitespace);
+            // --incremental: save this run's accumulators beside the cache, together with the
+            // BLAKE3 of the exact bytes they were computed over, so the next run can verify
+            // the file only grew.
+            if args.flag_incremental {
+                if !incremental_supported(&path) {
+                    wwarn!(
+                        "--incremental: accumulators were not saved, as compressed and \
+                         special-format inputs cannot be read incrementally."
+                    );
+                } else {
+                    current_stats_args.input_blake3 = match verified_input_blake3 {
+                        Some(hash) => hash,
+                        None => fingerprint_input(&path, 0)?.input_blake3,
+                    };
+                    accumulators = Some(serialize_accumulators(&stats)?);
+                }
+            }
+            if let Some(ref state_path) = args.flag_save_state {
+                let state = StatsState {
+                    args: current_stats_args.clone(),
+                    headers: headers.iter().map(<[u8]>::to_vec).collect(),
+                    record_count,
+                    stats: &stats[..],
+                };
+                fs::write(state_path, serialize_accumulators(&state)?)?;
+            }
+
+            let stats_sr_vec = args.stats_to_records(stats, args.flag_vis_whitespace, record_count);
             let mut work_br;
 
             // vec we use to compute dataset-level fingerprint hash
@@ -2405,6 +2571,21 @@ pub fn run(argv: &[&str]) -> CliResult<()> {
                 };
             std::fs::write(stats_pathbuf.clone(), json_string)?;
 
+            // save the --incremental accumulators to "<FILESTEM>.stats.csv.acc", and drop
+            // any left over from an earlier run - they describe some other version of the
+            // input, and the sidecar just written carries no fingerprint to check them against
+            let acc_pathbuf = stats_pathbuf.with_extension("acc");
+            if let Some(ref acc) = accumulators {
+                std::fs::write(&acc_pathbuf, acc)?;
+            } else if acc_pathbuf.exists()
+                && let Err(e) = fs::remove_file(&acc_pathbuf)
+            {
+                log::warn!(
+                    "Could not remove stale stats accumulators {}: {e:?}",
+                    acc_pathbuf.display()
+                );
+            }
+
             // save the stats data to "<FILESTEM>.stats.csv.data.jsonl"
             if write_stats_jsonl {
                 let mut stats_jsonl_pathbuf = stats_pathbuf.clone();
@@ -2491,6 +2672,18 @@ pub fn run(argv: &[&str]) -> CliResult<()> {
         }
     }
 
+    write_stats_output(&args, &currstats_filename, output_delim, snappy)
+}
+
+/// Writes the stats in `currstats_filename` (always comma-delimited CSV) to stdout or
+/// --output, in the format the args ask for: CSV/TSV/SSV (optionally Snappy-compressed),
+/// JSON Lines or pretty JSON.
+fn write_stats_output(
+    args: &Args,
+    currstats_filename: &str,
+    output_delim: u8,
+    snappy: bool,
+) -> CliResult<()> {
     if args.flag_jsonl || args.flag_pretty_json {
         // JSON stdout output mode: emit the per-column stats as JSON Lines (--jsonl)
         // or a single pretty JSON array (--pretty-json), reusing the same serialization
@@ -2501,13 +2694,13 @@ pub fn run(argv: &[&str]) -> CliResult<()> {
             // (one row per column), not the input data.
             if args.flag_pretty_json {
                 util::csv_to_json_array_writer(
-                    &currstats_filename,
+                    currstats_filename,
                     &STATSDATA_TYPES_MAP,
                     writer,
                     b',',
                 )
             } else {
-                util::csv_to_jsonl_writer(&currstats_filename, &STATSDATA_TYPES_MAP, writer, b',')
+                util::csv_to_jsonl_writer(currstats_filename, &STATSDATA_TYPES_MAP, writer, b',')
             }
         };
         if let Some(output) = &args.flag_output {
@@ -2521,7 +2714,7 @@ pub fn run(argv: &[&str]) -> CliResult<()> {
         return Ok(());
     }
 
-    if stdout_output_flag {
+    if args.flag_output.is_none() {
         // if we're outputting to stdout, copy the stats file to stdout
         let stdout = io::stdout();
         let mut handle = stdout.lock();
@@ -2530,20 +2723,19 @@ pub fn run(argv: &[&str]) -> CliResult<()> {
             handle.write_all(currstats.as_bytes())?;
         } else {
             // output has a non-comma delimiter, convert from CSV cache
-            util::csv_to_delimited_writer(&currstats_filename, &mut handle, output_delim)?;
+            util::csv_to_delimited_writer(currstats_filename, &mut handle, output_delim)?;
         }
         handle.flush()?;
-    } else if let Some(output) = args.flag_output {
+    } else if let Some(output) = &args.flag_output {
         // if we're outputting to a file, copy the stats file to the output file
-        if currstats_filename != output {
+        if currstats_filename != output.as_str() {
             if output_delim == b',' && !snappy {
                 // same format as cache - just copy the file
                 fs::copy(currstats_filename, output)?;
             } else {
                 // output needs delimiter conversion and/or Snappy compression;
                 // use Config so .sz extension is handled transparently
-                let out_config =
-                    Config::new(Some(output).as_ref()).delimiter(Some(Delimiter(output_delim)));
+                let out_config = Config::new(Some(output)).delimiter(Some(Delimiter(output_delim)));
                 let mut out_wtr = out_config.writer()?;
 
                 let in_file = fs::File::open(currstats_filename)?;
@@ -2720,6 +2912,46 @@ impl Args {
         Ok((headers, stats, records_read as u64))
     }
 
+    /// Extends the accumulators saved by an earlier `--incremental` run with the rows appended
+    /// to the input since - the bytes from `base.offset` to EOF - and returns the merged
+    /// statistics with the total record count, as if the whole file had been scanned.
+    ///
+    /// Only the appended rows are read, sequentially, by seeking the caller's `rconfig` reader
+    /// past the already-profiled prefix (see the `rconfig` note on `sequential_stats`).
+    fn incremental_stats(
+        &self,
+        whitelist: &str,
+        base: IncrementalBase,
+        rconfig: &Config,
+    ) -> CliResult<(csv::ByteRecord, Vec<Stats>, u64)> {
+        let mut rdr = rconfig.reader_file()?;
+        let full_headers = rdr.byte_headers()?.clone();
+
+        let (weight_col_idx, sel, headers) =
+            self.process_headers_with_weight_exclusion(&full_headers, rconfig)?;
+
+        init_date_inference(self.flag_infer_dates, &headers, whitelist)?;
+
+        let mut pos = csv::Position::new();
+        pos.set_byte(base.offset);
+        rdr.seek(pos)?;
+        let (appended, records_read) = self.compute(&sel, &mut rdr, usize::MAX, 0, weight_col_idx);
+        if appended.len() != base.stats.len() {
+            return fail_clierror!(
+                "The saved stats accumulators do not match the columns of the input. Rerun with \
+                 --force."
+            );
+        }
+
+        // the saved accumulators cover the rows BEFORE the appended ones, so - like the chunks
+        // of parallel_stats - they are merged first
+        let mut stats = base.stats;
+        for (acc, new) in stats.iter_mut().zip(appended) {
+            acc.merge(new);
+        }
+        Ok((headers, stats, base.record_count + records_read as u64))
+    }
+
     /// Computes statistics for CSV data using a multi-threaded parallel approach.
     ///
     /// This function processes the CSV file using multiple threads, dividing the work into
@@ -2935,6 +3167,235 @@ impl Args {
         Ok((headers, merge_chunks_in_order(&recv, nchunks)?, idx_count))
     }
 
+    /// Computes the `--group-by` statistics and writes them to `wtr`: one row per (group,
+    /// column), led by the group's values in the group-by columns.
+    ///
+    /// The groups are accumulated by `compute_grouped` - sequentially, or with an index and
+    /// more than one job, chunk by chunk in parallel and merged in chunk order as
+    /// `parallel_stats` merges its chunks. Each group is then rendered with its OWN record
+    /// count as the per-record denominator (sparsity, uniqueness ratio, ...).
+    ///
+    /// See the `rconfig` note on `sequential_stats`.
+    fn write_grouped_stats(
+        &self,
+        whitelist: &str,
+        rconfig: &Config,
+        wtr: &mut csv::Writer<Box<dyn io::Write + 'static>>,
+    ) -> CliResult<()> {
+        let mut rdr = rconfig.reader()?;
+        let full_headers = rdr.byte_headers()?.clone();
+
+        let (weight_col_idx, sel, _) =
+            self.process_headers_with_weight_exclusion(&full_headers, rconfig)?;
+        // safety: only called when --group-by is set
+        let group_sel = self
+            .flag_group_by
+            .as_ref()
+            .unwrap()
+            .selection(&full_headers, !self.flag_no_headers)?;
+        // a group-by column is constant within its group, so it is not profiled
+        let sel_vec: Vec<usize> = sel
+            .iter()
+            .copied()
+            .filter(|idx| !group_sel.contains(idx))
+            .collect();
+        if sel_vec.is_empty() {
+            return fail_incorrectusage_clierror!(
+                "After excluding the --group-by columns, no columns remain for statistics \
+                 computation."
+            );
+        }
+        let sel = Selection::from_indices(sel_vec);
+        let headers: csv::ByteRecord = sel.select(&full_headers).collect();
+
+        let group_headers: Vec<Vec<u8>> = group_sel
+            .iter()
+            .map(|&idx| {
+                if self.flag_no_headers {
+                    idx.to_string().into_bytes()
+                } else {
+                    full_headers[idx].to_vec()
+                }
+            })
+            .collect();
+        let stats_headers = self.stats_headers();
+        if let Some(clash) = group_headers
+            .iter()
+            .find(|h| stats_headers.iter().any(|s| s.as_bytes() == h.as_slice()))
+        {
+            return fail_incorrectusage_clierror!(
+                "--group-by column \"{}\" has the same name as a stats column. Rename it first \
+                 with `qsv rename`.",
+                String::from_utf8_lossy(clash)
+            );
+        }
+
+        init_date_inference(self.flag_infer_dates, &headers, whitelist)?;
+
+        let njobs = util::njobs(self.flag_jobs);
+        let groups = match rconfig.indexed()? {
+            Some(idx) if njobs > 1 && idx.count() > 0 => {
+                let idx_count = idx.count() as usize;
+                let chunk_size = util::chunk_size(idx_count, njobs);
+                let nchunks = util::num_of_chunks(idx_count, chunk_size);
+
+                let pool = ThreadPool::new(njobs);
+                let (send, recv) = crossbeam_channel::bounded(nchunks);
+                let args = Arc::new(self.clone());
+                for i in 0..nchunks {
+                    let (send, args, sel, group_sel) = (
+                        send.clone(),
+                        Arc::clone(&args),
+                        sel.clone(),
+                        group_sel.clone(),
+                    );
+                    // CLONE the resolved Config - see the note in parallel_stats
+                    let rconf = rconfig.clone();
+                    pool.execute(move || {
+                        let mut idx = rconf
+                            .indexed()
+                            .expect("Failed to re-open index for parallel stats.")
+                            .expect("Index is no longer available for parallel stats.");
+                        idx.seek((i * chunk_size) as u64)
+                            .expect("Index seek failed.");
+                        // a missing chunk is reported by merge_chunks_in_order
+                        let _ = send.send((
+                            i,
+                            vec![args.compute_grouped(
+                                &sel,
+                                &group_sel,
+                                &mut idx,
+                                chunk_size,
+                                weight_col_idx,
+                            )],
+                        ));
+                    });
+                }
+                drop(send);
+                merge_chunks_in_order(&recv, nchunks)?
+                    .pop()
+                    .unwrap_or_default()
+            },
+            _ => {
+                // like an unindexed ungrouped run, this holds every value in memory when
+                // non-streaming stats are requested
+                if self.which_stats().needs_memory_aware_chunking()
+                    && let Some(path) = rconfig.resolved_path()?
+                {
+                    util::mem_file_check(&path, false, self.flag_memcheck)?;
+                }
+                self.compute_grouped(&sel, &group_sel, &mut rdr, usize::MAX, weight_col_idx)
+            },
+        };
+
+        let round_places = self.flag_round;
+        let infer_boolean = self.flag_infer_boolean;
+        let visualize_ws = self.flag_vis_whitespace;
+        let rendered: Vec<(Vec<Vec<u8>>, Vec<csv::StringRecord>)> = groups
+            .0
+            .into_values()
+            .collect::<Vec<_>>()
+            .into_par_iter()
+            .map(|group| {
+                let records = group
+                    .stats
+                    .into_iter()
+                    .map(|mut stat| {
+                        stat.to_record(
+                            round_places,
+                            infer_boolean,
+                            visualize_ws,
+                            group.record_count,
+                        )
+                    })
+                    .collect();
+                (group.values, records)
+            })
+            .collect();
+
+        wtr.write_record(
+            group_headers
+                .iter()
+                .map(Vec::as_slice)
+                .chain(stats_headers.iter().map(str::as_bytes)),
+        )?;
+        for (values, records) in rendered {
+            for (i, (header, record)) in headers.iter().zip(records).enumerate() {
+                let field = if self.flag_no_headers {
+                    i.to_string().into_bytes()
+                } else {
+                    header.to_vec()
+                };
+                wtr.write_record(
+                    values
+                        .iter()
+                        .map(Vec::as_slice)
+                        .chain(std::iter::once(&field[..]))
+                        .chain(record.iter().map(str::as_bytes)),
+                )?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Like `compute`, but accumulates a separate set of per-column `Stats` for each group of
+    /// records that share the same values in the `group_sel` columns.
+    fn compute_grouped<R: std::io::Read>(
+        &self,
+        sel: &Selection,
+        group_sel: &Selection,
+        rdr: &mut csv::Reader<R>,
+        limit: usize,
+        weight_col_idx: Option<usize>,
+    ) -> GroupedStats {
+        // safety: we know INFER_DATE_FLAGS is Some because we called init_date_inference
+        let infer_date_flags = INFER_DATE_FLAGS.get().unwrap();
+        let infer_boolean = self.flag_infer_boolean;
+        let prefer_dmy = self.flag_prefer_dmy;
+
+        let mut groups = GroupedStats::default();
+        let mut row = csv::ByteRecord::new();
+        // the group's values, each length-prefixed so no two different groups share a key
+        let mut key: Vec<u8> = Vec::new();
+        let mut records_read = 0_usize;
+        while records_read < limit {
+            // safety: `stats` assumes a valid CSV - see compute
+            if !unsafe { rdr.read_byte_record(&mut row).unwrap_unchecked() } {
+                break;
+            }
+            records_read += 1;
+
+            key.clear();
+            for value in group_sel.select(&row) {
+                key.extend_from_slice(&(value.len() as u64).to_le_bytes());
+                key.extend_from_slice(value);
+            }
+            let group_idx = match groups.0.get_index_of(key.as_slice()) {
+                Some(group_idx) => group_idx,
+                None => {
+                    let group = GroupAcc {
+                        values:       group_sel.select(&row).map(<[u8]>::to_vec).collect(),
+                        record_count: 0,
+                        stats:        self.new_stats(sel.len(), 0),
+                    };
+                    groups.0.insert_full(key.clone(), group).0
+                },
+            };
+            let group = &mut groups.0[group_idx];
+            group.record_count += 1;
+            Self::add_row(
+                &mut group.stats,
+                sel,
+                &row,
+                weight_col_idx,
+                infer_date_flags,
+                infer_boolean,
+                prefer_dmy,
+            );
+        }
+        groups
+    }
+
     /// Converts a vector of `Stats` objects into CSV records for output.
     ///
     /// This function processes all computed statistics in parallel, converting each `Stats`
@@ -2945,6 +3406,7 @@ impl Args {
     ///
     /// * `stats` - Vector of computed statistics for each column
     /// * `visualize_ws` - Whether to visualize whitespace characters in string outputs
+    /// * `record_count` - The number of records the statistics were computed over
     ///
     /// # Returns
     ///
@@ -2969,7 +3431,12 @@ impl Args {
     /// * Uses unsafe code for performance-critical operations
     /// * Channel communication is thread-safe
     /// * Bounds checking is avoided where safe
-    fn stats_to_records(&self, stats: Vec<Stats>, visualize_ws: bool) -> Vec<csv::StringRecord> {
+    fn stats_to_records(
+        &self,
+        stats: Vec<Stats>,
+        visualize_ws: bool,
+        record_count: u64,
+    ) -> Vec<csv::StringRecord> {
         let round_places = self.flag_round;
         let infer_boolean = self.flag_infer_boolean;
         let mut records = Vec::with_capacity(stats.len());
@@ -2982,7 +3449,7 @@ impl Args {
             pool.execute(move || {
                 // safety: this will only return an Error if the channel has been disconnected
                 // which will not happen in this case
-                send.send(stat.to_record(round_places, infer_boolean, visualize_ws))
+                send.send(stat.to_record(round_places, infer_boolean, visualize_ws, record_count))
                     .unwrap();
             });
         }
@@ -3514,6 +3981,9 @@ impl Args {
         if self.flag_percentiles || everything {
             fields.push("percentiles");
         }
+        if self.which_stats().reports_rank_error() {
+            fields.push("quantile_rank_error");
+        }
         if self.flag_zero_padded_numeric || everything {
             fields.push("zero_padded_numeric");
         }
@@ -3523,8 +3993,8 @@ impl Args {
         // bumping it silently costs a reallocation on every --everything row, so
         // pin the undercount direction here: any debug-build run of
         // `stats --everything` checks it. An over-large constant is NOT caught -
-        // it cannot be, since --everything's width varies (48 under
-        // `--quantile-method approx`, which turns `mad` off).
+        // it cannot be, since --everything's width varies with the options that
+        // add or drop columns (e.g. `--quantile-method approx` turns `mad` off).
         debug_assert!(
             fields.len() <= MAX_STAT_COLUMNS,
             "stats_headers() emitted {} columns, exceeding MAX_STAT_COLUMNS ({MAX_STAT_COLUMNS})",
@@ -3795,7 +4265,8 @@ fn calculate_memory_aware_chunk_size(
 }
 
 /// Removes a stats cache pair - the `<FILESTEM>.stats.csv` file and its
-/// `<FILESTEM>.stats.csv.json` sidecar - as a unit.
+/// `<FILESTEM>.stats.csv.json` sidecar - as a unit, along with any `--incremental`
+/// accumulators (`<FILESTEM>.stats.csv.acc`).
 ///
 /// The two MUST be removed together. Deleting the stats CSV while leaving the sidecar
 /// behind lets a later run pass the sidecar's args comparison and be served stats that
@@ -3808,6 +4279,9 @@ fn remove_stats_cache_pair(stats_file: &Path) {
     for f in [
         stats_file.to_path_buf(),
         stats_file.with_extension("csv.json"),
+        // the --incremental accumulators are only meaningful next to the sidecar that
+        // fingerprints the input they were computed over
+        stats_file.with_extension("csv.acc"),
     ] {
         if f.exists()
             && let Err(e) = fs::remove_file(&f)
@@ -3817,6 +4291,331 @@ fn remove_stats_cache_pair(stats_file: &Path) {
     }
 }
 
+/// The accumulators a previous `--incremental` run saved, for an input that has since only
+/// grown by appended rows.
+struct IncrementalBase {
+    stats:        Vec<Stats>,
+    record_count: u64,
+    // the size of the input the accumulators cover - i.e. where the appended rows start
+    offset:       u64,
+    // BLAKE3 of the whole, grown input
+    input_blake3: String,
+}
+
+/// Returns the accumulators saved by a previous `--incremental` run if, and only if, they can
+/// be extended by scanning just the rows appended since: the cache was computed with the same
+/// args, the input grew, the bytes already profiled are unchanged and end on a record boundary,
+/// and the `<FILESTEM>.stats.csv.acc` file deserializes. Anything else means a full recompute.
+///
+/// `cached` is the sidecar as prepared for the cache-validity comparison in `run()`, so the
+/// fields that comparison ignores are already zeroed on both sides.
+fn load_incremental_base(
+    path: &Path,
+    stats_file: &Path,
+    cached: &StatsArgs,
+    current: &StatsArgs,
+    cached_record_count: u64,
+    cached_input_blake3: &str,
+) -> Option<IncrementalBase> {
+    let offset = cached.filesize_bytes;
+    if cached_input_blake3.is_empty()
+        || offset == 0
+        || current.filesize_bytes <= offset
+        || !incremental_supported(path)
+    {
+        return None;
+    }
+    // every arg must match - only the size may differ
+    let mut current = current.clone();
+    current.filesize_bytes = offset;
+    if *cached != current {
+        return None;
+    }
+
+    let fingerprint = match fingerprint_input(path, offset) {
+        Ok(fingerprint) => fingerprint,
+        Err(e) => {
+            log::warn!("Could not fingerprint {}: {e:?}", path.display());
+            return None;
+        },
+    };
+    if fingerprint.prefix_blake3 != cached_input_blake3 {
+        log::info!(
+            "{} changed within the {offset} bytes already profiled, so it was not just appended \
+             to.",
+            path.display()
+        );
+        return None;
+    }
+    // the appended bytes must start a new record, not continue the old last one
+    if fingerprint.prefix_last_byte != Some(b'\n') {
+        return None;
+    }
+
+    let acc_file = stats_file.with_extension("csv.acc");
+    let stats: Vec<Stats> = match fs::read(&acc_file)
+        .map_err(|e| e.to_string())
+        .and_then(|bytes| deserialize_accumulators(&bytes))
+    {
+        Ok(stats) => stats,
+        Err(e) => {
+            log::warn!(
+                "Could not load stats accumulators {}: {e}",
+                acc_file.display()
+            );
+            return None;
+        },
+    };
+
+    Some(IncrementalBase {
+        stats,
+        record_count: cached_record_count,
+        offset,
+        input_blake3: fingerprint.input_blake3,
+    })
+}
+
+/// Only plain (uncompressed) CSV inputs can be read incrementally - the others are converted to
+/// a temp file before every read, so there is no byte offset to resume from.
+fn incremental_supported(path: &Path) -> bool {
+    crate::config::get_special_format(path) == crate::config::SpecialFormat::Unknown
+        && !crate::config::is_snappy_extension(path)
+}
+
+struct InputFingerprint {
+    // BLAKE3 of the first `prefix_len` bytes
+    prefix_blake3:    String,
+    // the last of those bytes
+    prefix_last_byte: Option<u8>,
+    // BLAKE3 of the whole file
+    input_blake3:     String,
+}
+
+/// Hashes the first `prefix_len` bytes of `path`, and the whole file, in a single pass.
+fn fingerprint_input(path: &Path, prefix_len: u64) -> io::Result<InputFingerprint> {
+    let file = fs::File::open(path)?;
+    let len = file.metadata()?.len();
+    if prefix_len > len {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "prefix is longer than the file",
+        ));
+    }
+    let mut hasher = blake3::Hasher::new();
+    if len == 0 {
+        let empty = hasher.finalize().to_hex().to_string();
+        return Ok(InputFingerprint {
+            prefix_blake3:    empty.clone(),
+            prefix_last_byte: None,
+            input_blake3:     empty,
+        });
+    }
+    // safety: safe as we only read the mapped file
+    let mmap = unsafe { memmap2::MmapOptions::new().map(&file)? };
+    let (prefix, appended) = mmap.split_at(prefix_len as usize);
+    hasher.update_rayon(prefix);
+    let prefix_blake3 = hasher.finalize().to_hex().to_string();
+    hasher.update_rayon(appended);
+    Ok(InputFingerprint {
+        prefix_blake3,
+        prefix_last_byte: prefix.last().copied(),
+        input_blake3: hasher.finalize().to_hex().to_string(),
+    })
+}
+
+/// Serializes per-column accumulators - the `--incremental` `Vec<Stats>` or a `--save-state`
+/// `StatsState` - as Snappy-compressed MessagePack, with the t-digest and HLL sketches in
+/// their `DataSketches` binary formats (see `TDigestSlot`/`HllSlot`).
+fn serialize_accumulators<T: Serialize + ?Sized>(accumulators: &T) -> CliResult<Vec<u8>> {
+    let packed = rmp_serde::to_vec(accumulators)
+        .map_err(|e| CliError::Other(format!("Cannot serialize stats accumulators: {e}")))?;
+    snap::raw::Encoder::new()
+        .compress_vec(&packed)
+        .map_err(|e| CliError::Other(format!("Cannot compress stats accumulators: {e}")))
+}
+
+fn deserialize_accumulators<T: serde::de::DeserializeOwned>(bytes: &[u8]) -> Result<T, String> {
+    let packed = snap::raw::Decoder::new()
+        .decompress_vec(bytes)
+        .map_err(|e| e.to_string())?;
+    rmp_serde::from_slice(&packed).map_err(|e| e.to_string())
+}
+
+/// The accumulator state of one input, as saved by `--save-state` and combined by `--merge`.
+/// `S` is `&[Stats]` when saving and `Vec<Stats>` when loading.
+#[derive(Serialize, Deserialize)]
+struct StatsState<S> {
+    // the cache sidecar args of the run that saved it - the stats options states must agree on
+    args:         StatsArgs,
+    headers:      Vec<Vec<u8>>,
+    record_count: u64,
+    stats:        S,
+}
+
+impl StatsState<Vec<Stats>> {
+    /// The args that must match for two states to merge - everything except what describes
+    /// the particular input or run.
+    fn merge_key(&self) -> StatsArgs {
+        StatsArgs {
+            arg_input: String::new(),
+            canonical_input_path: String::new(),
+            canonical_stats_path: String::new(),
+            record_count: 0,
+            date_generated: String::new(),
+            compute_duration_ms: 0,
+            field_count: 0,
+            filesize_bytes: 0,
+            hash: FileHash::default(),
+            input_blake3: String::new(),
+            flag_dates_whitelist_raw: String::new(),
+            flag_output_snappy: false,
+            ..self.args.clone()
+        }
+    }
+}
+
+/// `--merge`: folds the saved states into one, in the order given, and writes the stats table
+/// for it exactly as a compute run would.
+fn merge_states(mut args: Args) -> CliResult<()> {
+    // with a single state, docopt matches the first usage pattern, so it lands in <input>
+    let state_paths: Vec<String> = args
+        .arg_input
+        .take()
+        .into_iter()
+        .chain(std::mem::take(&mut args.arg_state))
+        .collect();
+    let mut merged: Option<StatsState<Vec<Stats>>> = None;
+    for state_path in &state_paths {
+        let state: StatsState<Vec<Stats>> = fs::read(state_path)
+            .map_err(|e| e.to_string())
+            .and_then(|bytes| deserialize_accumulators(&bytes))
+            .map_err(|e| CliError::Other(format!("Cannot read stats state {state_path}: {e}")))?;
+        if state.args.qsv_version != env!("CARGO_PKG_VERSION") {
+            return fail_clierror!(
+                "Stats state {state_path} was saved by qsv {}. States can only be merged by the \
+                 qsv version that saved them.",
+                state.args.qsv_version
+            );
+        }
+        match merged {
+            None => merged = Some(state),
+            Some(ref mut acc) => {
+                if acc.headers != state.headers {
+                    return fail_incorrectusage_clierror!(
+                        "Stats state {state_path} is for different columns than the states before \
+                         it."
+                    );
+                }
+                if acc.merge_key() != state.merge_key() {
+                    return fail_incorrectusage_clierror!(
+                        "Stats state {state_path} was saved with different stats options than the \
+                         states before it."
+                    );
+                }
+                acc.record_count += state.record_count;
+                for (acc_col, col) in acc.stats.iter_mut().zip(state.stats) {
+                    acc_col.merge(col);
+                }
+            },
+        }
+    }
+    let Some(merged) = merged else {
+        return fail_incorrectusage_clierror!("--merge requires at least one stats state.");
+    };
+
+    // the stats options are those the states were saved with
+    let saved = &merged.args;
+    args.flag_everything = saved.flag_everything;
+    args.flag_typesonly = saved.flag_typesonly;
+    args.flag_infer_boolean = saved.flag_infer_boolean;
+    args.flag_boolean_patterns
+        .clone_from(&saved.flag_boolean_patterns);
+    args.flag_mode = saved.flag_mode;
+    args.flag_cardinality = saved.flag_cardinality;
+    args.flag_zero_padded_numeric = saved.flag_zero_padded_numeric;
+    args.flag_median = saved.flag_median;
+    args.flag_mad = saved.flag_mad;
+    args.flag_quartiles = saved.flag_quartiles;
+    args.flag_percentiles = saved.flag_percentiles;
+    args.flag_percentile_list
+        .clone_from(&saved.flag_percentile_list);
+    args.flag_quantile_method
+        .clone_from(&saved.flag_quantile_method);
+    args.flag_cardinality_method
+        .clone_from(&saved.flag_cardinality_method);
+    args.flag_mode_cardinality_cap = saved.flag_mode_cardinality_cap;
+    args.flag_round = saved.flag_round;
+    args.flag_nulls = saved.flag_nulls;
+    args.flag_infer_dates = saved.flag_infer_dates;
+    args.flag_prefer_dmy = saved.flag_prefer_dmy;
+    args.flag_no_headers = saved.flag_no_headers;
+    args.flag_vis_whitespace = saved.flag_vis_whitespace;
+    args.flag_weight = (!saved.flag_weight.is_empty()).then(|| saved.flag_weight.clone());
+    if args.flag_infer_boolean {
+        let patterns = parse_boolean_patterns(&args.flag_boolean_patterns)?;
+        let _ = BOOLEAN_PATTERNS.set(patterns);
+    }
+
+    let stats_csv_tempfile = TempFileBuilder::new().suffix(".csv").tempfile()?;
+    // safety: we know the tempfile is a valid NamedTempFile, so we can use unwrap
+    let stats_csv_tempfile_fname = stats_csv_tempfile.path().to_str().unwrap().to_string();
+    let mut wtr = Config::new(Some(&stats_csv_tempfile_fname))
+        .delimiter(Some(Delimiter(b',')))
+        .writer()?;
+    wtr.write_record(&args.stats_headers())?;
+    let stats_sr_vec =
+        args.stats_to_records(merged.stats, args.flag_vis_whitespace, merged.record_count);
+    for (i, (header, stat)) in merged.headers.iter().zip(stats_sr_vec).enumerate() {
+        let header = if args.flag_no_headers {
+            i.to_string().into_bytes()
+        } else {
+            header.clone()
+        };
+        wtr.write_record(std::iter::once(&header[..]).chain(stat.iter().map(str::as_bytes)))?;
+    }
+    wtr.flush()?;
+
+    let (_output_extension, output_delim, snappy) = match args.flag_output {
+        Some(ref output_path) => get_delim_by_extension(Path::new(&output_path), b','),
+        _ => (String::new(), b',', false),
+    };
+    write_stats_output(&args, &stats_csv_tempfile_fname, output_delim, snappy)
+}
+
+/// The accumulators of a `--group-by` run: a set of per-column `Stats` for each group, keyed by
+/// the group's length-prefixed values and kept in the order the groups were first seen.
+#[derive(Default)]
+struct GroupedStats(indexmap::IndexMap<Vec<u8>, GroupAcc, foldhash::fast::RandomState>);
+
+struct GroupAcc {
+    // the group's values in the group-by columns
+    values:       Vec<Vec<u8>>,
+    record_count: u64,
+    stats:        Vec<Stats>,
+}
+
+impl Commute for GroupedStats {
+    #[inline]
+    fn merge(&mut self, other: GroupedStats) {
+        // groups first seen in `other` come after those of `self`, so merging chunks in file
+        // order keeps the groups in the order they first appear in the file
+        for (key, group) in other.0 {
+            match self.0.entry(key) {
+                indexmap::map::Entry::Occupied(mut entry) => {
+                    let acc = entry.get_mut();
+                    acc.record_count += group.record_count;
+                    for (acc_col, col) in acc.stats.iter_mut().zip(group.stats) {
+                        acc_col.merge(col);
+                    }
+                },
+                indexmap::map::Entry::Vacant(entry) => {
+                    entry.insert(group);
+                },
+            }
+        }
+    }
+}
+
 /// Determines the path for the stats cache file.
 ///
 /// # Arguments
@@ -4091,6 +4890,44 @@ impl WhichStats {
             needs_quantiles && (cfg!(target_endian = "big") || !self.approx_quantiles);
         exact_modes_tracker || exact_quantiles
     }
+
+    /// Whether the output carries a `quantile_rank_error` column: some quantile is requested
+    /// and the t-digest engine computes it.
+    const fn reports_rank_error(&self) -> bool {
+        self.approx_quantiles && (self.median || self.quartiles || self.percentiles)
+    }
+
+    /// The rank of the requested quantile whose t-digest estimate has the widest rank error
+    /// bound: the one closest to the median (see `tdigest_rank_error`).
+    fn least_accurate_quantile_rank(&self) -> f64 {
+        if self.median || self.quartiles {
+            return 0.5;
+        }
+        self.percentile_list
+            .split(',')
+            .filter_map(|p| p.trim().parse::<f64>().ok())
+            .map(|p| p / 100.0)
+            .min_by(|a, b| (a - 0.5).abs().total_cmp(&(b - 0.5).abs()))
+            .unwrap_or(0.5)
+    }
+}
+
+/// The rank error bound of a t-digest estimate of the quantile of rank `q`, from a digest of
+/// `n` values.
+///
+/// The `DataSketches` t-digest uses the `K_2` scale function
+/// `k(q) = delta / Z * ln(q / (1 - q))`, with `Z = 4 * ln(n / delta) + 24`, and never grows
+/// a centroid past one unit of `k`. So the centroid around rank `q` spans at most
+/// `Z * q * (1 - q) / delta` of the ranks, and an estimate interpolated within it is off by
+/// at most half of that. The bound is widest at the median, and tightens towards the tails.
+#[allow(clippy::cast_precision_loss)]
+fn tdigest_rank_error(q: f64, n: u64) -> f64 {
+    if n == 0 {
+        return 0.0;
+    }
+    let compression = f64::from(TDIGEST_COMPRESSION);
+    let z = 4.0_f64.mul_add((n as f64 / compression).ln(), 24.0).max(0.0);
+    z * q * (1.0 - q) / (2.0 * compression)
 }
 
 /// Wrapper around `datasketches::tdigest::TDigestMut` so the `Stats` struct can keep its
@@ -4101,14 +4938,34 @@ impl WhichStats {
 /// different centroid layouts even for identical input multisets), so `PartialEq` is a
 /// constant `true` — Stats's `PartialEq` is used only in tests for non-quantile fields.
 ///
-/// Serde is intentionally NOT implemented: the field is annotated `#[serde(skip)]` on
-/// `Stats::tdigest`, so the derived `Serialize`/`Deserialize` on `Stats` skip this field
-/// entirely. T-digest state is intermediate; cache invalidation on `--quantile-method`
-/// change rides on `StatsArgs` serialization instead.
+/// Serde serializes the digest in the `DataSketches` binary format (its centroids), so the
+/// `--incremental` and `--save-state` accumulators carry it and later runs merge into it.
 #[cfg(not(target_endian = "big"))]
 #[derive(Default)]
 struct TDigestSlot(Option<datasketches::tdigest::TDigestMut>);
 
+#[cfg(not(target_endian = "big"))]
+impl Serialize for TDigestSlot {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        // serializing compresses the digest's buffered values first, so it works on a copy
+        self.0
+            .as_ref()
+            .map(|td| td.clone().serialize())
+            .serialize(serializer)
+    }
+}
+
+#[cfg(not(target_endian = "big"))]
+impl<'de> Deserialize<'de> for TDigestSlot {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Option::<Vec<u8>>::deserialize(deserializer)?
+            .map(|bytes| datasketches::tdigest::TDigestMut::deserialize(&bytes, false))
+            .transpose()
+            .map(Self)
+            .map_err(serde::de::Error::custom)
+    }
+}
+
 #[cfg(not(target_endian = "big"))]
 impl Clone for TDigestSlot {
     #[inline]
@@ -4184,14 +5041,33 @@ impl PartialEq for TDigestSlot {
 /// register collisions, but their estimates converge — equality between sketches is not
 /// a useful test (Stats's `PartialEq` is used only in tests for non-sketch fields).
 ///
-/// Serde is intentionally NOT implemented: the field is annotated `#[serde(skip)]` on
-/// `Stats::hll`, so the derived `Serialize`/`Deserialize` on `Stats` skip this field
-/// entirely. HLL state is intermediate; cache invalidation on `--cardinality-method`
-/// change rides on `StatsArgs` serialization instead.
+/// Serde serializes the sketch in the `DataSketches` binary format (its registers), so the
+/// `--incremental` and `--save-state` accumulators carry it and later runs union into it.
 #[cfg(not(target_endian = "big"))]
 #[derive(Default)]
 struct HllSlot(Option<datasketches::hll::HllSketch>);
 
+#[cfg(not(target_endian = "big"))]
+impl Serialize for HllSlot {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0
+            .as_ref()
+            .map(|hll| hll.serialize())
+            .serialize(serializer)
+    }
+}
+
+#[cfg(not(target_endian = "big"))]
+impl<'de> Deserialize<'de> for HllSlot {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Option::<Vec<u8>>::deserialize(deserializer)?
+            .map(|bytes| datasketches::hll::HllSketch::deserialize(&bytes))
+            .transpose()
+            .map(Self)
+            .map_err(serde::de::Error::custom)
+    }
+}
+
 #[cfg(not(target_endian = "big"))]
 impl Clone for HllSlot {
     #[inline]
@@ -4311,8 +5187,9 @@ struct Stats {
                                                        * quantiles */
     // Approximate-quantile engine. Mutually exclusive with `unsorted_stats` for a given
     // numeric column: when `which.approx_quantiles` is true and quantiles are requested,
-    // values flow into `tdigest` instead of `unsorted_stats`.
-    #[serde(skip)]
+    // values flow into `tdigest` instead of `unsorted_stats`. The big-endian stub has
+    // nothing to serialize.
+    #[cfg_attr(target_endian = "big", serde(skip))]
     tdigest:                 TDigestSlot,
 
     // Approximate-cardinality engine. Independent of modes: when
@@ -4320,7 +5197,7 @@ struct Stats {
     // sketch (regardless of `--mode-cardinality-cap`), and `to_record` emits the HLL
     // estimate in the cardinality column. Mode/antimode tracking is unaffected — they
     // still come from `modes` / `weighted_modes` and obey the cap.
-    #[serde(skip)]
+    #[cfg_attr(target_endian = "big", serde(skip))]
     hll: HllSlot,
 
     // CACHE LINE 6+: Min/Max tracking (largest field, least cache-friendly)
@@ -4853,7 +5730,7 @@ impl Stats {
         // minimum. If the row count is underestimated (an estimate, or a
         // partial count after a CSV read error), the accumulators simply grow
         // organically - capacity is never a limit.
-        // NOTE: this was previously read from RECORD_COUNT, which is only set
+        // NOTE: this was previously read from a global record count, which was only set
         // AFTER the compute pass, so a 10,000 fallback was always used (and
         // the repeat_n clone in new_stats discarded the reservation anyway).
         let record_count = expected_rows;
@@ -4897,7 +5774,9 @@ impl Stats {
             #[cfg(not(target_endian = "big"))]
             if which.approx_quantiles {
                 // k=200 is the upstream default; ~1% rank error, more accurate at the tails.
-                tdigest = TDigestSlot(Some(datasketches::tdigest::TDigestMut::new(200)));
+                tdigest = TDigestSlot(Some(datasketches::tdigest::TDigestMut::new(
+                    TDIGEST_COMPRESSION,
+                )));
             } else {
                 unsorted_stats = Some(stats::Unsorted::with_capacity(record_count));
                 if use_weights {
@@ -5279,6 +6158,8 @@ impl Stats {
     /// * `round_places` - Number of decimal places to round numeric values to
     /// * `infer_boolean` - Whether to attempt boolean type inference for columns with cardinality 2
     /// * `visualize_ws` - Whether to visualize whitespace characters in string outputs
+    /// * `record_count` - The number of records the statistics were computed over, the denominator
+    ///   of sparsity, uniqueness ratio and average length
     ///
     /// # Returns
     ///
@@ -5323,6 +6204,7 @@ impl Stats {
         round_places: u32,
         infer_boolean: bool,
         visualize_ws: bool,
+        record_count: u64,
     ) -> csv::StringRecord {
         // empty string constant to avoid repeated allocations
         const EMPTY_STR: &str = "";
@@ -5361,8 +6243,6 @@ impl Stats {
             minmax_range_sortorder_pieces = vec![EMPTY_STRING; 5];
         }
 
-        let record_count = *RECORD_COUNT.get().unwrap_or(&1);
-
         // get the stats separator
         let stats_separator = STATS_SEPARATOR.get_or_init(|| {
             if self.which.mode || self.which.percentiles {
@@ -6245,6 +7125,28 @@ impl Stats {
             }
         }
 
+        // quantile_rank_error: only for columns whose quantiles came from the t-digest - a
+        // String/NULL column never fed it, so it has no estimate to qualify.
+        if self.which.reports_rank_error() {
+            let estimated = matches!(typ, TInteger | TFloat | TDate | TDateTime)
+                && self.tdigest.0.as_ref().is_some_and(|td| !td.is_empty());
+            if estimated {
+                // every non-null value of a numeric/date column was fed to the digest
+                let rank_error = tdigest_rank_error(
+                    self.which.least_accurate_quantile_rank(),
+                    record_count.saturating_sub(self.nullcount),
+                );
+                // round up, so the rounded bound is still a bound
+                let scale = 10_f64.powi(round_places as i32);
+                record.push_field(&util::round_num(
+                    (rank_error * scale).ceil() / scale,
+                    round_places,
+                ));
+            } else {
+                record.push_field(EMPTY_STR);
+            }
+        }
+
         // zero_padded_numeric (last optional column, matching stats_headers ordering).
         // "true" when leading/padding zeros would be lost if the column were cast to a number;
         // empty otherwise. Qualifies when every non-null value is numeric-shaped (all-digit
@@ -161,6 +161,10 @@ pub fn run(argv: &[&str]) -> CliResult<()> {
         arg_input:            Some(input_filename.clone()),
         flag_memcheck:        args.flag_memcheck,
         flag_output:          None,
+        flag_format:          None,
+        flag_compare:         None,
+        flag_null_threshold:  0.0,
+        flag_null_ratios:     false,
     };
     // build schema for each field by their inferred type, min/max value/length, and unique values
     let properties_map: Map<String, Value> =
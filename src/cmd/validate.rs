@@ -32,7 +32,8 @@ qsv supports a custom format - `currency`. This format will only accept a valid
       Negative amounts: ($100.00) or -$100.00
       Different styles: 1.000,00 (used in some countries for euros)
 
-qsv also supports two custom keywords - `dynamicEnum` and `uniqueCombinedWith`.
+qsv also supports the custom keywords `dynamicEnum`, `uniqueCombinedWith`, `foreignKeys` and
+`referencedBy`.
 
 dynamicEnum
 ===========
@@ -107,6 +108,87 @@ records will be written to the .invalid file, while valid records will be writte
 `uniqueCombinedWith` complements the standard `uniqueItems` keyword, which can only validate
 uniqueness across a single column.
 
+foreignKeys & referencedBy
+==========================
+`foreignKeys` and `referencedBy` check referential integrity between related CSV files, e.g.
+orders -> customers, or line items -> orders. Like `uniqueCombinedWith`, they are set at the root
+of the schema. Each takes an array of relations between one or more (composite key) columns of the
+CSV being validated, and the same number of columns of a reference table. The reference "uri"
+takes the same form as `dynamicEnum` (local file, URL, dathere:// or ckan://, with caching).
+If the reference "columns" are omitted, they are the same as "columns".
+
+    // every order must be for a known customer - rows that aren't are "orphans"
+    "foreignKeys": [
+      { "columns": ["customer_id"], "reference": { "uri": "customers.csv", "columns": ["id"] } }
+    ]
+
+    // every line item must belong to a known order line (composite key)
+    "foreignKeys": [
+      { "columns": ["order_id", "line_no"],
+        "reference": { "uri": "order_lines.csv", "columns": ["order_id", "line_no"] } }
+    ]
+
+    // every order must have at least one, and at most 50, line items
+    "referencedBy": [
+      { "columns": ["id"], "reference": { "uri": "line_items.csv", "columns": ["order_id"] },
+        "minCount": 1, "maxCount": 50 }
+    ]
+
+`referencedBy` checks how many rows of the reference table have each row's key; "minCount"
+defaults to 1 ("every parent must have a child") and "maxCount" is unbounded by default.
+Keys with an empty (null) part are not checked. Numbers are matched on their text form.
+Failing rows are written to the .invalid file, and the validation-errors.tsv report describes
+each orphan/childless row and the reference it failed.
+
+FRICTIONLESS & CSVW METADATA
+============================
+Instead of a JSON Schema, you can also pass a Frictionless Table Schema (e.g. tableschema.json),
+a Frictionless Data Package (e.g. the datapackage.json created by `qsv to datapackage`) or
+W3C CSV on the Web (CSVW) metadata (e.g. data.csv-metadata.json). The metadata type is detected
+from its contents, and translated into the equivalent JSON Schema:
+
+  * field types/datatypes, formats and constraints (required, unique, minimum, maximum,
+    minLength, maxLength, pattern, enum) map to their JSON Schema keywords.
+  * `primaryKey` fields are required and validated as a `uniqueCombinedWith` combination.
+  * single-field `foreignKeys` are validated as a `dynamicEnum` against the referenced
+    resource's data file (resolved relative to the metadata file), and composite ones as
+    a `foreignKeys` relation. An empty resource name references the input file itself.
+  * `missingValues` (CSVW `null`) are treated as nulls. As in the specs, the default is "".
+
+For a Data Package or a CSVW table group, the table whose path/url has the same file name
+as the input is used. Custom date/time format patterns are not checked.
+
+    // round-trip a Data Package
+    qsv to datapackage datapackage.json cities.csv places.csv
+    qsv validate places.csv datapackage.json
+
+ROW-LEVEL RULES
+===============
+Rules that span columns, like "end_date >= start_date" or "if status is closed, closed_at is
+required", can't be expressed in JSON Schema. They can be checked with --rules, a JSON file with
+an array of named Luau boolean expressions that are evaluated for every row:
+
+    [
+      { "name": "end_after_start", "expr": "end_date >= start_date" },
+      { "name": "closed_has_date", "expr": "status ~= 'closed' or closed_at ~= nil",
+        "message": "closed rows need a closed_at date" }
+    ]
+
+As with the `luau` command, columns are available as globals and in the `col` table
+(e.g. col["Start Date"]) - a column named "col" is only available as col["col"]. Values
+are typed per the JSON Schema (e.g. "integer" columns are numbers), and empty values (or
+Frictionless/CSVW missing values) are nil. Without a JSON Schema, all values are strings -
+use tonumber() to compare them as numbers.
+
+A row fails a rule if the expression returns false. The rule name is written in the "field"
+column of the validation-errors.tsv report, with the rule's "message" (or the failed expression).
+Rules that error (e.g. comparing nil) or don't return a boolean also fail, with the error.
+Rules are checked alongside the JSON Schema, and rows failing either go to the invalid file.
+
+    qsv validate --rules rules.json data.csv schema.json
+    // only check the rules
+    qsv validate --rules rules.json data.csv
+
 -------------------------------------------------------
 
 You can create a JSON Schema file from a reference CSV file using the `qsv schema` command.
@@ -147,6 +229,8 @@ JSON SCHEMA SCHEMA VALIDATION SUBMODE:
      `qsv validate schema myjsonschema.json`
      // ignore format validation
      `qsv validate schema --no-format-validation myjsonschema.json`
+     // Frictionless/CSVW metadata is checked via its JSON Schema translation
+     `qsv validate schema datapackage.json`
 
 RFC 4180 VALIDATION MODE:
 ========================
@@ -213,6 +297,8 @@ Validate arguments:
     <json-schema>              JSON Schema file to validate against. If not provided, `validate`
                                will run in RFC 4180 validation mode. The file can be a local file
                                or a URL (http and https schemes supported).
+                               Frictionless Table Schema, Data Package and CSVW metadata
+                               files are also accepted (see FRICTIONLESS & CSVW METADATA).
 
 Validate options:
     --trim                     Trim leading and trailing whitespace from fields before validating.
@@ -220,6 +306,10 @@ Validate options:
                                "format" keywords (e.g. date,email, uri, currency, etc.). This is
                                useful when you want to validate the structure of the CSV file
                                w/o worrying about the data types and domain/range of the fields.
+    --rules <file>             JSON file of row-level Luau rules to check, in addition to
+                               the JSON Schema (see ROW-LEVEL RULES). Can be used without a
+                               JSON Schema. The file can be a local file or a URL.
+                               Requires the "luau" feature. Not available on qsvlite.
     --fail-fast                Stops on first error.
     --valid <suffix>           Valid record output file suffix. [default: valid]
     --invalid <suffix>         Invalid record output file suffix. [default: invalid]
@@ -318,7 +408,6 @@ use std::{
     env,
     fs::File,
     io::{BufReader, BufWriter, Read, Write},
-    path::PathBuf,
     str,
     sync::{
         OnceLock,
@@ -356,6 +445,13 @@ use crate::{
     util,
 };
 
+mod relations;
+#[cfg(feature = "luau")]
+mod rules;
+mod tableschema;
+
+use tableschema::MetadataKind;
+
 // to save on repeated init/allocs
 static NULL_TYPE: OnceLock<Value> = OnceLock::new();
 
@@ -387,6 +483,7 @@ struct Args {
     cmd_schema:                bool,
     flag_trim:                 bool,
     flag_no_format_validation: bool,
+    flag_rules:                Option<String>,
     flag_fail_fast:            bool,
     flag_valid:                Option<String>,
     flag_invalid:              Option<String>,
@@ -466,9 +563,7 @@ impl DynEnumValidator {
 impl<'i> Keyword<'i> for DynEnumValidator {
     #[inline]
     fn validate(&self, instance: &'i Value) -> Result<(), ValidationError<'i>> {
-        if let Value::String(s) = instance
-            && self.dynenum_set.contains(s)
-        {
+        if self.is_valid(instance) {
             return Ok(());
         }
         Err(ValidationError::custom(format!(
@@ -478,10 +573,11 @@ impl<'i> Keyword<'i> for DynEnumValidator {
 
     #[inline]
     fn is_valid(&self, instance: &'i Value) -> bool {
-        if let Value::String(s) = instance {
-            self.dynenum_set.contains(s)
-        } else {
-            false
+        match instance {
+            Value::String(s) => self.dynenum_set.contains(s),
+            // numeric columns (e.g. integer foreign keys) are matched on their text form
+            Value::Number(n) => self.dynenum_set.contains(&n.to_string()),
+            _ => false,
         }
     }
 }
@@ -908,6 +1004,102 @@ fn load_dynenum_set<'a>(
     Ok(enum_set)
 }
 
+/// A lookup table resolved to a local CSV file.
+struct LookupFile {
+    path:           String,
+    /// used to size the sets built from the table
+    capacity_hint:  usize,
+    /// qsvlite downloads remote tables to a temp file, deleted when this is dropped
+    #[cfg(feature = "lite")]
+    _temp_download: Option<NamedTempFile>,
+}
+
+/// Resolve a lookup table URI in `dynamicEnum` syntax to a local CSV file, downloading (and
+/// on non-lite variants, caching) remote tables as needed. A column suffix in `uri` is ignored.
+///
+/// Shared by `dynamicEnum` and the `foreignKeys`/`referencedBy` relation keywords.
+#[cfg(not(feature = "lite"))]
+fn fetch_lookup_file(uri: &str) -> Result<LookupFile, String> {
+    let (lookup_name, final_uri, cache_age_secs, _column) = parse_dynenum_uri(uri);
+
+    let opts = LookupTableOptions {
+        name: lookup_name,
+        uri: final_uri,
+        cache_age_secs,
+        cache_dir: QSV_CACHE_DIR.get().unwrap().to_string(),
+        delimiter: DELIMITER.get().copied().flatten(),
+        ckan_api_url: CKAN_API.get().cloned(),
+        ckan_token: CKAN_TOKEN.get().and_then(std::clone::Clone::clone),
+        timeout_secs: TIMEOUT_SECS.load(Ordering::Relaxed),
+    };
+
+    match load_lookup_table(&opts) {
+        Ok(result) => Ok(LookupFile {
+            path:          result.filepath,
+            capacity_hint: result.rowcount,
+        }),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+#[cfg(feature = "lite")]
+fn fetch_lookup_file(uri: &str) -> Result<LookupFile, String> {
+    let base_uri = uri.split('|').next().unwrap_or(uri);
+
+    if base_uri.starts_with("http") {
+        let valid_url = reqwest::Url::parse(base_uri)
+            .map_err(|e| format!("Error parsing lookup table URL: {e}"))?;
+
+        let temp_file =
+            NamedTempFile::new().map_err(|e| format!("Failed to create temporary file: {e}"))?;
+
+        let download_timeout = TIMEOUT_SECS.load(Ordering::Relaxed);
+        let future = util::download_file(
+            valid_url.as_str(),
+            temp_file.path().to_path_buf(),
+            false,
+            None,
+            Some(download_timeout),
+            None,
+        );
+        match tokio::runtime::Runtime::new() {
+            Ok(runtime) => {
+                if let Err(e) = runtime.block_on(future) {
+                    return Err(format!("Error downloading lookup table - {e}"));
+                }
+            },
+            Err(e) => return Err(format!("Error creating Tokio runtime - {e}")),
+        }
+        let Some(path) = temp_file.path().to_str().map(str::to_string) else {
+            return Err(format!(
+                "Downloaded lookup table path is not valid UTF-8: {}",
+                temp_file.path().display()
+            ));
+        };
+        Ok(LookupFile {
+            path,
+            capacity_hint: 50,
+            _temp_download: Some(temp_file),
+        })
+    } else {
+        let uri_path = std::path::Path::new(base_uri);
+        if !uri_path.exists() {
+            return Err(format!("lookup table not found - {base_uri}"));
+        }
+        let Some(path) = uri_path.to_str().map(str::to_string) else {
+            return Err(format!(
+                "lookup table path is not valid UTF-8: {}",
+                uri_path.display()
+            ));
+        };
+        Ok(LookupFile {
+            path,
+            capacity_hint: 50,
+            _temp_download: None,
+        })
+    }
+}
+
 /// Factory function that creates a `DynEnumValidator` for validating against dynamic enums loaded
 /// from CSV files.
 ///
@@ -945,26 +1137,14 @@ fn dyn_enum_validator_factory<'a>(
         )
     })?;
 
-    let (lookup_name, final_uri, cache_age_secs, column) = parse_dynenum_uri(uri);
-
-    let opts = LookupTableOptions {
-        name: lookup_name,
-        uri: final_uri,
-        cache_age_secs,
-        cache_dir: QSV_CACHE_DIR.get().unwrap().to_string(),
-        delimiter: DELIMITER.get().copied().flatten(),
-        ckan_api_url: CKAN_API.get().cloned(),
-        ckan_token: CKAN_TOKEN.get().and_then(std::clone::Clone::clone),
-        timeout_secs: TIMEOUT_SECS.load(Ordering::Relaxed),
-    };
+    let (_, _, _, column) = parse_dynenum_uri(uri);
 
-    let lookup_result = match load_lookup_table(&opts) {
-        Ok(result) => result,
+    let lookup = match fetch_lookup_file(uri) {
+        Ok(lookup) => lookup,
         Err(e) => return fail_validation_error!("Error loading dynamicEnum lookup table: {e}"),
     };
 
-    let initial_capacity = lookup_result.headers.len();
-    let enum_set = load_dynenum_set(&lookup_result.filepath, column, initial_capacity)?;
+    let enum_set = load_dynenum_set(&lookup.path, column, lookup.capacity_hint)?;
     Ok(Box::new(DynEnumValidator::new(enum_set)))
 }
 
@@ -981,154 +1161,74 @@ fn dyn_enum_validator_factory<'a>(
         ));
     };
 
-    // Split URI to get column specification
-    let parts: Vec<&str> = uri.split('|').collect();
-    let base_uri = parts[0];
-    let column = parts.get(1).map(std::string::ToString::to_string);
+    // the column specification is the part after the URI
+    let column = uri.split('|').nth(1).map(std::string::ToString::to_string);
 
-    // Hold the temp file across the load so it isn't deleted prematurely.
-    // Only created in the URL branch; local paths don't need a temp file.
-    let mut _temp_download: Option<NamedTempFile> = None;
+    let lookup = match fetch_lookup_file(uri) {
+        Ok(lookup) => lookup,
+        Err(e) => return fail_validation_error!("{e}"),
+    };
 
-    let dynenum_path = if base_uri.starts_with("http") {
-        let valid_url = reqwest::Url::parse(base_uri)
-            .map_err(|e| ValidationError::custom(format!("Error parsing dynamicEnum URL: {e}")))?;
+    let enum_set = load_dynenum_set(&lookup.path, column, lookup.capacity_hint)?;
+    // `lookup` (and its temp download, if any) is dropped after `enum_set` is fully populated.
+    Ok(Box::new(DynEnumValidator::new(enum_set)))
+}
 
-        let temp_file = match NamedTempFile::new() {
-            Ok(file) => file,
-            Err(e) => return fail_validation_error!("Failed to create temporary file: {e}"),
-        };
+/// The custom formats/keywords used by a parsed JSON Schema.
+#[derive(Default, Debug)]
+struct CustomSchemaFeatures {
+    currency_format:      bool,
+    email_format:         bool,
+    dynamic_enum:         bool,
+    unique_combined_with: bool,
+    foreign_keys:         bool,
+    referenced_by:        bool,
+}
 
-        let download_timeout = TIMEOUT_SECS.load(Ordering::Relaxed);
-        let future = util::download_file(
-            valid_url.as_str(),
-            temp_file.path().to_path_buf(),
-            false,
-            None,
-            Some(download_timeout),
-            None,
-        );
-        match tokio::runtime::Runtime::new() {
-            Ok(runtime) => {
-                if let Err(e) = runtime.block_on(future) {
-                    return fail_validation_error!("Error downloading dynamicEnum file - {e}");
+impl CustomSchemaFeatures {
+    /// Walk a parsed JSON Schema and detect which custom formats/keywords are present.
+    ///
+    /// We look for:
+    /// - `"format": "currency"` and `"format": "email"` on objects (any nesting)
+    /// - any object key named `dynamicEnum`, `uniqueCombinedWith`, `foreignKeys` or `referencedBy`
+    ///
+    /// This replaces a substring search on the raw schema text, which was sensitive to
+    /// whitespace and could false-match on descriptions/titles containing these literals.
+    fn detect(schema: &Value) -> Self {
+        let mut features = Self::default();
+        features.walk(schema);
+        features
+    }
+
+    fn walk(&mut self, v: &Value) {
+        match v {
+            Value::Object(map) => {
+                for (k, val) in map {
+                    match k.as_str() {
+                        "format" => match val.as_str() {
+                            Some("currency") => self.currency_format = true,
+                            Some("email") => self.email_format = true,
+                            _ => {},
+                        },
+                        "dynamicEnum" => self.dynamic_enum = true,
+                        "uniqueCombinedWith" => self.unique_combined_with = true,
+                        "foreignKeys" => self.foreign_keys = true,
+                        "referencedBy" => self.referenced_by = true,
+                        _ => {},
+                    }
+                    self.walk(val);
                 }
             },
-            Err(e) => {
-                return fail_validation_error!("Error creating Tokio runtime - {e}");
-            },
-        }
-        let path_str = match temp_file.path().to_str() {
-            Some(p) => p.to_string(),
-            None => {
-                return fail_validation_error!(
-                    "Downloaded dynamicEnum file path is not valid UTF-8: {}",
-                    temp_file.path().display()
-                );
-            },
-        };
-        _temp_download = Some(temp_file);
-        path_str
-    } else {
-        let uri_path = std::path::Path::new(base_uri);
-        if !uri_path.exists() {
-            return fail_validation_error!("dynamicEnum file not found - {base_uri}");
-        }
-        match uri_path.to_str() {
-            Some(p) => p.to_string(),
-            None => {
-                return fail_validation_error!(
-                    "dynamicEnum file path is not valid UTF-8: {}",
-                    uri_path.display()
-                );
+            Value::Array(arr) => {
+                for item in arr {
+                    self.walk(item);
+                }
             },
+            _ => {},
         }
-    };
-
-    let enum_set = load_dynenum_set(&dynenum_path, column, 50)?;
-    // `_temp_download` is dropped at end of scope after `enum_set` is fully populated.
-    Ok(Box::new(DynEnumValidator::new(enum_set)))
-}
-
-/// Walk a parsed JSON Schema and detect which custom formats/keywords are present.
-///
-/// Returns (`has_currency_format`, `has_email_format`, `has_dynamic_enum`, `has_unique_combined`).
-///
-/// We look for:
-/// - `"format": "currency"` and `"format": "email"` on objects (any nesting)
-/// - any object key named `dynamicEnum` or `uniqueCombinedWith`
-///
-/// This replaces a substring search on the raw schema text, which was sensitive to
-/// whitespace and could false-match on descriptions/titles containing these literals.
-fn detect_custom_schema_features_walk(
-    v: &Value,
-    has_currency: &mut bool,
-    has_email: &mut bool,
-    has_dynamic_enum: &mut bool,
-    has_unique_combined: &mut bool,
-) {
-    match v {
-        Value::Object(map) => {
-            for (k, val) in map {
-                match k.as_str() {
-                    "format" => {
-                        if let Some(s) = val.as_str() {
-                            match s {
-                                "currency" => *has_currency = true,
-                                "email" => *has_email = true,
-                                _ => {},
-                            }
-                        }
-                    },
-                    "dynamicEnum" => *has_dynamic_enum = true,
-                    "uniqueCombinedWith" => *has_unique_combined = true,
-                    _ => {},
-                }
-                detect_custom_schema_features_walk(
-                    val,
-                    has_currency,
-                    has_email,
-                    has_dynamic_enum,
-                    has_unique_combined,
-                );
-            }
-        },
-        Value::Array(arr) => {
-            for item in arr {
-                detect_custom_schema_features_walk(
-                    item,
-                    has_currency,
-                    has_email,
-                    has_dynamic_enum,
-                    has_unique_combined,
-                );
-            }
-        },
-        _ => {},
     }
 }
 
-fn detect_custom_schema_features(schema: &Value) -> (bool, bool, bool, bool) {
-    let mut has_currency = false;
-    let mut has_email = false;
-    let mut has_dynamic_enum = false;
-    let mut has_unique_combined = false;
-
-    detect_custom_schema_features_walk(
-        schema,
-        &mut has_currency,
-        &mut has_email,
-        &mut has_dynamic_enum,
-        &mut has_unique_combined,
-    );
-    (
-        has_currency,
-        has_email,
-        has_dynamic_enum,
-        has_unique_combined,
-    )
-}
-
 pub fn run(argv: &[&str]) -> CliResult<()> {
     let args: Args = util::get_args(USAGE, argv)?;
 
@@ -1136,28 +1236,39 @@ pub fn run(argv: &[&str]) -> CliResult<()> {
     if args.cmd_schema {
         if let Some(ref schema) = args.arg_json_schema {
             let schema_json_string = load_json(schema)?;
-            let schema_json = serde_json::from_str(&schema_json_string)?;
-            // First, validate the JSON Schema
-            if let Err(e) = jsonschema::meta::validate(&schema_json) {
-                return fail_clierror!("JSON Schema Meta-Reference Error: {e}");
-            }
-            // Now, validate the JSON Schema formats
-            let test_validator = if args.flag_no_format_validation {
-                Validator::options()
-                    .should_validate_formats(false)
-                    .should_ignore_unknown_formats(true)
-                    .build(&schema_json)
-            } else {
-                Validator::options()
-                    .should_validate_formats(true)
-                    .should_ignore_unknown_formats(false)
-                    .build(&schema_json)
+            let schema_json: Value = serde_json::from_str(&schema_json_string)?;
+            // Frictionless/CSVW metadata is checked via its JSON Schema translation(s)
+            let metadata_kind = MetadataKind::detect(&schema_json);
+            let schemas = match metadata_kind {
+                Some(kind) => tableschema::load_all(kind, &schema_json, schema)?,
+                None => vec![(schema.clone(), schema_json)],
             };
-            if let Err(e) = test_validator {
-                return fail_clierror!("JSON Schema Format Validation Error: {e}");
+            for (label, schema_json) in &schemas {
+                // First, validate the JSON Schema
+                if let Err(e) = jsonschema::meta::validate(schema_json) {
+                    return fail_clierror!("JSON Schema Meta-Reference Error ({label}): {e}");
+                }
+                // Now, validate the JSON Schema formats
+                let test_validator = if args.flag_no_format_validation {
+                    Validator::options()
+                        .should_validate_formats(false)
+                        .should_ignore_unknown_formats(true)
+                        .build(schema_json)
+                } else {
+                    Validator::options()
+                        .should_validate_formats(true)
+                        .should_ignore_unknown_formats(false)
+                        .build(schema_json)
+                };
+                if let Err(e) = test_validator {
+                    return fail_clierror!("JSON Schema Format Validation Error ({label}): {e}");
+                }
             }
             if !args.flag_quiet {
-                winfo!("Valid JSON Schema.");
+                match metadata_kind {
+                    Some(kind) => winfo!("Valid {} ({} table(s)).", kind.as_str(), schemas.len()),
+                    None => winfo!("Valid JSON Schema."),
+                }
             }
             return Ok(());
         }
@@ -1179,8 +1290,13 @@ pub fn run(argv: &[&str]) -> CliResult<()> {
         false
     };
 
-    // if no JSON Schema supplied, only let csv reader RFC4180-validate csv file
-    if !has_json_schema && args.arg_json_schema.is_none() {
+    #[cfg(not(feature = "luau"))]
+    if args.flag_rules.is_some() {
+        return fail_incorrectusage_clierror!("--rules requires the luau feature.");
+    }
+
+    // if no JSON Schema or rules supplied, only let csv reader RFC4180-validate csv file
+    if !has_json_schema && args.arg_json_schema.is_none() && args.flag_rules.is_none() {
         // Warn when a .json file appears earlier in the input list — schema detection
         // only looks at the last positional, so a misordered argument silently falls
         // into RFC 4180 mode.
@@ -1305,11 +1421,16 @@ pub fn run(argv: &[&str]) -> CliResult<()> {
     }
 
     // parse and compile supplied JSON Schema
-    let json_schema_path =
-        json_schema_path.unwrap_or_else(|| PathBuf::from(json_schema_arg.as_ref().unwrap()));
+    let schema_json_string = match &json_schema_arg {
+        Some(uri) => load_json(uri),
+        None => Ok(r#"{"properties": {}}"#.to_string()),
+    };
+    let json_schema_uri = json_schema_arg.unwrap_or_default();
+    // per-column missing-value markers; empty means only an empty field is null
+    let mut missing_values: Vec<Vec<Vec<u8>>> = Vec::new();
     let (schema_json, schema_compiled, has_unique_combined): (Value, Validator, bool) =
-            // safety: we know the schema is_some() because we checked above
-            match load_json(&json_schema_path.to_string_lossy()) {
+            // with only --rules, an empty schema is used, so every column is a string
+            match schema_json_string {
             Ok(s) => {
                 // parse JSON string - use platform-appropriate JSON deserialization
                 // simd_json mutates its input buffer; serde_json reads from the str directly.
@@ -1322,28 +1443,29 @@ pub fn run(argv: &[&str]) -> CliResult<()> {
 
                 match json_result {
                     Ok(json) => {
+                        // Frictionless Table Schema/Data Package and CSVW metadata are
+                        // translated into the equivalent JSON Schema for this input.
+                        let json = if let Some(kind) = MetadataKind::detect(&json) {
+                            let table =
+                                tableschema::load(kind, &json, &json_schema_uri, input_path)?;
+                            missing_values = table.missing_values_for(&headers);
+                            table.schema
+                        } else {
+                            json
+                        };
+
                         // Detect custom formats/keywords by walking the parsed schema.
                         // This is robust to whitespace and avoids false matches on
                         // descriptions/titles that mention these strings as text.
-                        let (
-                            has_currency_format,
-                            has_email_format,
-                            has_dynamic_enum,
-                            has_unique_combined,
-                        ) = detect_custom_schema_features(&json);
-                        debug!(
-                            "Custom formats/keywords: currency: {has_currency_format}, dynamicEnum: {has_dynamic_enum}"
-                        );
-                        debug!(
-                            "uniqueCombinedWith: {has_unique_combined}, email: {has_email_format}"
-                        );
+                        let features = CustomSchemaFeatures::detect(&json);
+                        debug!("Custom formats/keywords: {features:?}");
 
                         // compile JSON Schema
                         let mut validator_options = Validator::options()
                             .should_validate_formats(!args.flag_no_format_validation);
 
                         // Add custom validators based on pre-checked flags
-                        if has_email_format {
+                        if features.email_format {
                             // Apply each option explicitly:
                             // - required_tld is enable-only in the jsonschema crate, but the
                             //   default ("not required") matches the docopt default (flag off),
@@ -1376,18 +1498,26 @@ pub fn run(argv: &[&str]) -> CliResult<()> {
                             validator_options = validator_options.with_email_options(email_options);
                         }
 
-                        if has_currency_format {
+                        if features.currency_format {
                             validator_options = validator_options.with_format("currency", currency_format_checker);
                         }
 
-                        if has_dynamic_enum {
+                        if features.dynamic_enum {
                             validator_options = validator_options.with_keyword("dynamicEnum", dyn_enum_validator_factory);
                         }
 
-                        if has_unique_combined {
+                        if features.unique_combined_with {
                             validator_options = validator_options.with_keyword("uniqueCombinedWith", unique_combined_with_validator_factory);
                         }
 
+                        if features.foreign_keys {
+                            validator_options = validator_options.with_keyword("foreignKeys", relations::foreign_keys_validator_factory);
+                        }
+
+                        if features.referenced_by {
+                            validator_options = validator_options.with_keyword("referencedBy", relations::referenced_by_validator_factory);
+                        }
+
                         if args.flag_fancy_regex {
                             let fancy_regex_options = PatternOptions::fancy_regex()
                                 .backtrack_limit(args.flag_backtrack_limit)
@@ -1402,16 +1532,16 @@ pub fn run(argv: &[&str]) -> CliResult<()> {
                         }
 
                         match validator_options.build(&json) {
-                            Ok(schema) => (json, schema, has_unique_combined),
+                            Ok(schema) => (json, schema, features.unique_combined_with),
                             Err(e) => {
                                 return fail_clierror!(r"Cannot compile JSONschema. error: {e}
-Try running `qsv validate schema {}` to check the JSON Schema file.", json_schema_path.to_string_lossy());
+Try running `qsv validate schema {json_schema_uri}` to check the JSON Schema file.");
                             },
                         }
                     },
                     Err(e) => {
                         return fail_clierror!(r"Unable to parse JSONschema. error: {e}
-Try running `qsv validate schema {}` to check the JSON Schema file.", json_schema_arg.as_ref().unwrap());
+Try running `qsv validate schema {json_schema_uri}` to check the JSON Schema file.");
                     },
                 }
             },
@@ -1433,6 +1563,13 @@ Try running `qsv validate schema {}` to check the JSON Schema file.", json_schem
     // get JSON types for each column in CSV file
     let header_types = get_json_types(&headers, &schema_json)?;
 
+    #[cfg(feature = "luau")]
+    let rule_set = args
+        .flag_rules
+        .as_deref()
+        .map(rules::RuleSet::load)
+        .transpose()?;
+
     // how many rows read and processed as batches
     let mut row_number: u64 = 0;
     // how many invalid rows found
@@ -1512,57 +1649,64 @@ Try running `qsv validate schema {}` to check the JSON Schema file.", json_schem
                 }
 
                 // convert CSV record to JSON instance
-                let json_instance = match to_json_instance(&header_types, header_len, record) {
-                    Ok(obj) => obj,
-                    Err(e) => {
-                        std::hint::cold_path();
-                        // safety: row number was appended as the last field via itoa, so it is
-                        // always valid ASCII; the unwrap can never fire in practice.
-                        let row_number_string =
-                            simdutf8::basic::from_utf8(&record[header_len]).unwrap();
-                        return Some(format!("{row_number_string}\t<RECORD>\t{e}"));
-                    },
-                };
+                let json_instance =
+                    match to_json_instance(&header_types, header_len, record, &missing_values) {
+                        Ok(obj) => obj,
+                        Err(e) => {
+                            std::hint::cold_path();
+                            // safety: row number was appended as the last field via itoa, so it is
+                            // always valid ASCII; the unwrap can never fire in practice.
+                            let row_number_string =
+                                simdutf8::basic::from_utf8(&record[header_len]).unwrap();
+                            return Some(format!("{row_number_string}\t<RECORD>\t{e}"));
+                        },
+                    };
+
+                // safety: row number was appended as the last field via itoa, so it is
+                // always valid ASCII; the unwrap can never fire in practice.
+                let row_number_string = || simdutf8::basic::from_utf8(&record[header_len]).unwrap();
 
                 // validate JSON instance against JSON Schema
                 // if the schema has stateful validators (like uniqueCombinedWith),
                 // we must fully evaluate every record (this is the hot path in that
                 // configuration, so no cold_path hint). Otherwise, fast-check with
-                // is_valid() and short-circuit valid records; only the rare invalid
-                // case falls through to the full evaluate.
+                // is_valid() and skip the full evaluate for valid records; only the rare
+                // invalid case falls through to the full evaluate.
                 let evaluation = if has_unique_combined {
-                    schema_compiled.evaluate(&json_instance)
+                    Some(schema_compiled.evaluate(&json_instance))
                 } else if schema_compiled.is_valid(&json_instance) {
-                    return None;
+                    None
                 } else {
                     std::hint::cold_path();
-                    schema_compiled.evaluate(&json_instance)
+                    Some(schema_compiled.evaluate(&json_instance))
                 };
 
-                if evaluation.flag().valid {
-                    None
-                } else {
+                // there can be multiple validation errors for a single record,
+                // they're squashed into one long String with linebreaks
+                let mut error_messages = Vec::new();
+                if let Some(evaluation) = evaluation
+                    && !evaluation.flag().valid
+                {
                     std::hint::cold_path();
-                    // safety: row number was appended as the last field via itoa, so it is
-                    // always valid ASCII; the unwrap can never fire in practice.
-                    let row_number_string =
-                        simdutf8::basic::from_utf8(&record[header_len]).unwrap();
-
-                    // Collect errors into a vector
-                    let errors: Vec<_> = evaluation.iter_errors().collect();
-
-                    // Preallocate the vector with the known size
-                    let mut error_messages = Vec::with_capacity(errors.len());
-
-                    // there can be multiple validation errors for a single record,
-                    // squash multiple errors into one long String with linebreaks
-                    for e in errors {
+                    let row_number_string = row_number_string();
+                    for e in evaluation.iter_errors() {
                         error_messages.push(format!(
                             "{row_number_string}\t{field}\t{error}",
                             field = e.instance_location.as_str().trim_start_matches('/'),
                             error = e.error
                         ));
                     }
+                }
+
+                // row-level rules are checked whether or not the row is schema-valid
+                #[cfg(feature = "luau")]
+                if let Some(rule_set) = &rule_set {
+                    rule_set.check(&json_instance, row_number_string(), &mut error_messages);
+                }
+
+                if error_messages.is_empty() {
+                    None
+                } else {
                     Some(error_messages.join("\n"))
                 }
             })
@@ -2317,19 +2461,26 @@ fn write_error_report(input_path: &str, validation_error_messages: Vec<String>)
     Ok(())
 }
 
-/// convert CSV Record into JSON instance by referencing JSON types
+/// convert CSV Record into JSON instance by referencing JSON types.
+/// `missing_values` holds the per-column markers that map to JSON null. When empty,
+/// only an empty field is null.
 #[inline]
 fn to_json_instance(
     header_types: &[(String, JSONtypes)],
     header_len: usize,
     record: &ByteRecord,
+    missing_values: &[Vec<Vec<u8>>],
 ) -> CliResult<Value> {
     let mut json_object_map = Map::with_capacity(header_len);
 
     let mut json_value;
 
-    for ((key, json_type), value) in header_types.iter().zip(record.iter()) {
-        if value.is_empty() {
+    for (i, ((key, json_type), value)) in header_types.iter().zip(record.iter()).enumerate() {
+        let is_missing = match missing_values.get(i) {
+            Some(markers) => markers.iter().any(|m| m == value),
+            None => value.is_empty(),
+        };
+        if is_missing {
             json_object_map.insert(key.clone(), Value::Null);
             continue;
         }
@@ -2585,7 +2736,7 @@ mod tests_for_csv_to_json_conversion {
         record.trim();
 
         assert_eq!(
-            to_json_instance(&header_types, headers.len(), &record)
+            to_json_instance(&header_types, headers.len(), &record, &[])
                 .expect("can't convert csv to json instance"),
             json!({
                 "A": "hello",
@@ -2618,11 +2769,45 @@ mod tests_for_csv_to_json_conversion {
             &header_types,
             headers.len(),
             &rdr.byte_records().next().unwrap().unwrap(),
+            &[],
         );
         assert!(&result.is_err());
         let error = result.err().unwrap().to_string();
         assert_eq!("Can't cast to Integer. key: C, value: 3.0e8", error);
     }
+
+    #[test]
+    fn test_to_json_instance_missing_values() {
+        let _ = NULL_TYPE.get_or_init(|| Value::String("null".to_string()));
+        let csv = "A,G,K\nNA,-,";
+
+        let mut rdr = csv::Reader::from_reader(csv.as_bytes());
+        let headers = rdr.byte_headers().unwrap().clone();
+        let header_types = get_json_types(&headers, &schema_json()).unwrap();
+        let record = rdr.byte_records().next().unwrap().unwrap();
+
+        // "NA" and "-" are nulls, but an empty K is not, so it fails the integer cast
+        let missing_values = vec![
+            vec![b"NA".to_vec()],
+            vec![b"-".to_vec(), b"".to_vec()],
+            vec![b"NA".to_vec()],
+        ];
+        let result = to_json_instance(&header_types, headers.len(), &record, &missing_values);
+        assert_eq!(
+            "Can't cast to Integer. key: K, value: ",
+            result.err().unwrap().to_string()
+        );
+
+        let missing_values = vec![
+            vec![b"NA".to_vec()],
+            vec![b"-".to_vec()],
+            vec![b"".to_vec()],
+        ];
+        assert_eq!(
+            to_json_instance(&header_types, headers.len(), &record, &missing_values).unwrap(),
+            json!({"A": null, "G": null, "K": null})
+        );
+    }
 }
 
 #[cfg(test)]
@@ -2674,7 +2859,7 @@ mod tests_for_schema_validation {
 
         let record = &rdr.byte_records().next().unwrap().unwrap();
 
-        let instance = to_json_instance(&header_types, headers.len(), record).unwrap();
+        let instance = to_json_instance(&header_types, headers.len(), record, &[]).unwrap();
 
         let result = validate_json_instance(&instance, &compiled_schema());
 
@@ -2693,7 +2878,7 @@ mod tests_for_schema_validation {
 
         let record = &rdr.byte_records().next().unwrap().unwrap();
 
-        let instance = to_json_instance(&header_types, headers.len(), record).unwrap();
+        let instance = to_json_instance(&header_types, headers.len(), record, &[]).unwrap();
 
         let result = validate_json_instance(&instance, &compiled_schema());
 
@@ -2762,7 +2947,7 @@ fn test_validate_currency_email_dynamicenum_validator() {
 
     let record = &rdr.byte_records().next().unwrap().unwrap();
 
-    let instance = to_json_instance(&header_types, headers.len(), record).unwrap();
+    let instance = to_json_instance(&header_types, headers.len(), record, &[]).unwrap();
 
     let compiled_schema = Validator::options()
         .with_format("currency", currency_format_checker)
@@ -2791,7 +2976,7 @@ fn test_validate_currency_email_dynamicenum_validator() {
 
     let record = &rdr.byte_records().next().unwrap().unwrap();
 
-    let instance = to_json_instance(&header_types, headers.len(), record).unwrap();
+    let instance = to_json_instance(&header_types, headers.len(), record, &[]).unwrap();
 
     let compiled_schema = Validator::options()
         .with_format("currency", currency_format_checker)
@@ -2840,7 +3025,7 @@ fn test_validate_currency_email_dynamicenum_validator() {
 
     for (i, record) in rdr.byte_records().enumerate() {
         let record = record.unwrap();
-        let instance = to_json_instance(&header_types, headers.len(), &record).unwrap();
+        let instance = to_json_instance(&header_types, headers.len(), &record, &[]).unwrap();
 
         let result = validate_json_instance(&instance, &compiled_schema);
 
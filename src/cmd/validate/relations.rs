@@ -0,0 +1,319 @@
+//! Cross-file referential integrity keywords for `validate`.
+//!
+//! - `foreignKeys`: each row's (possibly composite) key must exist in a referenced table, e.g.
+//!   every order's `customer_id` must be a customer - rows failing this are orphans.
+//! - `referencedBy`: each row's key must be referenced by between `minCount` (default 1) and
+//!   `maxCount` rows of another table, e.g. every order must have at least one line item.
+//!
+//! Both are root-level keywords, like `uniqueCombinedWith`, holding an array of relations:
+//!
+//! ```json
+//! "foreignKeys": [
+//!   { "columns": ["order_id", "line_no"],
+//!     "reference": { "uri": "order_lines.csv", "columns": ["id", "line"] } }
+//! ]
+//! ```
+//!
+//! The referenced table is read once, when the schema is compiled, using the same lookup
+//! loading as `dynamicEnum` - so `uri` takes the same syntax, including caching and the
+//! `ckan://` and `dathere://` schemes. Keys with a null part are not checked, as with SQL
+//! foreign keys.
+
+use foldhash::{HashMap, HashMapExt};
+use jsonschema::{Keyword, ValidationError, paths::Location};
+use log::error;
+use serde_json::{Map, Value};
+
+use super::fetch_lookup_file;
+use crate::config::Config;
+
+/// joins the parts of a composite key; a control char unlikely to appear in CSV data
+const KEY_SEPARATOR: char = '\u{1f}';
+
+/// A relation between some columns of the CSV being validated and a referenced table.
+struct Relation {
+    columns:    Vec<String>,
+    /// e.g. `orders.csv (id, line)`, for error messages
+    reference:  String,
+    /// key -> number of referenced table rows with that key
+    key_counts: HashMap<String, u64>,
+    min_count:  u64,
+    max_count:  Option<u64>,
+}
+
+impl Relation {
+    /// The key of this relation's columns in `instance`, with its display form.
+    /// `Ok(None)` if any part of the key is null.
+    fn instance_key(&self, instance: &Value) -> Result<Option<(String, String)>, String> {
+        let Some(obj) = instance.as_object() else {
+            return Err("Instance must be an object".to_string());
+        };
+        let mut key = String::new();
+        let mut display = Vec::with_capacity(self.columns.len());
+        for (i, column) in self.columns.iter().enumerate() {
+            let Some(value) = obj.get(column) else {
+                return Err(format!("column '{column}' is not in the CSV"));
+            };
+            if i > 0 {
+                key.push(KEY_SEPARATOR);
+            }
+            match value {
+                Value::Null => return Ok(None),
+                Value::String(s) => key.push_str(s),
+                // numbers and booleans are matched on their text form
+                other => key.push_str(&other.to_string()),
+            }
+            display.push(value.to_string());
+        }
+        Ok(Some((key, display.join(", "))))
+    }
+}
+
+/// Parse one relation object and load the key counts of its referenced table.
+fn load_relation(value: &Value, keyword: &str, default_min_count: u64) -> Result<Relation, String> {
+    let columns = column_list(value.get("columns"))
+        .ok_or_else(|| format!("'{keyword}' relations need a 'columns' array"))?;
+    let reference = value.get("reference");
+    let Some(uri) = reference.and_then(|r| r.get("uri")).and_then(Value::as_str) else {
+        return Err(format!(
+            "'{keyword}' relations need a 'reference' with a 'uri'"
+        ));
+    };
+    // the referenced columns default to the same names
+    let ref_columns =
+        column_list(reference.and_then(|r| r.get("columns"))).unwrap_or_else(|| columns.clone());
+    if ref_columns.len() != columns.len() {
+        return Err(format!(
+            "'{keyword}' relation on ({}) has {} reference column(s), expected {}",
+            columns.join(", "),
+            ref_columns.len(),
+            columns.len()
+        ));
+    }
+
+    let count_bound = |name: &str| match value.get(name) {
+        None => Ok(None),
+        Some(v) => v
+            .as_u64()
+            .map(Some)
+            .ok_or_else(|| format!("'{keyword}' {name} must be a non-negative integer")),
+    };
+    let min_count = count_bound("minCount")?.unwrap_or(default_min_count);
+    let max_count = count_bound("maxCount")?;
+
+    Ok(Relation {
+        key_counts: load_key_counts(uri, &ref_columns)?,
+        reference: format!("{uri} ({})", ref_columns.join(", ")),
+        columns,
+        min_count,
+        max_count,
+    })
+}
+
+/// A string or an array of strings.
+fn column_list(value: Option<&Value>) -> Option<Vec<String>> {
+    let columns: Vec<String> = match value? {
+        Value::String(s) => vec![s.clone()],
+        Value::Array(arr) => arr
+            .iter()
+            .map(|v| v.as_str().map(str::to_string))
+            .collect::<Option<_>>()?,
+        _ => return None,
+    };
+    (!columns.is_empty()).then_some(columns)
+}
+
+/// Count the rows of the table at `uri` by their key in `columns`.
+/// Rows with an empty key part are skipped, as they can't match a non-null key.
+fn load_key_counts(uri: &str, columns: &[String]) -> Result<HashMap<String, u64>, String> {
+    let lookup = fetch_lookup_file(uri).map_err(|e| format!("Error loading {uri}: {e}"))?;
+
+    let mut rdr = Config::new(Some(lookup.path.clone()).as_ref())
+        .flexible(true)
+        .comment(Some(b'#'))
+        .skip_format_check(true)
+        .reader()
+        .map_err(|e| format!("Error opening {uri}: {e}"))?;
+    let headers = rdr
+        .headers()
+        .map_err(|e| format!("Error reading headers of {uri}: {e}"))?
+        .clone();
+    let indices = columns
+        .iter()
+        .map(|column| {
+            headers
+                .iter()
+                .position(|h| h == column)
+                .ok_or_else(|| format!("Column '{column}' not found in {uri}"))
+        })
+        .collect::<Result<Vec<usize>, String>>()?;
+
+    let mut key_counts = HashMap::with_capacity(lookup.capacity_hint);
+    let mut key = String::new();
+    'rows: for result in rdr.records() {
+        let record = result.map_err(|e| format!("Error reading {uri} - {e}"))?;
+        key.clear();
+        for (i, &idx) in indices.iter().enumerate() {
+            let part = record.get(idx).unwrap_or_default();
+            if part.is_empty() {
+                continue 'rows;
+            }
+            if i > 0 {
+                key.push(KEY_SEPARATOR);
+            }
+            key.push_str(part);
+        }
+        if let Some(count) = key_counts.get_mut(&key) {
+            *count += 1;
+        } else {
+            key_counts.insert(key.clone(), 1);
+        }
+    }
+    Ok(key_counts)
+}
+
+#[allow(clippy::result_large_err)]
+fn load_relations<'a>(
+    value: &'a Value,
+    keyword: &str,
+    default_min_count: u64,
+) -> Result<Vec<Relation>, ValidationError<'a>> {
+    let relations: Vec<&Value> = match value {
+        Value::Array(arr) => arr.iter().collect(),
+        Value::Object(_) => vec![value],
+        _ => {
+            return Err(ValidationError::custom(format!(
+                "'{keyword}' must be an array of relations"
+            )));
+        },
+    };
+    relations
+        .into_iter()
+        .map(|relation| {
+            load_relation(relation, keyword, default_min_count).map_err(|e| {
+                error!("{e}");
+                ValidationError::custom(e)
+            })
+        })
+        .collect()
+}
+
+/// Rows whose key is not in the referenced table (orphans).
+struct ForeignKeysValidator {
+    relations: Vec<Relation>,
+}
+
+impl ForeignKeysValidator {
+    fn errors(&self, instance: &Value) -> Vec<String> {
+        let mut errors = Vec::new();
+        for relation in &self.relations {
+            match relation.instance_key(instance) {
+                Ok(Some((key, display))) if !relation.key_counts.contains_key(&key) => {
+                    errors.push(format!(
+                        "orphan row: ({}) = ({display}) not found in {}",
+                        relation.columns.join(", "),
+                        relation.reference
+                    ));
+                },
+                Ok(_) => {},
+                Err(e) => errors.push(e),
+            }
+        }
+        errors
+    }
+}
+
+impl<'i> Keyword<'i> for ForeignKeysValidator {
+    fn validate(&self, instance: &'i Value) -> Result<(), ValidationError<'i>> {
+        let errors = self.errors(instance);
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(ValidationError::custom(errors.join("; ")))
+        }
+    }
+
+    fn is_valid(&self, instance: &'i Value) -> bool {
+        self.errors(instance).is_empty()
+    }
+}
+
+/// Rows referenced by too few (e.g. childless parents) or too many rows of another table.
+struct ReferencedByValidator {
+    relations: Vec<Relation>,
+}
+
+impl ReferencedByValidator {
+    fn errors(&self, instance: &Value) -> Vec<String> {
+        let mut errors = Vec::new();
+        for relation in &self.relations {
+            let (key, display) = match relation.instance_key(instance) {
+                Ok(Some(key)) => key,
+                Ok(None) => continue,
+                Err(e) => {
+                    errors.push(e);
+                    continue;
+                },
+            };
+            let count = relation.key_counts.get(&key).copied().unwrap_or(0);
+            let expected = if count < relation.min_count {
+                format!("at least {}", relation.min_count)
+            } else if let Some(max_count) = relation.max_count
+                && count > max_count
+            {
+                format!("at most {max_count}")
+            } else {
+                continue;
+            };
+            let label = if count == 0 {
+                "childless row"
+            } else {
+                "cardinality"
+            };
+            errors.push(format!(
+                "{label}: ({}) = ({display}) is referenced by {count} row(s) of {}, expected \
+                 {expected}",
+                relation.columns.join(", "),
+                relation.reference
+            ));
+        }
+        errors
+    }
+}
+
+impl<'i> Keyword<'i> for ReferencedByValidator {
+    fn validate(&self, instance: &'i Value) -> Result<(), ValidationError<'i>> {
+        let errors = self.errors(instance);
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(ValidationError::custom(errors.join("; ")))
+        }
+    }
+
+    fn is_valid(&self, instance: &'i Value) -> bool {
+        self.errors(instance).is_empty()
+    }
+}
+
+#[allow(clippy::result_large_err)]
+pub(super) fn foreign_keys_validator_factory<'a>(
+    _parent: &'a Map<String, Value>,
+    value: &'a Value,
+    _location: Location,
+) -> Result<Box<dyn for<'i> Keyword<'i>>, ValidationError<'a>> {
+    Ok(Box::new(ForeignKeysValidator {
+        relations: load_relations(value, "foreignKeys", 0)?,
+    }))
+}
+
+#[allow(clippy::result_large_err)]
+pub(super) fn referenced_by_validator_factory<'a>(
+    _parent: &'a Map<String, Value>,
+    value: &'a Value,
+    _location: Location,
+) -> Result<Box<dyn for<'i> Keyword<'i>>, ValidationError<'a>> {
+    Ok(Box::new(ReferencedByValidator {
+        relations: load_relations(value, "referencedBy", 1)?,
+    }))
+}
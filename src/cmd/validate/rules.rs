@@ -0,0 +1,193 @@
+//! Row-level rules for `validate --rules`, for constraints JSON Schema can't express, such as
+//! comparisons between columns (`end_date >= start_date`) or conditional requirements
+//! (`status ~= 'closed' or closed_at ~= nil`).
+//!
+//! A rules file is a JSON array of named Luau expressions:
+//!
+//! ```json
+//! [
+//!   { "name": "end_after_start", "expr": "end_date >= start_date" },
+//!   { "name": "closed_has_date", "expr": "status ~= 'closed' or closed_at ~= nil",
+//!     "message": "closed rows need a closed_at date" }
+//! ]
+//! ```
+//!
+//! Each expression is evaluated against the row's JSON instance, so columns are typed by the
+//! JSON Schema (strings without one) and nulls are `nil`. As with `luau`, columns are globals,
+//! and also available through the `col` table for names that aren't valid identifiers. A
+//! column named `col` is only available as `col["col"]`, so it can't shadow the table.
+//! Rules are compiled once; as Luau states can't be shared across threads, each validation
+//! thread loads its own copy of the compiled rules the first time it sees a rule set.
+
+use std::{
+    cell::RefCell,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use foldhash::{HashSet, HashSetExt};
+use mlua::{Function, Lua, LuaSerdeExt, Table};
+use serde::Deserialize;
+use serde_json::Value;
+
+use super::load_json;
+use crate::CliResult;
+
+/// each loaded rule set gets a new id, so threads reused across runs don't keep stale rules
+static NEXT_RULE_SET_ID: AtomicUsize = AtomicUsize::new(0);
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Rule {
+    name:    String,
+    expr:    String,
+    #[serde(default)]
+    message: Option<String>,
+}
+
+/// The compiled rules of a rules file.
+pub(super) struct RuleSet {
+    id:       usize,
+    rules:    Vec<Rule>,
+    bytecode: Vec<Vec<u8>>,
+}
+
+/// A validation thread's Luau state, with the rules of rule set `rule_set_id` loaded.
+struct RuleRunner {
+    rule_set_id: usize,
+    luau:        Lua,
+    col:         Table,
+    functions:   Vec<Function>,
+}
+
+thread_local! {
+    static RULE_RUNNER: RefCell<Option<RuleRunner>> = const { RefCell::new(None) };
+}
+
+impl RuleSet {
+    /// Load and compile the rules file at `uri`, a local file or URL.
+    pub(super) fn load(uri: &str) -> CliResult<Self> {
+        let rules_json = load_json(uri)?;
+        let rules: Vec<Rule> = match serde_json::from_str(&rules_json) {
+            Ok(rules) => rules,
+            Err(e) => {
+                return fail_incorrectusage_clierror!(
+                    "Invalid rules file {uri}: {e}. Expected an array of {{\"name\": ..., \
+                     \"expr\": ...}} objects."
+                );
+            },
+        };
+        if rules.is_empty() {
+            return fail_incorrectusage_clierror!("Rules file {uri} has no rules.");
+        }
+
+        let compiler = mlua::chunk::Compiler::new()
+            .set_optimization_level(2)
+            .set_debug_level(1);
+        let mut names = HashSet::with_capacity(rules.len());
+        let mut bytecode = Vec::with_capacity(rules.len());
+        for rule in &rules {
+            if rule.name.is_empty() || rule.expr.trim().is_empty() {
+                return fail_incorrectusage_clierror!(
+                    "Rules in {uri} need a non-empty name and expr."
+                );
+            }
+            if !names.insert(rule.name.as_str()) {
+                return fail_incorrectusage_clierror!(
+                    "Duplicate rule name \"{}\" in {uri}.",
+                    rule.name
+                );
+            }
+            match compiler.compile(format!("return {}", rule.expr)) {
+                Ok(code) => bytecode.push(code),
+                Err(e) => {
+                    return fail_incorrectusage_clierror!("Invalid rule \"{}\": {e}", rule.name);
+                },
+            }
+        }
+
+        Ok(Self {
+            id: NEXT_RULE_SET_ID.fetch_add(1, Ordering::Relaxed),
+            rules,
+            bytecode,
+        })
+    }
+
+    /// Evaluate all rules against a row's JSON instance, adding a validation-errors.tsv line
+    /// for each rule that doesn't return `true`.
+    pub(super) fn check(&self, instance: &Value, row_number: &str, errors: &mut Vec<String>) {
+        let result = RULE_RUNNER.with_borrow_mut(|runner| {
+            if runner.as_ref().is_none_or(|r| r.rule_set_id != self.id) {
+                *runner = Some(self.new_runner()?);
+            }
+            // safety: we just made sure there's a runner for this rule set
+            let runner = runner.as_ref().unwrap();
+            runner.set_row(instance)?;
+
+            for (rule, function) in self.rules.iter().zip(&runner.functions) {
+                let error = match function.call::<mlua::Value>(()) {
+                    Ok(mlua::Value::Boolean(true)) => continue,
+                    Ok(mlua::Value::Boolean(false)) => match &rule.message {
+                        Some(message) => message.clone(),
+                        None => format!("failed rule: {}", rule.expr),
+                    },
+                    Ok(other) => format!("rule must return a boolean, got {}", other.type_name()),
+                    // only the first line - the rest is the Luau traceback
+                    Err(e) => format!(
+                        "rule error: {}",
+                        e.to_string().lines().next().unwrap_or_default()
+                    ),
+                };
+                errors.push(format!("{row_number}\t{}\t{error}", rule.name));
+            }
+            Ok::<(), mlua::Error>(())
+        });
+        if let Err(e) = result {
+            errors.push(format!("{row_number}\t<RULES>\t{e}"));
+        }
+    }
+
+    fn new_runner(&self) -> mlua::Result<RuleRunner> {
+        let luau = Lua::new();
+        // enable sandboxing which enables several optimizations and keeps rules side-effect free
+        luau.sandbox(true)?;
+        let col = luau.create_table()?;
+        luau.globals().raw_set("col", &col)?;
+        let functions = self
+            .bytecode
+            .iter()
+            .zip(&self.rules)
+            .map(|(code, rule)| luau.load(&code[..]).set_name(&rule.name).into_function())
+            .collect::<mlua::Result<Vec<_>>>()?;
+        Ok(RuleRunner {
+            rule_set_id: self.id,
+            luau,
+            col,
+            functions,
+        })
+    }
+}
+
+impl RuleRunner {
+    /// Bind the row's columns as globals and in the `col` table. Nulls are bound as `nil`,
+    /// which also clears the previous row's value. A column named `col` is not bound as a
+    /// global, as it would replace the `col` table for the rest of the run.
+    fn set_row(&self, instance: &Value) -> mlua::Result<()> {
+        let Some(obj) = instance.as_object() else {
+            return Err(mlua::Error::runtime("Instance must be an object"));
+        };
+        let globals = self.luau.globals();
+        for (column, value) in obj {
+            let value = match value {
+                Value::Null => mlua::Value::Nil,
+                other => self.luau.to_value(other)?,
+            };
+            if column == "col" {
+                self.col.raw_set(column.as_str(), value)?;
+            } else {
+                self.col.raw_set(column.as_str(), value.clone())?;
+                globals.raw_set(column.as_str(), value)?;
+            }
+        }
+        Ok(())
+    }
+}
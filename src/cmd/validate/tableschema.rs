@@ -0,0 +1,877 @@
+//! Frictionless Table Schema / Data Package and W3C CSVW metadata support for `validate`.
+//!
+//! Rather than running a second validation engine, the metadata is translated into the
+//! JSON Schema dialect `validate` already enforces:
+//!
+//! - each field/column becomes a JSON Schema property, with its type, format and constraints mapped
+//!   to the equivalent JSON Schema keywords;
+//! - `primaryKey` becomes a root-level `uniqueCombinedWith` and makes its fields required;
+//! - `unique` fields get a `uniqueCombinedWith` that skips nulls;
+//! - single-column `foreignKeys` become `dynamicEnum` lookups against the referenced resource's
+//!   data file, and composite ones become `foreignKeys` relations;
+//! - `missingValues` (CSVW `null`) are returned as per-column markers so `to_json_instance` can map
+//!   them to JSON `null` before type casting.
+//!
+//! Frictionless types and CSVW datatypes that have no JSON Schema counterpart (e.g.
+//! `geopoint`, or dates with a custom `format` pattern) are only checked for presence.
+
+use std::path::Path;
+
+use csv::ByteRecord;
+use foldhash::{HashMap, HashMapExt};
+use serde_json::{Map, Value, json};
+
+use super::load_json;
+use crate::CliResult;
+
+const JSON_SCHEMA_DRAFT: &str = "https://json-schema.org/draft/2020-12/schema";
+
+/// RFC 3339 `partial-time`. The JSON Schema `time` format also requires a UTC offset,
+/// which neither Frictionless nor CSVW times carry by default.
+const TIME_PATTERN: &str = r"^([01][0-9]|2[0-3]):[0-5][0-9]:[0-5][0-9](\.[0-9]+)?$";
+const YEARMONTH_PATTERN: &str = r"^-?[0-9]{4}-(0[1-9]|1[0-2])$";
+
+const FRICTIONLESS_TRUE_VALUES: [&str; 4] = ["true", "True", "TRUE", "1"];
+const FRICTIONLESS_FALSE_VALUES: [&str; 4] = ["false", "False", "FALSE", "0"];
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub(super) enum MetadataKind {
+    /// a bare Frictionless Table Schema, e.g. `tableschema.json`
+    TableSchema,
+    /// a Frictionless Data Package, e.g. `datapackage.json`
+    DataPackage,
+    /// W3C CSV on the Web metadata, e.g. `data.csv-metadata.json`
+    Csvw,
+}
+
+impl MetadataKind {
+    /// Sniff the shape of a parsed metadata file. Returns `None` for a JSON Schema,
+    /// which `validate` uses as-is.
+    pub(super) fn detect(json: &Value) -> Option<Self> {
+        let obj = json.as_object()?;
+        if obj.contains_key("properties")
+            || obj
+                .get("$schema")
+                .and_then(Value::as_str)
+                .is_some_and(|s| s.contains("json-schema.org"))
+        {
+            return None;
+        }
+
+        let is_csvw_context = match obj.get("@context") {
+            Some(Value::String(s)) => s.contains("csvw"),
+            Some(Value::Array(arr)) => arr
+                .first()
+                .and_then(Value::as_str)
+                .is_some_and(|s| s.contains("csvw")),
+            _ => false,
+        };
+        if is_csvw_context && (obj.contains_key("tableSchema") || obj.contains_key("tables")) {
+            Some(Self::Csvw)
+        } else if obj.get("resources").is_some_and(Value::is_array) {
+            Some(Self::DataPackage)
+        } else if obj.get("fields").is_some_and(Value::is_array) {
+            Some(Self::TableSchema)
+        } else {
+            None
+        }
+    }
+
+    pub(super) const fn as_str(self) -> &'static str {
+        match self {
+            Self::TableSchema => "Frictionless Table Schema",
+            Self::DataPackage => "Frictionless Data Package",
+            Self::Csvw => "CSVW metadata",
+        }
+    }
+}
+
+/// The JSON Schema translation of one table described by the metadata.
+pub(super) struct TableMetadata {
+    pub(super) schema:      Value,
+    /// per-field missing-value markers, keyed by CSV header
+    missing_values:         HashMap<String, Vec<String>>,
+    /// markers for headers without field-level overrides
+    default_missing_values: Vec<String>,
+}
+
+impl TableMetadata {
+    /// Missing-value markers for each CSV header, in header order.
+    pub(super) fn missing_values_for(&self, headers: &ByteRecord) -> Vec<Vec<Vec<u8>>> {
+        headers
+            .iter()
+            .map(|header| {
+                let key = String::from_utf8_lossy(header);
+                self.missing_values
+                    .get(key.as_ref())
+                    .unwrap_or(&self.default_missing_values)
+                    .iter()
+                    .map(|mv| mv.as_bytes().to_vec())
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+/// A table described by the metadata: its name, its (resolved) data file(s) and its schema.
+struct TableRef {
+    name:   Option<String>,
+    paths:  Vec<String>,
+    schema: Value,
+}
+
+impl TableRef {
+    fn matches_input(&self, input: &Path) -> bool {
+        let Some(input_name) = input.file_name() else {
+            return false;
+        };
+        self.paths
+            .iter()
+            .any(|p| Path::new(p).file_name() == Some(input_name))
+    }
+
+    fn label(&self) -> String {
+        self.name
+            .clone()
+            .or_else(|| self.paths.first().cloned())
+            .unwrap_or_else(|| "<unnamed>".to_string())
+    }
+}
+
+/// Translate the table in `metadata` that describes `input` into a JSON Schema.
+pub(super) fn load(
+    kind: MetadataKind,
+    metadata: &Value,
+    metadata_uri: &str,
+    input: &Path,
+) -> CliResult<TableMetadata> {
+    let tables = table_refs(kind, metadata, metadata_uri)?;
+    let with_schema: Vec<&TableRef> = tables.iter().filter(|t| !t.schema.is_null()).collect();
+
+    let table = if let Some(t) = with_schema.iter().find(|t| t.matches_input(input)) {
+        t
+    } else if let [only] = with_schema.as_slice() {
+        only
+    } else {
+        let available: Vec<String> = with_schema.iter().map(|t| t.label()).collect();
+        return fail_clierror!(
+            "{} does not describe {}. Tables with a schema: {}",
+            kind.as_str(),
+            input.display(),
+            available.join(", ")
+        );
+    };
+
+    let input_uri = input.to_string_lossy();
+    convert_table(kind, table, &tables, metadata_uri, &input_uri)
+}
+
+/// Translate every table in `metadata` into a JSON Schema, labelled by table name/path.
+/// Used by `validate schema` to check metadata files on their own.
+pub(super) fn load_all(
+    kind: MetadataKind,
+    metadata: &Value,
+    metadata_uri: &str,
+) -> CliResult<Vec<(String, Value)>> {
+    let tables = table_refs(kind, metadata, metadata_uri)?;
+    let mut schemas = Vec::with_capacity(tables.len());
+    for table in tables.iter().filter(|t| !t.schema.is_null()) {
+        let self_uri = table.paths.first().cloned().unwrap_or_default();
+        let converted = convert_table(kind, table, &tables, metadata_uri, &self_uri)?;
+        schemas.push((table.label(), converted.schema));
+    }
+    if schemas.is_empty() {
+        return fail_clierror!("{} does not contain any table schema.", kind.as_str());
+    }
+    Ok(schemas)
+}
+
+fn convert_table(
+    kind: MetadataKind,
+    table: &TableRef,
+    tables: &[TableRef],
+    metadata_uri: &str,
+    input_uri: &str,
+) -> CliResult<TableMetadata> {
+    match kind {
+        MetadataKind::TableSchema | MetadataKind::DataPackage => {
+            frictionless_table(kind, table, tables, metadata_uri, input_uri)
+        },
+        MetadataKind::Csvw => csvw_table(table, tables, metadata_uri, input_uri),
+    }
+}
+
+/// List the tables described by the metadata, loading external schemas referenced by URI.
+fn table_refs(
+    kind: MetadataKind,
+    metadata: &Value,
+    metadata_uri: &str,
+) -> CliResult<Vec<TableRef>> {
+    let tables = match kind {
+        MetadataKind::TableSchema => vec![TableRef {
+            name:   None,
+            paths:  Vec::new(),
+            schema: metadata.clone(),
+        }],
+        MetadataKind::DataPackage => {
+            let resources = metadata
+                .get("resources")
+                .and_then(Value::as_array)
+                .map(Vec::as_slice)
+                .unwrap_or_default();
+            let mut tables = Vec::with_capacity(resources.len());
+            for resource in resources {
+                let paths = string_list(resource.get("path"))
+                    .unwrap_or_default()
+                    .iter()
+                    .map(|p| resolve_uri(metadata_uri, p))
+                    .collect();
+                tables.push(TableRef {
+                    name: resource
+                        .get("name")
+                        .and_then(Value::as_str)
+                        .map(str::to_string),
+                    paths,
+                    schema: load_schema_ref(resource.get("schema"), metadata_uri)?,
+                });
+            }
+            tables
+        },
+        MetadataKind::Csvw => {
+            // a table group may declare a tableSchema that its tables inherit
+            let group_schema = metadata.get("tableSchema");
+            let table_descs = match metadata.get("tables").and_then(Value::as_array) {
+                Some(arr) => arr.iter().collect(),
+                None => vec![metadata],
+            };
+            let mut tables = Vec::with_capacity(table_descs.len());
+            for desc in table_descs {
+                tables.push(TableRef {
+                    name:   None,
+                    paths:  desc
+                        .get("url")
+                        .and_then(Value::as_str)
+                        .map(|url| vec![resolve_uri(metadata_uri, url)])
+                        .unwrap_or_default(),
+                    schema: load_schema_ref(
+                        desc.get("tableSchema").or(group_schema),
+                        metadata_uri,
+                    )?,
+                });
+            }
+            tables
+        },
+    };
+    Ok(tables)
+}
+
+/// A schema is either inline or a path/URL to a separate JSON file.
+fn load_schema_ref(schema: Option<&Value>, metadata_uri: &str) -> CliResult<Value> {
+    match schema {
+        Some(Value::String(uri)) => {
+            let uri = resolve_uri(metadata_uri, uri);
+            let schema_string = load_json(&uri)?;
+            match serde_json::from_str(&schema_string) {
+                Ok(json) => Ok(json),
+                Err(e) => fail_clierror!("Unable to parse schema {uri}. error: {e}"),
+            }
+        },
+        Some(schema @ Value::Object(_)) => Ok(schema.clone()),
+        _ => Ok(Value::Null),
+    }
+}
+
+/// Resolve `uri` relative to the location of the metadata file.
+fn resolve_uri(metadata_uri: &str, uri: &str) -> String {
+    if uri.contains("://") || Path::new(uri).is_absolute() {
+        return uri.to_string();
+    }
+    if metadata_uri.contains("://") {
+        return reqwest::Url::parse(metadata_uri)
+            .and_then(|base| base.join(uri))
+            .map_or_else(|_| uri.to_string(), |u| u.to_string());
+    }
+    Path::new(metadata_uri).parent().map_or_else(
+        || uri.to_string(),
+        |dir| dir.join(uri).to_string_lossy().to_string(),
+    )
+}
+
+/// Read a string-or-array-of-strings property. Frictionless v2 `missingValues` entries may
+/// also be `{"value": ..., "label": ...}` objects.
+fn string_list(value: Option<&Value>) -> Option<Vec<String>> {
+    match value? {
+        Value::String(s) => Some(vec![s.clone()]),
+        Value::Array(arr) => Some(
+            arr.iter()
+                .filter_map(|v| match v {
+                    Value::String(s) => Some(s.clone()),
+                    Value::Object(o) => o.get("value").and_then(Value::as_str).map(str::to_string),
+                    _ => None,
+                })
+                .collect(),
+        ),
+        _ => None,
+    }
+}
+
+/// A field translated into a JSON Schema property, before nullability is applied.
+struct FieldSpec {
+    key:            String,
+    json_type:      &'static str,
+    property:       Map<String, Value>,
+    required:       bool,
+    unique:         bool,
+    missing_values: Option<Vec<String>>,
+}
+
+/// A foreign key, keyed by CSV headers, with its referenced data file.
+struct ForeignKey {
+    columns:     Vec<String>,
+    target:      String,
+    ref_columns: Vec<String>,
+}
+
+fn frictionless_table(
+    kind: MetadataKind,
+    table: &TableRef,
+    tables: &[TableRef],
+    metadata_uri: &str,
+    input_uri: &str,
+) -> CliResult<TableMetadata> {
+    let schema = &table.schema;
+    let Some(fields) = schema.get("fields").and_then(Value::as_array) else {
+        return fail_clierror!(
+            "{} for {} has no 'fields' array.",
+            kind.as_str(),
+            table.label()
+        );
+    };
+    let primary_key = string_list(schema.get("primaryKey")).unwrap_or_default();
+
+    let mut specs = Vec::with_capacity(fields.len());
+    for field in fields {
+        let Some(name) = field.get("name").and_then(Value::as_str) else {
+            return fail_clierror!("{} field without a 'name': {field}", kind.as_str());
+        };
+        specs.push(frictionless_field(
+            name,
+            field,
+            primary_key.iter().any(|pk| pk == name),
+        ));
+    }
+
+    let mut foreign_keys = Vec::new();
+    for fk in schema
+        .get("foreignKeys")
+        .and_then(Value::as_array)
+        .map(Vec::as_slice)
+        .unwrap_or_default()
+    {
+        let fields = string_list(fk.get("fields")).unwrap_or_default();
+        let reference = fk.get("reference");
+        let ref_fields = string_list(reference.and_then(|r| r.get("fields"))).unwrap_or_default();
+        if fields.is_empty() || fields.len() != ref_fields.len() {
+            wwarn!(
+                "Skipping foreign key ({}): it must reference as many fields as it has.",
+                fields.join(", ")
+            );
+            continue;
+        }
+        let target = match reference
+            .and_then(|r| r.get("resource"))
+            .and_then(Value::as_str)
+        {
+            // an empty resource name is a self-reference
+            None | Some("") => Some(input_uri.to_string()),
+            Some(resource) => tables
+                .iter()
+                .find(|t| t.name.as_deref() == Some(resource))
+                .and_then(|t| t.paths.first().cloned())
+                // a bare table schema can only point at a data file directly
+                .or_else(|| {
+                    (kind == MetadataKind::TableSchema).then(|| resolve_uri(metadata_uri, resource))
+                }),
+        };
+        let Some(target) = target else {
+            return fail_clierror!(
+                "Foreign key ({}) references unknown resource {}.",
+                fields.join(", "),
+                reference
+                    .and_then(|r| r.get("resource"))
+                    .unwrap_or(&Value::Null)
+            );
+        };
+        foreign_keys.push(ForeignKey {
+            columns: fields,
+            target,
+            ref_columns: ref_fields,
+        });
+    }
+
+    Ok(assemble(
+        table.label(),
+        specs,
+        &primary_key,
+        foreign_keys,
+        string_list(schema.get("missingValues")).unwrap_or_else(|| vec![String::new()]),
+    ))
+}
+
+fn frictionless_field(name: &str, field: &Value, in_primary_key: bool) -> FieldSpec {
+    let constraints = field.get("constraints");
+    let constraint = |key: &str| constraints.and_then(|c| c.get(key));
+    let ftype = field
+        .get("type")
+        .and_then(Value::as_str)
+        .unwrap_or("string");
+    let format = field
+        .get("format")
+        .and_then(Value::as_str)
+        .unwrap_or("default");
+
+    let mut property = Map::new();
+    let json_type = match ftype {
+        "integer" | "year" => "integer",
+        "number" => "number",
+        "boolean" => {
+            let mut values = string_list(field.get("trueValues"))
+                .unwrap_or_else(|| Vec::from(FRICTIONLESS_TRUE_VALUES.map(str::to_string)));
+            values.extend(
+                string_list(field.get("falseValues"))
+                    .unwrap_or_else(|| Vec::from(FRICTIONLESS_FALSE_VALUES.map(str::to_string))),
+            );
+            property.insert("enum".to_string(), json!(values));
+            "string"
+        },
+        "date" | "datetime" | "time" => {
+            match (ftype, format) {
+                ("date", "default") => {
+                    property.insert("format".to_string(), json!("date"));
+                },
+                ("datetime", "default") => {
+                    property.insert("format".to_string(), json!("date-time"));
+                },
+                ("time", "default") => {
+                    property.insert("pattern".to_string(), json!(TIME_PATTERN));
+                },
+                (_, "any") => {},
+                _ => wwarn!(
+                    "Field '{name}': custom {ftype} format '{format}' is not checked by validate."
+                ),
+            }
+            "string"
+        },
+        "yearmonth" => {
+            property.insert("pattern".to_string(), json!(YEARMONTH_PATTERN));
+            "string"
+        },
+        "duration" => {
+            property.insert("format".to_string(), json!("duration"));
+            "string"
+        },
+        "string" => {
+            if matches!(format, "email" | "uri" | "uuid") {
+                property.insert("format".to_string(), json!(format));
+            }
+            "string"
+        },
+        // any, object, array, geopoint, geojson: presence/nullability only
+        _ => "string",
+    };
+
+    if let Some(description) = field.get("description") {
+        property.insert("description".to_string(), description.clone());
+    }
+
+    let numeric = matches!(json_type, "integer" | "number");
+    for keyword in ["minimum", "maximum", "exclusiveMinimum", "exclusiveMaximum"] {
+        if let Some(bound) = constraint(keyword) {
+            if numeric {
+                property.insert(keyword.to_string(), coerce_number(bound));
+            } else {
+                wwarn!("Field '{name}': '{keyword}' constraint on a {ftype} is not checked.");
+            }
+        }
+    }
+    if ftype == "string" {
+        for keyword in ["minLength", "maxLength"] {
+            if let Some(len) = constraint(keyword) {
+                property.insert(keyword.to_string(), len.clone());
+            }
+        }
+        if let Some(pattern) = constraint("pattern").and_then(Value::as_str) {
+            // Table Schema patterns must match the whole value
+            property.insert("pattern".to_string(), json!(format!("^(?:{pattern})$")));
+        }
+    }
+    if let Some(Value::Array(values)) = constraint("enum")
+        && ftype != "boolean"
+    {
+        let values: Vec<Value> = if numeric {
+            values.iter().map(coerce_number).collect()
+        } else {
+            values.clone()
+        };
+        property.insert("enum".to_string(), Value::Array(values));
+    }
+
+    FieldSpec {
+        key: name.to_string(),
+        json_type,
+        property,
+        required: in_primary_key
+            || constraint("required")
+                .and_then(Value::as_bool)
+                .unwrap_or(false),
+        unique: constraint("unique")
+            .and_then(Value::as_bool)
+            .unwrap_or(false),
+        missing_values: string_list(field.get("missingValues")),
+    }
+}
+
+/// Table Schema v1 allows numeric constraints to be given as strings.
+fn coerce_number(value: &Value) -> Value {
+    if let Value::String(s) = value
+        && let Ok(n) = s.parse::<serde_json::Number>()
+    {
+        Value::Number(n)
+    } else {
+        value.clone()
+    }
+}
+
+fn csvw_table(
+    table: &TableRef,
+    tables: &[TableRef],
+    metadata_uri: &str,
+    input_uri: &str,
+) -> CliResult<TableMetadata> {
+    let schema = &table.schema;
+    let Some(columns) = schema.get("columns").and_then(Value::as_array) else {
+        return fail_clierror!(
+            "CSVW tableSchema for {} has no 'columns' array.",
+            table.label()
+        );
+    };
+    let primary_key: Vec<String> = string_list(schema.get("primaryKey"))
+        .unwrap_or_default()
+        .iter()
+        .map(|name| csvw_header_for(schema, name))
+        .collect();
+    let default_missing = string_list(schema.get("null")).unwrap_or_else(|| vec![String::new()]);
+
+    let mut specs = Vec::with_capacity(columns.len());
+    for column in columns {
+        if column
+            .get("virtual")
+            .and_then(Value::as_bool)
+            .unwrap_or(false)
+        {
+            continue;
+        }
+        let Some(key) = csvw_column_header(column) else {
+            return fail_clierror!("CSVW column without a 'name' or 'titles': {column}");
+        };
+        let in_primary_key = primary_key.contains(&key);
+        specs.push(csvw_column(key, column, in_primary_key));
+    }
+
+    let mut foreign_keys = Vec::new();
+    for fk in schema
+        .get("foreignKeys")
+        .and_then(Value::as_array)
+        .map(Vec::as_slice)
+        .unwrap_or_default()
+    {
+        let columns = string_list(fk.get("columnReference")).unwrap_or_default();
+        let reference = fk.get("reference");
+        let ref_columns =
+            string_list(reference.and_then(|r| r.get("columnReference"))).unwrap_or_default();
+        if columns.is_empty() || columns.len() != ref_columns.len() {
+            wwarn!(
+                "Skipping foreign key ({}): it must reference as many columns as it has.",
+                columns.join(", ")
+            );
+            continue;
+        }
+        let Some(resource) = reference
+            .and_then(|r| r.get("resource"))
+            .and_then(Value::as_str)
+        else {
+            wwarn!(
+                "Skipping foreign key ({}): only 'resource' references are supported.",
+                columns.join(", ")
+            );
+            continue;
+        };
+        let target = resolve_uri(metadata_uri, resource);
+        // the referenced columns are names; the lookup needs the target's headers
+        let target_schema = tables
+            .iter()
+            .find(|t| t.paths.contains(&target))
+            .map(|t| &t.schema);
+        let ref_headers = ref_columns
+            .iter()
+            .map(|name| target_schema.map_or_else(|| name.clone(), |s| csvw_header_for(s, name)))
+            .collect();
+        let target = if target == table.paths.first().map_or("", String::as_str) {
+            input_uri.to_string()
+        } else {
+            target
+        };
+        foreign_keys.push(ForeignKey {
+            columns: columns
+                .iter()
+                .map(|name| csvw_header_for(schema, name))
+                .collect(),
+            target,
+            ref_columns: ref_headers,
+        });
+    }
+
+    Ok(assemble(
+        table.label(),
+        specs,
+        &primary_key,
+        foreign_keys,
+        default_missing,
+    ))
+}
+
+/// CSVW columns are matched to CSV headers by their first title, falling back to the name.
+fn csvw_column_header(column: &Value) -> Option<String> {
+    let title = match column.get("titles") {
+        Some(Value::String(s)) => Some(s.clone()),
+        Some(Value::Array(arr)) => arr.first().and_then(Value::as_str).map(str::to_string),
+        // language map, e.g. {"en": ["Country"]}
+        Some(Value::Object(langs)) => langs.values().next().and_then(|v| match v {
+            Value::String(s) => Some(s.clone()),
+            Value::Array(arr) => arr.first().and_then(Value::as_str).map(str::to_string),
+            _ => None,
+        }),
+        _ => None,
+    };
+    title.or_else(|| {
+        column
+            .get("name")
+            .and_then(Value::as_str)
+            .map(str::to_string)
+    })
+}
+
+/// Map a CSVW column name (as used by keys) to its CSV header.
+fn csvw_header_for(schema: &Value, name: &str) -> String {
+    schema
+        .get("columns")
+        .and_then(Value::as_array)
+        .and_then(|cols| {
+            cols.iter()
+                .find(|c| c.get("name").and_then(Value::as_str) == Some(name))
+        })
+        .and_then(csvw_column_header)
+        .unwrap_or_else(|| name.to_string())
+}
+
+fn csvw_column(key: String, column: &Value, in_primary_key: bool) -> FieldSpec {
+    let datatype = column.get("datatype");
+    let facet = |name: &str| datatype.and_then(|d| d.get(name));
+    let base = match datatype {
+        Some(Value::String(s)) => s.as_str(),
+        Some(Value::Object(d)) => d.get("base").and_then(Value::as_str).unwrap_or("string"),
+        _ => "string",
+    };
+    let format = facet("format").and_then(Value::as_str);
+
+    let mut property = Map::new();
+    let json_type = match base {
+        "integer" | "int" | "long" | "short" | "byte" | "nonNegativeInteger"
+        | "positiveInteger" | "unsignedLong" | "unsignedInt" | "unsignedShort" | "unsignedByte"
+        | "nonPositiveInteger" | "negativeInteger" => {
+            let implied = match base {
+                "nonNegativeInteger" | "unsignedLong" | "unsignedInt" | "unsignedShort"
+                | "unsignedByte" => Some(("minimum", 0)),
+                "positiveInteger" => Some(("minimum", 1)),
+                "nonPositiveInteger" => Some(("maximum", 0)),
+                "negativeInteger" => Some(("maximum", -1)),
+                _ => None,
+            };
+            if let Some((keyword, bound)) = implied {
+                property.insert(keyword.to_string(), json!(bound));
+            }
+            "integer"
+        },
+        "decimal" | "double" | "float" | "number" => "number",
+        "boolean" => {
+            // a boolean format is "true-value|false-value", e.g. "Y|N"
+            let values: Vec<&str> = format
+                .and_then(|f| f.split_once('|'))
+                .map_or_else(|| vec!["true", "false", "1", "0"], |(t, f)| vec![t, f]);
+            property.insert("enum".to_string(), json!(values));
+            "string"
+        },
+        "date" | "dateTime" | "datetime" | "dateTimeStamp" | "time" => {
+            match (base, format) {
+                ("date", None) => {
+                    property.insert("format".to_string(), json!("date"));
+                },
+                ("time", None) => {
+                    property.insert("pattern".to_string(), json!(TIME_PATTERN));
+                },
+                (_, None) => {
+                    property.insert("format".to_string(), json!("date-time"));
+                },
+                (_, Some(format)) => wwarn!(
+                    "Column '{key}': custom {base} format '{format}' is not checked by validate."
+                ),
+            }
+            "string"
+        },
+        "duration" => {
+            property.insert("format".to_string(), json!("duration"));
+            "string"
+        },
+        "anyURI" => {
+            property.insert("format".to_string(), json!("uri"));
+            "string"
+        },
+        _ => {
+            // for string datatypes, format is a regular expression
+            if let Some(format) = format {
+                property.insert("pattern".to_string(), json!(format!("^(?:{format})$")));
+            }
+            "string"
+        },
+    };
+
+    if let Some(description) = column.get("dc:description") {
+        property.insert("description".to_string(), description.clone());
+    }
+
+    let numeric = matches!(json_type, "integer" | "number");
+    for (facet_name, keyword) in [
+        ("minimum", "minimum"),
+        ("minInclusive", "minimum"),
+        ("maximum", "maximum"),
+        ("maxInclusive", "maximum"),
+        ("minExclusive", "exclusiveMinimum"),
+        ("maxExclusive", "exclusiveMaximum"),
+    ] {
+        if let Some(bound) = facet(facet_name) {
+            if numeric {
+                property.insert(keyword.to_string(), coerce_number(bound));
+            } else {
+                wwarn!("Column '{key}': '{facet_name}' on a {base} is not checked.");
+            }
+        }
+    }
+    if json_type == "string" {
+        for (facet_name, keyword) in [
+            ("minLength", "minLength"),
+            ("maxLength", "maxLength"),
+            ("length", "minLength"),
+            ("length", "maxLength"),
+        ] {
+            if let Some(len) = facet(facet_name) {
+                property.insert(keyword.to_string(), len.clone());
+            }
+        }
+    }
+
+    FieldSpec {
+        key,
+        json_type,
+        property,
+        required: in_primary_key
+            || column
+                .get("required")
+                .and_then(Value::as_bool)
+                .unwrap_or(false),
+        unique: false,
+        missing_values: string_list(column.get("null")),
+    }
+}
+
+/// Build the JSON Schema for a table from its translated fields and keys.
+fn assemble(
+    title: String,
+    specs: Vec<FieldSpec>,
+    primary_key: &[String],
+    foreign_keys: Vec<ForeignKey>,
+    default_missing_values: Vec<String>,
+) -> TableMetadata {
+    let mut properties = Map::with_capacity(specs.len());
+    let mut missing_values = HashMap::new();
+    let mut unique_rules = Vec::new();
+
+    for spec in specs {
+        let mut property = spec.property;
+        let nullable = !spec.required;
+        if nullable {
+            property.insert("type".to_string(), json!([spec.json_type, "null"]));
+            if let Some(Value::Array(values)) = property.get_mut("enum") {
+                values.push(Value::Null);
+            }
+        } else {
+            property.insert("type".to_string(), json!(spec.json_type));
+        }
+        // single-column foreign keys are field-level lookups
+        if let Some(fk) = foreign_keys
+            .iter()
+            .find(|fk| fk.columns.len() == 1 && fk.columns[0] == spec.key)
+        {
+            let dynenum = format!("{}|{}", fk.target, fk.ref_columns[0]);
+            if nullable {
+                // nulls are exempt from the lookup
+                property.insert("if".to_string(), json!({"type": "null"}));
+                property.insert("else".to_string(), json!({"dynamicEnum": dynenum}));
+            } else {
+                property.insert("dynamicEnum".to_string(), json!(dynenum));
+            }
+        }
+        if spec.unique && !primary_key.contains(&spec.key) {
+            // nulls are exempt from uniqueness
+            unique_rules.push(json!({
+                "if": {"properties": {spec.key.as_str(): {"type": "null"}}},
+                "else": {"uniqueCombinedWith": [spec.key.as_str()]}
+            }));
+        }
+        if let Some(mv) = spec.missing_values {
+            missing_values.insert(spec.key.clone(), mv);
+        }
+        properties.insert(spec.key, Value::Object(property));
+    }
+
+    let mut schema = Map::new();
+    schema.insert("$schema".to_string(), json!(JSON_SCHEMA_DRAFT));
+    schema.insert("title".to_string(), json!(title));
+    schema.insert("type".to_string(), json!("object"));
+    schema.insert("properties".to_string(), Value::Object(properties));
+    if !primary_key.is_empty() {
+        schema.insert("uniqueCombinedWith".to_string(), json!(primary_key));
+    }
+    if !unique_rules.is_empty() {
+        schema.insert("allOf".to_string(), Value::Array(unique_rules));
+    }
+    // composite foreign keys are row-level relations
+    let relations: Vec<Value> = foreign_keys
+        .iter()
+        .filter(|fk| fk.columns.len() > 1)
+        .map(|fk| {
+            json!({
+                "columns": fk.columns,
+                "reference": {"uri": fk.target, "columns": fk.ref_columns}
+            })
+        })
+        .collect();
+    if !relations.is_empty() {
+        schema.insert("foreignKeys".to_string(), Value::Array(relations));
+    }
+
+    TableMetadata {
+        schema: Value::Object(schema),
+        missing_values,
+        default_missing_values,
+    }
+}
@@ -975,6 +975,7 @@ use plotly::{
         PatternShape, TextPosition, TickMode, Title,
     },
     funnel::Connector as FunnelConnector,
+    histogram::{Bins as HistogramBins, HistFunc},
     indicator::{Delta, Gauge, GaugeAxis, Mode as IndicatorMode, Number},
     layout::{
         Animation, AnimationMode, AnimationOptions, Annotation, Axis, AxisType, CategoryOrder,
@@ -9401,6 +9402,27 @@ fn is_strict_nesting(tuples: &HashMap<Vec<String>, f64>, depth: usize) -> bool {
 fn build_histogram(args: &Args) -> CliResult<(Box<dyn Trace>, String)> {
     let (mut rdr, headers, nh) = reader_and_headers(args)?;
     let x_idx = resolve_one(args.flag_x.as_ref(), &headers, nh, "x")?;
+    let label = col_label(&headers, x_idx, nh);
+
+    // reuse the equal-width bins of a current `frequency --bins --frequency-jsonl` cache
+    // instead of re-reading the column, unless --bins asks for a particular bin count
+    if args.flag_bins.is_none()
+        && let Some(input) = args.arg_input.as_deref()
+        && let Some(cached) = crate::cmd::frequency::read_bins_cache_histogram(
+            std::path::Path::new(input),
+            &label,
+            nh,
+            args.flag_delimiter,
+        )
+    {
+        #[allow(clippy::cast_precision_loss)]
+        let counts: Vec<f64> = cached.counts.iter().map(|&c| c as f64).collect();
+        let hist = Histogram::new_xy(cached.midpoints, counts)
+            .hist_func(HistFunc::Sum)
+            .x_bins(HistogramBins::new(cached.start, cached.end, cached.width))
+            .name(escape_hover(&label));
+        return Ok((hist, label));
+    }
 
     let mut values: Vec<f64> = Vec::new();
     let mut record = csv::ByteRecord::new();
@@ -9413,7 +9435,6 @@ fn build_histogram(args: &Args) -> CliResult<(Box<dyn Trace>, String)> {
         return fail_clierror!("No numeric values found in the --x column for the histogram.");
     }
 
-    let label = col_label(&headers, x_idx, nh);
     let mut hist = Histogram::new(values).name(escape_hover(&label));
     if let Some(bins) = args.flag_bins {
         hist = hist.n_bins_x(bins);
@@ -27886,6 +27907,10 @@ impl<'a> SmartCtx<'a> {
             arg_input:            Some(prep.stats_input.clone()),
             flag_memcheck:        false,
             flag_output:          None,
+            flag_format:          None,
+            flag_compare:         None,
+            flag_null_threshold:  0.0,
+            flag_null_ratios:     false,
         };
 
         // Keep the spinner live: get_stats_records reuses the cache or runs `qsv stats` as a
@@ -0,0 +1,126 @@
+//! Locale-aware string comparison, shared by `sort`, `sortcheck`, `dedup` and `frequency`.
+//!
+//! qsv compares strings byte-wise by default, which puts accented letters after `z` and
+//! orders non-Latin scripts by code point. `--collation <locale>` compares them with the
+//! Unicode Collation Algorithm as tailored for the locale (using ICU4X's collation data),
+//! e.g. `de`, `sv`, `es-u-co-trad` or `und` for the root collation. `--normalize` converts
+//! values to NFC or NFKC before comparing them, so e.g. the ligature `ﬁ` equals `fi` under
+//! NFKC.
+
+use std::{borrow::Cow, cmp::Ordering, str::FromStr};
+
+use icu_collator::{
+    Collator, CollatorBorrowed, CollatorPreferences,
+    options::{CollatorOptions, Strength},
+};
+use icu_locale_core::Locale;
+use unicode_normalization::{UnicodeNormalization, is_nfc, is_nfkc};
+
+use crate::{CliResult, util};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Normalization {
+    Nfc,
+    Nfkc,
+}
+
+#[derive(Debug)]
+pub struct Collation {
+    collator:      Option<CollatorBorrowed<'static>>,
+    normalization: Option<Normalization>,
+    /// only used without a collator, which folds case itself
+    ignore_case:   bool,
+}
+
+impl Collation {
+    /// The collation of the `--collation` locale and `--normalize` form, or None if neither
+    /// is set. With `ignore_case`, strings that only differ in case compare equal.
+    pub fn new(
+        locale: Option<&str>,
+        normalize: Option<&str>,
+        ignore_case: bool,
+    ) -> CliResult<Option<Self>> {
+        if locale.is_none() && normalize.is_none() {
+            return Ok(None);
+        }
+
+        let normalization = match normalize.map(str::to_ascii_lowercase).as_deref() {
+            None | Some("none") => None,
+            Some("nfc") => Some(Normalization::Nfc),
+            Some("nfkc") => Some(Normalization::Nfkc),
+            Some(other) => {
+                return fail_incorrectusage_clierror!(
+                    "Invalid --normalize form \"{other}\". Valid forms are nfc, nfkc and none."
+                );
+            },
+        };
+
+        let collator = match locale {
+            Some(locale) => {
+                let Ok(parsed) = Locale::from_str(locale) else {
+                    return fail_incorrectusage_clierror!(
+                        "Invalid --collation locale \"{locale}\". Use a BCP 47 locale such as en, \
+                         de-AT or sv."
+                    );
+                };
+                let mut options = CollatorOptions::default();
+                if ignore_case {
+                    // secondary strength compares base letters and accents, but not case
+                    options.strength = Some(Strength::Secondary);
+                }
+                match Collator::try_new(CollatorPreferences::from(&parsed), options) {
+                    Ok(collator) => Some(collator),
+                    Err(e) => {
+                        return fail_clierror!("Cannot load the \"{locale}\" collation: {e}");
+                    },
+                }
+            },
+            None => None,
+        };
+
+        Ok(Some(Self {
+            collator,
+            normalization,
+            ignore_case,
+        }))
+    }
+
+    fn normalize<'a>(&self, s: &'a str) -> Cow<'a, str> {
+        match self.normalization {
+            Some(Normalization::Nfc) if !is_nfc(s) => Cow::Owned(s.nfc().collect()),
+            Some(Normalization::Nfkc) if !is_nfkc(s) => Cow::Owned(s.nfkc().collect()),
+            _ => Cow::Borrowed(s),
+        }
+    }
+
+    /// Compare two fields. Invalid UTF-8 is compared with its invalid bytes replaced.
+    pub fn compare(&self, a: &[u8], b: &[u8]) -> Ordering {
+        let a = util::bytes_to_cow_str(a);
+        let b = util::bytes_to_cow_str(b);
+        let (a, b) = (self.normalize(&a), self.normalize(&b));
+        match &self.collator {
+            Some(collator) => collator.compare(&a, &b),
+            None if self.ignore_case => a.to_lowercase().cmp(&b.to_lowercase()),
+            None => a.cmp(&b),
+        }
+    }
+
+    /// Compare two rows' selected fields, field by field.
+    pub fn iter_cmp<'a, L, R>(&self, mut a: L, mut b: R) -> Ordering
+    where
+        L: Iterator<Item = &'a [u8]>,
+        R: Iterator<Item = &'a [u8]>,
+    {
+        loop {
+            match (a.next(), b.next()) {
+                (None, None) => return Ordering::Equal,
+                (None, _) => return Ordering::Less,
+                (_, None) => return Ordering::Greater,
+                (Some(x), Some(y)) => match self.compare(x, y) {
+                    Ordering::Equal => (),
+                    non_eq => return non_eq,
+                },
+            }
+        }
+    }
+}
@@ -33,6 +33,7 @@ rust_i18n::i18n!("src/cmd/locales", fallback = "en");
 
 mod clitypes;
 mod cmd;
+mod collation;
 mod config;
 mod diskcache;
 mod generators_common;
@@ -45,6 +46,9 @@ mod mcp_skills_gen;
 mod minijinja_filters;
 mod odhtcache;
 mod select;
+#[cfg(any(feature = "apply", feature = "polars"))]
+mod similarity;
+mod statscatalog;
 mod util;
 
 const USAGE_COMMON: &str = r#"
@@ -183,6 +187,10 @@ fn main() -> QsvExitCode {
     #[cfg(all(feature = "lens", feature = "feature_capable"))]
     enabled_commands.push_str("    lens        View a CSV file interactively\n");
 
+    #[cfg(all(feature = "apply", feature = "feature_capable"))]
+    enabled_commands
+        .push_str("    link        Link records of the same entity with weighted fuzzy matching\n");
+
     #[cfg(all(feature = "luau", feature = "feature_capable"))]
     enabled_commands.push_str("    luau        Execute Luau script on CSV data\n");
 
@@ -191,7 +199,8 @@ fn main() -> QsvExitCode {
 
     enabled_commands.push_str(
         "    moarstats   Add \"moar\" statistics to existing stats CSV
-    partition   Partition CSV data based on a column value\n",
+    partition   Partition CSV data based on a column value
+    patch       Apply a diff result to a CSV\n",
     );
 
     #[cfg(all(feature = "polars", feature = "feature_capable"))]
@@ -496,11 +505,14 @@ enum Command {
     Jsonl,
     #[cfg(all(feature = "lens", feature = "feature_capable"))]
     Lens,
+    #[cfg(all(feature = "apply", feature = "feature_capable"))]
+    Link,
     #[cfg(all(feature = "luau", feature = "feature_capable"))]
     Luau,
     #[cfg(feature = "mcp")]
     Log,
     Partition,
+    Patch,
     #[cfg(all(feature = "polars", feature = "feature_capable"))]
     PivotP,
     Pragmastat,
@@ -625,11 +637,14 @@ impl Command {
             Command::Jsonl => cmd::jsonl::run(argv),
             #[cfg(all(feature = "lens", feature = "feature_capable"))]
             Command::Lens => cmd::lens::run(argv),
+            #[cfg(all(feature = "apply", feature = "feature_capable"))]
+            Command::Link => cmd::link::run(argv),
             #[cfg(all(feature = "luau", feature = "feature_capable"))]
             Command::Luau => cmd::luau::run(argv),
             #[cfg(feature = "mcp")]
             Command::Log => cmd::log::run(argv),
             Command::Partition => cmd::partition::run(argv),
+            Command::Patch => cmd::patch::run(argv),
             #[cfg(all(feature = "polars", feature = "feature_capable"))]
             Command::PivotP => cmd::pivotp::run(argv),
             Command::Pragmastat => cmd::pragmastat::run(argv),
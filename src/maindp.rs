@@ -56,6 +56,7 @@ static GLOBAL: tikv_jemallocator::Jemalloc = tikv_jemallocator::Jemalloc;
 
 mod clitypes;
 mod cmd;
+mod collation;
 mod config;
 mod diskcache;
 mod index;
@@ -64,6 +65,9 @@ mod lookup;
 mod minijinja_filters;
 mod odhtcache;
 mod select;
+#[cfg(any(feature = "apply", feature = "polars"))]
+mod similarity;
+mod statscatalog;
 mod util;
 
 static USAGE: &str = r#"
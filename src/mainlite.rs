@@ -49,6 +49,7 @@ static COMMAND_LIST: &str = r#"
     jsonl       Convert newline-delimited JSON files to CSV
     moarstats   Add "moar" statistics to existing stats CSV
     partition   Partition CSV data based on a column value
+    patch       Apply a diff result to a CSV
     pragmastat  Pragmatic statistical toolkit
     pro         Interact with the qsv pro API
     pseudo      Pseudonymise the values of a column
@@ -75,10 +76,14 @@ static COMMAND_LIST: &str = r#"
 
 mod clitypes;
 mod cmd;
+mod collation;
 mod config;
 mod index;
 mod odhtcache;
 mod select;
+#[cfg(any(feature = "apply", feature = "polars"))]
+mod similarity;
+mod statscatalog;
 mod util;
 
 static USAGE: &str = r#"
@@ -264,6 +269,7 @@ enum Command {
     Json,
     Jsonl,
     Partition,
+    Patch,
     Pragmastat,
     Pro,
     Pseudo,
@@ -336,6 +342,7 @@ impl Command {
             Command::Json => cmd::json::run(argv),
             Command::Jsonl => cmd::jsonl::run(argv),
             Command::Partition => cmd::partition::run(argv),
+            Command::Patch => cmd::patch::run(argv),
             Command::Pragmastat => cmd::pragmastat::run(argv),
             Command::Pro => cmd::pro::run(argv),
             Command::Pseudo => cmd::pseudo::run(argv),
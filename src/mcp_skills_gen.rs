@@ -1261,6 +1261,7 @@ pub fn generate_mcp_skills() -> CliResult<()> {
         "jsonl",
         "moarstats",
         "partition",
+        "patch",
         "pivotp",
         "pragmastat",
         "pseudo",
@@ -0,0 +1,64 @@
+//! String similarity metrics, shared by `apply operations`, `dedup --fuzzy` and
+//! `joinp --fuzzy` so their names and semantics can't drift apart.
+
+use std::str::FromStr;
+
+use eudex::Hash;
+use strsim::{jaro_winkler, normalized_damerau_levenshtein, sorensen_dice};
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Metric {
+    /// `simjw`: Jaro-Winkler similarity
+    JaroWinkler,
+    /// `simdln`: normalized Damerau-Levenshtein similarity
+    DamerauLevenshtein,
+    /// `simsd`: Sørensen-Dice similarity
+    SorensenDice,
+    /// `eudex`: sounds alike
+    Eudex,
+}
+
+impl FromStr for Metric {
+    type Err = String;
+
+    /// Parse a metric by its `apply operations` name or its full name, ignoring case.
+    /// The error lists the valid metrics, for the caller's invalid metric message.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "simjw" | "jaro-winkler" => Ok(Metric::JaroWinkler),
+            "simdln" | "damerau-levenshtein" => Ok(Metric::DamerauLevenshtein),
+            "simsd" | "sorensen-dice" => Ok(Metric::SorensenDice),
+            "eudex" => Ok(Metric::Eudex),
+            _ => Err(
+                "Valid metrics are simjw (Jaro-Winkler), simdln (normalized Damerau-Levenshtein), \
+                 simsd (Sørensen-Dice) and eudex (sounds alike)."
+                    .to_string(),
+            ),
+        }
+    }
+}
+
+impl Metric {
+    /// The similarity of `a` and `b`, between 0.0 and 1.0.
+    /// Eudex is 1.0 when they sound alike, and 0.0 otherwise.
+    pub fn score(self, a: &str, b: &str) -> f64 {
+        match self {
+            Metric::JaroWinkler => jaro_winkler(a, b),
+            Metric::DamerauLevenshtein => normalized_damerau_levenshtein(a, b),
+            Metric::SorensenDice => sorensen_dice(a, b),
+            Metric::Eudex => {
+                if sounds_alike(Hash::new(a), Hash::new(b)) {
+                    1.0
+                } else {
+                    0.0
+                }
+            },
+        }
+    }
+}
+
+/// Do the strings with these eudex hashes sound alike?
+/// Hashing once and comparing many times is cheaper than calling [`Metric::score`].
+pub fn sounds_alike(a: Hash, b: Hash) -> bool {
+    (a - b).similar()
+}
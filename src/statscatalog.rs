@@ -0,0 +1,357 @@
+//! A typed, read-only catalog over the `stats` cache.
+//!
+//! `stats` leaves a `<input>.stats.csv.data.jsonl` cache (one `StatsData` per column) and a
+//! `<input>.stats.csv.json` metadata sidecar beside its input. Commands that only want to
+//! *plan* with those numbers - pick a Polars schema instead of inferring one, skip a sort the
+//! cache proves unnecessary - go through [`StatsCatalog`] rather than picking fields out of
+//! `StatsData` ad hoc.
+//!
+//! Loading is strictly opportunistic: [`StatsCatalog::load`] never runs `stats`, and returns
+//! `None` whenever the cache is absent, stale, built with different parsing options, written by
+//! another qsv version, or disabled with `QSV_STATSCACHE_MODE=none`. Every decision made from a
+//! catalog must therefore have a correct fallback that does the full work.
+
+use std::path::Path;
+
+use crate::{cmd::stats::StatsData, config::Delimiter, util};
+
+/// A column's inferred type, as recorded by `stats`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ColumnType {
+    String,
+    Integer,
+    Float,
+    Boolean,
+    Date,
+    DateTime,
+    // every value is empty
+    Null,
+}
+
+impl ColumnType {
+    fn from_stats_type(r#type: &str) -> Self {
+        match r#type {
+            "Integer" => ColumnType::Integer,
+            "Float" => ColumnType::Float,
+            "Boolean" => ColumnType::Boolean,
+            "Date" => ColumnType::Date,
+            "DateTime" => ColumnType::DateTime,
+            "NULL" => ColumnType::Null,
+            _ => ColumnType::String,
+        }
+    }
+}
+
+/// How wide the numeric types of a derived Polars schema should be.
+#[cfg(feature = "polars")]
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum NumericWidth {
+    /// The smallest type that holds the column's min and max (e.g. `UInt8`, `Float32`).
+    /// Best for memory, but two files can disagree on a shared column's type.
+    Narrowest,
+    /// Always `Int64`/`Float64`, so the same column in two files gets the same type -
+    /// which join keys need.
+    Widest,
+}
+
+pub struct StatsCatalog {
+    stats:                 Vec<StatsData>,
+    // from the metadata sidecar
+    record_count:          Option<u64>,
+    cardinality_is_approx: bool,
+}
+
+impl StatsCatalog {
+    /// Load the catalog for `input_path` from a CURRENT stats cache, without ever generating
+    /// one. See [`util::get_stats_records_readonly`] for when a cache is considered current.
+    ///
+    /// The catalog is versioned with qsv itself: the cache's fields and their meaning may
+    /// change between releases, so a cache whose metadata sidecar was written by another
+    /// qsv version is ignored.
+    pub fn load(
+        input_path: Option<&str>,
+        no_headers: bool,
+        delimiter: Option<Delimiter>,
+    ) -> Option<Self> {
+        let stats = util::get_stats_records_readonly(input_path, no_headers, delimiter)?;
+        let metadata: serde_json::Value = input_path
+            .and_then(|p| Path::new(p).canonicalize().ok())
+            .and_then(|p| std::fs::read_to_string(p.with_extension("stats.csv.json")).ok())
+            .and_then(|s| serde_json::from_str(&s).ok())?;
+        let qsv_version = metadata.get("qsv_version").and_then(|v| v.as_str());
+        if qsv_version != Some(env!("CARGO_PKG_VERSION")) {
+            log::debug!(
+                "stats catalog for {input_path:?} ignored: cache written by qsv {}",
+                qsv_version.unwrap_or("unknown")
+            );
+            return None;
+        }
+        let catalog = Self {
+            stats,
+            record_count: metadata
+                .get("record_count")
+                .and_then(serde_json::Value::as_u64),
+            cardinality_is_approx: util::stats_cache_cardinality_is_approx(input_path),
+        };
+        log::debug!(
+            "stats catalog loaded for {input_path:?} ({} columns)",
+            catalog.column_count()
+        );
+        Some(catalog)
+    }
+
+    /// Wrap stats records that were already loaded (e.g. by `util::get_stats_records`).
+    /// Such a catalog has no metadata, so it never proves uniqueness.
+    #[cfg_attr(not(feature = "polars"), allow(dead_code))]
+    pub fn from_stats(stats: Vec<StatsData>) -> Self {
+        Self {
+            stats,
+            record_count: None,
+            cardinality_is_approx: true,
+        }
+    }
+
+    /// The number of columns.
+    pub fn column_count(&self) -> usize {
+        self.stats.len()
+    }
+
+    /// The number of records, from the metadata sidecar.
+    pub fn record_count(&self) -> Option<u64> {
+        self.record_count
+    }
+
+    /// The column's inferred type.
+    #[cfg_attr(not(feature = "polars"), allow(dead_code))]
+    pub fn column_type(&self, idx: usize) -> Option<ColumnType> {
+        self.stats
+            .get(idx)
+            .map(|s| ColumnType::from_stats_type(&s.r#type))
+    }
+
+    /// The number of distinct values of the column, counting empty cells once.
+    /// Approximate when [`Self::cardinality_is_approx`].
+    pub fn cardinality(&self, idx: usize) -> Option<u64> {
+        self.stats.get(idx).map(|s| s.cardinality)
+    }
+
+    /// Were the cardinalities estimated (`stats --cardinality-method approx`)?
+    pub const fn cardinality_is_approx(&self) -> bool {
+        self.cardinality_is_approx
+    }
+
+    /// The number of empty cells of the column.
+    pub fn null_count(&self, idx: usize) -> Option<u64> {
+        self.stats.get(idx).map(|s| s.nullcount)
+    }
+
+    /// The column's smallest value, as formatted by `stats`. None for all-empty columns.
+    #[cfg_attr(not(feature = "polars"), allow(dead_code))]
+    pub fn min(&self, idx: usize) -> Option<&str> {
+        self.stats.get(idx)?.min.as_deref()
+    }
+
+    /// The column's largest value, as formatted by `stats`. None for all-empty columns.
+    #[cfg_attr(not(feature = "polars"), allow(dead_code))]
+    pub fn max(&self, idx: usize) -> Option<&str> {
+        self.stats.get(idx)?.max.as_deref()
+    }
+
+    /// Does the cache prove the single selected column is in ascending order under the
+    /// caller's comparator? See [`util::stats_cache_proves_ascending`] for the exact rules.
+    pub fn proves_ascending(&self, sel: &[usize], numeric: bool, ascii_only: bool) -> bool {
+        util::stats_cache_proves_ascending(sel, &self.stats, numeric, ascii_only)
+    }
+
+    /// Does the cache prove that the single selected column has no duplicate values?
+    /// Needs an exact cardinality and the record count from the metadata sidecar.
+    pub fn proves_unique(&self, sel: &[usize]) -> bool {
+        if sel.len() != 1 || self.cardinality_is_approx() {
+            return false;
+        }
+        let (Some(cardinality), Some(null_count), Some(record_count)) = (
+            self.cardinality(sel[0]),
+            self.null_count(sel[0]),
+            self.record_count(),
+        ) else {
+            return false;
+        };
+        // empty cells are counted once by cardinality, but are all equal to each other
+        null_count == 0 && cardinality == record_count
+    }
+
+    /// The fraction of the selected column's values that are empty.
+    /// Needs the record count from the metadata sidecar.
+    #[allow(clippy::cast_precision_loss)]
+    pub fn null_ratio(&self, idx: usize) -> Option<f64> {
+        let (null_count, record_count) = (self.null_count(idx)?, self.record_count()?);
+        (record_count > 0).then(|| null_count as f64 / record_count as f64)
+    }
+
+    /// Derive a Polars schema from the cached column types, so the CSV reader can skip
+    /// schema inference.
+    ///
+    /// Date and DateTime columns are only typed as such when `parse_dates` is set - the same
+    /// columns Polars' own inference would type with `try_parse_dates`. Otherwise they stay
+    /// strings.
+    #[cfg(feature = "polars")]
+    pub fn polars_schema(&self, width: NumericWidth, parse_dates: bool) -> polars::prelude::Schema {
+        use polars::datatypes::{DataType, TimeUnit};
+
+        // fetch the decimal scale from the QSV_POLARS_DECIMAL_SCALE env var
+        let scale = std::env::var("QSV_POLARS_DECIMAL_SCALE")
+            .ok()
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(5); // default scale is 5
+
+        let mut schema = polars::prelude::Schema::with_capacity(self.stats.len());
+        for (idx, stat) in self.stats.iter().enumerate() {
+            let min = self.min(idx).unwrap_or_default();
+            let max = self.max(idx).unwrap_or_default();
+            #[allow(clippy::match_same_arms)]
+            let datatype = match self.column_type(idx).unwrap_or(ColumnType::String) {
+                ColumnType::Integer if width == NumericWidth::Widest => DataType::Int64,
+                ColumnType::Integer => match (min.parse::<i64>(), max.parse::<i64>()) {
+                    // all values non-negative - use the smallest unsigned type that holds max
+                    (Ok(min_val), Ok(max_val)) if min_val >= 0 => {
+                        if max_val <= u8::MAX as i64 {
+                            DataType::UInt8
+                        } else if max_val <= u16::MAX as i64 {
+                            DataType::UInt16
+                        } else if max_val <= u32::MAX as i64 {
+                            DataType::UInt32
+                        } else {
+                            DataType::UInt64
+                        }
+                    },
+                    (Ok(min_val), Ok(max_val))
+                        if min_val >= i32::MIN as i64 && max_val <= i32::MAX as i64 =>
+                    {
+                        DataType::Int32
+                    },
+                    _ => DataType::Int64,
+                },
+                ColumnType::Float if width == NumericWidth::Widest => DataType::Float64,
+                ColumnType::Float => {
+                    let precision = stat.max_precision.unwrap_or(0);
+                    // As we use f64 internally, its unlikely that we have more
+                    // than 16 digits of precision, but we do this anyway to
+                    // document it as the polars engine does support it
+                    if precision > 16 {
+                        DataType::Decimal(precision as usize, scale)
+                    } else if precision > 7
+                        || min.parse::<f32>().is_err()
+                        || max.parse::<f32>().is_err()
+                    {
+                        DataType::Float64
+                    } else {
+                        DataType::Float32
+                    }
+                },
+                ColumnType::Boolean => DataType::Boolean,
+                ColumnType::Date if parse_dates => DataType::Date,
+                ColumnType::DateTime if parse_dates => {
+                    DataType::Datetime(TimeUnit::Milliseconds, None)
+                },
+                _ => DataType::String,
+            };
+            schema.insert(polars::prelude::PlSmallStr::from_str(&stat.field), datatype);
+        }
+        schema
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sd(r#type: &str, cardinality: u64, nullcount: u64) -> StatsData {
+        StatsData {
+            field: "col".to_string(),
+            r#type: r#type.to_string(),
+            sort_order: Some("Ascending".to_string()),
+            cardinality,
+            nullcount,
+            is_ascii: true,
+            ..Default::default()
+        }
+    }
+
+    fn catalog(stats: Vec<StatsData>, record_count: Option<u64>, approx: bool) -> StatsCatalog {
+        StatsCatalog {
+            stats,
+            record_count,
+            cardinality_is_approx: approx,
+        }
+    }
+
+    #[test]
+    fn proves_unique_needs_exact_cardinality_and_record_count() {
+        assert!(catalog(vec![sd("Integer", 10, 0)], Some(10), false).proves_unique(&[0]));
+        // a duplicate value
+        assert!(!catalog(vec![sd("Integer", 9, 0)], Some(10), false).proves_unique(&[0]));
+        // an approximate cardinality proves nothing
+        assert!(!catalog(vec![sd("Integer", 10, 0)], Some(10), true).proves_unique(&[0]));
+        // no record count
+        assert!(!catalog(vec![sd("Integer", 10, 0)], None, false).proves_unique(&[0]));
+        // a single empty cell still counts once, but two would not
+        assert!(!catalog(vec![sd("Integer", 10, 1)], Some(10), false).proves_unique(&[0]));
+        // multi-column selections are never proven
+        let two = vec![sd("Integer", 10, 0), sd("Integer", 10, 0)];
+        assert!(!catalog(two, Some(10), false).proves_unique(&[0, 1]));
+    }
+
+    #[test]
+    fn null_ratio_needs_record_count() {
+        let cat = catalog(vec![sd("Integer", 10, 3)], Some(12), false);
+        assert_eq!(cat.null_ratio(0), Some(0.25));
+        assert_eq!(cat.null_ratio(1), None);
+        assert_eq!(
+            catalog(vec![sd("Integer", 10, 3)], None, false).null_ratio(0),
+            None
+        );
+    }
+
+    #[test]
+    fn typed_accessors() {
+        let mut col = sd("Integer", 10, 2);
+        col.min = Some("-3".to_string());
+        col.max = Some("42".to_string());
+        let empty = sd("NULL", 1, 12);
+        let cat = catalog(vec![col, empty], Some(12), false);
+
+        assert_eq!(cat.column_count(), 2);
+        assert_eq!(cat.record_count(), Some(12));
+        assert_eq!(cat.column_type(0), Some(ColumnType::Integer));
+        assert_eq!(cat.column_type(1), Some(ColumnType::Null));
+        assert_eq!(cat.cardinality(0), Some(10));
+        assert!(!cat.cardinality_is_approx());
+        assert_eq!(cat.null_count(1), Some(12));
+        assert_eq!((cat.min(0), cat.max(0)), (Some("-3"), Some("42")));
+        assert_eq!((cat.min(1), cat.max(1)), (None, None));
+        // out of range
+        assert_eq!(cat.column_type(2), None);
+    }
+
+    #[cfg(feature = "polars")]
+    #[test]
+    fn polars_schema_widths_and_dates() {
+        use polars::datatypes::DataType;
+
+        let mut int_col = sd("Integer", 1, 0);
+        int_col.field = "n".to_string();
+        int_col.min = Some("0".to_string());
+        int_col.max = Some("200".to_string());
+        let mut date_col = sd("Date", 1, 0);
+        date_col.field = "d".to_string();
+        let cat = StatsCatalog::from_stats(vec![int_col, date_col]);
+
+        let narrow = cat.polars_schema(NumericWidth::Narrowest, false);
+        assert_eq!(narrow.get("n"), Some(&DataType::UInt8));
+        assert_eq!(narrow.get("d"), Some(&DataType::String));
+
+        let wide = cat.polars_schema(NumericWidth::Widest, true);
+        assert_eq!(wide.get("n"), Some(&DataType::Int64));
+        assert_eq!(wide.get("d"), Some(&DataType::Date));
+    }
+}
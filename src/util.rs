@@ -127,6 +127,10 @@ pub struct SchemaArgs {
     pub arg_input:            Option<String>,
     pub flag_memcheck:        bool,
     pub flag_output:          Option<String>,
+    pub flag_format:          Option<String>,
+    pub flag_compare:         Option<String>,
+    pub flag_null_threshold:  f64,
+    pub flag_null_ratios:     bool,
 }
 
 #[inline]
@@ -3649,7 +3653,9 @@ pub fn get_stats_records(
             // the dc:-resolved concrete path, so the stats subprocess reads the
             // real (materialized) file rather than re-resolving the "dc:" handle
             arg_input:                 Some(input_path.clone()),
+            arg_state:                 vec![],
             flag_select:               crate::select::SelectColumns::parse("").unwrap(),
+            flag_group_by:             None,
             flag_everything:           false,
             flag_typesonly:            false,
             flag_infer_boolean:        false,
@@ -3671,6 +3677,9 @@ pub fn get_stats_records(
             flag_dates_whitelist:      args.flag_dates_whitelist.to_string(),
             flag_prefer_dmy:           args.flag_prefer_dmy,
             flag_force:                args.flag_force,
+            flag_incremental:          false,
+            flag_save_state:           None,
+            flag_merge:                false,
             flag_jobs:                 Some(njobs(args.flag_jobs)),
             flag_stats_jsonl:          true,
             flag_jsonl:                false,
@@ -4031,9 +4040,15 @@ pub fn get_stats_records_readonly(
         // Parse leniently via serde_json::Value rather than strictly into StatsData:
         // an opportunistic cache may have been produced by a lean `stats --stats-jsonl`
         // run that omits non-Option fields like `cardinality`, which would make a strict
-        // StatsData deserialize fail. We only need a handful of fields to decide a sort
-        // short-circuit, so we extract just those (defaulting the rest).
+        // StatsData deserialize fail. We only need the handful of fields the stats catalog
+        // plans with (see `statscatalog`), so we extract just those (defaulting the rest).
         let v: serde_json::Value = serde_json::from_slice(curr_line.as_bytes()).ok()?;
+        // min/max are typed String in the cache, but accept a bare number too
+        let rendering = |key: &str| match v.get(key) {
+            Some(serde_json::Value::String(s)) => Some(s.clone()),
+            Some(serde_json::Value::Number(n)) => Some(n.to_string()),
+            _ => None,
+        };
         let stats = StatsData {
             field: v
                 .get("field")
@@ -4051,6 +4066,17 @@ pub fn get_stats_records_readonly(
                 .map(ToString::to_string),
             // nullcount is a base streaming stat always present; fail closed if absent
             nullcount: v.get("nullcount").and_then(serde_json::Value::as_u64)?,
+            min: rendering("min"),
+            max: rendering("max"),
+            max_precision: v
+                .get("max_precision")
+                .and_then(serde_json::Value::as_u64)
+                .map(|p| p as u32),
+            // absent from a lean cache; 0 never proves anything
+            cardinality: v
+                .get("cardinality")
+                .and_then(serde_json::Value::as_u64)
+                .unwrap_or_default(),
             ..Default::default()
         };
         csv_stats.push(stats);
@@ -4979,92 +5005,14 @@ pub fn infer_polars_schema(
         arg_input:            Some(table.to_string_lossy().into_owned()),
         flag_memcheck:        false,
         flag_output:          None,
+        flag_format:          None,
+        flag_compare:         None,
+        flag_null_threshold:  0.0,
+        flag_null_ratios:     false,
     };
-    let (csv_fields, csv_stats) = get_stats_records(&schema_args, StatsMode::PolarsSchema)?;
-    let mut schema = polars::prelude::Schema::with_capacity(csv_stats.len());
-
-    // fetch the decimal scale from the QSV_POLARS_DECIMAL_SCALE env var
-    let scale = std::env::var("QSV_POLARS_DECIMAL_SCALE")
-        .ok()
-        .and_then(|s| s.parse().ok())
-        .unwrap_or(5); // default scale is 5
-    for (idx, stat) in csv_stats.iter().enumerate() {
-        // safety: we know that the get(idx) will not be None as we are using an iterator
-        schema.insert(
-            polars::prelude::PlSmallStr::from_str(
-                simdutf8::basic::from_utf8(csv_fields.get(idx).unwrap()).unwrap(),
-            ),
-            {
-                let datatype = &stat.r#type;
-                #[allow(clippy::match_same_arms)]
-                match datatype.as_str() {
-                    "String" => polars::datatypes::DataType::String,
-                    "Integer" => {
-                        // safety: integer types are guaranteed to have a min and max
-                        let min = stat.min.as_ref().unwrap();
-                        let max = stat.max.as_ref().unwrap();
-
-                        // Check if all values are non-negative to
-                        // use unsigned types
-                        if let (Ok(min_val), Ok(max_val)) = (min.parse::<i64>(), max.parse::<i64>())
-                        {
-                            if min_val >= 0 {
-                                // Use smallest unsigned type that can hold
-                                // the max value
-                                if max_val <= u8::MAX as i64 {
-                                    polars::datatypes::DataType::UInt8
-                                } else if max_val <= u16::MAX as i64 {
-                                    polars::datatypes::DataType::UInt16
-                                } else if max_val <= u32::MAX as i64 {
-                                    polars::datatypes::DataType::UInt32
-                                } else {
-                                    polars::datatypes::DataType::UInt64
-                                }
-                            } else {
-                                // Use signed types for negative values
-                                if min_val >= i32::MIN as i64 && max_val <= i32::MAX as i64 {
-                                    polars::datatypes::DataType::Int32
-                                } else {
-                                    polars::datatypes::DataType::Int64
-                                }
-                            }
-                        } else {
-                            // Fallback to Int64 if parsing fails
-                            polars::datatypes::DataType::Int64
-                        }
-                    },
-                    "Float" => {
-                        // safety: float types are guaranteed to have a min and max
-                        let min = stat.min.as_ref().unwrap();
-                        let max = stat.max.as_ref().unwrap();
-                        let precision = stat.max_precision.unwrap_or(0);
-
-                        // As we use f64 internally, its unlikely that we have more
-                        // than 16 digits of precision, but we do this anyway to
-                        // document it as the polars engine does support it
-                        if precision > 16 {
-                            // For very high precision, use Decimal type
-                            polars::datatypes::DataType::Decimal(precision as usize, scale)
-                        } else if precision > 7
-                            || min.parse::<f32>().is_err()
-                            || max.parse::<f32>().is_err()
-                        {
-                            polars::datatypes::DataType::Float64
-                        } else {
-                            polars::datatypes::DataType::Float32
-                        }
-                    },
-                    "Boolean" => polars::datatypes::DataType::Boolean,
-                    "Date" => polars::datatypes::DataType::Date,
-                    "DateTime" => polars::datatypes::DataType::Datetime(
-                        polars::datatypes::TimeUnit::Milliseconds,
-                        None,
-                    ),
-                    _ => polars::datatypes::DataType::String,
-                }
-            },
-        );
-    }
+    let (_, csv_stats) = get_stats_records(&schema_args, StatsMode::PolarsSchema)?;
+    let schema = crate::statscatalog::StatsCatalog::from_stats(csv_stats)
+        .polars_schema(crate::statscatalog::NumericWidth::Narrowest, true);
     let stats_schema = std::sync::Arc::new(schema);
     // Use serde_json for schema serialization as the schema may contain compound types
     // (e.g. Datetime) that simd_json::to_string_pretty doesn't serialize correctly
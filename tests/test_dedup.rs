@@ -422,3 +422,179 @@ fn dedup_not_sorted2() {
     let got: String = wrk.output_stderr(&mut cmd);
     assert!(got.contains("Aborting! Input not sorted!"));
 }
+
+// unsorted single column with a duplicate; tampered cache says Ascending -> the sort is
+// skipped and only adjacent duplicates are dropped, proving the catalog was used.
+#[test]
+fn dedup_statscache_skips_sort() {
+    let wrk = Workdir::new("dedup_statscache_skips_sort");
+    wrk.create_from_string("in.csv", "name\nbanana\napple\napple\n");
+    wrk.build_stats_cache("in.csv", true);
+    wrk.tamper_sort_order("in", "name", "Ascending");
+
+    let mut cmd = wrk.command("dedup");
+    cmd.arg("in.csv");
+
+    let got: String = wrk.stdout(&mut cmd);
+    assert_eq!(got, "name\nbanana\napple");
+}
+
+// proven sorted AND unique (exact cardinality == record count) -> streamed unchanged
+#[test]
+fn dedup_statscache_sorted_unique_passthrough() {
+    let wrk = Workdir::new("dedup_statscache_sorted_unique_passthrough");
+    wrk.create_from_string("in.csv", "name\ncherry\nbanana\napple\n");
+    wrk.build_stats_cache("in.csv", true);
+    wrk.tamper_sort_order("in", "name", "Ascending");
+
+    let mut cmd = wrk.command("dedup");
+    cmd.arg("in.csv");
+
+    let got: String = wrk.stdout(&mut cmd);
+    assert_eq!(got, "name\ncherry\nbanana\napple");
+}
+
+#[test]
+fn dedup_statscache_disabled() {
+    let wrk = Workdir::new("dedup_statscache_disabled");
+    wrk.create_from_string("in.csv", "name\nbanana\napple\napple\n");
+    wrk.build_stats_cache("in.csv", true);
+    wrk.tamper_sort_order("in", "name", "Ascending");
+
+    let mut cmd = wrk.command("dedup");
+    cmd.env("QSV_STATSCACHE_MODE", "none").arg("in.csv");
+
+    let got: String = wrk.stdout(&mut cmd);
+    assert_eq!(got, "name\napple\nbanana");
+}
+
+#[test]
+fn dedup_normalize_nfkc() {
+    let wrk = Workdir::new("dedup_normalize_nfkc");
+    wrk.create(
+        "in.csv",
+        vec![svec!["name"], svec!["file"], svec!["zip"], svec!["ﬁle"]],
+    );
+
+    let mut cmd = wrk.command("dedup");
+    cmd.args(["--normalize", "nfkc"]).arg("in.csv");
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![svec!["name"], svec!["file"], svec!["zip"]];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn dedup_collation_ignore_case() {
+    let wrk = Workdir::new("dedup_collation_ignore_case");
+    wrk.create(
+        "in.csv",
+        vec![
+            svec!["name"],
+            svec!["Zoë"],
+            svec!["Müller"],
+            svec!["MÜLLER"],
+            svec!["Mueller"],
+        ],
+    );
+
+    let mut cmd = wrk.command("dedup");
+    cmd.args(["--collation", "de"])
+        .arg("--ignore-case")
+        .arg("in.csv");
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["name"],
+        svec!["Mueller"],
+        svec!["Müller"],
+        svec!["Zoë"],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[cfg(feature = "apply")]
+fn fuzzy_fixture(wrk: &Workdir) {
+    wrk.create(
+        "in.csv",
+        vec![
+            svec!["name", "street", "zip"],
+            svec!["Jon Smith", "12 Main St", "10001"],
+            svec!["John Smith", "12 Main Street", "10001"],
+            svec!["Jane Doe", "5 Oak Ave", "10001"],
+            svec!["JOHN SMITH", "12 main st", "94105"],
+            svec!["john smith", "12 Main St", "94105"],
+        ],
+    );
+}
+
+#[cfg(feature = "apply")]
+#[test]
+fn dedup_fuzzy_clusters() {
+    let wrk = Workdir::new("dedup_fuzzy_clusters");
+    fuzzy_fixture(&wrk);
+
+    let mut cmd = wrk.command("dedup");
+    cmd.args(["--fuzzy", "name:simjw:0.9,street:simsd:0.8"])
+        .args(["--block", "zip"])
+        .arg("--ignore-case")
+        .arg("in.csv");
+
+    let output = wrk.output(&mut cmd);
+    assert!(output.status.success());
+    let got = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(
+        got,
+        "name,street,zip,cluster_id\nJon Smith,12 Main St,10001,1\nJohn Smith,12 Main \
+         Street,10001,1\nJane Doe,5 Oak Ave,10001,2\nJOHN SMITH,12 main st,94105,3\njohn smith,12 \
+         Main St,94105,3\n"
+    );
+    assert_eq!(String::from_utf8(output.stderr).unwrap().trim(), "2");
+}
+
+#[cfg(feature = "apply")]
+#[test]
+fn dedup_fuzzy_case_sensitive_cluster_column() {
+    let wrk = Workdir::new("dedup_fuzzy_case_sensitive_cluster_column");
+    fuzzy_fixture(&wrk);
+
+    // without --ignore-case, "JOHN SMITH" isn't similar to "john smith"
+    let mut cmd = wrk.command("dedup");
+    cmd.args(["--fuzzy", "name:simjw:0.9"])
+        .args(["--block", "zip"])
+        .args(["--cluster-column", "group"])
+        .arg("in.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let clusters: Vec<&str> = got.iter().map(|row| row[3].as_str()).collect();
+    assert_eq!(clusters, vec!["group", "1", "1", "2", "3", "4"]);
+}
+
+#[cfg(feature = "apply")]
+#[test]
+fn dedup_fuzzy_errors() {
+    let wrk = Workdir::new("dedup_fuzzy_errors");
+    fuzzy_fixture(&wrk);
+
+    let mut cmd = wrk.command("dedup");
+    cmd.args(["--fuzzy", "name:eudex:0.5"]).arg("in.csv");
+    wrk.assert_err(&mut cmd);
+
+    let mut cmd = wrk.command("dedup");
+    cmd.args(["--fuzzy", "name:simjw"]).arg("in.csv");
+    wrk.assert_err(&mut cmd);
+
+    let mut cmd = wrk.command("dedup");
+    cmd.args(["--fuzzy", "nope:simjw:0.9"]).arg("in.csv");
+    wrk.assert_err(&mut cmd);
+
+    let mut cmd = wrk.command("dedup");
+    cmd.args(["--fuzzy", "name:simjw:0.9"])
+        .arg("--sorted")
+        .arg("in.csv");
+    wrk.assert_err(&mut cmd);
+
+    let mut cmd = wrk.command("dedup");
+    cmd.args(["--fuzzy", "name:simjw:0.9"])
+        .args(["--select", "name"])
+        .arg("in.csv");
+    wrk.assert_err(&mut cmd);
+}
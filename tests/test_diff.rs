@@ -1004,3 +1004,383 @@ diffresult|h1|h2|h3
 +|1|foo_changed|bar";
     assert_eq!(got.as_str(), expected);
 }
+
+#[test]
+fn diff_key_with_select_syntax() {
+    let wrk = Workdir::new("diff_key_with_select_syntax");
+
+    let left = vec![
+        svec!["id", "region_a", "region_b", "value"],
+        svec!["1", "x", "y", "10"],
+        svec!["1", "x", "z", "20"],
+    ];
+    wrk.create("left.csv", left);
+
+    let right = vec![
+        svec!["id", "region_a", "region_b", "value"],
+        svec!["1", "x", "y", "10"],
+        svec!["1", "x", "z", "25"],
+    ];
+    wrk.create("right.csv", right);
+
+    let mut cmd = wrk.command("diff");
+    cmd.args(["left.csv", "right.csv", "--key", "id,/^region_/"]);
+
+    let got: Vec<Vec<String>> = wrk.read_stdout_on_success(&mut cmd);
+    let expected = vec![
+        svec!["diffresult", "id", "region_a", "region_b", "value"],
+        svec!["-", "1", "x", "z", "20"],
+        svec!["+", "1", "x", "z", "25"],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn diff_align_columns() {
+    let wrk = Workdir::new("diff_align_columns");
+
+    let left = vec![
+        svec!["h1", "h2", "h3"],
+        svec!["1", "foo", "bar"],
+        svec!["2", "fooz", "bart"],
+    ];
+    wrk.create("left.csv", left);
+
+    let right = vec![
+        svec!["h3", "h1", "h2"],
+        svec!["bar", "1", "foo"],
+        svec!["fart", "2", "booz"],
+    ];
+    wrk.create("right.csv", right);
+
+    let mut cmd = wrk.command("diff");
+    cmd.args(["left.csv", "right.csv", "--key", "h1", "--align-columns"]);
+
+    let got: Vec<Vec<String>> = wrk.read_stdout_on_success(&mut cmd);
+    let expected = vec![
+        svec!["diffresult", "h1", "h2", "h3"],
+        svec!["-", "2", "fooz", "bart"],
+        svec!["+", "2", "booz", "fart"],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn diff_align_columns_different_column_names_error() {
+    let wrk = Workdir::new("diff_align_columns_different_column_names_error");
+
+    let left = vec![svec!["h1", "h2", "h3"], svec!["1", "foo", "bar"]];
+    wrk.create("left.csv", left);
+
+    let right = vec![svec!["h3", "h1", "h4"], svec!["bar", "1", "foo"]];
+    wrk.create("right.csv", right);
+
+    let mut cmd = wrk.command("diff");
+    cmd.args(["left.csv", "right.csv", "--align-columns"]);
+
+    let got = wrk.output_stderr(&mut cmd);
+    assert!(got.contains("Only on the left CSV: [\"h2\"]"), "{got}");
+    assert!(got.contains("Only on the right CSV: [\"h4\"]"), "{got}");
+    wrk.assert_err(&mut cmd);
+}
+
+#[test]
+fn diff_typed_ignores_equal_numbers_and_dates() {
+    let wrk = Workdir::new("diff_typed_ignores_equal_numbers_and_dates");
+
+    let left = vec![
+        svec!["id", "amount", "when", "zip"],
+        svec!["1", "1.0", "2024-01-05", "02135"],
+        svec!["2", "2.5", "2024-02-01", "02109"],
+        svec!["3", "7", "2024-03-01", "02118"],
+    ];
+    wrk.create("left.csv", left);
+
+    let right = vec![
+        svec!["id", "amount", "when", "zip"],
+        svec!["1", "1.00", "Jan 5, 2024", "02135"],
+        svec!["2", "2.6", "2024-02-01", "02109"],
+        svec!["3", "7", "2024-03-01", "2118"],
+    ];
+    wrk.create("right.csv", right);
+
+    let mut cmd = wrk.command("diff");
+    cmd.args(["left.csv", "right.csv", "--typed", "--drop-equal-fields"]);
+
+    let got: Vec<Vec<String>> = wrk.read_stdout_on_success(&mut cmd);
+    let expected = vec![
+        svec!["diffresult", "id", "amount", "when", "zip"],
+        svec!["-", "2", "2.5", "", ""],
+        svec!["+", "2", "2.6", "", ""],
+        svec!["-", "3", "", "", "02118"],
+        svec!["+", "3", "", "", "2118"],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn diff_tolerance_per_column() {
+    let wrk = Workdir::new("diff_tolerance_per_column");
+
+    let left = vec![
+        svec!["id", "price", "weight", "updated_at"],
+        svec!["1", "9.99", "1.0", "2024-01-05 10:00:00"],
+        svec!["2", "5.00", "2.0", "2024-01-05 10:00:00"],
+    ];
+    wrk.create("left.csv", left);
+
+    let right = vec![
+        svec!["id", "price", "weight", "updated_at"],
+        svec!["1", "10.00", "1.0", "2024-01-05 10:00:30"],
+        svec!["2", "5.00", "2.1", "2024-01-05 10:00:00"],
+    ];
+    wrk.create("right.csv", right);
+
+    let mut cmd = wrk.command("diff");
+    cmd.args([
+        "left.csv",
+        "right.csv",
+        "--tolerance",
+        "price=0.01,updated_at=60",
+    ]);
+
+    let got: Vec<Vec<String>> = wrk.read_stdout_on_success(&mut cmd);
+    let expected = vec![
+        svec!["diffresult", "id", "price", "weight", "updated_at"],
+        svec!["-", "2", "5.00", "2.0", "2024-01-05 10:00:00"],
+        svec!["+", "2", "5.00", "2.1", "2024-01-05 10:00:00"],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn diff_tolerance_invalid_error() {
+    let wrk = Workdir::new("diff_tolerance_invalid_error");
+
+    let left = vec![svec!["id", "price"], svec!["1", "9.99"]];
+    wrk.create("left.csv", left);
+    let right = vec![svec!["id", "price"], svec!["1", "10.00"]];
+    wrk.create("right.csv", right);
+
+    let mut cmd = wrk.command("diff");
+    cmd.args(["left.csv", "right.csv", "--tolerance", "price=-1"]);
+
+    let got = wrk.output_stderr(&mut cmd);
+    assert!(got.contains("Must be a non-negative number"), "{got}");
+    wrk.assert_err(&mut cmd);
+}
+
+#[test]
+fn diff_tolerance_selection_with_commas() {
+    let wrk = Workdir::new("diff_tolerance_selection_with_commas");
+
+    let left = vec![
+        svec!["id", "price", "cost", "ratio:pct"],
+        svec!["1", "9.99", "5.00", "10"],
+        svec!["2", "5.00", "2.00", "10"],
+    ];
+    wrk.create("left.csv", left);
+
+    let right = vec![
+        svec!["id", "price", "cost", "ratio:pct"],
+        svec!["1", "10.00", "5.01", "10.5"],
+        svec!["2", "5.00", "2.00", "12"],
+    ];
+    wrk.create("right.csv", right);
+
+    // a selection of two columns, and a column name with a colon
+    let mut cmd = wrk.command("diff");
+    cmd.args([
+        "left.csv",
+        "right.csv",
+        "--tolerance",
+        "price,cost=0.01,ratio:pct=1",
+    ]);
+
+    let got: Vec<Vec<String>> = wrk.read_stdout_on_success(&mut cmd);
+    let expected = vec![
+        svec!["diffresult", "id", "price", "cost", "ratio:pct"],
+        svec!["-", "2", "5.00", "2.00", "10"],
+        svec!["+", "2", "5.00", "2.00", "12"],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn diff_format_jsonl() {
+    let wrk = Workdir::new("diff_format_jsonl");
+
+    let left = vec![
+        svec!["id", "name", "city"],
+        svec!["1", "Ann", "Boston"],
+        svec!["2", "Bob", "Austin"],
+    ];
+    wrk.create("left.csv", left);
+
+    let right = vec![
+        svec!["id", "name", "city"],
+        svec!["1", "Ann", "Cambridge"],
+        svec!["3", "Cy", "Denver"],
+    ];
+    wrk.create("right.csv", right);
+
+    let mut cmd = wrk.command("diff");
+    cmd.args([
+        "left.csv",
+        "right.csv",
+        "--format",
+        "jsonl",
+        "--sort-columns",
+        "id",
+    ]);
+
+    let got: String = wrk.stdout(&mut cmd);
+    let expected = r#"{"op":"modify","key":{"id":"1"},"changed":["city"],"before":{"id":"1","name":"Ann","city":"Boston"},"after":{"id":"1","name":"Ann","city":"Cambridge"}}
+{"op":"delete","key":{"id":"2"},"before":{"id":"2","name":"Bob","city":"Austin"},"after":null}
+{"op":"add","key":{"id":"3"},"before":null,"after":{"id":"3","name":"Cy","city":"Denver"}}"#;
+    assert_eq!(dos2unix(&got), expected);
+}
+
+#[test]
+fn diff_format_jsonpatch() {
+    let wrk = Workdir::new("diff_format_jsonpatch");
+
+    let left = vec![
+        svec!["id", "name", "city"],
+        svec!["1", "Ann", "Boston"],
+        svec!["2/b", "Bob", "Austin"],
+    ];
+    wrk.create("left.csv", left);
+
+    let right = vec![
+        svec!["id", "name", "city"],
+        svec!["1", "Ann", "Cambridge"],
+        svec!["3", "Cy", "Denver"],
+    ];
+    wrk.create("right.csv", right);
+
+    let mut cmd = wrk.command("diff");
+    cmd.args([
+        "left.csv",
+        "right.csv",
+        "--format",
+        "jsonpatch",
+        "--sort-columns",
+        "id",
+    ]);
+
+    let got: String = wrk.stdout(&mut cmd);
+    let got_json: serde_json::Value = serde_json::from_str(&got).unwrap();
+    let expected_json = serde_json::json!([
+        {"op": "test", "path": "/1/city", "value": "Boston"},
+        {"op": "replace", "path": "/1/city", "value": "Cambridge"},
+        {"op": "test", "path": "/2~1b", "value": {"id": "2/b", "name": "Bob", "city": "Austin"}},
+        {"op": "remove", "path": "/2~1b"},
+        {"op": "add", "path": "/3", "value": {"id": "3", "name": "Cy", "city": "Denver"}},
+    ]);
+    assert_eq!(got_json, expected_json);
+}
+
+#[test]
+fn diff_format_jsonpatch_composite_key() {
+    let wrk = Workdir::new("diff_format_jsonpatch_composite_key");
+
+    let left = vec![svec!["region", "id", "city"], svec!["east", "1", "Boston"]];
+    wrk.create("left.csv", left);
+
+    let right = vec![
+        svec!["region", "id", "city"],
+        svec!["east", "1", "Cambridge"],
+        svec!["west, north", "2", "Seattle"],
+    ];
+    wrk.create("right.csv", right);
+
+    let mut cmd = wrk.command("diff");
+    cmd.args([
+        "left.csv",
+        "right.csv",
+        "--key",
+        "region,id",
+        "--format",
+        "jsonpatch",
+    ]);
+
+    // a composite key is one reference token, so the "add" doesn't need a parent
+    let got: String = wrk.stdout(&mut cmd);
+    let got_json: serde_json::Value = serde_json::from_str(&got).unwrap();
+    let expected_json = serde_json::json!([
+        {"op": "test", "path": "/east,1/city", "value": "Boston"},
+        {"op": "replace", "path": "/east,1/city", "value": "Cambridge"},
+        {"op": "add", "path": "/\"west, north\",2", "value": {"region": "west, north", "id": "2", "city": "Seattle"}},
+    ]);
+    assert_eq!(got_json, expected_json);
+}
+
+#[test]
+fn diff_external() {
+    let wrk = Workdir::new("diff_external");
+
+    let left = vec![
+        svec!["id", "name", "price"],
+        svec!["3", "Cy", "3.50"],
+        svec!["1", "Ann", "1.00"],
+        svec!["2", "Bob", "2.00"],
+    ];
+    wrk.create("left.csv", left);
+
+    let right = vec![
+        svec!["id", "name", "price"],
+        svec!["4", "Di", "4.00"],
+        svec!["2", "Bob", "2.25"],
+        svec!["1", "Ann", "1.0"],
+    ];
+    wrk.create("right.csv", right);
+
+    let mut cmd = wrk.command("diff");
+    cmd.args(["left.csv", "right.csv", "--external", "--typed"]);
+
+    // in key order, not line order
+    let got: Vec<Vec<String>> = wrk.read_stdout_on_success(&mut cmd);
+    let expected = vec![
+        svec!["diffresult", "id", "name", "price"],
+        svec!["-", "2", "Bob", "2.00"],
+        svec!["+", "2", "Bob", "2.25"],
+        svec!["-", "3", "Cy", "3.50"],
+        svec!["+", "4", "Di", "4.00"],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn diff_external_duplicate_key_error() {
+    let wrk = Workdir::new("diff_external_duplicate_key_error");
+
+    let left = vec![svec!["id", "name"], svec!["1", "Ann"], svec!["1", "Bob"]];
+    wrk.create("left.csv", left);
+    let right = vec![svec!["id", "name"], svec!["1", "Ann"]];
+    wrk.create("right.csv", right);
+
+    let mut cmd = wrk.command("diff");
+    cmd.args(["left.csv", "right.csv", "--external"]);
+
+    let got = wrk.output_stderr(&mut cmd);
+    assert!(got.contains("The left CSV has duplicate key: 1"), "{got}");
+    wrk.assert_err(&mut cmd);
+}
+
+#[test]
+fn diff_external_sort_columns_error() {
+    let wrk = Workdir::new("diff_external_sort_columns_error");
+
+    let left = vec![svec!["id", "name"], svec!["1", "Ann"]];
+    wrk.create("left.csv", left);
+    let right = vec![svec!["id", "name"], svec!["1", "Bob"]];
+    wrk.create("right.csv", right);
+
+    let mut cmd = wrk.command("diff");
+    cmd.args(["left.csv", "right.csv", "--external", "--sort-columns", "1"]);
+
+    let got = wrk.output_stderr(&mut cmd);
+    assert!(got.contains("not supported with --external"), "{got}");
+    wrk.assert_err(&mut cmd);
+}
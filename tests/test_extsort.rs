@@ -191,46 +191,20 @@ fn extsort_csvmode_crlf_no_headers() {
 // falls through (real external sort).
 // ---------------------------------------------------------------------------
 
-fn build_stats_cache(wrk: &Workdir, csv: &str) {
-    let mut stats_cmd = wrk.command("stats");
-    stats_cmd.arg(csv).arg("--stats-jsonl");
-    wrk.assert_success(&mut stats_cmd);
-}
-
 fn build_index(wrk: &Workdir, csv: &str) {
     let mut idx_cmd = wrk.command("index");
     idx_cmd.arg(csv);
     wrk.assert_success(&mut idx_cmd);
 }
 
-fn tamper_sort_order(wrk: &Workdir, stem: &str, field: &str, new_order: &str) {
-    let path = wrk.path(&format!("{stem}.stats.csv.data.jsonl"));
-    let contents = std::fs::read_to_string(&path).unwrap();
-    let mut lines = Vec::new();
-    let mut found = false;
-    for line in contents.lines() {
-        if line.trim().is_empty() {
-            continue;
-        }
-        let mut v: serde_json::Value = serde_json::from_str(line).unwrap();
-        if v.get("field").and_then(serde_json::Value::as_str) == Some(field) {
-            v["sort_order"] = serde_json::Value::String(new_order.to_string());
-            found = true;
-        }
-        lines.push(serde_json::to_string(&v).unwrap());
-    }
-    assert!(found, "field {field} not found in stats cache");
-    std::fs::write(&path, lines.join("\n")).unwrap();
-}
-
 // unsorted ASCII single column; tampered cache says Ascending -> passthrough
 // (output preserves the unsorted input order, proving the sort was skipped).
 #[test]
 fn extsort_statscache_passthrough() {
     let wrk = Workdir::new("extsort_statscache_passthrough").flexible(true);
     wrk.create_from_string("in.csv", "name\nbanana\napple\ncherry\n");
-    build_stats_cache(&wrk, "in.csv");
-    tamper_sort_order(&wrk, "in", "name", "Ascending");
+    wrk.build_stats_cache("in.csv", false);
+    wrk.tamper_sort_order("in", "name", "Ascending");
     build_index(&wrk, "in.csv");
 
     let mut cmd = wrk.command("extsort");
@@ -246,8 +220,8 @@ fn extsort_statscache_passthrough() {
 fn extsort_statscache_optout_none() {
     let wrk = Workdir::new("extsort_statscache_optout_none").flexible(true);
     wrk.create_from_string("in.csv", "name\nbanana\napple\ncherry\n");
-    build_stats_cache(&wrk, "in.csv");
-    tamper_sort_order(&wrk, "in", "name", "Ascending");
+    wrk.build_stats_cache("in.csv", false);
+    wrk.tamper_sort_order("in", "name", "Ascending");
     build_index(&wrk, "in.csv");
 
     let mut cmd = wrk.command("extsort");
@@ -267,8 +241,8 @@ fn extsort_statscache_optout_none() {
 fn extsort_statscache_is_ascii_guard() {
     let wrk = Workdir::new("extsort_statscache_is_ascii_guard").flexible(true);
     wrk.create_from_string("in.csv", "name\nbanana\napple\nz\u{00fc}rich\n");
-    build_stats_cache(&wrk, "in.csv");
-    tamper_sort_order(&wrk, "in", "name", "Ascending");
+    wrk.build_stats_cache("in.csv", false);
+    wrk.tamper_sort_order("in", "name", "Ascending");
     build_index(&wrk, "in.csv");
 
     let mut cmd = wrk.command("extsort");
@@ -286,8 +260,8 @@ fn extsort_statscache_is_ascii_guard() {
 fn extsort_statscache_reverse_no_shortcircuit() {
     let wrk = Workdir::new("extsort_statscache_reverse_no_shortcircuit").flexible(true);
     wrk.create_from_string("in.csv", "name\nbanana\napple\ncherry\n");
-    build_stats_cache(&wrk, "in.csv");
-    tamper_sort_order(&wrk, "in", "name", "Descending");
+    wrk.build_stats_cache("in.csv", false);
+    wrk.tamper_sort_order("in", "name", "Descending");
     build_index(&wrk, "in.csv");
 
     let mut cmd = wrk.command("extsort");
@@ -308,8 +282,8 @@ fn extsort_statscache_reverse_no_shortcircuit() {
 fn extsort_statscache_options_mismatch_no_shortcircuit() {
     let wrk = Workdir::new("extsort_statscache_options_mismatch_no_shortcircuit").flexible(true);
     wrk.create_from_string("in.csv", "name\nbanana\napple\ncherry\n");
-    build_stats_cache(&wrk, "in.csv"); // generated WITH headers
-    tamper_sort_order(&wrk, "in", "name", "Ascending");
+    wrk.build_stats_cache("in.csv", false); // generated WITH headers
+    wrk.tamper_sort_order("in", "name", "Ascending");
     build_index(&wrk, "in.csv");
 
     let mut cmd = wrk.command("extsort");
@@ -330,9 +304,9 @@ fn extsort_statscache_options_mismatch_no_shortcircuit() {
 fn extsort_statscache_multicolumn_no_shortcircuit() {
     let wrk = Workdir::new("extsort_statscache_multicolumn_no_shortcircuit").flexible(true);
     wrk.create_from_string("in.csv", "c1,c2\nb,2\na,1\nc,3\n");
-    build_stats_cache(&wrk, "in.csv");
-    tamper_sort_order(&wrk, "in", "c1", "Ascending");
-    tamper_sort_order(&wrk, "in", "c2", "Ascending");
+    wrk.build_stats_cache("in.csv", false);
+    wrk.tamper_sort_order("in", "c1", "Ascending");
+    wrk.tamper_sort_order("in", "c2", "Ascending");
     build_index(&wrk, "in.csv");
 
     let mut cmd = wrk.command("extsort");
@@ -342,3 +316,77 @@ fn extsort_statscache_multicolumn_no_shortcircuit() {
     let got: String = wrk.from_str(&wrk.path("out.csv"));
     assert_eq!(dos2unix(&got), "c1,c2\na,1\nb,2\nc,3\n");
 }
+
+#[test]
+fn extsort_order_multi_key() {
+    let wrk = Workdir::new("extsort_order_multi_key");
+    wrk.create(
+        "in.csv",
+        vec![
+            svec!["state", "population", "note"],
+            svec!["NY", "900", "b"],
+            svec!["CA", "1000", "multi\nline"],
+            svec!["NY", "", "c"],
+            svec!["CA", "20", "x|y"],
+            svec!["NY", "10000", "a"],
+            svec!["ca", "20", "z"],
+        ],
+    );
+    build_index(&wrk, "in.csv");
+
+    let mut cmd = wrk.command("extsort");
+    cmd.arg("in.csv")
+        .args(["--order", "state:icase, population:numeric desc"]);
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["state", "population", "note"],
+        svec!["CA", "1000", "multi\nline"],
+        svec!["CA", "20", "x|y"],
+        svec!["ca", "20", "z"],
+        svec!["NY", "10000", "a"],
+        svec!["NY", "900", "b"],
+        svec!["NY", "", "c"],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn extsort_order_collate() {
+    let wrk = Workdir::new("extsort_order_collate");
+    wrk.create(
+        "in.csv",
+        vec![
+            svec!["name", "n"],
+            svec!["Zebra", "1"],
+            svec!["Äpfel", "2"],
+            svec!["apple", "3"],
+            svec!["Ob", "4"],
+        ],
+    );
+    build_index(&wrk, "in.csv");
+
+    // Swedish sorts Ä after Z
+    let mut cmd = wrk.command("extsort");
+    cmd.arg("in.csv").args(["--order", "name:collate=sv"]);
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["name", "n"],
+        svec!["apple", "3"],
+        svec!["Ob", "4"],
+        svec!["Zebra", "1"],
+        svec!["Äpfel", "2"],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn extsort_order_with_select_errors() {
+    let wrk = Workdir::new("extsort_order_with_select_errors");
+    wrk.create("in.csv", vec![svec!["a", "b"], svec!["1", "2"]]);
+
+    let mut cmd = wrk.command("extsort");
+    cmd.arg("in.csv").args(["--order", "a", "--select", "b"]);
+    wrk.assert_err(&mut cmd);
+}
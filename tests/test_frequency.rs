@@ -531,6 +531,28 @@ fn frequency_asc_ignorecase() {
     assert_eq!(got, expected);
 }
 
+#[test]
+fn frequency_collation_orders_ties() {
+    let wrk = Workdir::new("frequency_collation_orders_ties");
+    wrk.create(
+        "in.csv",
+        vec![
+            svec!["h"],
+            svec!["b"],
+            svec!["Zebra"],
+            svec!["Äpfel"],
+            svec!["b"],
+            svec!["apple"],
+        ],
+    );
+    let mut cmd = wrk.command("frequency");
+    cmd.arg("in.csv").args(["--collation", "de"]);
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let values: Vec<&str> = got[1..].iter().map(|row| row[1].as_str()).collect();
+    assert_eq!(values, vec!["b", "apple", "Äpfel", "Zebra"]);
+}
+
 #[test]
 fn frequency_custom_other_text() {
     let (wrk, mut cmd) = setup("frequency_custom_other_text");
@@ -7321,3 +7343,232 @@ fn frequency_statscache_mode_force_creates_the_cache() {
         "none mode created a stats cache"
     );
 }
+
+fn setup_crosstab(name: &str) -> Workdir {
+    let wrk = Workdir::new(name);
+    wrk.create(
+        "in.csv",
+        vec![
+            svec!["group", "answer", "n"],
+            svec!["A", "yes", "1"],
+            svec!["A", "yes", "2"],
+            svec!["A", "yes", "3"],
+            svec!["A", "no", "4"],
+            svec!["B", "yes", "5"],
+            svec!["B", "yes", "6"],
+            svec!["B", "no", "7"],
+            svec!["B", "no", "8"],
+        ],
+    );
+    wrk
+}
+
+#[test]
+fn frequency_crosstab_two_way() {
+    let wrk = setup_crosstab("frequency_crosstab_two_way");
+    let mut cmd = wrk.command("frequency");
+    cmd.args(["--crosstab", "group,answer"]).arg("in.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout_on_success(&mut cmd);
+    let expected = vec![
+        svec![
+            "group",
+            "answer",
+            "count",
+            "percentage",
+            "row_percentage",
+            "col_percentage"
+        ],
+        svec!["A", "yes", "3", "37.5", "75", "60"],
+        svec!["B", "no", "2", "25", "50", "66.66667"],
+        svec!["B", "yes", "2", "25", "50", "40"],
+        svec!["A", "no", "1", "12.5", "25", "33.33333"],
+        svec!["A", "(TOTAL)", "4", "50", "100", "50"],
+        svec!["B", "(TOTAL)", "4", "50", "100", "50"],
+        svec!["(TOTAL)", "yes", "5", "62.5", "62.5", "100"],
+        svec!["(TOTAL)", "no", "3", "37.5", "37.5", "100"],
+        svec!["(TOTAL)", "(TOTAL)", "8", "100", "100", "100"],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn frequency_crosstab_json_chi_square() {
+    let wrk = setup_crosstab("frequency_crosstab_json_chi_square");
+    let mut cmd = wrk.command("frequency");
+    cmd.args(["--crosstab", "group,answer"])
+        .arg("--json")
+        .arg("in.csv");
+
+    let got: String = wrk.stdout(&mut cmd);
+    let v: Value = serde_json::from_str(&got).unwrap();
+    assert_eq!(v["rowcount"], 8);
+    assert_eq!(v["fields"], serde_json::json!(["group", "answer"]));
+
+    // expected counts are 2.5/1.5 per group, so chi-square = 2 * (0.25/2.5 + 0.25/1.5)
+    let chi = &v["chi_square"];
+    assert!((chi["statistic"].as_f64().unwrap() - 0.533_333).abs() < 1e-5);
+    assert_eq!(chi["dof"], 1);
+    assert!((chi["p_value"].as_f64().unwrap() - 0.465_209).abs() < 1e-5);
+    assert!((chi["cramers_v"].as_f64().unwrap() - 0.258_199).abs() < 1e-5);
+
+    let cells = v["cells"].as_array().unwrap();
+    assert_eq!(cells.len(), 4);
+    assert_eq!(cells[1]["values"], serde_json::json!(["B", "no"]));
+    assert_eq!(cells[1]["count"], 2);
+    assert!((cells[1]["col_percentage"].as_f64().unwrap() - 66.66667).abs() < 1e-5);
+
+    let margins = v["margins"].as_array().unwrap();
+    assert_eq!(margins[1]["field"], "answer");
+    assert_eq!(margins[1]["values"][0]["value"], "yes");
+    assert_eq!(margins[1]["values"][0]["count"], 5);
+}
+
+#[test]
+fn frequency_crosstab_chi_square_dof_overflow() {
+    let wrk = Workdir::new("frequency_crosstab_chi_square_dof_overflow");
+    // 7200^5 cells overflows u64, so the dof and p-value are n/a
+    let mut rows = vec![svec!["a", "b", "c", "d", "e"]];
+    rows.extend((0..7200).map(|i| vec![i.to_string(); 5]));
+    wrk.create("in.csv", rows);
+
+    let mut cmd = wrk.command("frequency");
+    cmd.args(["--crosstab", "a,b,c,d,e"])
+        .arg("--json")
+        .arg("in.csv");
+
+    let got: String = wrk.stdout(&mut cmd);
+    let v: Value = serde_json::from_str(&got).unwrap();
+    assert_eq!(v["rowcount"], 7200);
+    let chi = &v["chi_square"];
+    assert!(chi["statistic"].as_f64().unwrap() > 0.0);
+    assert!(chi.get("dof").is_none());
+    assert!(chi.get("p_value").is_none());
+}
+
+#[test]
+fn frequency_crosstab_rejects_incompatible_options() {
+    let wrk = setup_crosstab("frequency_crosstab_rejects_incompatible_options");
+
+    let mut cmd = wrk.command("frequency");
+    cmd.args(["--crosstab", "group"]).arg("in.csv");
+    wrk.assert_err(&mut cmd);
+
+    let mut cmd = wrk.command("frequency");
+    cmd.args(["--crosstab", "group,answer"])
+        .args(["--select", "group"])
+        .arg("in.csv");
+    wrk.assert_err(&mut cmd);
+
+    let mut cmd = wrk.command("frequency");
+    cmd.args(["--crosstab", "group,answer"])
+        .args(["--weight", "n"])
+        .arg("in.csv");
+    wrk.assert_err(&mut cmd);
+}
+
+#[test]
+fn frequency_bins_equal_width() {
+    let wrk = Workdir::new("frequency_bins_equal_width");
+    let mut rows = vec![svec!["n", "s"]];
+    for i in 1..=10 {
+        rows.push(vec![i.to_string(), format!("s{i}")]);
+    }
+    rows.push(svec!["", "s11"]);
+    wrk.create("in.csv", rows);
+
+    let mut cmd = wrk.command("frequency");
+    cmd.args(["--bins", "equal:3"]).arg("in.csv");
+
+    // Integer bins are whole numbers wide: [1, 4), [4, 7) and the closed [7, 10].
+    // The String column is skipped.
+    let got: Vec<Vec<String>> = wrk.read_stdout_on_success(&mut cmd);
+    let expected = vec![
+        svec!["field", "bin", "lower", "upper", "count", "percentage"],
+        svec!["n", "1", "1", "4", "3", "30"],
+        svec!["n", "2", "4", "7", "3", "30"],
+        svec!["n", "3", "7", "10", "4", "40"],
+        svec!["n", "(NULL)", "", "", "1", ""],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn frequency_bins_quantile_json() {
+    let wrk = Workdir::new("frequency_bins_quantile_json");
+    let mut rows = vec![svec!["x"]];
+    for i in 1..=8 {
+        rows.push(vec![format!("{i}.5")]);
+    }
+    wrk.create("in.csv", rows);
+
+    let mut cmd = wrk.command("frequency");
+    cmd.args(["--bins", "quantile:2"])
+        .arg("--json")
+        .arg("in.csv");
+
+    let got: String = wrk.stdout(&mut cmd);
+    let v: Value = serde_json::from_str(&got).unwrap();
+    assert_eq!(v["bin_strategy"], "quantile:2");
+    assert_eq!(v["rowcount"], 8);
+    let bins = v["fields"][0]["bins"].as_array().unwrap();
+    assert_eq!(bins.len(), 2);
+    assert_eq!(bins[0]["lower"], 1.5);
+    assert_eq!(bins[0]["upper"], 5.5);
+    assert_eq!(bins[0]["count"], 4);
+    assert_eq!(bins[0]["percentage"], 50.0);
+    assert_eq!(bins[1]["upper"], 8.5);
+    assert_eq!(bins[1]["count"], 4);
+}
+
+#[test]
+fn frequency_bins_dates_sturges_and_cache() {
+    let wrk = Workdir::new("frequency_bins_dates_sturges_and_cache");
+    let mut rows = vec![svec!["day"]];
+    for d in 1..=8 {
+        rows.push(vec![format!("2024-01-0{d}")]);
+    }
+    wrk.create("in.csv", rows);
+
+    let mut cmd = wrk.command("frequency");
+    cmd.args(["--bins", "sturges"])
+        .arg("--frequency-jsonl")
+        .arg("in.csv");
+
+    // Sturges' rule gives 4 bins for 8 values, each 2 whole days wide
+    let got: Vec<Vec<String>> = wrk.read_stdout_on_success(&mut cmd);
+    let expected = vec![
+        svec!["field", "bin", "lower", "upper", "count", "percentage"],
+        svec!["day", "1", "2024-01-01", "2024-01-03", "2", "25"],
+        svec!["day", "2", "2024-01-03", "2024-01-05", "2", "25"],
+        svec!["day", "3", "2024-01-05", "2024-01-07", "2", "25"],
+        svec!["day", "4", "2024-01-07", "2024-01-09", "2", "25"],
+    ];
+    assert_eq!(got, expected);
+
+    let cache = std::fs::read_to_string(wrk.path("in.freq.csv.bins.jsonl")).unwrap();
+    let mut lines = cache.lines();
+    let metadata: Value = serde_json::from_str(lines.next().unwrap()).unwrap();
+    assert_eq!(metadata["bin_strategy"], "sturges");
+    let entry: Value = serde_json::from_str(lines.next().unwrap()).unwrap();
+    assert_eq!(entry["field"], "day");
+    assert_eq!(entry["bins"].as_array().unwrap().len(), 4);
+    // the distinct-value cache is left alone
+    assert!(!wrk.path("in.freq.csv.data.jsonl").exists());
+}
+
+#[test]
+fn frequency_bins_rejects_bad_strategies_and_stdin() {
+    let wrk = setup_crosstab("frequency_bins_rejects_bad_strategies_and_stdin");
+
+    for bins in ["equal", "quantile:0", "sturges:3", "squares"] {
+        let mut cmd = wrk.command("frequency");
+        cmd.args(["--bins", bins]).arg("in.csv");
+        wrk.assert_err(&mut cmd);
+    }
+
+    let mut cmd = wrk.command("frequency");
+    cmd.args(["--bins", "sturges"]).arg("-");
+    cmd.stdin(process::Stdio::piped());
+    wrk.assert_err(&mut cmd);
+}
@@ -1,3 +1,5 @@
+use std::process;
+
 use crate::workdir::Workdir;
 
 // This macro takes *two* identifiers: one for the test with headers
@@ -1012,3 +1014,80 @@ fn join_right_anti_different_fields_issue_2437() {
     let expected = vec![svec!["id", "PA", "PB"], svec!["4", "105", "0101"]];
     assert_eq!(got, expected);
 }
+
+/// The rows of a spilled join, with the body sorted as partitions reorder it.
+fn spilled_join_rows(wrk: &Workdir, cmd: &mut process::Command, headers: bool) -> Vec<Vec<String>> {
+    let mut rows: Vec<Vec<String>> = wrk.read_stdout(cmd);
+    let skip = usize::from(headers).min(rows.len());
+    rows[skip..].sort();
+    rows
+}
+
+#[test]
+fn join_memory_limit_spills_like_in_memory() {
+    for headers in [true, false] {
+        let wrk = setup(&format!("join_memory_limit_spills_{headers}"), headers);
+        for flag in [
+            None,
+            Some("--left"),
+            Some("--left-anti"),
+            Some("--left-semi"),
+            Some("--right"),
+            Some("--right-anti"),
+            Some("--right-semi"),
+            Some("--full"),
+        ] {
+            for ignore_case in [false, true] {
+                let join = |spill: bool| {
+                    let mut cmd = wrk.command("join");
+                    if headers {
+                        cmd.args(["city", "cities.csv", "city", "places.csv"]);
+                    } else {
+                        cmd.args(["--no-headers", "1", "cities.csv", "1", "places.csv"]);
+                    }
+                    cmd.args(flag);
+                    if ignore_case {
+                        cmd.arg("--ignore-case");
+                    }
+                    if spill {
+                        // 0 always spills
+                        cmd.args(["--memory-limit", "0", "--tmp-dir", "."]);
+                    }
+                    spilled_join_rows(&wrk, &mut cmd, headers)
+                };
+                assert_eq!(
+                    join(true),
+                    join(false),
+                    "join {flag:?} ignore_case={ignore_case} headers={headers}"
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn join_memory_limit_keys_output() {
+    let wrk = setup("join_memory_limit_keys_output", true);
+    let mut cmd = wrk.command("join");
+    cmd.args(["city", "cities.csv", "city", "places.csv"])
+        .args(["--memory-limit", "0", "--keys-output", "keys.csv"]);
+    wrk.run(&mut cmd);
+
+    let mut got: Vec<String> = wrk
+        .read_to_string("keys.csv")
+        .unwrap()
+        .lines()
+        .map(String::from)
+        .collect();
+    got.sort();
+    assert_eq!(got, svec!["Boston", "Buffalo"]);
+}
+
+#[test]
+fn join_tmp_dir_must_exist() {
+    let wrk = setup("join_tmp_dir_must_exist", true);
+    let mut cmd = wrk.command("join");
+    cmd.args(["city", "cities.csv", "city", "places.csv"])
+        .args(["--memory-limit", "0", "--tmp-dir", "no-such-dir"]);
+    wrk.assert_err(&mut cmd);
+}
@@ -3173,3 +3173,172 @@ fn joinp_tsv_cache_schema_minus1() {
     assert_eq!(got.len(), 3); // header + 2 matched rows
     assert_eq!(got[0], svec!["id", "name", "city"]);
 }
+
+#[test]
+fn joinp_catalog_view() {
+    let wrk = Workdir::new("joinp_catalog_view");
+    wrk.create(
+        "customers.csv",
+        vec![
+            svec!["id", "name", "active"],
+            svec!["1", "alice", "true"],
+            svec!["2", "bob", "false"],
+        ],
+    );
+    wrk.create(
+        "orders.csv",
+        vec![
+            svec!["order_id", "customer_id"],
+            svec!["10", "1"],
+            svec!["11", "2"],
+        ],
+    );
+    wrk.create_from_string(
+        "qsv-catalog.toml",
+        r#"
+[tables.customers]
+path = "customers.csv"
+
+[[views]]
+name = "active_customers"
+sql = "SELECT id, name FROM customers WHERE active"
+"#,
+    );
+
+    let mut cmd = wrk.command("joinp");
+    cmd.args(["customer_id", "orders.csv", "id", "active_customers"]);
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["order_id", "customer_id", "name"],
+        svec!["10", "1", "alice"],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn joinp_hive_dataset() {
+    let wrk = Workdir::new("joinp_hive_dataset");
+    std::fs::create_dir_all(wrk.path("orders/year=2023")).unwrap();
+    std::fs::create_dir_all(wrk.path("orders/year=2024")).unwrap();
+    wrk.create(
+        "orders/year=2023/part-0.csv",
+        vec![svec!["order_id", "customer_id"], svec!["10", "1"]],
+    );
+    wrk.create(
+        "orders/year=2024/part-0.csv",
+        vec![svec!["order_id", "customer_id"], svec!["11", "2"]],
+    );
+    wrk.create(
+        "customers.csv",
+        vec![svec!["id", "name"], svec!["1", "alice"], svec!["2", "bob"]],
+    );
+
+    let mut cmd = wrk.command("joinp");
+    cmd.args(["customer_id", "orders", "id", "customers.csv"]);
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["order_id", "customer_id", "year", "name"],
+        svec!["10", "1", "2023", "alice"],
+        svec!["11", "2", "2024", "bob"],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn joinp_fuzzy_blocked() {
+    let wrk = Workdir::new("joinp_fuzzy_blocked");
+    wrk.create(
+        "people.csv",
+        vec![
+            svec!["id", "name", "zip"],
+            svec!["1", "Jonathan Smith", "10001"],
+            svec!["2", "Mary Jones", "20002"],
+            svec!["3", "Zed Zebra", "20002"],
+        ],
+    );
+    wrk.create(
+        "customers.csv",
+        vec![
+            svec!["cid", "full_name", "zip"],
+            svec!["a", "Jonathon Smith", "10001"],
+            // same name, but a different block
+            svec!["b", "Jonathan Smith", "20002"],
+            svec!["c", "mary jones", "20002"],
+        ],
+    );
+
+    let mut cmd = wrk.command("joinp");
+    cmd.args(["name", "people.csv", "full_name", "customers.csv"])
+        .args(["--fuzzy", "simjw:0.9"])
+        .args(["--block-left", "zip"])
+        .arg("--ignore-case");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    assert_eq!(
+        got[0],
+        svec!["id", "name", "zip", "cid", "full_name", "fuzzy_score"]
+    );
+    let pairs: Vec<(&str, &str)> = got[1..]
+        .iter()
+        .map(|row| (row[0].as_str(), row[3].as_str()))
+        .collect();
+    assert_eq!(pairs, vec![("1", "a"), ("2", "c")]);
+    // case is ignored, so Mary Jones is an exact match
+    assert!(got[1][5].parse::<f64>().unwrap() >= 0.9);
+    assert_eq!(got[2][5].parse::<f64>().unwrap(), 1.0);
+}
+
+#[test]
+fn joinp_fuzzy_unblocked() {
+    let wrk = Workdir::new("joinp_fuzzy_unblocked");
+    wrk.create(
+        "left.csv",
+        vec![
+            svec!["id", "name"],
+            svec!["1", "Jonathan Smith"],
+            svec!["2", "Mary Jones"],
+        ],
+    );
+    wrk.create(
+        "right.csv",
+        vec![
+            svec!["id", "name"],
+            svec!["a", "Mary Jones"],
+            svec!["b", "Jonathon Smith"],
+            svec!["c", "Jonathan Smith"],
+        ],
+    );
+
+    let mut cmd = wrk.command("joinp");
+    cmd.args(["name", "left.csv", "name", "right.csv"])
+        .args(["--fuzzy", "simjw:0.9"]);
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    assert_eq!(
+        got[0],
+        svec!["id", "name", "id_right", "name_right", "fuzzy_score"]
+    );
+    // every left row is compared with every right row, best match first
+    let pairs: Vec<(&str, &str)> = got[1..]
+        .iter()
+        .map(|row| (row[0].as_str(), row[2].as_str()))
+        .collect();
+    assert_eq!(pairs, vec![("1", "c"), ("1", "b"), ("2", "a")]);
+}
+
+#[test]
+fn joinp_fuzzy_invalid_metric() {
+    let wrk = Workdir::new("joinp_fuzzy_invalid_metric");
+    wrk.create("a.csv", vec![svec!["name"], svec!["x"]]);
+    wrk.create("b.csv", vec![svec!["name"], svec!["y"]]);
+
+    let mut cmd = wrk.command("joinp");
+    cmd.args(["name", "a.csv", "name", "b.csv"])
+        .args(["--fuzzy", "levenshtein:0.9"]);
+
+    let got = wrk.output_stderr(&mut cmd);
+    assert!(got.contains("Invalid --fuzzy metric \"levenshtein\""));
+    wrk.assert_err(&mut cmd);
+}
@@ -0,0 +1,187 @@
+use crate::workdir::Workdir;
+
+fn setup(name: &str) -> Workdir {
+    let wrk = Workdir::new(name);
+    wrk.create(
+        "people1.csv",
+        vec![
+            svec!["name", "dob", "zip"],
+            svec!["John Smith", "1980-01-15", "10001"],
+            svec!["Mary Jones", "1975-06-30", "10001"],
+            svec!["Peter Parker", "1990-03-01", "94105"],
+        ],
+    );
+    wrk.create(
+        "people2.csv",
+        vec![
+            svec!["full_name", "dob", "zip"],
+            svec!["Jon Smith", "1980-01-16", "10001"],
+            svec!["Mary Jones", "1975-06-30", "10001"],
+            svec!["Bob Brown", "1960-01-01", "10001"],
+            svec!["Peter Parker", "1990-03-01", "94105"],
+        ],
+    );
+    wrk
+}
+
+#[test]
+fn link_two_files() {
+    let wrk = setup("link_two_files");
+    let mut cmd = wrk.command("link");
+    cmd.args(["--compare", "name=full_name:jw:0.9,dob:date:2"])
+        .args(["--block", "zip"])
+        .args(["people1.csv", "people2.csv"]);
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec![
+            "left_row",
+            "right_row",
+            "score",
+            "class",
+            "left_name",
+            "right_full_name",
+            "left_dob",
+            "right_dob"
+        ],
+        svec![
+            "1",
+            "1",
+            "6.3399",
+            "match",
+            "John Smith",
+            "Jon Smith",
+            "1980-01-15",
+            "1980-01-16"
+        ],
+        svec![
+            "2",
+            "2",
+            "6.3399",
+            "match",
+            "Mary Jones",
+            "Mary Jones",
+            "1975-06-30",
+            "1975-06-30"
+        ],
+        svec![
+            "3",
+            "4",
+            "6.3399",
+            "match",
+            "Peter Parker",
+            "Peter Parker",
+            "1990-03-01",
+            "1990-03-01"
+        ],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn link_self_possible_matches() {
+    let wrk = Workdir::new("link_self_possible_matches");
+    wrk.create(
+        "people.csv",
+        vec![
+            svec!["name", "dob", "zip"],
+            svec!["John Smith", "1980-01-15", "10001"],
+            svec!["Jon Smith", "1980-01-16", "10001"],
+            svec!["John Smith", "1999-12-31", "10001"],
+            svec!["Ann Lee", "1980-01-15", "94105"],
+        ],
+    );
+
+    // the name agrees and the birth date doesn't in pairs with the third record
+    let mut cmd = wrk.command("link");
+    cmd.args(["--compare", "name:jw:0.9,dob:date:2"])
+        .args(["--block", "zip"])
+        .args(["--possible-threshold", "-1"])
+        .arg("people.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let pairs: Vec<(&str, &str, &str)> = got[1..]
+        .iter()
+        .map(|row| (row[0].as_str(), row[1].as_str(), row[3].as_str()))
+        .collect();
+    assert_eq!(
+        pairs,
+        vec![
+            ("1", "2", "match"),
+            ("1", "3", "possible"),
+            ("2", "3", "possible")
+        ]
+    );
+}
+
+#[test]
+fn link_negative_match_threshold() {
+    let wrk = Workdir::new("link_negative_match_threshold");
+    wrk.create(
+        "people.csv",
+        vec![
+            svec!["name", "dob", "zip"],
+            svec!["John Smith", "1980-01-15", "10001"],
+            svec!["Jon Smith", "1980-01-16", "10001"],
+            svec!["John Smith", "1999-12-31", "10001"],
+        ],
+    );
+
+    // without --possible-threshold, a negative match threshold is valid
+    let mut cmd = wrk.command("link");
+    cmd.args(["--compare", "name:jw:0.9,dob:date:2"])
+        .args(["--block", "zip"])
+        .args(["--match-threshold", "-1"])
+        .arg("people.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let classes: Vec<&str> = got[1..].iter().map(|row| row[3].as_str()).collect();
+    assert_eq!(classes, vec!["match", "match", "match"]);
+}
+
+#[test]
+fn link_non_matches_without_block() {
+    let wrk = setup("link_non_matches_without_block");
+    let mut cmd = wrk.command("link");
+    cmd.args(["--compare", "zip:exact:m=0.99:u=0.5"])
+        .arg("--non-matches")
+        .args(["people1.csv", "people2.csv"]);
+
+    // every pair is a candidate pair without --block
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    assert_eq!(got.len(), 1 + 3 * 4);
+    let matches = got[1..].iter().filter(|row| row[3] == "match").count();
+    let non_matches = got[1..].iter().filter(|row| row[3] == "non-match").count();
+    assert_eq!((matches, non_matches), (7, 5));
+}
+
+#[test]
+fn link_errors() {
+    let wrk = setup("link_errors");
+
+    for compare in [
+        "name:soundex",
+        "name:exact:0.5",
+        "name:jw:1.5",
+        "name:jw:m=1.5",
+        "nope:jw",
+        "name",
+    ] {
+        let mut cmd = wrk.command("link");
+        cmd.args(["--compare", compare]).arg("people1.csv");
+        wrk.assert_err(&mut cmd);
+    }
+
+    let mut cmd = wrk.command("link");
+    cmd.args(["--compare", "name:jw"])
+        .args(["--block-right", "zip"])
+        .arg("people1.csv");
+    wrk.assert_err(&mut cmd);
+
+    let mut cmd = wrk.command("link");
+    cmd.args(["--compare", "name:jw"])
+        .args(["--match-threshold", "1"])
+        .args(["--possible-threshold", "2"])
+        .arg("people1.csv");
+    wrk.assert_err(&mut cmd);
+}
@@ -0,0 +1,252 @@
+use crate::workdir::Workdir;
+
+fn left_and_right(wrk: &Workdir) {
+    wrk.create(
+        "left.csv",
+        vec![
+            svec!["id", "name", "city"],
+            svec!["1", "Ann", "Boston"],
+            svec!["2", "Bob", "Austin"],
+            svec!["3", "Cy", "Denver"],
+        ],
+    );
+    wrk.create(
+        "right.csv",
+        vec![
+            svec!["id", "name", "city"],
+            svec!["1", "Ann", "Cambridge"],
+            svec!["3", "Cy", "Denver"],
+            svec!["4", "Di", "Erie"],
+        ],
+    );
+}
+
+fn make_diff(wrk: &Workdir) {
+    let mut cmd = wrk.command("diff");
+    cmd.args(["left.csv", "right.csv", "--key", "id", "-o", "changes.csv"]);
+    wrk.assert_success(&mut cmd);
+}
+
+#[test]
+fn patch_reproduces_right() {
+    let wrk = Workdir::new("patch_reproduces_right");
+    left_and_right(&wrk);
+    make_diff(&wrk);
+
+    let mut cmd = wrk.command("patch");
+    cmd.args(["--key", "id", "left.csv", "changes.csv"]);
+
+    let got: Vec<Vec<String>> = wrk.read_stdout_on_success(&mut cmd);
+    let expected = vec![
+        svec!["id", "name", "city"],
+        svec!["1", "Ann", "Cambridge"],
+        svec!["3", "Cy", "Denver"],
+        svec!["4", "Di", "Erie"],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn patch_reverse_reproduces_left() {
+    let wrk = Workdir::new("patch_reverse_reproduces_left");
+    left_and_right(&wrk);
+    make_diff(&wrk);
+
+    let mut cmd = wrk.command("patch");
+    cmd.args(["--key", "id", "--reverse", "right.csv", "changes.csv"]);
+
+    let got: Vec<Vec<String>> = wrk.read_stdout_on_success(&mut cmd);
+    // the deleted record comes back at the end
+    let expected = vec![
+        svec!["id", "name", "city"],
+        svec!["1", "Ann", "Boston"],
+        svec!["3", "Cy", "Denver"],
+        svec!["2", "Bob", "Austin"],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn patch_default_key_is_first_column() {
+    let wrk = Workdir::new("patch_default_key_is_first_column");
+    left_and_right(&wrk);
+    make_diff(&wrk);
+
+    let mut cmd = wrk.command("patch");
+    cmd.args(["left.csv", "changes.csv"]);
+
+    let got: Vec<Vec<String>> = wrk.read_stdout_on_success(&mut cmd);
+    assert_eq!(got.len(), 4);
+    assert_eq!(got[1], svec!["1", "Ann", "Cambridge"]);
+}
+
+#[test]
+fn patch_conflict_modified_base_error() {
+    let wrk = Workdir::new("patch_conflict_modified_base_error");
+    left_and_right(&wrk);
+    make_diff(&wrk);
+
+    // the base changed since the diff was computed
+    wrk.create(
+        "base.csv",
+        vec![
+            svec!["id", "name", "city"],
+            svec!["1", "Ann", "Brookline"],
+            svec!["2", "Bob", "Austin"],
+            svec!["3", "Cy", "Denver"],
+        ],
+    );
+
+    let mut cmd = wrk.command("patch");
+    cmd.args(["--key", "id", "base.csv", "changes.csv"]);
+
+    let got = wrk.output_stderr(&mut cmd);
+    assert!(
+        got.contains("(key: 1): record differs from the diff result"),
+        "{got}"
+    );
+    wrk.assert_err(&mut cmd);
+}
+
+#[test]
+fn patch_skip_conflicts_writes_conflicts_file() {
+    let wrk = Workdir::new("patch_skip_conflicts_writes_conflicts_file");
+    left_and_right(&wrk);
+    make_diff(&wrk);
+
+    // record 1 changed, record 2 is already gone and record 4 already exists
+    wrk.create(
+        "base.csv",
+        vec![
+            svec!["id", "name", "city"],
+            svec!["1", "Ann", "Brookline"],
+            svec!["3", "Cy", "Denver"],
+            svec!["4", "Di", "Erie"],
+        ],
+    );
+
+    let mut cmd = wrk.command("patch");
+    cmd.args([
+        "--key",
+        "id",
+        "--skip-conflicts",
+        "--conflicts",
+        "conflicts.csv",
+        "base.csv",
+        "changes.csv",
+    ]);
+
+    let got: Vec<Vec<String>> = wrk.read_stdout_on_success(&mut cmd);
+    let expected = vec![
+        svec!["id", "name", "city"],
+        svec!["1", "Ann", "Brookline"],
+        svec!["3", "Cy", "Denver"],
+        svec!["4", "Di", "Erie"],
+    ];
+    assert_eq!(got, expected);
+
+    let conflicts: Vec<Vec<String>> = wrk.read_csv("conflicts.csv");
+    let expected_conflicts = vec![
+        svec!["conflict", "patch_line", "diffresult", "id", "name", "city"],
+        svec![
+            "record differs from the diff result",
+            "2",
+            "-",
+            "1",
+            "Ann",
+            "Boston"
+        ],
+        svec![
+            "record differs from the diff result",
+            "2",
+            "+",
+            "1",
+            "Ann",
+            "Cambridge"
+        ],
+        svec!["record to add already exists", "5", "+", "4", "Di", "Erie"],
+        svec![
+            "record to delete or modify not found",
+            "4",
+            "-",
+            "2",
+            "Bob",
+            "Austin"
+        ],
+    ];
+    assert_eq!(conflicts, expected_conflicts);
+}
+
+#[test]
+fn patch_diff_with_aligned_columns() {
+    let wrk = Workdir::new("patch_diff_with_aligned_columns");
+    left_and_right(&wrk);
+
+    // the diff result's columns are in a different order than the base
+    wrk.create(
+        "changes.csv",
+        vec![
+            svec!["diffresult", "city", "id", "name"],
+            svec!["-", "Boston", "1", "Ann"],
+            svec!["+", "Cambridge", "1", "Ann"],
+        ],
+    );
+
+    let mut cmd = wrk.command("patch");
+    cmd.args(["--key", "id", "left.csv", "changes.csv"]);
+
+    let got: Vec<Vec<String>> = wrk.read_stdout_on_success(&mut cmd);
+    let expected = vec![
+        svec!["id", "name", "city"],
+        svec!["1", "Ann", "Cambridge"],
+        svec!["2", "Bob", "Austin"],
+        svec!["3", "Cy", "Denver"],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn patch_duplicate_column_names() {
+    let wrk = Workdir::new("patch_duplicate_column_names");
+    wrk.create(
+        "base.csv",
+        vec![
+            svec!["id", "phone", "phone"],
+            svec!["1", "111", "222"],
+            svec!["2", "444", "555"],
+        ],
+    );
+    // each "phone" column of the base is the same "phone" column of the diff result
+    wrk.create(
+        "changes.csv",
+        vec![
+            svec!["diffresult", "id", "phone", "phone"],
+            svec!["-", "1", "111", "222"],
+            svec!["+", "1", "111", "333"],
+        ],
+    );
+
+    let mut cmd = wrk.command("patch");
+    cmd.args(["--key", "id", "base.csv", "changes.csv"]);
+
+    let got: Vec<Vec<String>> = wrk.read_stdout_on_success(&mut cmd);
+    let expected = vec![
+        svec!["id", "phone", "phone"],
+        svec!["1", "111", "333"],
+        svec!["2", "444", "555"],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn patch_not_a_diff_result_error() {
+    let wrk = Workdir::new("patch_not_a_diff_result_error");
+    left_and_right(&wrk);
+
+    let mut cmd = wrk.command("patch");
+    cmd.args(["left.csv", "right.csv"]);
+
+    let got = wrk.output_stderr(&mut cmd);
+    assert!(got.contains("is not a `qsv diff` result"), "{got}");
+    wrk.assert_err(&mut cmd);
+}
@@ -835,3 +835,226 @@ fn diagnose_schema_generation_s390x() {
     // This test always passes - we just want the diagnostic output
     // The actual assertion failures are in the other tests
 }
+
+#[test]
+#[file_serial]
+fn generate_schema_format_frictionless_and_validate() {
+    let wrk = Workdir::new("generate_schema_format_frictionless_and_validate").flexible(true);
+    wrk.clear_contents().unwrap();
+
+    let csv = wrk.load_test_resource("adur-public-toilets.csv");
+    wrk.create_from_string("adur-public-toilets.csv", &csv);
+
+    let mut cmd = wrk.command("schema");
+    cmd.arg("adur-public-toilets.csv")
+        .args(["--format", "frictionless"]);
+    wrk.assert_success(&mut cmd);
+
+    let output_schema_string: String =
+        wrk.from_str(&wrk.path("adur-public-toilets.csv.tableschema.json"));
+    let output_schema_json: Value =
+        serde_json::from_str(&output_schema_string).expect("parse table schema json");
+    let fields = output_schema_json["fields"].as_array().unwrap();
+    assert_eq!(fields[0]["name"], "ExtractDate");
+    assert_eq!(output_schema_json["missingValues"], serde_json::json!([""]));
+
+    // the original data is valid against its own Table Schema
+    let mut cmd = wrk.command("validate");
+    cmd.arg("adur-public-toilets.csv")
+        .arg("--trim")
+        .arg("adur-public-toilets.csv.tableschema.json");
+    wrk.assert_success(&mut cmd);
+    assert!(!Path::new(&wrk.path("adur-public-toilets.csv.validation-errors.tsv")).exists());
+}
+
+#[test]
+#[file_serial]
+fn generate_schema_format_postgres() {
+    let wrk = Workdir::new("generate_schema_format_postgres");
+    wrk.clear_contents().unwrap();
+    wrk.create_from_string(
+        "in.csv",
+        "id,status,score\n1,open,1.5\n2,closed,\n3,o'pen,2.5\n",
+    );
+
+    // a stats cache with cardinality, so the primary key candidate can be proven
+    let mut stats_cmd = wrk.command("stats");
+    stats_cmd.args(["in.csv", "--cardinality", "--stats-jsonl"]);
+    wrk.assert_success(&mut stats_cmd);
+
+    let mut cmd = wrk.command("schema");
+    cmd.args(["in.csv", "--format", "postgres", "--stdout"]);
+    let got: String = wrk.stdout(&mut cmd);
+
+    assert!(got.starts_with("-- Inferred schema with `qsv schema"));
+    assert!(got.contains("CREATE TABLE \"in\" ("));
+    assert!(got.contains("\"id\" BIGINT NOT NULL"));
+    assert!(got.contains("\"status\" TEXT NOT NULL CHECK (\"status\" IN ("));
+    assert!(got.contains("'o''pen'"));
+    assert!(got.contains("\"score\" DOUBLE PRECISION,"));
+    assert!(got.contains("PRIMARY KEY (\"id\")"));
+}
+
+#[test]
+fn generate_schema_invalid_format() {
+    let wrk = Workdir::new("generate_schema_invalid_format");
+    wrk.create_from_string("in.csv", "a\n1\n");
+
+    let mut cmd = wrk.command("schema");
+    cmd.args(["in.csv", "--format", "xsd"]);
+    wrk.assert_err(&mut cmd);
+}
+
+#[test]
+#[file_serial]
+fn generate_schema_compare_breaking_drift() {
+    let wrk = Workdir::new("generate_schema_compare_breaking_drift");
+    wrk.clear_contents().unwrap();
+    wrk.create_from_string("jan.csv", "id,status,amount\n1,open,10\n2,closed,20\n");
+    wrk.create_from_string(
+        "feb.csv",
+        "id,status,amount,note\n1,open,10.5,x\n2,pending,,y\n",
+    );
+
+    let mut cmd = wrk.command("schema");
+    cmd.arg("jan.csv");
+    wrk.assert_success(&mut cmd);
+
+    let mut cmd = wrk.command("schema");
+    cmd.args([
+        "feb.csv",
+        "--compare",
+        "jan.csv.schema.json",
+        "--output",
+        "drift.csv",
+    ]);
+    wrk.assert_err(&mut cmd);
+
+    let got: Vec<Vec<String>> = wrk.read_csv("drift.csv");
+    assert_eq!(
+        got[0],
+        svec!["severity", "column", "change", "baseline", "current"]
+    );
+    assert_eq!(
+        got[1],
+        svec!["non-breaking", "note", "added", "", "position 4"]
+    );
+    assert_eq!(
+        got[2],
+        svec![
+            "breaking",
+            "status",
+            "enum values added",
+            "2 values",
+            "pending"
+        ]
+    );
+    assert_eq!(
+        got[3],
+        svec![
+            "non-breaking",
+            "status",
+            "enum values removed",
+            "closed",
+            "2 values"
+        ]
+    );
+    assert_eq!(
+        got[4],
+        svec!["breaking", "amount", "type widened", "integer", "number"]
+    );
+    assert_eq!(got[5][..3], svec!["breaking", "amount", "nulls introduced"]);
+    assert!(got[5][4].starts_with("nullable"));
+    assert_eq!(got.len(), 6);
+}
+
+#[test]
+#[file_serial]
+fn generate_schema_compare_null_ratio_increased() {
+    let wrk = Workdir::new("generate_schema_compare_null_ratio_increased");
+    wrk.clear_contents().unwrap();
+    wrk.create_from_string("jan.csv", "id,amount\n1,10\n2,\n3,30\n4,40\n");
+    wrk.create_from_string("feb.csv", "id,amount\n1,10\n2,\n3,\n4,\n");
+
+    let mut cmd = wrk.command("schema");
+    cmd.args(["jan.csv", "--null-ratios"]);
+    wrk.assert_success(&mut cmd);
+
+    let baseline_string: String = wrk.from_str(&wrk.path("jan.csv.schema.json"));
+    let baseline: Value = serde_json::from_str(&baseline_string).unwrap();
+    assert_eq!(
+        baseline["properties"]["amount"]["x-qsv"]["null_ratio"],
+        serde_json::json!(0.25)
+    );
+    assert!(baseline["properties"]["id"].get("x-qsv").is_none());
+
+    // still valid against the baseline, so the null ratio jump is non-breaking
+    let mut cmd = wrk.command("schema");
+    cmd.args(["feb.csv", "--compare", "jan.csv.schema.json"]);
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    assert!(got.contains(&svec![
+        "non-breaking",
+        "amount",
+        "null ratio increased",
+        "25.0% null",
+        "75.0% null"
+    ]));
+
+    let mut cmd = wrk.command("schema");
+    cmd.args([
+        "feb.csv",
+        "--compare",
+        "jan.csv.schema.json",
+        "--null-threshold",
+        "0.6",
+    ]);
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    assert!(!got.iter().any(|row| row[2] == "null ratio increased"));
+}
+
+#[test]
+#[file_serial]
+fn generate_schema_compare_without_null_ratios() {
+    let wrk = Workdir::new("generate_schema_compare_without_null_ratios");
+    wrk.clear_contents().unwrap();
+    wrk.create_from_string("jan.csv", "id,amount\n1,10\n2,\n3,30\n4,40\n");
+    wrk.create_from_string("feb.csv", "id,amount\n1,10\n2,\n3,\n4,\n");
+
+    // null ratios are only recorded with --null-ratios
+    let mut cmd = wrk.command("schema");
+    cmd.arg("jan.csv");
+    wrk.assert_success(&mut cmd);
+
+    let baseline_string: String = wrk.from_str(&wrk.path("jan.csv.schema.json"));
+    let baseline: Value = serde_json::from_str(&baseline_string).unwrap();
+    assert!(baseline["properties"]["amount"].get("x-qsv").is_none());
+
+    let mut cmd = wrk.command("schema");
+    cmd.args(["feb.csv", "--compare", "jan.csv.schema.json"]);
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    assert!(!got.iter().any(|row| row[2] == "null ratio increased"));
+
+    let got = wrk.output_stderr(&mut cmd);
+    assert!(got.contains("null ratio drift wasn't checked for: amount."));
+}
+
+#[test]
+#[file_serial]
+fn generate_schema_compare_no_drift() {
+    let wrk = Workdir::new("generate_schema_compare_no_drift");
+    wrk.clear_contents().unwrap();
+    wrk.create_from_string("jan.csv", "id,status\n1,open\n2,closed\n");
+    wrk.create_from_string("feb.csv", "id,status\n2,closed\n1,open\n");
+
+    let mut cmd = wrk.command("schema");
+    cmd.arg("jan.csv");
+    wrk.assert_success(&mut cmd);
+
+    let mut cmd = wrk.command("schema");
+    cmd.args(["feb.csv", "--compare", "jan.csv.schema.json"]);
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    assert_eq!(
+        got,
+        vec![svec!["severity", "column", "change", "baseline", "current"]]
+    );
+}
@@ -788,6 +788,277 @@ fn sort_from_zip_reads_decompressed() {
     assert_eq!(got[0][0], "case_enquiry_id");
 }
 
+// unsorted single column; tampered cache says Ascending -> passthrough
+// (output preserves the unsorted input order, proving the sort was skipped).
+#[test]
+fn sort_statscache_passthrough() {
+    let wrk = Workdir::new("sort_statscache_passthrough");
+    wrk.create_from_string("in.csv", "name\nbanana\napple\ncherry\n");
+    wrk.build_stats_cache("in.csv", false);
+    wrk.tamper_sort_order("in", "name", "Ascending");
+
+    let mut cmd = wrk.command("sort");
+    cmd.arg("in.csv");
+
+    let got: String = wrk.stdout(&mut cmd);
+    assert_eq!(got, "name\nbanana\napple\ncherry");
+}
+
+// same tampered cache, but QSV_STATSCACHE_MODE=none disables the catalog -> real sort
+#[test]
+fn sort_statscache_disabled() {
+    let wrk = Workdir::new("sort_statscache_disabled");
+    wrk.create_from_string("in.csv", "name\nbanana\napple\ncherry\n");
+    wrk.build_stats_cache("in.csv", false);
+    wrk.tamper_sort_order("in", "name", "Ascending");
+
+    let mut cmd = wrk.command("sort");
+    cmd.env("QSV_STATSCACHE_MODE", "none").arg("in.csv");
+
+    let got: String = wrk.stdout(&mut cmd);
+    assert_eq!(got, "name\napple\nbanana\ncherry");
+}
+
+// --unique without a cached cardinality cannot be proven -> real sort and dedupe
+#[test]
+fn sort_statscache_unique_not_proven() {
+    let wrk = Workdir::new("sort_statscache_unique_not_proven");
+    wrk.create_from_string("in.csv", "name\nbanana\napple\nbanana\n");
+    wrk.build_stats_cache("in.csv", false);
+    wrk.tamper_sort_order("in", "name", "Ascending");
+
+    let mut cmd = wrk.command("sort");
+    cmd.arg("--unique").arg("in.csv");
+
+    let got: String = wrk.stdout(&mut cmd);
+    assert_eq!(got, "name\napple\nbanana");
+}
+
+fn order_fixture(wrk: &Workdir) {
+    wrk.create(
+        "in.csv",
+        vec![
+            svec!["state", "population", "founded"],
+            svec!["NY", "900", "2001-03-04"],
+            svec!["CA", "1000", "1999-12-31"],
+            svec!["NY", "", "1850-01-01"],
+            svec!["CA", "20", "3/5/1999"],
+            svec!["NY", "10000", "2001-03-04"],
+            svec!["ca", "20", ""],
+        ],
+    );
+}
+
+#[test]
+fn sort_order_multi_key() {
+    let wrk = Workdir::new("sort_order_multi_key");
+    order_fixture(&wrk);
+
+    let mut cmd = wrk.command("sort");
+    cmd.args([
+        "--order",
+        "state:icase, population:numeric desc, founded:date",
+    ])
+    .arg("in.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["state", "population", "founded"],
+        svec!["CA", "1000", "1999-12-31"],
+        svec!["ca", "20", ""],
+        svec!["CA", "20", "3/5/1999"],
+        svec!["NY", "10000", "2001-03-04"],
+        svec!["NY", "900", "2001-03-04"],
+        svec!["NY", "", "1850-01-01"],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn sort_order_nulls_and_indices() {
+    let wrk = Workdir::new("sort_order_nulls_and_indices");
+    order_fixture(&wrk);
+
+    let mut cmd = wrk.command("sort");
+    cmd.args(["--order", "3:date desc nulls first, 2:numeric"])
+        .arg("in.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["state", "population", "founded"],
+        svec!["ca", "20", ""],
+        svec!["NY", "900", "2001-03-04"],
+        svec!["NY", "10000", "2001-03-04"],
+        svec!["CA", "1000", "1999-12-31"],
+        svec!["CA", "20", "3/5/1999"],
+        svec!["NY", "", "1850-01-01"],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn sort_order_unique() {
+    let wrk = Workdir::new("sort_order_unique");
+    order_fixture(&wrk);
+
+    let mut cmd = wrk.command("sort");
+    cmd.args(["--order", "state:icase"])
+        .arg("--unique")
+        .arg("in.csv");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["state", "population", "founded"],
+        svec!["CA", "1000", "1999-12-31"],
+        svec!["NY", "900", "2001-03-04"],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn sort_order_errors() {
+    let wrk = Workdir::new("sort_order_errors");
+    order_fixture(&wrk);
+
+    let mut cmd = wrk.command("sort");
+    cmd.args(["--order", "state", "--reverse"]).arg("in.csv");
+    wrk.assert_err(&mut cmd);
+
+    let mut cmd = wrk.command("sort");
+    cmd.args(["--order", "nope:numeric"]).arg("in.csv");
+    wrk.assert_err(&mut cmd);
+
+    let mut cmd = wrk.command("sort");
+    cmd.args(["--order", "state nulls sometimes"]).arg("in.csv");
+    wrk.assert_err(&mut cmd);
+}
+
+#[test]
+fn sort_collation() {
+    let wrk = Workdir::new("sort_collation");
+    wrk.create(
+        "in.csv",
+        vec![
+            svec!["name"],
+            svec!["Zebra"],
+            svec!["Äpfel"],
+            svec!["apple"],
+            svec!["Ob"],
+        ],
+    );
+
+    // German sorts Ä with A, Swedish after Z
+    let mut cmd = wrk.command("sort");
+    cmd.args(["--collation", "de"]).arg("in.csv");
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["name"],
+        svec!["apple"],
+        svec!["Äpfel"],
+        svec!["Ob"],
+        svec!["Zebra"],
+    ];
+    assert_eq!(got, expected);
+
+    let mut cmd = wrk.command("sort");
+    cmd.args(["--collation", "sv"]).arg("in.csv");
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["name"],
+        svec!["apple"],
+        svec!["Ob"],
+        svec!["Zebra"],
+        svec!["Äpfel"],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn sort_order_collate() {
+    let wrk = Workdir::new("sort_order_collate");
+    wrk.create(
+        "in.csv",
+        vec![
+            svec!["name", "n"],
+            svec!["Zebra", "1"],
+            svec!["Äpfel", "2"],
+            svec!["apple", "3"],
+            svec!["Ob", "4"],
+        ],
+    );
+
+    let mut cmd = wrk.command("sort");
+    cmd.args(["--order", "name:collate=de"]).arg("in.csv");
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["name", "n"],
+        svec!["apple", "3"],
+        svec!["Äpfel", "2"],
+        svec!["Ob", "4"],
+        svec!["Zebra", "1"],
+    ];
+    assert_eq!(got, expected);
+
+    let mut cmd = wrk.command("sort");
+    cmd.args(["--order", "name:collate=sv desc"]).arg("in.csv");
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["name", "n"],
+        svec!["Äpfel", "2"],
+        svec!["Zebra", "1"],
+        svec!["Ob", "4"],
+        svec!["apple", "3"],
+    ];
+    assert_eq!(got, expected);
+
+    // the collation is set per key, not with --collation
+    let mut cmd = wrk.command("sort");
+    cmd.args(["--order", "name", "--collation", "de"])
+        .arg("in.csv");
+    wrk.assert_err(&mut cmd);
+
+    let mut cmd = wrk.command("sort");
+    cmd.args(["--order", "name:collate=@@"]).arg("in.csv");
+    wrk.assert_err(&mut cmd);
+}
+
+#[test]
+fn sort_normalize_unique() {
+    let wrk = Workdir::new("sort_normalize_unique");
+    wrk.create(
+        "in.csv",
+        vec![svec!["name"], svec!["fish"], svec!["ﬁsh"], svec!["cod"]],
+    );
+
+    let mut cmd = wrk.command("sort");
+    cmd.args(["--normalize", "nfkc"])
+        .arg("--unique")
+        .arg("in.csv");
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![svec!["name"], svec!["cod"], svec!["fish"]];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn sort_collation_errors() {
+    let wrk = Workdir::new("sort_collation_errors");
+    wrk.create("in.csv", vec![svec!["name"], svec!["a"]]);
+
+    let mut cmd = wrk.command("sort");
+    cmd.args(["--collation", "de"])
+        .arg("--numeric")
+        .arg("in.csv");
+    wrk.assert_err(&mut cmd);
+
+    let mut cmd = wrk.command("sort");
+    cmd.args(["--normalize", "nfd"]).arg("in.csv");
+    wrk.assert_err(&mut cmd);
+
+    let mut cmd = wrk.command("sort");
+    cmd.args(["--collation", "not a locale"]).arg("in.csv");
+    wrk.assert_err(&mut cmd);
+}
+
 /// Order `a` and `b` lexicographically using `Ord`
 pub fn iter_cmp<A, L, R>(mut a: L, mut b: R) -> cmp::Ordering
 where
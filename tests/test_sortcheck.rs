@@ -434,40 +434,14 @@ fn sortcheck_simple_all_json_progressbar() {
 // consulted (short-circuit fired) vs. a full scan happened (fell through).
 // ---------------------------------------------------------------------------
 
-fn build_stats_cache(wrk: &Workdir, csv: &str) {
-    let mut stats_cmd = wrk.command("stats");
-    stats_cmd.arg(csv).arg("--stats-jsonl");
-    wrk.assert_success(&mut stats_cmd);
-}
-
-fn tamper_sort_order(wrk: &Workdir, stem: &str, field: &str, new_order: &str) {
-    let path = wrk.path(&format!("{stem}.stats.csv.data.jsonl"));
-    let contents = std::fs::read_to_string(&path).unwrap();
-    let mut lines = Vec::new();
-    let mut found = false;
-    for line in contents.lines() {
-        if line.trim().is_empty() {
-            continue;
-        }
-        let mut v: serde_json::Value = serde_json::from_str(line).unwrap();
-        if v.get("field").and_then(serde_json::Value::as_str) == Some(field) {
-            v["sort_order"] = serde_json::Value::String(new_order.to_string());
-            found = true;
-        }
-        lines.push(serde_json::to_string(&v).unwrap());
-    }
-    assert!(found, "field {field} not found in stats cache");
-    std::fs::write(&path, lines.join("\n")).unwrap();
-}
-
 // genuinely unsorted single String column; the cache (after tamper) claims it
 // is Ascending, so a short-circuit returns "sorted" (exit 0) without scanning.
 #[test]
 fn sortcheck_statscache_shortcircuit() {
     let wrk = Workdir::new("sortcheck_statscache_shortcircuit");
     wrk.create_from_string("in.csv", "name\nbanana\napple\ncherry\n");
-    build_stats_cache(&wrk, "in.csv");
-    tamper_sort_order(&wrk, "in", "name", "Ascending");
+    wrk.build_stats_cache("in.csv", false);
+    wrk.tamper_sort_order("in", "name", "Ascending");
 
     let mut cmd = wrk.command("sortcheck");
     cmd.args(["--select", "name"]).arg("in.csv");
@@ -480,8 +454,8 @@ fn sortcheck_statscache_shortcircuit() {
 fn sortcheck_statscache_optout_none() {
     let wrk = Workdir::new("sortcheck_statscache_optout_none");
     wrk.create_from_string("in.csv", "name\nbanana\napple\ncherry\n");
-    build_stats_cache(&wrk, "in.csv");
-    tamper_sort_order(&wrk, "in", "name", "Ascending");
+    wrk.build_stats_cache("in.csv", false);
+    wrk.tamper_sort_order("in", "name", "Ascending");
 
     let mut cmd = wrk.command("sortcheck");
     cmd.env("QSV_STATSCACHE_MODE", "none")
@@ -495,8 +469,8 @@ fn sortcheck_statscache_optout_none() {
 fn sortcheck_statscache_ignorecase_no_shortcircuit() {
     let wrk = Workdir::new("sortcheck_statscache_ignorecase_no_shortcircuit");
     wrk.create_from_string("in.csv", "name\nbanana\napple\ncherry\n");
-    build_stats_cache(&wrk, "in.csv");
-    tamper_sort_order(&wrk, "in", "name", "Ascending");
+    wrk.build_stats_cache("in.csv", false);
+    wrk.tamper_sort_order("in", "name", "Ascending");
 
     let mut cmd = wrk.command("sortcheck");
     cmd.arg("--ignore-case")
@@ -510,9 +484,9 @@ fn sortcheck_statscache_ignorecase_no_shortcircuit() {
 fn sortcheck_statscache_multicolumn_no_shortcircuit() {
     let wrk = Workdir::new("sortcheck_statscache_multicolumn_no_shortcircuit");
     wrk.create_from_string("in.csv", "c1,c2\nb,2\na,1\nc,3\n");
-    build_stats_cache(&wrk, "in.csv");
-    tamper_sort_order(&wrk, "in", "c1", "Ascending");
-    tamper_sort_order(&wrk, "in", "c2", "Ascending");
+    wrk.build_stats_cache("in.csv", false);
+    wrk.tamper_sort_order("in", "c1", "Ascending");
+    wrk.tamper_sort_order("in", "c2", "Ascending");
 
     let mut cmd = wrk.command("sortcheck");
     cmd.args(["--select", "c1,c2"]).arg("in.csv");
@@ -525,8 +499,8 @@ fn sortcheck_statscache_multicolumn_no_shortcircuit() {
 fn sortcheck_statscache_nullcount_guard() {
     let wrk = Workdir::new("sortcheck_statscache_nullcount_guard");
     wrk.create_from_string("in.csv", "id,name\n1,banana\n2,\n3,apple\n");
-    build_stats_cache(&wrk, "in.csv");
-    tamper_sort_order(&wrk, "in", "name", "Ascending");
+    wrk.build_stats_cache("in.csv", false);
+    wrk.tamper_sort_order("in", "name", "Ascending");
 
     let mut cmd = wrk.command("sortcheck");
     cmd.args(["--select", "name"]).arg("in.csv");
@@ -539,8 +513,8 @@ fn sortcheck_statscache_nullcount_guard() {
 fn sortcheck_statscache_json_full_scan() {
     let wrk = Workdir::new("sortcheck_statscache_json_full_scan");
     wrk.create_from_string("in.csv", "name\nbanana\napple\ncherry\n");
-    build_stats_cache(&wrk, "in.csv");
-    tamper_sort_order(&wrk, "in", "name", "Ascending");
+    wrk.build_stats_cache("in.csv", false);
+    wrk.tamper_sort_order("in", "name", "Ascending");
 
     let mut cmd = wrk.command("sortcheck");
     cmd.arg("--json").args(["--select", "name"]).arg("in.csv");
@@ -558,7 +532,7 @@ fn sortcheck_statscache_json_full_scan() {
 fn sortcheck_statscache_valid_positive() {
     let wrk = Workdir::new("sortcheck_statscache_valid_positive");
     wrk.create_from_string("in.csv", "name\napple\nbanana\ncherry\n");
-    build_stats_cache(&wrk, "in.csv");
+    wrk.build_stats_cache("in.csv", false);
 
     let mut cmd = wrk.command("sortcheck");
     cmd.args(["--select", "name"]).arg("in.csv");
@@ -571,8 +545,8 @@ fn sortcheck_statscache_valid_positive() {
 fn sortcheck_statscache_options_mismatch_no_shortcircuit() {
     let wrk = Workdir::new("sortcheck_statscache_options_mismatch_no_shortcircuit");
     wrk.create_from_string("in.csv", "name\nbanana\napple\ncherry\n");
-    build_stats_cache(&wrk, "in.csv");
-    tamper_sort_order(&wrk, "in", "name", "Ascending");
+    wrk.build_stats_cache("in.csv", false);
+    wrk.tamper_sort_order("in", "name", "Ascending");
 
     let mut cmd = wrk.command("sortcheck");
     // --no-headers makes "name" a data row; the column is not ascending
@@ -587,8 +561,8 @@ fn sortcheck_statscache_options_mismatch_no_shortcircuit() {
 fn sortcheck_statscache_missing_metadata_no_shortcircuit() {
     let wrk = Workdir::new("sortcheck_statscache_missing_metadata_no_shortcircuit");
     wrk.create_from_string("in.csv", "name\nbanana\napple\ncherry\n");
-    build_stats_cache(&wrk, "in.csv");
-    tamper_sort_order(&wrk, "in", "name", "Ascending");
+    wrk.build_stats_cache("in.csv", false);
+    wrk.tamper_sort_order("in", "name", "Ascending");
     std::fs::remove_file(wrk.path("in.stats.csv.json")).unwrap();
 
     let mut cmd = wrk.command("sortcheck");
@@ -699,3 +673,21 @@ fn sortcheck_rejects_cache_whose_sidecar_cannot_prove_input_size() {
     cmd.args(["--select", "id"]).arg("--numeric").arg("sc2.csv");
     wrk.assert_err(&mut cmd);
 }
+
+#[test]
+fn sortcheck_collation() {
+    let wrk = Workdir::new("sortcheck_collation");
+    wrk.create(
+        "in.csv",
+        vec![svec!["name"], svec!["apple"], svec!["Äpfel"], svec!["Ob"]],
+    );
+
+    // sorted in German, but not byte-wise
+    let mut cmd = wrk.command("sortcheck");
+    cmd.args(["--collation", "de"]).arg("in.csv");
+    wrk.assert_success(&mut cmd);
+
+    let mut cmd = wrk.command("sortcheck");
+    cmd.arg("in.csv");
+    wrk.assert_err(&mut cmd);
+}
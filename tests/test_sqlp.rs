@@ -4599,3 +4599,366 @@ fn sqlp_invalid_format_errors() {
 
     wrk.assert_err(&mut cmd);
 }
+
+#[test]
+fn sqlp_catalog_view_without_inputs() {
+    let wrk = Workdir::new("sqlp_catalog_view_without_inputs");
+    wrk.create(
+        "customers.csv",
+        vec![
+            svec!["id", "name", "email"],
+            svec!["1", "alice", "alice@example.com"],
+            svec!["2", "bob", ""],
+            svec!["3", "carol", "carol@example.com"],
+        ],
+    );
+    wrk.create_from_string(
+        "project.toml",
+        r#"
+[tables.customers]
+path = "customers.csv"
+
+[[views]]
+name = "customers_clean"
+sql = "SELECT id, name FROM customers WHERE email IS NOT NULL"
+
+[[views]]
+name = "customer_names"
+sql = "SELECT name FROM customers_clean"
+"#,
+    );
+
+    let mut cmd = wrk.command("sqlp");
+    cmd.args([
+        "--catalog",
+        "project.toml",
+        "select * from customer_names order by name desc",
+    ]);
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![svec!["name"], svec!["carol"], svec!["alice"]];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn sqlp_catalog_discovered_with_inputs() {
+    let wrk = Workdir::new("sqlp_catalog_discovered_with_inputs");
+    wrk.create(
+        "customers.csv",
+        vec![svec!["id", "name"], svec!["1", "alice"], svec!["2", "bob"]],
+    );
+    wrk.create(
+        "orders.csv",
+        vec![
+            svec!["order_id", "customer_id"],
+            svec!["10", "2"],
+            svec!["11", "1"],
+        ],
+    );
+    wrk.create_from_string(
+        "qsv-catalog.json",
+        r#"{"tables": {"customers": {"path": "customers.csv"}}}"#,
+    );
+
+    let mut cmd = wrk.command("sqlp");
+    cmd.args([
+        "orders.csv",
+        "select o.order_id, c.name from _t_1 o join customers c on o.customer_id = c.id order by \
+         o.order_id",
+    ]);
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["order_id", "name"],
+        svec!["10", "bob"],
+        svec!["11", "alice"],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn sqlp_no_inputs_without_catalog_errors() {
+    let wrk = Workdir::new("sqlp_no_inputs_without_catalog_errors");
+
+    let mut cmd = wrk.command("sqlp");
+    cmd.arg("select 1 as one");
+
+    wrk.assert_err(&mut cmd);
+}
+
+fn create_params_cities(wrk: &Workdir) {
+    wrk.create(
+        "cities.csv",
+        vec![
+            svec!["city", "state", "pop", "founded"],
+            svec!["Albany", "NY", "99224", "1686-07-22"],
+            svec!["Buffalo", "NY", "278349", "1789-01-01"],
+            svec!["Fresno", "CA", "542107", "1872-05-01"],
+            svec!["Oakland", "CA", "440646", "1852-05-04"],
+        ],
+    );
+}
+
+#[test]
+fn sqlp_params_cli() {
+    let wrk = Workdir::new("sqlp_params_cli");
+    create_params_cities(&wrk);
+
+    let mut cmd = wrk.command("sqlp");
+    cmd.args(["--param", "state=NY", "--param", "min_pop:int=100000"])
+        .arg("cities.csv")
+        .arg(
+            "select city, ':state' as lit from cities where state = :state and pop::int >= \
+             $min_pop -- :not_a_param",
+        );
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![svec!["city", "lit"], svec!["Buffalo", ":state"]];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn sqlp_params_negative_number() {
+    let wrk = Workdir::new("sqlp_params_negative_number");
+    create_params_cities(&wrk);
+
+    // bound as (-5), so `pop::int-:delta` doesn't become `pop::int--5`, a comment
+    let mut cmd = wrk.command("sqlp");
+    cmd.args(["--param", "delta=-5", "--param", "min:float=-1.5"])
+        .arg("cities.csv")
+        .arg(
+            "select city, pop::int-:delta as adjusted from cities where pop::int>:min and city = \
+             'Albany'",
+        );
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![svec!["city", "adjusted"], svec!["Albany", "99229"]];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn sqlp_params_are_not_injected() {
+    let wrk = Workdir::new("sqlp_params_are_not_injected");
+    create_params_cities(&wrk);
+
+    let mut cmd = wrk.command("sqlp");
+    cmd.args(["--param", "state=NY' OR '1'='1"])
+        .arg("cities.csv")
+        .arg("select city from cities where state = :state");
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    assert_eq!(got, vec![svec!["city"]]);
+}
+
+#[test]
+fn sqlp_params_file_and_env() {
+    let wrk = Workdir::new("sqlp_params_file_and_env");
+    create_params_cities(&wrk);
+    wrk.create_from_string(
+        "params.json",
+        r#"{"cities": ["Albany", "Fresno", "Oakland"], "since:date": "1800-01-01", "state": "NY"}"#,
+    );
+
+    // the env var takes precedence over the params file
+    let mut cmd = wrk.command("sqlp");
+    cmd.env("QSV_SQLP_PARAM_STATE", "CA")
+        .args(["--params-file", "params.json", "--try-parsedates"])
+        .arg("cities.csv")
+        .arg(
+            "select city from cities where city in (:cities) and state = :state and founded >= \
+             :since order by city",
+        );
+
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![svec!["city"], svec!["Fresno"], svec!["Oakland"]];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn sqlp_params_unbound_errors() {
+    let wrk = Workdir::new("sqlp_params_unbound_errors");
+    create_params_cities(&wrk);
+
+    let mut cmd = wrk.command("sqlp");
+    cmd.arg("cities.csv")
+        .arg("select city from cities where state = :state");
+
+    let got = wrk.output_stderr(&mut cmd);
+    assert!(got.contains("SQL parameter :state is not bound"));
+    wrk.assert_err(&mut cmd);
+}
+
+#[test]
+fn sqlp_partition_by_hive_directories() {
+    let wrk = Workdir::new("sqlp_partition_by_hive_directories");
+    wrk.create(
+        "sales.csv",
+        vec![
+            svec!["year", "month", "amount"],
+            svec!["2024", "may", "10"],
+            svec!["2024", "may", "20"],
+            svec!["2024", "jun", "30"],
+            svec!["2025", "", "40"],
+        ],
+    );
+
+    let mut cmd = wrk.command("sqlp");
+    cmd.arg("sales.csv")
+        .arg("select * from sales")
+        .args(["--format", "parquet"])
+        .args(["--partition-by", "year,month"])
+        .args(["--row-group-size", "1"])
+        .args(["--output", "sales"]);
+    wrk.assert_success(&mut cmd);
+
+    assert!(
+        wrk.path("sales/year=2024/month=may/part-0.parquet")
+            .exists()
+    );
+    assert!(
+        wrk.path("sales/year=2024/month=jun/part-0.parquet")
+            .exists()
+    );
+    assert!(
+        wrk.path("sales/year=2025/month=__HIVE_DEFAULT_PARTITION__/part-0.parquet")
+            .exists()
+    );
+
+    // the partition columns are in the path, not in the files
+    let mut cmd = wrk.command("sqlp");
+    cmd.arg("SKIP_INPUT")
+        .arg("select * from read_parquet('sales/year=2024/month=may/part-0.parquet')");
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![svec!["amount"], svec!["10"], svec!["20"]];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn sqlp_partition_by_escapes_values() {
+    let wrk = Workdir::new("sqlp_partition_by_escapes_values");
+    wrk.create("paths.csv", vec![svec!["path", "n"], svec!["a/b=c", "1"]]);
+
+    let mut cmd = wrk.command("sqlp");
+    cmd.arg("paths.csv")
+        .arg("select * from paths")
+        .args(["--format", "parquet"])
+        .args(["--partition-by", "path"])
+        .args(["--output", "out"]);
+    wrk.assert_success(&mut cmd);
+
+    assert!(wrk.path("out/path=a%2Fb%3Dc/part-0.parquet").exists());
+}
+
+#[test]
+fn sqlp_partition_by_requires_parquet_and_output() {
+    let wrk = Workdir::new("sqlp_partition_by_requires_parquet_and_output");
+    wrk.create("data.csv", vec![svec!["a", "b"], svec!["1", "2"]]);
+
+    let mut cmd = wrk.command("sqlp");
+    cmd.arg("data.csv")
+        .arg("select * from data")
+        .args(["--partition-by", "a"])
+        .args(["--output", "out"]);
+    let got = wrk.output_stderr(&mut cmd);
+    assert!(got.contains("--partition-by requires --format parquet"));
+
+    let mut cmd = wrk.command("sqlp");
+    cmd.arg("data.csv")
+        .arg("select * from data")
+        .args(["--format", "parquet"])
+        .args(["--partition-by", "a"]);
+    let got = wrk.output_stderr(&mut cmd);
+    assert!(got.contains("--partition-by requires --output"));
+
+    let mut cmd = wrk.command("sqlp");
+    cmd.arg("data.csv")
+        .arg("select * from data")
+        .args(["--format", "parquet"])
+        .args(["--partition-by", "nope"])
+        .args(["--output", "out"]);
+    let got = wrk.output_stderr(&mut cmd);
+    assert!(got.contains("--partition-by column \"nope\" is not in the query result"));
+    wrk.assert_err(&mut cmd);
+}
+
+#[test]
+fn sqlp_hive_parquet_dataset() {
+    let wrk = Workdir::new("sqlp_hive_parquet_dataset");
+    wrk.create(
+        "sales.csv",
+        vec![
+            svec!["region", "year", "amount"],
+            svec!["east", "2023", "10"],
+            svec!["east", "2024", "20"],
+            svec!["west", "2024", "30"],
+            svec!["west", "2024", "40"],
+        ],
+    );
+
+    let mut cmd = wrk.command("sqlp");
+    cmd.arg("sales.csv")
+        .arg("select * from sales")
+        .args(["--format", "parquet"])
+        .args(["--partition-by", "year"])
+        .args(["--output", "lake"]);
+    wrk.assert_success(&mut cmd);
+
+    // the partitioned directory is one table, with the partition column from the paths
+    let mut cmd = wrk.command("sqlp");
+    cmd.arg("lake").arg(
+        "select region, sum(amount) as total from lake where year = 2024 group by region order by \
+         region",
+    );
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["region", "total"],
+        svec!["east", "20"],
+        svec!["west", "70"],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn sqlp_hive_csv_glob_dataset() {
+    let wrk = Workdir::new("sqlp_hive_csv_glob_dataset");
+    std::fs::create_dir_all(wrk.path("logs/day=2024-05-01")).unwrap();
+    std::fs::create_dir_all(wrk.path("logs/day=2024-05-02")).unwrap();
+    wrk.create(
+        "logs/day=2024-05-01/part-0.csv",
+        vec![svec!["level", "msg"], svec!["info", "started"]],
+    );
+    // a later file with an extra column
+    wrk.create(
+        "logs/day=2024-05-02/part-0.csv",
+        vec![
+            svec!["level", "msg", "host"],
+            svec!["error", "failed", "db1"],
+        ],
+    );
+    wrk.create_from_string("logs/day=2024-05-01/_SUCCESS", "");
+
+    let mut cmd = wrk.command("sqlp");
+    cmd.arg("logs/day=*/")
+        .arg("select day, level, msg, host from logs order by day");
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let expected = vec![
+        svec!["day", "level", "msg", "host"],
+        svec!["2024-05-01", "info", "started", ""],
+        svec!["2024-05-02", "error", "failed", "db1"],
+    ];
+    assert_eq!(got, expected);
+}
+
+#[test]
+fn sqlp_dataset_mixed_formats_errors() {
+    let wrk = Workdir::new("sqlp_dataset_mixed_formats_errors");
+    std::fs::create_dir_all(wrk.path("mixed/part=1")).unwrap();
+    wrk.create("mixed/part=1/a.csv", vec![svec!["a"], svec!["1"]]);
+    wrk.create_from_string("mixed/part=1/b.parquet", "");
+
+    let mut cmd = wrk.command("sqlp");
+    cmd.arg("mixed").arg("select * from mixed");
+    let got = wrk.output_stderr(&mut cmd);
+    assert!(got.contains("has both CSV and Parquet files"));
+    wrk.assert_err(&mut cmd);
+}
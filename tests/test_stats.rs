@@ -6960,6 +6960,38 @@ fn stats_quantile_method_approx_percentiles_empty_numeric_column() {
     );
 }
 
+#[test]
+fn stats_quantile_method_approx_reports_rank_error() {
+    // approx quantiles carry the digest's rank error; exact ones do not, and a column whose
+    // quantiles were never estimated (all strings) leaves it empty
+    let wrk = Workdir::new("stats_quantile_method_approx_reports_rank_error");
+    let mut rows = approx_quartiles_fixture(100);
+    rows[0].push("name".to_string());
+    for row in rows.iter_mut().skip(1) {
+        row.push("x".to_string());
+    }
+    wrk.create("data.csv", rows);
+
+    let mut cmd = wrk.command("stats");
+    cmd.arg("--quartiles")
+        .args(["--quantile-method", "approx"])
+        .args(["--jobs", "1"])
+        .arg("data.csv");
+    let got: Vec<Vec<String>> = wrk.read_stdout_on_success(&mut cmd);
+    let idx = got[0]
+        .iter()
+        .position(|h| h == "quantile_rank_error")
+        .expect("quantile_rank_error column should exist under approx");
+    // (4 * ln(100 / 200) + 24) * 0.5 * 0.5 / (2 * 200), rounded up
+    assert_eq!(got[1][idx], "0.0133");
+    assert_eq!(got[2][idx], "");
+
+    let mut cmd = wrk.command("stats");
+    cmd.arg("--quartiles").arg("data.csv");
+    let got: Vec<Vec<String>> = wrk.read_stdout_on_success(&mut cmd);
+    assert!(!got[0].iter().any(|h| h == "quantile_rank_error"));
+}
+
 // --- --mode-cardinality-cap tests ---------------------------------------------------
 
 #[test]
@@ -9317,3 +9349,320 @@ fn stats_infer_dates_autoindex_does_not_leak_across_configs() {
         "stats --infer-dates for a .zip input must equal the same data uncompressed"
     );
 }
+
+fn append_to(wrk: &Workdir, name: &str, data: &str) {
+    use std::io::Write;
+
+    let mut f = std::fs::OpenOptions::new()
+        .append(true)
+        .open(wrk.path(name))
+        .unwrap();
+    f.write_all(data.as_bytes()).unwrap();
+}
+
+#[test]
+fn stats_incremental_append_matches_full_recompute() {
+    use std::path::Path;
+
+    let wrk = Workdir::new("stats_incremental_append_matches_full_recompute");
+    let initial = "n,s\n5,banana\n1,apple\n3,cherry\n";
+    let appended = "9,date\n1,apple\n7,elderberry\n";
+    wrk.create_from_string("data.csv", initial);
+
+    let mut cmd = wrk.command("stats");
+    cmd.arg("--everything").arg("--incremental").arg("data.csv");
+    wrk.assert_success(&mut cmd);
+    assert!(Path::new(&wrk.path("data.stats.csv.acc")).exists());
+
+    append_to(&wrk, "data.csv", appended);
+    let mut cmd = wrk.command("stats");
+    cmd.arg("--everything").arg("--incremental").arg("data.csv");
+    let incremental: String = wrk.stdout(&mut cmd);
+
+    wrk.create_from_string("full.csv", &format!("{initial}{appended}"));
+    let mut cmd = wrk.command("stats");
+    cmd.arg("--everything").arg("full.csv");
+    let full: String = wrk.stdout(&mut cmd);
+
+    assert_eq!(incremental, full);
+}
+
+#[test]
+fn stats_incremental_scans_only_appended_rows() {
+    // swap in the accumulators of a different file with the same columns: if the next run
+    // merges into them instead of rescanning data.csv, it was incremental
+    let wrk = Workdir::new("stats_incremental_scans_only_appended_rows");
+    wrk.create_from_string("data.csv", "n\n1\n2\n");
+    wrk.create_from_string("other.csv", "n\n100\n200\n");
+    for input in ["data.csv", "other.csv"] {
+        let mut cmd = wrk.command("stats");
+        cmd.arg("--incremental").arg(input);
+        wrk.assert_success(&mut cmd);
+    }
+    std::fs::copy(
+        wrk.path("other.stats.csv.acc"),
+        wrk.path("data.stats.csv.acc"),
+    )
+    .unwrap();
+
+    append_to(&wrk, "data.csv", "3\n");
+    let mut cmd = wrk.command("stats");
+    cmd.arg("--incremental").arg("data.csv");
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let max_idx = got[0].iter().position(|h| h == "max").unwrap();
+    let min_idx = got[0].iter().position(|h| h == "min").unwrap();
+    assert_eq!(got[1][min_idx], "3");
+    assert_eq!(got[1][max_idx], "200");
+}
+
+#[test]
+fn stats_incremental_recomputes_when_prefix_changed() {
+    let wrk = Workdir::new("stats_incremental_recomputes_when_prefix_changed");
+    wrk.create_from_string("data.csv", "n\n1\n2\n");
+    let mut cmd = wrk.command("stats");
+    cmd.arg("--incremental").arg("data.csv");
+    wrk.assert_success(&mut cmd);
+
+    // the file grew, but an already-profiled row was edited too
+    wrk.create_from_string("data.csv", "n\n9\n2\n3\n");
+    let mut cmd = wrk.command("stats");
+    cmd.arg("--incremental").arg("data.csv");
+    let got: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+    let max_idx = got[0].iter().position(|h| h == "max").unwrap();
+    let min_idx = got[0].iter().position(|h| h == "min").unwrap();
+    assert_eq!(got[1][min_idx], "2");
+    assert_eq!(got[1][max_idx], "9");
+}
+
+#[test]
+fn stats_incremental_merges_approx_sketches() {
+    let wrk = Workdir::new("stats_incremental_merges_approx_sketches");
+    let initial = "n\n5\n1\n3\n";
+    let appended = "9\n1\n7\n";
+    let stats_args = [
+        "--everything",
+        "--quantile-method",
+        "approx",
+        "--cardinality-method",
+        "approx",
+    ];
+    wrk.create_from_string("data.csv", initial);
+
+    let mut cmd = wrk.command("stats");
+    cmd.args(stats_args).arg("--incremental").arg("data.csv");
+    wrk.assert_success(&mut cmd);
+
+    append_to(&wrk, "data.csv", appended);
+    let mut cmd = wrk.command("stats");
+    cmd.args(stats_args).arg("--incremental").arg("data.csv");
+    let incremental: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+
+    wrk.create_from_string("full.csv", &format!("{initial}{appended}"));
+    let mut cmd = wrk.command("stats");
+    cmd.args(stats_args).arg("full.csv");
+    let full: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+
+    // the saved sketches saw the initial rows, so the estimates cover all six
+    for column in ["cardinality", "q2_median", "max"] {
+        let idx = full[0].iter().position(|h| h == column).unwrap();
+        assert_eq!(incremental[1][idx], full[1][idx], "{column}");
+    }
+    let idx = full[0].iter().position(|h| h == "cardinality").unwrap();
+    assert_eq!(incremental[1][idx], "5");
+}
+
+#[test]
+fn stats_merge_matches_concatenation() {
+    let wrk = Workdir::new("stats_merge_matches_concatenation");
+    let header = "n,s\n";
+    let part0 = "5,banana\n1,apple\n3,cherry\n";
+    let part1 = "9,date\n1,apple\n7,elderberry\n";
+    wrk.create_from_string("part0.csv", &format!("{header}{part0}"));
+    wrk.create_from_string("part1.csv", &format!("{header}{part1}"));
+    wrk.create_from_string("all.csv", &format!("{header}{part0}{part1}"));
+
+    for part in ["part0", "part1"] {
+        let mut cmd = wrk.command("stats");
+        cmd.arg("--everything")
+            .args(["--save-state", &format!("{part}.state")])
+            .arg(format!("{part}.csv"));
+        wrk.assert_success(&mut cmd);
+    }
+
+    let mut cmd = wrk.command("stats");
+    cmd.arg("--merge").arg("part0.state").arg("part1.state");
+    let merged: String = wrk.stdout(&mut cmd);
+
+    let mut cmd = wrk.command("stats");
+    cmd.arg("--everything").arg("all.csv");
+    let full: String = wrk.stdout(&mut cmd);
+
+    assert_eq!(merged, full);
+}
+
+#[test]
+fn stats_merge_approx_sketches() {
+    let wrk = Workdir::new("stats_merge_approx_sketches");
+    let stats_args = [
+        "--everything",
+        "--quantile-method",
+        "approx",
+        "--cardinality-method",
+        "approx",
+    ];
+    wrk.create_from_string("part0.csv", "n\n5\n1\n3\n");
+    wrk.create_from_string("part1.csv", "n\n9\n1\n7\n");
+    wrk.create_from_string("all.csv", "n\n5\n1\n3\n9\n1\n7\n");
+
+    for part in ["part0", "part1"] {
+        let mut cmd = wrk.command("stats");
+        cmd.args(stats_args)
+            .args(["--save-state", &format!("{part}.state")])
+            .arg(format!("{part}.csv"));
+        wrk.assert_success(&mut cmd);
+    }
+
+    let mut cmd = wrk.command("stats");
+    cmd.arg("--merge").arg("part0.state").arg("part1.state");
+    let merged: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+
+    let mut cmd = wrk.command("stats");
+    cmd.args(stats_args).arg("all.csv");
+    let full: Vec<Vec<String>> = wrk.read_stdout(&mut cmd);
+
+    // the t-digests and HLL sketches of both partitions are merged
+    for column in ["cardinality", "q2_median", "max"] {
+        let idx = full[0].iter().position(|h| h == column).unwrap();
+        assert_eq!(merged[1][idx], full[1][idx], "{column}");
+    }
+    let idx = full[0].iter().position(|h| h == "cardinality").unwrap();
+    assert_eq!(merged[1][idx], "5");
+}
+
+#[test]
+fn stats_merge_single_state() {
+    let wrk = Workdir::new("stats_merge_single_state");
+    wrk.create_from_string("data.csv", "n\n3\n1\n2\n");
+
+    let mut cmd = wrk.command("stats");
+    cmd.args(["--save-state", "data.state"]).arg("data.csv");
+    let direct: String = wrk.stdout(&mut cmd);
+
+    let mut cmd = wrk.command("stats");
+    cmd.arg("--merge").arg("data.state");
+    let merged: String = wrk.stdout(&mut cmd);
+
+    assert_eq!(merged, direct);
+}
+
+#[test]
+fn stats_merge_rejects_different_options() {
+    let wrk = Workdir::new("stats_merge_rejects_different_options");
+    wrk.create_from_string("part0.csv", "n\n1\n2\n");
+    wrk.create_from_string("part1.csv", "n\n3\n4\n");
+
+    let mut cmd = wrk.command("stats");
+    cmd.arg("--everything")
+        .args(["--save-state", "part0.state"])
+        .arg("part0.csv");
+    wrk.assert_success(&mut cmd);
+    let mut cmd = wrk.command("stats");
+    cmd.args(["--save-state", "part1.state"]).arg("part1.csv");
+    wrk.assert_success(&mut cmd);
+
+    let mut cmd = wrk.command("stats");
+    cmd.arg("--merge").arg("part0.state").arg("part1.state");
+    wrk.assert_err(&mut cmd);
+}
+
+#[test]
+fn stats_group_by_matches_per_group_stats() {
+    let wrk = Workdir::new("stats_group_by_matches_per_group_stats");
+    wrk.create_from_string(
+        "data.csv",
+        "region,amount,name\neast,5,ann\nwest,2,bob\neast,,cy\neast,9,ann\nwest,7,dee\n",
+    );
+    wrk.create_from_string("east.csv", "amount,name\n5,ann\n,cy\n9,ann\n");
+
+    let mut cmd = wrk.command("stats");
+    cmd.arg("--everything")
+        .args(["--group-by", "region"])
+        .arg("data.csv");
+    let grouped: Vec<Vec<String>> = wrk.read_stdout_on_success(&mut cmd);
+
+    let mut cmd = wrk.command("stats");
+    cmd.arg("--everything").arg("east.csv");
+    let east: Vec<Vec<String>> = wrk.read_stdout_on_success(&mut cmd);
+
+    assert_eq!(grouped[0][0], "region");
+    assert_eq!(grouped[0][1..], east[0][..]);
+    // one row per (group, column), groups in the order they first appear
+    let groups: Vec<(&str, &str)> = grouped[1..]
+        .iter()
+        .map(|r| (r[0].as_str(), r[1].as_str()))
+        .collect();
+    assert_eq!(
+        groups,
+        vec![
+            ("east", "amount"),
+            ("east", "name"),
+            ("west", "amount"),
+            ("west", "name")
+        ]
+    );
+    // each group's stats - sparsity, cardinality and uniqueness ratio included - are those of
+    // its own rows
+    assert_eq!(grouped[1][1..], east[1][..]);
+    assert_eq!(grouped[2][1..], east[2][..]);
+}
+
+#[test]
+fn stats_group_by_parallel_matches_sequential() {
+    let wrk = Workdir::new("stats_group_by_parallel_matches_sequential");
+    let mut rows = vec![svec!["grp", "n", "s"]];
+    for i in 0..1000 {
+        rows.push(vec![
+            format!("g{}", (i * 7) % 5),
+            ((i * 7919) % 1009).to_string(),
+            format!("v{}", i % 13),
+        ]);
+    }
+    wrk.create("data.csv", rows);
+
+    let mut cmd = wrk.command("stats");
+    cmd.arg("--everything")
+        .args(["--group-by", "grp"])
+        .args(["--jobs", "1"])
+        .arg("data.csv");
+    let sequential: String = wrk.stdout(&mut cmd);
+
+    let mut cmd = wrk.command("index");
+    cmd.arg("data.csv");
+    wrk.assert_success(&mut cmd);
+
+    let mut cmd = wrk.command("stats");
+    cmd.arg("--everything")
+        .args(["--group-by", "grp"])
+        .args(["--jobs", "4"])
+        .arg("data.csv");
+    let parallel: String = wrk.stdout(&mut cmd);
+
+    assert_eq!(parallel, sequential);
+}
+
+#[test]
+fn stats_group_by_rejects_cached_modes_and_name_clashes() {
+    let wrk = Workdir::new("stats_group_by_rejects_cached_modes_and_name_clashes");
+    wrk.create_from_string("data.csv", "type,n\na,1\nb,2\n");
+
+    let mut cmd = wrk.command("stats");
+    cmd.args(["--group-by", "n"])
+        .arg("--stats-jsonl")
+        .arg("data.csv");
+    wrk.assert_err(&mut cmd);
+
+    // a "type" group column would be indistinguishable from the stats "type" column
+    let mut cmd = wrk.command("stats");
+    cmd.args(["--group-by", "type"]).arg("data.csv");
+    wrk.assert_err(&mut cmd);
+}
@@ -3343,3 +3343,429 @@ fn validate_with_multiple_formats_strict() {
     ];
     assert_eq!(invalid_records, expected_invalid);
 }
+
+#[test]
+fn validate_frictionless_table_schema() {
+    let wrk = Workdir::new("validate_frictionless_table_schema").flexible(true);
+
+    wrk.create(
+        "countries.csv",
+        vec![
+            svec!["code", "name"],
+            svec!["US", "United States"],
+            svec!["CA", "Canada"],
+        ],
+    );
+    wrk.create(
+        "data.csv",
+        vec![
+            svec!["id", "country", "score"],
+            svec!["1", "US", "10"],
+            svec!["2", "MX", "20"], // Invalid - foreign key not in countries.csv
+            svec!["1", "CA", "30"], // Invalid - duplicate primary key
+            svec!["NA", "CA", "5"], // Invalid - primary key is required
+            svec!["5", "NA", "NA"], // Valid - missing values are nulls
+            svec!["6", "US", "-1"], // Invalid - below minimum
+        ],
+    );
+    wrk.create_from_string(
+        "tableschema.json",
+        r#"{
+            "fields": [
+                { "name": "id", "type": "integer" },
+                { "name": "country", "type": "string", "constraints": { "pattern": "[A-Z]{2}" } },
+                { "name": "score", "type": "integer", "constraints": { "minimum": 0 } }
+            ],
+            "missingValues": ["", "NA"],
+            "primaryKey": "id",
+            "foreignKeys": [
+                {
+                    "fields": "country",
+                    "reference": { "resource": "countries.csv", "fields": "code" }
+                }
+            ]
+        }"#,
+    );
+
+    let mut cmd = wrk.command("validate");
+    cmd.arg("data.csv").arg("tableschema.json");
+    wrk.assert_err(&mut cmd);
+
+    let validation_errors: String = wrk.from_str(&wrk.path("data.csv.validation-errors.tsv"));
+    let lines: Vec<&str> = validation_errors.lines().collect();
+    assert_eq!(lines.len(), 5);
+    assert_eq!(lines[0], "row_number\tfield\terror");
+    assert_eq!(
+        lines[1],
+        "2\tcountry\t\"MX\" is not a valid dynamicEnum value"
+    );
+    assert_eq!(
+        lines[2],
+        "3\t\tCombination of values for columns id is not unique"
+    );
+    assert_eq!(lines[3], "4\tid\tnull is not of type \"integer\"");
+    assert!(lines[4].starts_with("6\tscore\t"));
+
+    let valid_records: Vec<Vec<String>> = wrk.read_csv("data.csv.valid");
+    let expected_valid = vec![svec!["1", "US", "10"], svec!["5", "NA", "NA"]];
+    assert_eq!(valid_records, expected_valid);
+}
+
+#[test]
+fn validate_datapackage_round_trip() {
+    let wrk = Workdir::new("validate_datapackage_round_trip").flexible(true);
+
+    wrk.create(
+        "cities.csv",
+        vec![
+            svec!["city", "population"],
+            svec!["Boston", "650000"],
+            svec!["Buffalo", "275000"],
+        ],
+    );
+    wrk.create(
+        "places.csv",
+        vec![
+            svec!["city", "place"],
+            svec!["Boston", "Logan Airport"],
+            svec!["Buffalo", "Ralph Wilson Stadium"],
+            svec!["Orlando", "Disney World"],
+        ],
+    );
+
+    let mut cmd = wrk.command("to");
+    cmd.arg("datapackage")
+        .arg("datapackage.json")
+        .arg("cities.csv")
+        .arg("places.csv");
+    wrk.assert_success(&mut cmd);
+
+    // the generated datapackage validates its own resources as-is
+    let mut cmd = wrk.command("validate");
+    cmd.arg("cities.csv").arg("datapackage.json");
+    wrk.assert_success(&mut cmd);
+
+    let mut cmd = wrk.command("validate");
+    cmd.arg("schema").arg("datapackage.json");
+    let got = wrk.stderr_on_success(&mut cmd);
+    assert_eq!(got, "Valid Frictionless Data Package (2 table(s)).\n");
+
+    // add a foreign key from places to cities
+    let mut datapackage: serde_json::Value =
+        serde_json::from_str(&wrk.read_to_string("datapackage.json").unwrap()).unwrap();
+    for resource in datapackage["resources"].as_array_mut().unwrap() {
+        if resource["name"] == "places" {
+            resource["schema"]["foreignKeys"] = serde_json::json!([{
+                "fields": ["city"],
+                "reference": { "resource": "cities", "fields": ["city"] }
+            }]);
+        }
+    }
+    wrk.create_from_string("datapackage.json", &datapackage.to_string());
+
+    let mut cmd = wrk.command("validate");
+    cmd.arg("places.csv").arg("datapackage.json");
+    wrk.assert_err(&mut cmd);
+
+    let validation_errors: String = wrk.from_str(&wrk.path("places.csv.validation-errors.tsv"));
+    assert_eq!(
+        validation_errors,
+        "row_number\tfield\terror\n3\tcity\t\"Orlando\" is not a valid dynamicEnum value\n"
+    );
+}
+
+#[test]
+fn validate_csvw_metadata() {
+    let wrk = Workdir::new("validate_csvw_metadata").flexible(true);
+
+    wrk.create(
+        "data.csv",
+        vec![
+            svec!["Item", "Quantity", "In Stock"],
+            svec!["widget", "3", "Y"],
+            svec!["gadget", "0", "N"],    // Invalid - positiveInteger
+            svec!["doohickey", "-", "N"], // Valid - "-" is null
+            svec!["gizmo", "2", "maybe"], // Invalid - not a boolean
+        ],
+    );
+    wrk.create_from_string(
+        "data.csv-metadata.json",
+        r#"{
+            "@context": "http://www.w3.org/ns/csvw",
+            "url": "data.csv",
+            "tableSchema": {
+                "columns": [
+                    { "name": "item", "titles": "Item", "required": true },
+                    { "name": "qty", "titles": "Quantity", "datatype": "positiveInteger", "null": "-" },
+                    { "name": "stock", "titles": "In Stock", "datatype": { "base": "boolean", "format": "Y|N" } }
+                ],
+                "primaryKey": "item"
+            }
+        }"#,
+    );
+
+    let mut cmd = wrk.command("validate");
+    cmd.arg("data.csv").arg("data.csv-metadata.json");
+    wrk.assert_err(&mut cmd);
+
+    let validation_errors: String = wrk.from_str(&wrk.path("data.csv.validation-errors.tsv"));
+    let lines: Vec<&str> = validation_errors.lines().collect();
+    assert_eq!(lines.len(), 3);
+    assert!(lines[1].starts_with("2\tQuantity\t"));
+    assert!(lines[2].starts_with("4\tIn Stock\t"));
+
+    let valid_records: Vec<Vec<String>> = wrk.read_csv("data.csv.valid");
+    let expected_valid = vec![svec!["widget", "3", "Y"], svec!["doohickey", "-", "N"]];
+    assert_eq!(valid_records, expected_valid);
+}
+
+#[test]
+fn validate_foreign_keys_composite() {
+    let wrk = Workdir::new("validate_foreign_keys_composite").flexible(true);
+
+    wrk.create(
+        "order_lines.csv",
+        vec![
+            svec!["order_id", "line_no", "sku"],
+            svec!["A1", "1", "widget"],
+            svec!["A1", "2", "gadget"],
+            svec!["A2", "1", "widget"],
+        ],
+    );
+    wrk.create(
+        "shipments.csv",
+        vec![
+            svec!["shipment", "order_id", "line_no"],
+            svec!["S1", "A1", "1"],
+            svec!["S2", "A1", "3"], // orphan - A1 has no line 3
+            svec!["S3", "A2", "1"],
+            svec!["S4", "", "2"],   // not checked - null key part
+            svec!["S5", "A3", "1"], // orphan - no order A3
+        ],
+    );
+    wrk.create_from_string(
+        "schema.json",
+        r#"{
+            "$schema": "https://json-schema.org/draft/2020-12/schema",
+            "type": "object",
+            "properties": {
+                "shipment": { "type": "string" },
+                "order_id": { "type": ["string", "null"] },
+                "line_no": { "type": "integer" }
+            },
+            "foreignKeys": [
+                {
+                    "columns": ["order_id", "line_no"],
+                    "reference": { "uri": "order_lines.csv" }
+                }
+            ]
+        }"#,
+    );
+
+    let mut cmd = wrk.command("validate");
+    cmd.arg("shipments.csv").arg("schema.json");
+    wrk.assert_err(&mut cmd);
+
+    let validation_errors: String = wrk.from_str(&wrk.path("shipments.csv.validation-errors.tsv"));
+    let expected_errors = r#"row_number	field	error
+2		orphan row: (order_id, line_no) = ("A1", 3) not found in order_lines.csv (order_id, line_no)
+5		orphan row: (order_id, line_no) = ("A3", 1) not found in order_lines.csv (order_id, line_no)
+"#;
+    assert_eq!(validation_errors, expected_errors);
+
+    let invalid_records: Vec<Vec<String>> = wrk.read_csv("shipments.csv.invalid");
+    let expected_invalid = vec![svec!["S2", "A1", "3"], svec!["S5", "A3", "1"]];
+    assert_eq!(invalid_records, expected_invalid);
+}
+
+#[test]
+fn validate_referenced_by_cardinality() {
+    let wrk = Workdir::new("validate_referenced_by_cardinality").flexible(true);
+
+    wrk.create(
+        "orders.csv",
+        vec![
+            svec!["id", "customer"],
+            svec!["1", "Acme"],
+            svec!["2", "Globex"],  // childless - no line items
+            svec!["3", "Initech"], // too many line items
+        ],
+    );
+    wrk.create(
+        "line_items.csv",
+        vec![
+            svec!["order_id", "sku"],
+            svec!["1", "widget"],
+            svec!["3", "widget"],
+            svec!["3", "gadget"],
+            svec!["3", "gizmo"],
+        ],
+    );
+    wrk.create_from_string(
+        "schema.json",
+        r#"{
+            "$schema": "https://json-schema.org/draft/2020-12/schema",
+            "type": "object",
+            "properties": {
+                "id": { "type": "integer" },
+                "customer": { "type": "string" }
+            },
+            "referencedBy": [
+                {
+                    "columns": "id",
+                    "reference": { "uri": "line_items.csv", "columns": "order_id" },
+                    "maxCount": 2
+                }
+            ]
+        }"#,
+    );
+
+    let mut cmd = wrk.command("validate");
+    cmd.arg("orders.csv").arg("schema.json");
+    wrk.assert_err(&mut cmd);
+
+    let validation_errors: String = wrk.from_str(&wrk.path("orders.csv.validation-errors.tsv"));
+    let expected_errors = r#"row_number	field	error
+2		childless row: (id) = (2) is referenced by 0 row(s) of line_items.csv (order_id), expected at least 1
+3		cardinality: (id) = (3) is referenced by 3 row(s) of line_items.csv (order_id), expected at most 2
+"#;
+    assert_eq!(validation_errors, expected_errors);
+
+    let valid_records: Vec<Vec<String>> = wrk.read_csv("orders.csv.valid");
+    assert_eq!(valid_records, vec![svec!["1", "Acme"]]);
+}
+
+#[test]
+#[cfg(feature = "luau")]
+fn validate_rules_with_schema() {
+    let wrk = Workdir::new("validate_rules_with_schema").flexible(true);
+
+    wrk.create(
+        "data.csv",
+        vec![
+            svec!["id", "status", "start_date", "end_date", "closed_at"],
+            svec!["1", "open", "2024-01-01", "2024-02-01", ""],
+            svec!["2", "closed", "2024-01-10", "2024-01-05", "2024-01-06"],
+            svec!["3", "closed", "2024-03-01", "2024-04-01", ""],
+            svec!["", "open", "2024-01-01", "2024-01-15", ""],
+            svec!["250", "open", "2024-01-01", "2024-01-02", ""],
+        ],
+    );
+    wrk.create_from_string(
+        "schema.json",
+        r#"{
+            "$schema": "https://json-schema.org/draft/2020-12/schema",
+            "type": "object",
+            "properties": {
+                "id": { "type": "integer" },
+                "status": { "type": "string" },
+                "start_date": { "type": "string" },
+                "end_date": { "type": "string" },
+                "closed_at": { "type": ["string", "null"] }
+            }
+        }"#,
+    );
+    wrk.create_from_string(
+        "rules.json",
+        r#"[
+            { "name": "end_after_start", "expr": "end_date >= start_date" },
+            { "name": "closed_has_date", "expr": "status ~= 'closed' or closed_at ~= nil",
+              "message": "closed rows need a closed_at date" },
+            { "name": "id_below_100", "expr": "id == nil or id < 100" }
+        ]"#,
+    );
+
+    let mut cmd = wrk.command("validate");
+    cmd.args(["--rules", "rules.json"])
+        .arg("data.csv")
+        .arg("schema.json");
+    wrk.assert_err(&mut cmd);
+
+    // schema and rule errors are reported together; id is an integer per the schema
+    let validation_errors: String = wrk.from_str(&wrk.path("data.csv.validation-errors.tsv"));
+    let expected_errors = r#"row_number	field	error
+2	end_after_start	failed rule: end_date >= start_date
+3	closed_has_date	closed rows need a closed_at date
+4	id	null is not of type "integer"
+5	id_below_100	failed rule: id == nil or id < 100
+"#;
+    assert_eq!(validation_errors, expected_errors);
+
+    let valid_records: Vec<Vec<String>> = wrk.read_csv("data.csv.valid");
+    assert_eq!(
+        valid_records,
+        vec![svec!["1", "open", "2024-01-01", "2024-02-01", ""]]
+    );
+}
+
+#[test]
+#[cfg(feature = "luau")]
+fn validate_rules_without_schema() {
+    let wrk = Workdir::new("validate_rules_without_schema").flexible(true);
+
+    wrk.create(
+        "data.csv",
+        vec![
+            svec!["name", "qty", "max qty"],
+            svec!["a", "5", "10"],
+            svec!["b", "12", "10"],
+            svec!["c", "", "10"],
+        ],
+    );
+    wrk.create_from_string(
+        "rules.json",
+        r#"[{ "name": "qty_within_max", "expr": "tonumber(qty) <= tonumber(col['max qty'])" }]"#,
+    );
+
+    let mut cmd = wrk.command("validate");
+    cmd.args(["--rules", "rules.json"]).arg("data.csv");
+    wrk.assert_err(&mut cmd);
+
+    let validation_errors: String = wrk.from_str(&wrk.path("data.csv.validation-errors.tsv"));
+    let lines: Vec<&str> = validation_errors.lines().collect();
+    assert_eq!(lines.len(), 3);
+    assert_eq!(
+        lines[1],
+        "2\tqty_within_max\tfailed rule: tonumber(qty) <= tonumber(col['max qty'])"
+    );
+    // comparing the nil of an empty qty is a rule error
+    assert!(lines[2].starts_with("3\tqty_within_max\trule error: "));
+
+    let valid_records: Vec<Vec<String>> = wrk.read_csv("data.csv.valid");
+    assert_eq!(valid_records, vec![svec!["a", "5", "10"]]);
+}
+
+#[test]
+#[cfg(feature = "luau")]
+fn validate_rules_column_named_col() {
+    let wrk = Workdir::new("validate_rules_column_named_col").flexible(true);
+
+    wrk.create(
+        "data.csv",
+        vec![
+            svec!["col", "max qty"],
+            svec!["5", "10"],
+            svec!["12", "10"],
+        ],
+    );
+    // a `col` column must not replace the `col` table after the first row
+    wrk.create_from_string(
+        "rules.json",
+        r#"[{ "name": "col_within_max", "expr": "tonumber(col.col) <= tonumber(col['max qty'])" }]"#,
+    );
+
+    let mut cmd = wrk.command("validate");
+    cmd.args(["--rules", "rules.json"]).arg("data.csv");
+    wrk.assert_err(&mut cmd);
+
+    let validation_errors: String = wrk.from_str(&wrk.path("data.csv.validation-errors.tsv"));
+    let lines: Vec<&str> = validation_errors.lines().collect();
+    assert_eq!(lines.len(), 2);
+    assert_eq!(
+        lines[1],
+        "2\tcol_within_max\tfailed rule: tonumber(col.col) <= tonumber(col['max qty'])"
+    );
+
+    let valid_records: Vec<Vec<String>> = wrk.read_csv("data.csv.valid");
+    assert_eq!(valid_records, vec![svec!["5", "10"]]);
+}
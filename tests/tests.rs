@@ -111,6 +111,8 @@ mod test_joinp;
 mod test_json;
 #[cfg(any(feature = "feature_capable", feature = "lite"))]
 mod test_jsonl;
+#[cfg(feature = "apply")]
+mod test_link;
 #[cfg(feature = "mcp")]
 mod test_log;
 #[cfg(feature = "luau")]
@@ -118,6 +120,8 @@ mod test_luau;
 mod test_moarstats;
 #[cfg(any(feature = "feature_capable", feature = "lite"))]
 mod test_partition;
+#[cfg(any(feature = "feature_capable", feature = "lite"))]
+mod test_patch;
 #[cfg(feature = "polars")]
 mod test_pivotp;
 mod test_pragmastat;
@@ -503,6 +503,38 @@ impl Workdir {
             .map(|r| r.unwrap().iter().map(|s| s.to_string()).collect())
             .collect()
     }
+
+    /// Build a `--stats-jsonl` stats cache for `csv`, with an exact cardinality if set.
+    pub fn build_stats_cache(&self, csv: &str, cardinality: bool) {
+        let mut stats_cmd = self.command("stats");
+        stats_cmd.arg(csv).arg("--stats-jsonl");
+        if cardinality {
+            stats_cmd.arg("--cardinality");
+        }
+        self.assert_success(&mut stats_cmd);
+    }
+
+    /// Overwrite the `sort_order` of `field` in the stats cache of `<stem>.csv`, so a test
+    /// can tell whether a command trusted the cache or scanned the data.
+    pub fn tamper_sort_order(&self, stem: &str, field: &str, new_order: &str) {
+        let path = self.path(&format!("{stem}.stats.csv.data.jsonl"));
+        let contents = fs::read_to_string(&path).unwrap();
+        let mut lines = Vec::new();
+        let mut found = false;
+        for line in contents.lines() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let mut v: serde_json::Value = serde_json::from_str(line).unwrap();
+            if v.get("field").and_then(serde_json::Value::as_str) == Some(field) {
+                v["sort_order"] = serde_json::Value::String(new_order.to_string());
+                found = true;
+            }
+            lines.push(serde_json::to_string(&v).unwrap());
+        }
+        assert!(found, "field {field} not found in stats cache");
+        fs::write(&path, lines.join("\n")).unwrap();
+    }
 }
 
 impl fmt::Debug for Workdir {